@@ -0,0 +1,161 @@
+use std::collections::HashMap;
+use crate::{
+	Id,
+	Reference,
+	Lenient,
+	Indexed,
+	Object,
+	Node,
+	ExpandedDocument
+};
+
+/// Adjacency view over the nodes of an expanded document.
+///
+/// Each edge `(subject, property, object)` of the view connects two identified node objects,
+/// wherever they appear in the document (nested nodes, lists, graphs and included nodes).
+/// Reverse properties are taken into account by swapping the subject and object.
+/// Node objects without identifier are traversed, but do not appear in the view.
+pub struct Adjacency<'a, T: Id> {
+	/// Outgoing edges, indexed by subject.
+	outgoing: HashMap<&'a Reference<T>, Vec<(&'a Reference<T>, &'a Reference<T>)>>,
+
+	/// Incoming edges, indexed by object.
+	incoming: HashMap<&'a Reference<T>, Vec<(&'a Reference<T>, &'a Reference<T>)>>
+}
+
+impl<'a, T: Id> Adjacency<'a, T> {
+	/// Build the adjacency view of the given expanded document.
+	pub fn new(doc: &'a ExpandedDocument<T>) -> Adjacency<'a, T> {
+		let mut adjacency = Adjacency {
+			outgoing: HashMap::new(),
+			incoming: HashMap::new()
+		};
+
+		for object in doc {
+			adjacency.add_object(object);
+		}
+
+		adjacency
+	}
+
+	fn add_edge(&mut self, subject: &'a Reference<T>, property: &'a Reference<T>, object: &'a Reference<T>) {
+		self.outgoing.entry(subject).or_insert_with(Vec::new).push((property, object));
+		self.incoming.entry(object).or_insert_with(Vec::new).push((property, subject));
+	}
+
+	fn add_object(&mut self, object: &'a Indexed<Object<T>>) {
+		match object.inner() {
+			Object::Node(node) => self.add_node(node),
+			Object::Graph(graph) => {
+				for object in graph {
+					self.add_object(object)
+				}
+			},
+			Object::List(items) => {
+				for item in items {
+					self.add_object(item)
+				}
+			},
+			Object::Value(_) => ()
+		}
+	}
+
+	fn add_node(&mut self, node: &'a Node<T>) {
+		let id = node_id(node);
+
+		for (property, values) in &node.properties {
+			for value in values {
+				if let Some(id) = id {
+					self.add_object_edges(id, property, value);
+				}
+
+				self.add_object(value)
+			}
+		}
+
+		for (property, nodes) in &node.reverse_properties {
+			for subject in nodes {
+				if let (Some(id), Some(subject_id)) = (id, node_id(subject)) {
+					self.add_edge(subject_id, property, id)
+				}
+
+				self.add_node(subject)
+			}
+		}
+
+		if let Some(graph) = &node.graph {
+			for object in graph {
+				self.add_object(object)
+			}
+		}
+
+		if let Some(included) = &node.included {
+			for node in included {
+				self.add_node(node)
+			}
+		}
+	}
+
+	/// Add the edges from `subject` to the given object, or the items of the given list.
+	fn add_object_edges(&mut self, subject: &'a Reference<T>, property: &'a Reference<T>, object: &'a Indexed<Object<T>>) {
+		match object.inner() {
+			Object::Node(node) => {
+				if let Some(object_id) = node_id(node) {
+					self.add_edge(subject, property, object_id)
+				}
+			},
+			Object::Graph(graph) => {
+				if let Some(Lenient::Ok(object_id)) = graph.id() {
+					self.add_edge(subject, property, object_id)
+				}
+			},
+			Object::List(items) => {
+				for item in items {
+					self.add_object_edges(subject, property, item)
+				}
+			},
+			Object::Value(_) => ()
+		}
+	}
+
+	/// Returns the outgoing edges of the given node, as `(property, object)` pairs.
+	pub fn outgoing(&self, id: &Reference<T>) -> &[(&'a Reference<T>, &'a Reference<T>)] {
+		match self.outgoing.get(id) {
+			Some(edges) => edges.as_ref(),
+			None => &[]
+		}
+	}
+
+	/// Returns the incoming edges of the given node, as `(property, subject)` pairs.
+	pub fn incoming(&self, id: &Reference<T>) -> &[(&'a Reference<T>, &'a Reference<T>)] {
+		match self.incoming.get(id) {
+			Some(edges) => edges.as_ref(),
+			None => &[]
+		}
+	}
+
+	/// Returns the nodes connected to the given node through the given property.
+	///
+	/// This follows outgoing edges only. Use [`incoming`](Adjacency::incoming) to follow
+	/// edges backward.
+	pub fn objects<'b>(&'b self, id: &Reference<T>, property: &'b Reference<T>) -> impl 'b + Iterator<Item = &'a Reference<T>> {
+		self.outgoing(id).iter().filter(move |(p, _)| *p == property).map(|(_, object)| *object)
+	}
+
+	/// Returns the nodes connected to the given node, in any direction.
+	///
+	/// A node may appear multiple times if it is connected through multiple edges.
+	pub fn neighbors<'b>(&'b self, id: &Reference<T>) -> impl 'b + Iterator<Item = &'a Reference<T>> {
+		let outgoing = self.outgoing(id).iter().map(|(_, object)| *object);
+		let incoming = self.incoming(id).iter().map(|(_, subject)| *subject);
+		outgoing.chain(incoming)
+	}
+}
+
+/// Identifier of the given node, if it is a valid reference.
+fn node_id<T: Id>(node: &Node<T>) -> Option<&Reference<T>> {
+	match node.id() {
+		Some(Lenient::Ok(id)) => Some(id),
+		_ => None
+	}
+}
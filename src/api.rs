@@ -0,0 +1,170 @@
+//! Top-level functions mirroring the
+//! [JSON-LD API](https://www.w3.org/TR/json-ld11-api/#the-jsonldprocessor-interface).
+//!
+//! These functions use the [`JsonContext`] context type and [`IriBuf`] identifiers.
+//! Except for [`expand_from`], they use the [`NoLoader`] document loader:
+//! remote documents and contexts are not loaded.
+//! Use the [`Document`] trait methods directly to customize any of those.
+//!
+//! Serialization into RDF is provided by [`to_rdf`], with the `oxrdf` feature.
+//! Deserialization from RDF is not implemented yet.
+use futures::future::{BoxFuture, FutureExt};
+use iref::{Iri, IriBuf};
+use json::JsonValue;
+use crate::{
+	Error,
+	Options,
+	Document,
+	ExpandedDocument,
+	JsonContext,
+	NoLoader,
+	Loader,
+	Context,
+	context::Local
+};
+
+/// Expand the given document.
+///
+/// # Example
+/// ```
+/// # fn main() -> Result<(), json_ld::Error> {
+/// use async_std::task;
+///
+/// let doc = json::parse("{
+/// 	\"@context\": { \"name\": \"http://xmlns.com/foaf/0.1/name\" },
+/// 	\"name\": \"Timothée Haudebourg\"
+/// }").unwrap();
+/// let expanded_doc = task::block_on(json_ld::expand(&doc, json_ld::Options::default()))?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn expand<'a, D: Sync + Document<IriBuf, LocalContext = JsonValue>>(document: &'a D, options: Options) -> BoxFuture<'a, Result<ExpandedDocument<IriBuf>, Error>> {
+	async move {
+		let context: JsonContext = JsonContext::new(document.base_url());
+		document.expand_with(document.base_url(), &context, &mut NoLoader, options.into()).await
+	}.boxed()
+}
+
+/// Load the document behind the given IRI and expand it.
+///
+/// The document is loaded using the given loader,
+/// which is also used to load the remote contexts referenced by the document.
+/// The final URL of the loaded document (after redirections, if any) is used as base URL,
+/// unless the `base` option is set.
+/// The context referenced by the document HTTP `Link` header, if any, is processed before expansion.
+pub fn expand_from<'a, L: Send + Sync + Loader<Document = JsonValue>>(url: Iri<'a>, loader: &'a mut L, options: Options) -> BoxFuture<'a, Result<ExpandedDocument<IriBuf>, Error>> {
+	async move {
+		let document = loader.load(url).await?;
+		let base_url = Document::<IriBuf>::base_url(&document);
+		let context: JsonContext = JsonContext::new(base_url);
+		Document::<IriBuf>::expand_with(&document, base_url, &context, loader, options.into()).await
+	}.boxed()
+}
+
+/// Compact the given document with the given local context.
+///
+/// The local context is processed relatively to the base IRI
+/// (the `base` option if set, or the document base URL),
+/// and emitted verbatim as the `@context` entry of the compacted document.
+///
+/// # Example
+/// ```
+/// # fn main() -> Result<(), json_ld::Error> {
+/// use async_std::task;
+///
+/// let doc = json::parse("{
+/// 	\"http://xmlns.com/foaf/0.1/name\": \"Timothée Haudebourg\"
+/// }").unwrap();
+/// let context = json::parse("{ \"name\": \"http://xmlns.com/foaf/0.1/name\" }").unwrap();
+/// let compacted_doc = task::block_on(json_ld::compact(&doc, &context, json_ld::Options::default()))?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn compact<'a, D: Sync + Document<IriBuf, LocalContext = JsonValue>>(document: &'a D, context: &'a JsonValue, options: Options) -> BoxFuture<'a, Result<JsonValue, Error>> {
+	async move {
+		let base_url = match &options.compaction().base {
+			Some(base) => Some(base.as_iri()),
+			None => document.base_url()
+		};
+
+		let active_context: JsonContext = JsonContext::new(base_url);
+		let processed = context.process_with(&active_context, &mut NoLoader, base_url, options.processing()).await?;
+		document.compact_with(base_url, &processed, &mut NoLoader, options.compaction().clone()).await
+	}.boxed()
+}
+
+/// Flatten the given document, and compact it with the given local context, if any.
+///
+/// The local context is processed like [`compact`] does.
+/// Blank nodes are labeled `_:b0`, `_:b1`, etc.
+///
+/// # Example
+/// ```
+/// # fn main() -> Result<(), json_ld::Error> {
+/// use async_std::task;
+///
+/// let doc = json::parse("{
+/// 	\"http://xmlns.com/foaf/0.1/name\": \"Timothée Haudebourg\",
+/// 	\"http://xmlns.com/foaf/0.1/knows\": { \"http://xmlns.com/foaf/0.1/name\": \"Amélie Barbe\" }
+/// }").unwrap();
+/// let context = json::parse("{ \"name\": \"http://xmlns.com/foaf/0.1/name\" }").unwrap();
+/// let flattened_doc = task::block_on(json_ld::flatten(&doc, Some(&context), json_ld::Options::default()))?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn flatten<'a, D: Sync + Document<IriBuf, LocalContext = JsonValue>>(document: &'a D, context: Option<&'a JsonValue>, options: Options) -> BoxFuture<'a, Result<JsonValue, Error>> {
+	async move {
+		let base_url = match &options.compaction().base {
+			Some(base) => Some(base.as_iri()),
+			None => document.base_url()
+		};
+
+		match context {
+			Some(context) => {
+				let active_context: JsonContext = JsonContext::new(base_url);
+				let processed = context.process_with(&active_context, &mut NoLoader, base_url, options.processing()).await?;
+				document.flatten_with(Some(&processed), &mut NoLoader, options.compaction().clone()).await
+			},
+			None => document.flatten_with(None::<&crate::context::Processed<JsonValue, JsonContext>>, &mut NoLoader, options.compaction().clone()).await
+		}
+	}.boxed()
+}
+
+/// Serialize the given document into RDF quads.
+///
+/// The document is expanded and flattened like [`flatten`] does,
+/// then converted with [`oxrdf::to_rdf`](`crate::oxrdf::to_rdf`) using the default datatype handlers.
+/// Flattened blank nodes are labeled `_:b0`, `_:b1`, etc.
+/// and list nodes `_:l0`, `_:l1`, etc.
+///
+/// This function is enabled by the `oxrdf` feature.
+///
+/// # Example
+/// ```
+/// # fn main() -> Result<(), json_ld::Error> {
+/// use async_std::task;
+///
+/// let doc = json::parse("{
+/// 	\"@context\": { \"name\": \"http://xmlns.com/foaf/0.1/name\" },
+/// 	\"@id\": \"https://www.rust-lang.org\",
+/// 	\"name\": \"Rust\"
+/// }").unwrap();
+/// let quads = task::block_on(json_ld::to_rdf(&doc, json_ld::Options::default()))?;
+/// assert_eq!(quads.len(), 1);
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature="oxrdf")]
+pub fn to_rdf<'a, D: Sync + Document<IriBuf, LocalContext = JsonValue>>(document: &'a D, options: Options) -> BoxFuture<'a, Result<Vec<::oxrdf::Quad>, Error>> {
+	async move {
+		let base_url = match &options.compaction().base {
+			Some(base) => Some(base.as_iri()),
+			None => document.base_url()
+		};
+
+		let context: JsonContext = JsonContext::new(base_url);
+		let expanded = document.expand_with(base_url, &context, &mut NoLoader, options.expansion().clone()).await?;
+		let flattened = expanded.flatten(crate::BlankIdCounter::new())?;
+		Ok(crate::oxrdf::to_rdf(&flattened, &crate::oxrdf::Datatypes::default(), crate::BlankIdCounter::with_prefix("l")))
+	}.boxed()
+}
@@ -1,5 +1,7 @@
 use std::fmt;
+use std::borrow::Borrow;
 use std::convert::TryFrom;
+use std::str::FromStr;
 use json::JsonValue;
 use crate::util;
 
@@ -25,10 +27,29 @@ impl BlankId {
 	/// Create a new blank identifier from a given `name`.
 	///
 	/// The created blank node will be of the form `_:name`.
+	/// The name is not validated, use [`BlankId::try_new`] to do so.
 	pub fn new(name: &str) -> BlankId {
 		BlankId("_:".to_string() + name)
 	}
 
+	/// Create a new blank identifier from a given `name`, if it is valid.
+	///
+	/// See [`BlankId::is_valid_name`].
+	pub fn try_new(name: &str) -> Result<BlankId, InvalidBlankId> {
+		if Self::is_valid_name(name) {
+			Ok(BlankId::new(name))
+		} else {
+			Err(InvalidBlankId("_:".to_string() + name))
+		}
+	}
+
+	/// Checks if the given string is a valid blank identifier name.
+	///
+	/// A valid name is not empty, and contains no whitespace nor control characters.
+	pub fn is_valid_name(name: &str) -> bool {
+		!name.is_empty() && !name.chars().any(|c| c.is_whitespace() || c.is_control())
+	}
+
 	/// Get the blank identifier as a string.
 	///
 	/// This includes the `_:` prefix.
@@ -43,20 +64,76 @@ impl BlankId {
 	pub fn name(&self) -> &str {
 		&self.0[2..self.0.len()]
 	}
+
+	/// Consume the identifier and return the underlying string, including the `_:` prefix.
+	pub fn into_string(self) -> String {
+		self.0
+	}
+}
+
+/// Invalid blank node identifier.
+///
+/// Returned when a string is not of the form `_:name` with a valid name.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct InvalidBlankId(pub String);
+
+impl fmt::Display for InvalidBlankId {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "invalid blank node identifier `{}`", self.0)
+	}
 }
 
+impl std::error::Error for InvalidBlankId {}
+
 impl<'a> TryFrom<&'a str> for BlankId {
-	type Error = ();
+	type Error = InvalidBlankId;
 
-	fn try_from(str: &'a str) -> Result<BlankId, ()> {
-		if let Some(name) = str.strip_prefix("_:") {
-			Ok(BlankId::new(name))
-		} else {
-			Err(())
+	fn try_from(str: &'a str) -> Result<BlankId, InvalidBlankId> {
+		match str.strip_prefix("_:") {
+			Some(name) if BlankId::is_valid_name(name) => Ok(BlankId(str.to_string())),
+			_ => Err(InvalidBlankId(str.to_string()))
+		}
+	}
+}
+
+impl TryFrom<String> for BlankId {
+	type Error = InvalidBlankId;
+
+	/// Reuses the string buffer.
+	fn try_from(str: String) -> Result<BlankId, InvalidBlankId> {
+		match str.strip_prefix("_:") {
+			Some(name) if BlankId::is_valid_name(name) => Ok(BlankId(str)),
+			_ => Err(InvalidBlankId(str))
 		}
 	}
 }
 
+impl FromStr for BlankId {
+	type Err = InvalidBlankId;
+
+	fn from_str(str: &str) -> Result<BlankId, InvalidBlankId> {
+		BlankId::try_from(str)
+	}
+}
+
+impl From<BlankId> for String {
+	fn from(id: BlankId) -> String {
+		id.0
+	}
+}
+
+impl AsRef<str> for BlankId {
+	fn as_ref(&self) -> &str {
+		&self.0
+	}
+}
+
+impl Borrow<str> for BlankId {
+	fn borrow(&self) -> &str {
+		&self.0
+	}
+}
+
 impl util::AsJson for BlankId {
 	/// Returns a JSON string of the form `_:name`.
 	fn as_json(&self) -> JsonValue {
@@ -69,3 +146,61 @@ impl fmt::Display for BlankId {
 		self.0.fmt(f)
 	}
 }
+
+/// Blank node identifier generator.
+///
+/// Used by the flattening algorithm to label nodes without identifier,
+/// and to relabel existing blank nodes.
+pub trait BlankIdGenerator {
+	/// Generate a fresh blank node identifier.
+	fn next_blank_id(&mut self) -> BlankId;
+}
+
+impl<'a, G: BlankIdGenerator> BlankIdGenerator for &'a mut G {
+	fn next_blank_id(&mut self) -> BlankId {
+		(**self).next_blank_id()
+	}
+}
+
+/// Blank node identifier generator producing `_:b0`, `_:b1`, etc.
+///
+/// This is the identifier scheme used in the JSON-LD API specification.
+#[derive(Clone, Debug)]
+pub struct BlankIdCounter {
+	prefix: String,
+	count: usize
+}
+
+impl BlankIdCounter {
+	/// Create a new generator using the `b` prefix.
+	pub fn new() -> BlankIdCounter {
+		Self::with_prefix("b")
+	}
+
+	/// Create a new generator producing identifiers of the form `_:{prefix}{n}`.
+	pub fn with_prefix(prefix: &str) -> BlankIdCounter {
+		BlankIdCounter {
+			prefix: prefix.to_string(),
+			count: 0
+		}
+	}
+
+	/// Number of identifiers generated so far.
+	pub fn count(&self) -> usize {
+		self.count
+	}
+}
+
+impl Default for BlankIdCounter {
+	fn default() -> BlankIdCounter {
+		BlankIdCounter::new()
+	}
+}
+
+impl BlankIdGenerator for BlankIdCounter {
+	fn next_blank_id(&mut self) -> BlankId {
+		let id = BlankId::new(&format!("{}{}", self.prefix, self.count));
+		self.count += 1;
+		id
+	}
+}
@@ -0,0 +1,362 @@
+//! [CBOR-LD](https://json-ld.github.io/cbor-ld-spec/) encoding and decoding.
+//!
+//! This module is enabled by the `cbor-ld` feature.
+//! A compacted JSON-LD document is encoded into CBOR where keys are compressed into integers
+//! using a term dictionary derived from the active context:
+//! keywords have predefined codes, and the terms of the context are assigned codes
+//! in lexicographical order starting from `100`.
+//! Even codes are used for single values, odd codes (code + 1) for arrays.
+//!
+//! Only keys are compressed: values (including IRIs and `@context` URLs) are encoded as is.
+//! The same context must be used for encoding and decoding.
+
+use std::fmt;
+use std::convert::TryFrom;
+use std::collections::HashMap;
+use json::JsonValue;
+use crate::{
+	Id,
+	context::Context,
+	util
+};
+
+/// CBOR tag identifying compressed CBOR-LD payloads.
+const CBOR_LD_TAG: u64 = 0x0501;
+
+/// First code assigned to context terms.
+const FIRST_TERM_CODE: u64 = 100;
+
+/// Keyword codes.
+const KEYWORDS: &[(&str, u64)] = &[
+	("@context", 0),
+	("@type", 2),
+	("@id", 4),
+	("@value", 6),
+	("@direction", 8),
+	("@graph", 10),
+	("@included", 12),
+	("@index", 14),
+	("@json", 16),
+	("@language", 18),
+	("@list", 20),
+	("@nest", 22),
+	("@reverse", 24),
+	("@base", 26),
+	("@container", 28),
+	("@default", 30),
+	("@embed", 32),
+	("@explicit", 34),
+	("@none", 36),
+	("@omitDefault", 38),
+	("@prefix", 40),
+	("@preserve", 42),
+	("@protected", 44),
+	("@requireAll", 46),
+	("@set", 48),
+	("@version", 50),
+	("@vocab", 52)
+];
+
+/// CBOR-LD error.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Error {
+	/// The input is not well-formed CBOR, or uses unsupported CBOR features.
+	InvalidCbor,
+
+	/// The input is not tagged as a compressed CBOR-LD payload.
+	NotCborLd,
+
+	/// The input uses a key code that is not in the dictionary.
+	UnknownCode(u64),
+
+	/// A plural (odd) key code is not associated to an array.
+	ExpectedArray(u64)
+}
+
+impl fmt::Display for Error {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Error::InvalidCbor => write!(f, "invalid CBOR"),
+			Error::NotCborLd => write!(f, "not a CBOR-LD payload"),
+			Error::UnknownCode(code) => write!(f, "unknown term code {}", code),
+			Error::ExpectedArray(code) => write!(f, "expected an array for term code {}", code)
+		}
+	}
+}
+
+impl std::error::Error for Error {}
+
+/// Term dictionary.
+///
+/// Maps keywords and terms to integer codes.
+pub struct Dictionary {
+	codes: HashMap<String, u64>,
+	terms: HashMap<u64, String>
+}
+
+impl Dictionary {
+	/// Build the dictionary of the given context.
+	pub fn new<T: Id, C: Context<T>>(context: &C) -> Dictionary {
+		let mut codes = HashMap::new();
+		let mut terms = HashMap::new();
+
+		for (keyword, code) in KEYWORDS {
+			codes.insert(keyword.to_string(), *code);
+			terms.insert(*code, keyword.to_string());
+		}
+
+		let mut context_terms: Vec<_> = context.definitions().map(|(term, _)| term.as_str()).collect();
+		context_terms.sort_unstable();
+
+		let mut code = FIRST_TERM_CODE;
+		for term in context_terms {
+			codes.insert(term.to_string(), code);
+			terms.insert(code, term.to_string());
+			code += 2
+		}
+
+		Dictionary {
+			codes,
+			terms
+		}
+	}
+
+	/// Code of the given term or keyword.
+	pub fn code(&self, term: &str) -> Option<u64> {
+		self.codes.get(term).cloned()
+	}
+
+	/// Term or keyword associated to the given code.
+	///
+	/// Plural (odd) codes are associated to the same term as the previous even code.
+	pub fn term(&self, code: u64) -> Option<&str> {
+		self.terms.get(&(code & !1)).map(|term| term.as_str())
+	}
+}
+
+/// Encode a compacted JSON-LD document into CBOR-LD, using the term dictionary of the given context.
+pub fn to_cborld<T: Id, C: Context<T>>(document: &JsonValue, context: &C) -> Vec<u8> {
+	let dictionary = Dictionary::new(context);
+	let mut buffer = Vec::new();
+	write_head(&mut buffer, 6, CBOR_LD_TAG);
+	encode(&mut buffer, document, &dictionary);
+	buffer
+}
+
+/// Decode a CBOR-LD payload into a compacted JSON-LD document, using the term dictionary of the given context.
+pub fn from_cborld<T: Id, C: Context<T>>(bytes: &[u8], context: &C) -> Result<JsonValue, Error> {
+	let dictionary = Dictionary::new(context);
+	let mut decoder = Decoder { bytes, offset: 0 };
+
+	match decoder.head()? {
+		(6, CBOR_LD_TAG) => (),
+		_ => return Err(Error::NotCborLd)
+	}
+
+	let document = decoder.decode(&dictionary)?;
+
+	if decoder.offset == bytes.len() {
+		Ok(document)
+	} else {
+		Err(Error::InvalidCbor)
+	}
+}
+
+fn write_head(buffer: &mut Vec<u8>, major: u8, value: u64) {
+	let major = major << 5;
+	if value < 24 {
+		buffer.push(major | value as u8)
+	} else if value <= 0xff {
+		buffer.push(major | 24);
+		buffer.push(value as u8)
+	} else if value <= 0xffff {
+		buffer.push(major | 25);
+		buffer.extend_from_slice(&(value as u16).to_be_bytes())
+	} else if value <= 0xffff_ffff {
+		buffer.push(major | 26);
+		buffer.extend_from_slice(&(value as u32).to_be_bytes())
+	} else {
+		buffer.push(major | 27);
+		buffer.extend_from_slice(&value.to_be_bytes())
+	}
+}
+
+fn write_str(buffer: &mut Vec<u8>, s: &str) {
+	write_head(buffer, 3, s.len() as u64);
+	buffer.extend_from_slice(s.as_bytes())
+}
+
+fn encode(buffer: &mut Vec<u8>, value: &JsonValue, dictionary: &Dictionary) {
+	match value {
+		JsonValue::Null => buffer.push(0xf6),
+		JsonValue::Boolean(false) => buffer.push(0xf4),
+		JsonValue::Boolean(true) => buffer.push(0xf5),
+		JsonValue::Number(n) => {
+			// Only integral numbers that fit are encoded as CBOR integers.
+			match (util::json_number_as_i64(n), util::json_number_as_u64(n)) {
+				(Some(i), _) if i < 0 => write_head(buffer, 1, !(i as u64)),
+				(_, Some(u)) => write_head(buffer, 0, u),
+				_ => {
+					let f: f64 = (*n).into();
+					buffer.push(0xfb);
+					buffer.extend_from_slice(&f.to_bits().to_be_bytes())
+				}
+			}
+		},
+		JsonValue::Short(s) => write_str(buffer, s.as_str()),
+		JsonValue::String(s) => write_str(buffer, s.as_str()),
+		JsonValue::Array(items) => {
+			write_head(buffer, 4, items.len() as u64);
+			for item in items {
+				encode(buffer, item, dictionary)
+			}
+		},
+		JsonValue::Object(obj) => {
+			write_head(buffer, 5, obj.len() as u64);
+			for (key, value) in obj.iter() {
+				match dictionary.code(key) {
+					Some(code) => match value {
+						JsonValue::Array(_) => write_head(buffer, 0, code + 1),
+						_ => write_head(buffer, 0, code)
+					},
+					None => write_str(buffer, key)
+				}
+
+				encode(buffer, value, dictionary)
+			}
+		}
+	}
+}
+
+struct Decoder<'a> {
+	bytes: &'a [u8],
+	offset: usize
+}
+
+impl<'a> Decoder<'a> {
+	fn take(&mut self, len: usize) -> Result<&'a [u8], Error> {
+		match self.offset.checked_add(len) {
+			Some(end) if end <= self.bytes.len() => {
+				let bytes = &self.bytes[self.offset..end];
+				self.offset = end;
+				Ok(bytes)
+			},
+			_ => Err(Error::InvalidCbor)
+		}
+	}
+
+	/// Number of bytes left to decode.
+	fn remaining(&self) -> usize {
+		self.bytes.len() - self.offset
+	}
+
+	/// Initial capacity of an array or map of the given length.
+	///
+	/// The length comes from the input and cannot be trusted:
+	/// since each item takes at least one byte,
+	/// the capacity is bounded by the number of remaining bytes.
+	fn capacity(&self, len: u64) -> usize {
+		std::cmp::min(len, self.remaining() as u64) as usize
+	}
+
+	fn uint(&mut self, len: usize) -> Result<u64, Error> {
+		Ok(self.take(len)?.iter().fold(0, |value, b| (value << 8) | *b as u64))
+	}
+
+	/// Read a data item head, returning its major type and argument.
+	///
+	/// For major type 7, the argument is the raw additional information or value bits.
+	fn head(&mut self) -> Result<(u8, u64), Error> {
+		let initial = self.take(1)?[0];
+		let major = initial >> 5;
+		let value = match initial & 0x1f {
+			info if info < 24 => info as u64,
+			24 => self.uint(1)?,
+			25 => self.uint(2)?,
+			26 => self.uint(4)?,
+			27 => self.uint(8)?,
+			_ => return Err(Error::InvalidCbor) // indefinite lengths are not supported.
+		};
+
+		Ok((major, value))
+	}
+
+	fn text(&mut self, len: u64) -> Result<String, Error> {
+		let len = usize::try_from(len).map_err(|_| Error::InvalidCbor)?;
+		let bytes = self.take(len)?;
+		std::str::from_utf8(bytes).map(|s| s.to_string()).map_err(|_| Error::InvalidCbor)
+	}
+
+	fn decode(&mut self, dictionary: &Dictionary) -> Result<JsonValue, Error> {
+		let start = self.offset;
+		let (major, value) = self.head()?;
+		match major {
+			0 => Ok(JsonValue::Number(value.into())),
+			1 => if value <= i64::MAX as u64 {
+				Ok(JsonValue::Number((-1 - value as i64).into()))
+			} else {
+				Ok(JsonValue::Number((-1.0 - value as f64).into()))
+			},
+			3 => Ok(JsonValue::String(self.text(value)?)),
+			4 => {
+				let mut items = Vec::with_capacity(self.capacity(value));
+				for _ in 0..value {
+					items.push(self.decode(dictionary)?)
+				}
+
+				Ok(JsonValue::Array(items))
+			},
+			5 => {
+				let mut obj = json::object::Object::with_capacity(self.capacity(value));
+				for _ in 0..value {
+					let key = match self.head()? {
+						(0, code) => match dictionary.term(code) {
+							Some(term) => {
+								let item = self.decode(dictionary)?;
+								if code & 1 == 1 && !item.is_array() {
+									return Err(Error::ExpectedArray(code))
+								}
+
+								obj.insert(term, item);
+								continue
+							},
+							None => return Err(Error::UnknownCode(code))
+						},
+						(3, len) => self.text(len)?,
+						_ => return Err(Error::InvalidCbor)
+					};
+
+					let item = self.decode(dictionary)?;
+					obj.insert(&key, item)
+				}
+
+				Ok(JsonValue::Object(obj))
+			},
+			7 => {
+				let initial = self.bytes[start] & 0x1f;
+				match (initial, value) {
+					(20, _) => Ok(JsonValue::Boolean(false)),
+					(21, _) => Ok(JsonValue::Boolean(true)),
+					(22, _) | (23, _) => Ok(JsonValue::Null),
+					(25, bits) => Ok(JsonValue::Number(half_to_f64(bits as u16).into())),
+					(26, bits) => Ok(JsonValue::Number((f32::from_bits(bits as u32) as f64).into())),
+					(27, bits) => Ok(JsonValue::Number(f64::from_bits(bits).into())),
+					_ => Err(Error::InvalidCbor)
+				}
+			},
+			_ => Err(Error::InvalidCbor) // byte strings and nested tags are not supported.
+		}
+	}
+}
+
+/// Convert an IEEE 754 half-precision float into a double.
+fn half_to_f64(bits: u16) -> f64 {
+	let sign = if bits & 0x8000 != 0 { -1.0 } else { 1.0 };
+	let exponent = ((bits >> 10) & 0x1f) as i32;
+	let mantissa = (bits & 0x3ff) as f64;
+	match exponent {
+		0 => sign * mantissa * 2f64.powi(-24),
+		0x1f => if mantissa == 0.0 { sign * f64::INFINITY } else { f64::NAN },
+		_ => sign * (1.0 + mantissa / 1024.0) * 2f64.powi(exponent - 15)
+	}
+}
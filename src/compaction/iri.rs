@@ -1,4 +1,5 @@
 use std::borrow::Borrow;
+use iref::Iri;
 use json::JsonValue;
 use crate::{
 	Id,
@@ -14,6 +15,7 @@ use crate::{
 	ProcessingMode,
 	context::inverse::{
 		Inversible,
+		CompactedIriKey,
 		TypeSelection,
 		LangSelection,
 		Selection
@@ -33,21 +35,50 @@ use super::{
 /// Compact the given term without considering any value.
 /// 
 /// Calls [`compact_iri_full`] with `None` for `value`.
-pub(crate) fn compact_iri<'a, T: 'a + Id, C: Context<T>, V: ToLenientTerm<T>>(active_context: Inversible<T, &C>, var: V, vocab: bool, reverse: bool, options: Options) -> Result<JsonValue, Error> {
-	compact_iri_full::<T, C, V, Object<T>>(active_context, var, None, vocab, reverse, options)
+/// Since the result only depends on the active context, the given term
+/// and the processing mode and base IRI options,
+/// it is memoized in the active context.
+pub(crate) fn compact_iri<'a, T: 'a + Id, C: Context<T>, V: ToLenientTerm<T>>(active_context: Inversible<T, &C>, var: V, vocab: bool, reverse: bool, options: &Options) -> Result<JsonValue, Error> {
+	let var = var.to_lenient_term();
+	let var = var.borrow();
+
+	let relative_to = if !vocab && options.compact_to_relative {
+		match &options.base {
+			Some(base) => Some(base.clone()),
+			None => active_context.base_iri().map(|base| base.into())
+		}
+	} else {
+		None
+	};
+
+	let key = CompactedIriKey {
+		term: var.clone(),
+		vocab,
+		reverse,
+		processing_mode: options.processing_mode,
+		relative_to
+	};
+
+	if let Some(compacted) = active_context.compacted_iri(&key) {
+		return Ok(compacted)
+	}
+
+	let compacted = compact_iri_full::<T, C, _, Object<T>>(active_context.clone(), var, None, vocab, reverse, options)?;
+	active_context.set_compacted_iri(key, compacted.clone());
+	Ok(compacted)
 }
 
 /// Compact the given term considering the given value object.
 /// 
 /// Calls [`compact_iri_full`] with `Some(value)`.
-pub(crate) fn compact_iri_with<'a, T: 'a + Id, C: Context<T>, V: ToLenientTerm<T>, N: object::Any<T>>(active_context: Inversible<T, &C>, var: V, value: &Indexed<N>, vocab: bool, reverse: bool, options: Options) -> Result<JsonValue, Error> {
+pub(crate) fn compact_iri_with<'a, T: 'a + Id, C: Context<T>, V: ToLenientTerm<T>, N: object::Any<T>>(active_context: Inversible<T, &C>, var: V, value: &Indexed<N>, vocab: bool, reverse: bool, options: &Options) -> Result<JsonValue, Error> {
 	compact_iri_full(active_context, var, Some(value), vocab, reverse, options)
 }
 
 /// Compact the given term.
 /// 
 /// Default value for `value` is `None` and `false` for `vocab` and `reverse`.
-pub(crate) fn compact_iri_full<'a, T: 'a + Id, C: Context<T>, V: ToLenientTerm<T>, N: object::Any<T>>(active_context: Inversible<T, &C>, var: V, value: Option<&Indexed<N>>, vocab: bool, reverse: bool, options: Options) -> Result<JsonValue, Error> {
+pub(crate) fn compact_iri_full<'a, T: 'a + Id, C: Context<T>, V: ToLenientTerm<T>, N: object::Any<T>>(active_context: Inversible<T, &C>, var: V, value: Option<&Indexed<N>>, vocab: bool, reverse: bool, options: &Options) -> Result<JsonValue, Error> {
 	let var = var.to_lenient_term();
 	let var = var.borrow();
 
@@ -57,7 +88,7 @@ pub(crate) fn compact_iri_full<'a, T: 'a + Id, C: Context<T>, V: ToLenientTerm<T
 
 	if vocab {
 		if let Lenient::Ok(var) = var {
-			if let Some(entry) = active_context.inverse().get(var) {
+			if let Some(entry) = active_context.inverse_definition(var) {
 				// Initialize containers to an empty array.
 				// This array will be used to keep track of an ordered list of preferred container
 				// mapping for a term, based on what is compatible with value.
@@ -157,7 +188,7 @@ pub(crate) fn compact_iri_full<'a, T: 'a + Id, C: Context<T>, V: ToLenientTerm<T
 								}
 							}
 						},
-						Some(object::Ref::Node(node)) if node.is_graph() => {
+						Some(object::Ref::Graph(graph)) => {
 							// Otherwise, if value is a graph object, prefer a mapping most
 							// appropriate for the particular value.
 							if has_index {
@@ -167,7 +198,7 @@ pub(crate) fn compact_iri_full<'a, T: 'a + Id, C: Context<T>, V: ToLenientTerm<T
 								containers.push(Container::GraphIndexSet);
 							}
 
-							if node.id().is_some() {
+							if graph.id().is_some() {
 								// If value contains an @id entry, append the values @graph@id and
 								// @graph@id@set to containers.
 								containers.push(Container::GraphId);
@@ -186,7 +217,7 @@ pub(crate) fn compact_iri_full<'a, T: 'a + Id, C: Context<T>, V: ToLenientTerm<T
 								containers.push(Container::GraphIndexSet);
 							}
 
-							if node.id().is_none() {
+							if graph.id().is_none() {
 								// If the value does not contain an @id entry, append the values
 								// @graph@id and @graph@id@set to containers.
 								containers.push(Container::GraphId);
@@ -399,14 +430,93 @@ pub(crate) fn compact_iri_full<'a, T: 'a + Id, C: Context<T>, V: ToLenientTerm<T
 	// If vocab is false,
 	// transform var to a relative IRI reference using the base IRI from active context,
 	// if it exists.
-	if !vocab {
-		if let Some(base_iri) = active_context.base_iri() {
+	// The `base` option, if set, takes precedence over the base IRI of the active context.
+	if !vocab && options.compact_to_relative {
+		let base_iri = match &options.base {
+			Some(base) => Some(base.as_iri()),
+			None => active_context.base_iri()
+		};
+
+		if let Some(base_iri) = base_iri {
 			if let Some(iri) = var.as_iri() {
-				return Ok(iri.relative_to(base_iri).as_str().into())
+				return Ok(relative_reference(iri, base_iri).into())
 			}
 		}
 	}
 
 	// Finally, return var as is.
 	Ok(var.as_str().into())
-}
\ No newline at end of file
+}
+/// Computes a relative reference to `iri` from `base`, following RFC 3986.
+///
+/// The returned reference resolves to `iri` against `base`.
+/// If `iri` and `base` do not share the same scheme and authority,
+/// `iri` is returned as is.
+/// Same-document references are reduced to their fragment (`#fragment`),
+/// and the remaining path segments of `base` are walked up using `../`.
+fn relative_reference(iri: Iri, base: Iri) -> String {
+	if iri.scheme().as_str() != base.scheme().as_str() || iri.authority().map(|a| a.as_str()) != base.authority().map(|a| a.as_str()) {
+		return iri.as_str().to_string()
+	}
+
+	let path = iri.path();
+	let base_path = base.path();
+	let query = iri.query().map(|q| q.as_str());
+	let base_query = base.query().map(|q| q.as_str());
+	let fragment = iri.fragment().map(|f| f.as_str());
+
+	let mut result = String::new();
+
+	if path.as_str() == base_path.as_str() && query == base_query && fragment.is_some() {
+		// Same-document reference.
+	} else if path.as_str() == base_path.as_str() && query.is_some() && query != base_query {
+		// Only the query differs.
+		result.push('?');
+		result.push_str(query.unwrap());
+	} else if path.as_str().is_empty() {
+		// No relative-path reference resolves to an empty path,
+		// unless the base path is also empty.
+		if !base_path.as_str().is_empty() {
+			return iri.as_str().to_string()
+		}
+	} else {
+		let segments: Vec<&str> = path.as_str().split('/').collect();
+		let base_segments: Vec<&str> = base_path.as_str().split('/').collect();
+
+		// The last segment of each path is not a directory.
+		let (last_segment, directories) = segments.split_last().unwrap();
+		let base_directories = &base_segments[0..(base_segments.len() - 1)];
+
+		// Since both paths are absolute (or the base path is empty),
+		// they at least share their root when the base path has a directory.
+		let common = directories.iter().zip(base_directories).take_while(|(a, b)| a == b).count();
+		for _ in common..base_directories.len() {
+			result.push_str("../");
+		}
+
+		for segment in &directories[common..] {
+			result.push_str(segment);
+			result.push('/');
+		}
+
+		result.push_str(last_segment);
+
+		// A relative-path reference must not be empty,
+		// nor have its first segment looking like a scheme.
+		if result.is_empty() || result.split('/').next().unwrap().contains(':') {
+			result.insert_str(0, "./");
+		}
+
+		if let Some(query) = query {
+			result.push('?');
+			result.push_str(query);
+		}
+	}
+
+	if let Some(fragment) = fragment {
+		result.push('#');
+		result.push_str(fragment);
+	}
+
+	result
+}
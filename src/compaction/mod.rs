@@ -1,8 +1,12 @@
 use std::collections::HashSet;
+use std::time::Instant;
 use futures::future::{BoxFuture, FutureExt};
+use futures::stream::{self, StreamExt};
+use iref::{Iri, IriBuf};
 use json::JsonValue;
 use crate::{
 	Id,
+	LanguageNormalization,
 	ContextMut,
 	Indexed,
 	object,
@@ -10,10 +14,16 @@ use crate::{
 	Value,
 	Error,
 	ProcessingMode,
+	OptionsError,
+	CancellationToken,
+	SharedIssueHandler,
+	Limits,
+	interrupt,
 	context::{
 		self,
 		Loader,
 		Local,
+		ReservedTerms,
 		inverse::{
 			Inversible,
 			TypeSelection,
@@ -38,18 +48,303 @@ use node::*;
 use value::*;
 use property::*;
 
-#[derive(Clone, Copy)]
+#[derive(Clone)]
 pub struct Options {
 	pub processing_mode: ProcessingMode,
+	pub base: Option<IriBuf>,
 	pub compact_to_relative: bool,
 	pub compact_arrays: bool,
-	pub ordered: bool
+	pub ordered: bool,
+
+	/// If false, reverse properties that cannot be compacted into a term
+	/// defined with `@reverse` raise an error instead of being put in
+	/// an explicit `@reverse` entry.
+	pub allow_reverse_block: bool,
+
+	/// If true, `@type` values are always compacted into arrays,
+	/// even when `compact_arrays` is true and the node has a single type.
+	pub type_as_array: bool,
+
+	/// If true, the keys of language maps are normalized to lowercase.
+	///
+	/// Language tags are case-insensitive.
+	pub lowercase_language_keys: bool,
+
+	/// If set, the items of each collection (top-level objects, property values, graph members)
+	/// are compacted concurrently, with at most this many items compacted at the same time.
+	///
+	/// Items are polled concurrently by the same task, so this does not use multiple threads,
+	/// but lets the loading of the remote scoped contexts of different items overlap.
+	/// The document loader is then shared between the items,
+	/// each load locking it for its duration.
+	/// The order of the compacted items is preserved.
+	pub concurrency: Option<usize>,
+
+	/// Instant after which the compaction is aborted with a `timeout` error, if any.
+	///
+	/// The deadline is checked before compacting each object.
+	pub deadline: Option<Instant>,
+
+	/// Token used to cancel the compaction, if any.
+	///
+	/// Once cancelled, the compaction is aborted with a `cancelled` error before compacting the next object.
+	pub cancellation: Option<CancellationToken>,
+
+	/// Maximum nesting depth of the input document, if any.
+	///
+	/// When compacting a document that is not yet expanded,
+	/// documents nested deeper are rejected with a `document too deep` error
+	/// before being expanded.
+	pub max_nesting_depth: Option<usize>,
+
+	/// Resource limits enforced when expanding a document that is not yet expanded.
+	///
+	/// See [`expansion::Options::limits`](`crate::expansion::Options::limits`).
+	pub limits: Limits,
+
+	/// Handler of the recoverable issues raised when expanding a document that is not yet expanded.
+	///
+	/// See [`expansion::Options::issue_handler`](`crate::expansion::Options::issue_handler`).
+	pub issue_handler: Option<SharedIssueHandler>,
+
+	/// If true, a value failing to expand aborts the expansion of a document that is not yet expanded.
+	///
+	/// See [`expansion::Options::strict`](`crate::expansion::Options::strict`).
+	pub strict: bool,
+
+	/// If true, `@annotation` entries are expanded when expanding a document that is not yet expanded (JSON-LD-star).
+	///
+	/// See [`expansion::Options::rdf_star`](`crate::expansion::Options::rdf_star`).
+	pub rdf_star: bool,
+
+	/// If true, the top-level objects are always put in a `@graph` array,
+	/// even when `compact_arrays` is true and there is only one object.
+	///
+	/// See also `type_as_array` to keep `@type` values in arrays.
+	pub keep_graph_array: bool,
+
+	/// Properties whose values are always compacted into arrays,
+	/// even when `compact_arrays` is true and there is only one value.
+	///
+	/// Properties are given by compacted term or expanded IRI.
+	pub keep_arrays: HashSet<String>,
+
+	/// If true, type-scoped contexts propagate to nested nodes like any other context,
+	/// as in JSON-LD 1.0.
+	///
+	/// See [`ProcessingOptions::ignore_propagation`](`crate::context::ProcessingOptions::ignore_propagation`).
+	pub ignore_propagation: bool,
+
+	/// Handling of context terms having the form of a keyword.
+	pub reserved_terms: ReservedTerms,
+
+	/// Normalization of the emitted language tags, in `@language` entries and language map keys.
+	pub language_normalization: LanguageNormalization
+}
+
+impl Options {
+	/// Create a new options builder, starting from the default options.
+	pub fn builder() -> OptionsBuilder {
+		OptionsBuilder::new()
+	}
+
+	/// Checks if the values of the given compacted property must be kept in an array,
+	/// following the `keep_arrays` option.
+	pub(crate) fn keeps_array<T: Id, C: ContextMut<T>>(&self, active_context: &Inversible<T, &C>, property: &str) -> bool {
+		if self.keep_arrays.is_empty() {
+			return false
+		}
+
+		self.keep_arrays.contains(property) || match active_context.get(property) {
+			Some(term_definition) => match &term_definition.value {
+				Some(value) => self.keep_arrays.contains(value.as_str()),
+				None => false
+			},
+			None => false
+		}
+	}
+
+	/// Checks the deadline and cancellation token.
+	pub(crate) fn check_interrupt(&self) -> Result<(), Error> {
+		interrupt::check(self.deadline, self.cancellation.as_ref())
+	}
+}
+
+/// Builder of compaction [`Options`].
+pub struct OptionsBuilder {
+	options: Options
+}
+
+impl OptionsBuilder {
+	/// Create a new builder, starting from the default options.
+	pub fn new() -> OptionsBuilder {
+		OptionsBuilder {
+			options: Options::default()
+		}
+	}
+
+	/// Set the processing mode.
+	pub fn processing_mode(mut self, mode: ProcessingMode) -> Self {
+		self.options.processing_mode = mode;
+		self
+	}
+
+	/// Set the base IRI overriding the document base URL.
+	pub fn base(mut self, base: Option<IriBuf>) -> Self {
+		self.options.base = base;
+		self
+	}
+
+	/// Compact IRIs relative to the base IRI.
+	pub fn compact_to_relative(mut self, value: bool) -> Self {
+		self.options.compact_to_relative = value;
+		self
+	}
+
+	/// Compact single-item arrays into their item.
+	pub fn compact_arrays(mut self, value: bool) -> Self {
+		self.options.compact_arrays = value;
+		self
+	}
+
+	/// Process entries in lexicographical order.
+	pub fn ordered(mut self, ordered: bool) -> Self {
+		self.options.ordered = ordered;
+		self
+	}
+
+	/// Allow explicit `@reverse` entries in compacted documents.
+	pub fn allow_reverse_block(mut self, value: bool) -> Self {
+		self.options.allow_reverse_block = value;
+		self
+	}
+
+	/// Always compact `@type` values into arrays.
+	pub fn type_as_array(mut self, value: bool) -> Self {
+		self.options.type_as_array = value;
+		self
+	}
+
+	/// Normalize the keys of language maps to lowercase.
+	pub fn lowercase_language_keys(mut self, value: bool) -> Self {
+		self.options.lowercase_language_keys = value;
+		self
+	}
+
+	/// Compact the items of each collection concurrently.
+	pub fn concurrency(mut self, concurrency: Option<usize>) -> Self {
+		self.options.concurrency = concurrency;
+		self
+	}
+
+	/// Set the instant after which the compaction is aborted.
+	pub fn deadline(mut self, deadline: Option<Instant>) -> Self {
+		self.options.deadline = deadline;
+		self
+	}
+
+	/// Set the token used to cancel the compaction.
+	pub fn cancellation(mut self, token: Option<CancellationToken>) -> Self {
+		self.options.cancellation = token;
+		self
+	}
+
+	/// Set the maximum nesting depth of the input document.
+	pub fn max_nesting_depth(mut self, max: Option<usize>) -> Self {
+		self.options.max_nesting_depth = max;
+		self
+	}
+
+	/// Set the resource limits enforced when expanding the input document.
+	pub fn limits(mut self, limits: Limits) -> Self {
+		self.options.limits = limits;
+		self
+	}
+
+	/// Set the handler of the recoverable issues raised when expanding the input document.
+	pub fn issue_handler(mut self, handler: Option<SharedIssueHandler>) -> Self {
+		self.options.issue_handler = handler;
+		self
+	}
+
+	/// Abort the expansion of the input document when a value fails to expand.
+	pub fn strict(mut self, strict: bool) -> Self {
+		self.options.strict = strict;
+		self
+	}
+
+	/// Expand the `@annotation` entries of the input document (JSON-LD-star).
+	pub fn rdf_star(mut self, rdf_star: bool) -> Self {
+		self.options.rdf_star = rdf_star;
+		self
+	}
+
+	/// Let type-scoped contexts propagate to nested nodes.
+	pub fn ignore_propagation(mut self, value: bool) -> Self {
+		self.options.ignore_propagation = value;
+		self
+	}
+
+	/// Set the handling of context terms having the form of a keyword.
+	pub fn reserved_terms(mut self, policy: ReservedTerms) -> Self {
+		self.options.reserved_terms = policy;
+		self
+	}
+
+	/// Set the normalization of the emitted language tags.
+	pub fn language_normalization(mut self, normalization: LanguageNormalization) -> Self {
+		self.options.language_normalization = normalization;
+		self
+	}
+
+	/// Always put the top-level objects in a `@graph` array.
+	pub fn keep_graph_array(mut self, value: bool) -> Self {
+		self.options.keep_graph_array = value;
+		self
+	}
+
+	/// Always compact the values of the given property into an array.
+	///
+	/// The property is given by compacted term or expanded IRI.
+	pub fn keep_array<P: Into<String>>(mut self, property: P) -> Self {
+		self.options.keep_arrays.insert(property.into());
+		self
+	}
+
+	/// Validate and build the options.
+	///
+	/// The concurrency, if set, must be at least 1.
+	/// As for the expansion options, JSON-LD-star requires the `json-ld-1.1` processing mode,
+	/// and the `strict` flag cannot be combined with an issue handler.
+	pub fn build(self) -> Result<Options, OptionsError> {
+		if self.options.concurrency == Some(0) {
+			return Err(OptionsError::ZeroConcurrency)
+		}
+
+		if self.options.rdf_star && self.options.processing_mode == ProcessingMode::JsonLd1_0 {
+			return Err(OptionsError::RdfStarRequiresJsonLd1_1)
+		}
+
+		if self.options.strict && self.options.issue_handler.is_some() {
+			return Err(OptionsError::StrictWithIssueHandler)
+		}
+
+		Ok(self.options)
+	}
 }
 
-impl From<Options> for context::ProcessingOptions {
-	fn from(options: Options) -> context::ProcessingOptions {
+impl Default for OptionsBuilder {
+	fn default() -> OptionsBuilder {
+		OptionsBuilder::new()
+	}
+}
+
+impl<'a> From<&'a Options> for context::ProcessingOptions {
+	fn from(options: &'a Options) -> context::ProcessingOptions {
 		let mut opt = context::ProcessingOptions::default();
 		opt.processing_mode = options.processing_mode;
+		opt.ignore_propagation = options.ignore_propagation;
+		opt.reserved_terms = options.reserved_terms;
 		opt
 	}
 }
@@ -58,7 +353,17 @@ impl From<crate::expansion::Options> for Options {
 	fn from(options: crate::expansion::Options) -> Options {
 		Options {
 			processing_mode: options.processing_mode,
+			base: options.base,
 			ordered: options.ordered,
+			deadline: options.deadline,
+			cancellation: options.cancellation,
+			max_nesting_depth: options.max_nesting_depth,
+			limits: options.limits,
+			issue_handler: options.issue_handler,
+			strict: options.strict,
+			rdf_star: options.rdf_star,
+			ignore_propagation: options.ignore_propagation,
+			reserved_terms: options.reserved_terms,
 			..Options::default()
 		}
 	}
@@ -68,19 +373,37 @@ impl Default for Options {
 	fn default() -> Options {
 		Options {
 			processing_mode: ProcessingMode::default(),
+			base: None,
 			compact_to_relative: true,
 			compact_arrays: true,
-			ordered: false
+			ordered: false,
+			allow_reverse_block: true,
+			type_as_array: false,
+			lowercase_language_keys: false,
+			concurrency: None,
+			deadline: None,
+			cancellation: None,
+			max_nesting_depth: None,
+			limits: Limits::default(),
+			issue_handler: None,
+			strict: false,
+			rdf_star: false,
+			keep_graph_array: false,
+			keep_arrays: HashSet::new(),
+			ignore_propagation: false,
+			reserved_terms: ReservedTerms::Ignore,
+			language_normalization: LanguageNormalization::Preserve
 		}
 	}
 }
 
 pub trait Compact<T: Id> {
-	fn compact_with<'a, C: ContextMut<T>, L: Loader>(&'a self, active_context: Inversible<T, &'a C>, type_scoped_context: Inversible<T, &'a C>, active_property: Option<&'a str>, loader: &'a mut L, options: Options) -> BoxFuture<'a, Result<JsonValue, Error>> where T:'a, C: Sync + Send, C::LocalContext: Send + Sync + From<L::Output>, L: Sync + Send;
+	fn compact_with<'a, C: ContextMut<T>, L: Loader>(&'a self, active_context: Inversible<T, &'a C>, type_scoped_context: Inversible<T, &'a C>, active_property: Option<&'a str>, loader: &'a mut L, options: &'a Options) -> BoxFuture<'a, Result<JsonValue, Error>> where T:'a, C: Sync + Send, C::LocalContext: Send + Sync + From<L::Output>, L: Sync + Send;
 
 	fn compact<'a, C: ContextMut<T>, L: Loader>(&'a self, active_context: Inversible<T, &'a C>, loader: &'a mut L) -> BoxFuture<'a, Result<JsonValue, Error>> where Self: Sync, T: 'a + Sync + Send, C: Sync + Send, C::LocalContext: Send + Sync + From<L::Output>, L: Sync + Send {
 		async move {
-			self.compact_with(active_context.clone(), active_context, None, loader, Options::default()).await
+			let options = Options::default();
+			self.compact_with(active_context.clone(), active_context, None, loader, &options).await
 		}.boxed()
 	}
 }
@@ -91,17 +414,21 @@ enum TypeLangValue<'a, T: Id> {
 }
 
 pub trait CompactIndexed<T: Id> {
-	fn compact_indexed_with<'a, C: ContextMut<T>, L: Loader>(&'a self, index: Option<&'a str>, active_context: Inversible<T, &'a C>, type_scoped_context: Inversible<T, &'a C>, active_property: Option<&'a str>, loader: &'a mut L, options: Options) -> BoxFuture<'a, Result<JsonValue, Error>> where T: 'a, C: Sync + Send, C::LocalContext: Send + Sync + From<L::Output>, L: Sync + Send;
+	fn compact_indexed_with<'a, C: ContextMut<T>, L: Loader>(&'a self, index: Option<&'a str>, active_context: Inversible<T, &'a C>, type_scoped_context: Inversible<T, &'a C>, active_property: Option<&'a str>, loader: &'a mut L, options: &'a Options) -> BoxFuture<'a, Result<JsonValue, Error>> where T: 'a, C: Sync + Send, C::LocalContext: Send + Sync + From<L::Output>, L: Sync + Send;
 }
 
 impl<T: Sync + Send + Id, V: Sync + Send + CompactIndexed<T>> Compact<T> for Indexed<V> {
-	fn compact_with<'a, C: ContextMut<T>, L: Loader>(&'a self, active_context: Inversible<T, &'a C>, type_scoped_context: Inversible<T, &'a C>, active_property: Option<&'a str>, loader: &'a mut L, options: Options) -> BoxFuture<'a, Result<JsonValue, Error>> where T: 'a, C: Sync + Send, C::LocalContext: Send + Sync + From<L::Output>, L: Sync + Send {
+	fn compact_with<'a, C: ContextMut<T>, L: Loader>(&'a self, active_context: Inversible<T, &'a C>, type_scoped_context: Inversible<T, &'a C>, active_property: Option<&'a str>, loader: &'a mut L, options: &'a Options) -> BoxFuture<'a, Result<JsonValue, Error>> where T: 'a, C: Sync + Send, C::LocalContext: Send + Sync + From<L::Output>, L: Sync + Send {
 		self.inner().compact_indexed_with(self.index(), active_context, type_scoped_context, active_property, loader, options)
 	}
 }
 
 impl<T: Sync + Send + Id, N: object::Any<T> + Sync + Send> CompactIndexed<T> for N {
-	fn compact_indexed_with<'a, C: ContextMut<T>, L: Loader>(&'a self, index: Option<&'a str>, active_context: Inversible<T, &'a C>, type_scoped_context: Inversible<T, &'a C>, active_property: Option<&'a str>, loader: &'a mut L, options: Options) -> BoxFuture<'a, Result<JsonValue, Error>> where T: 'a, C: Sync + Send, C::LocalContext: Send + Sync + From<L::Output>, L: Sync + Send {
+	fn compact_indexed_with<'a, C: ContextMut<T>, L: Loader>(&'a self, index: Option<&'a str>, active_context: Inversible<T, &'a C>, type_scoped_context: Inversible<T, &'a C>, active_property: Option<&'a str>, loader: &'a mut L, options: &'a Options) -> BoxFuture<'a, Result<JsonValue, Error>> where T: 'a, C: Sync + Send, C::LocalContext: Send + Sync + From<L::Output>, L: Sync + Send {
+		if let Err(e) = options.check_interrupt() {
+			return async move { Err(e) }.boxed()
+		}
+
 		match self.as_ref() {
 			object::Ref::Value(value) => async move {
 				compact_indexed_value_with(value, index, active_context, active_property, loader, options).await
@@ -109,14 +436,17 @@ impl<T: Sync + Send + Id, N: object::Any<T> + Sync + Send> CompactIndexed<T> for
 			object::Ref::Node(node) => async move {
 				compact_indexed_node_with(node, index, active_context, type_scoped_context, active_property, loader, options).await
 			}.boxed(),
+			object::Ref::Graph(graph) => async move {
+				compact_indexed_graph_with(graph, index, active_context, type_scoped_context, active_property, loader, options).await
+			}.boxed(),
 			object::Ref::List(list) => async move {
 				let mut active_context = active_context;
 				// If active context has a previous context, the active context is not propagated.
 				// If element does not contain an @value entry, and element does not consist of
 				// a single @id entry, set active context to previous context from active context,
 				// as the scope of a term-scoped context does not apply when processing new node objects.
-				if let Some(previous_context) = active_context.previous_context() {
-					active_context = Inversible::new(previous_context)
+				if let Some(previous_context) = active_context.previous() {
+					active_context = previous_context
 				}
 
 				// If the term definition for active property in active context has a local context:
@@ -172,28 +502,46 @@ impl<T: Sync + Send + Id, N: object::Any<T> + Sync + Send> CompactIndexed<T> for
 
 
 /// Default value of `as_array` is false.
-fn add_value(map: &mut json::object::Object, key: &str, value: JsonValue, as_array: bool) {
-	match map.get(key) {
-		Some(JsonValue::Array(_)) => (),
-		Some(original_value) => {
-			let value = original_value.clone();
-			map.insert(key, JsonValue::Array(vec![value]))
+/// Push the given value, or the items of the given array (recursively), to `values`.
+fn push_values(values: &mut Vec<JsonValue>, value: JsonValue) {
+	match value {
+		JsonValue::Array(items) => {
+			values.reserve(items.len());
+			for item in items {
+				push_values(values, item)
+			}
 		},
-		None if as_array => map.insert(key, JsonValue::Array(Vec::new())),
-		None => ()
+		value => values.push(value)
 	}
+}
 
-	match value {
-		JsonValue::Array(values) => {
-			for value in values {
-				add_value(map, key, value, false)
+/// Add a value to the `key` entry of the given map.
+///
+/// If the entry already exists, it is turned into an array (if it is not already one),
+/// and the value is appended to it. If the value is an array, its items are appended instead.
+/// If the entry does not exist, the value is inserted as is if it is a single value,
+/// or as an array if it contains more than one value or if `as_array` is `true`.
+fn add_value(map: &mut json::object::Object, key: &str, value: JsonValue, as_array: bool) {
+	match map.get_mut(key) {
+		Some(entry) => {
+			// Turn the original value into an array, without copying it.
+			if !entry.is_array() {
+				let original_value = std::mem::replace(entry, JsonValue::Null);
+				*entry = JsonValue::Array(vec![original_value])
+			}
+
+			if let JsonValue::Array(values) = entry {
+				push_values(values, value)
 			}
 		},
-		value => {
-			match map.get_mut(key) {
-				Some(JsonValue::Array(values)) => values.push(value),
-				Some(_) => unreachable!(),
-				None => map.insert(key, value)
+		None => {
+			let mut values = Vec::new();
+			push_values(&mut values, value);
+
+			if as_array || values.len() > 1 {
+				map.insert(key, JsonValue::Array(values))
+			} else if let Some(value) = values.pop() {
+				map.insert(key, value)
 			}
 		}
 	}
@@ -212,44 +560,101 @@ fn value_value<T: Id>(value: &Value<T>) -> JsonValue {
 			}
 		},
 		Value::LangString(str) => str.as_str().into(),
-		Value::Json(json) => json.clone()
+		Value::Json(json) => json.as_json_value().clone()
 	}
 }
 
-fn compact_collection_with<'a, T: 'a + Sync + Send + Id, O: 'a + Send + Iterator<Item=&'a Indexed<Object<T>>>, C: ContextMut<T>, L: Loader>(items: O, active_context: Inversible<T, &'a C>, type_scoped_context: Inversible<T, &'a C>, active_property: Option<&'a str>, loader: &'a mut L, options: Options) -> BoxFuture<'a, Result<JsonValue, Error>> where C: Sync + Send, C::LocalContext: Send + Sync + From<L::Output>, L: Sync + Send {
+pub(crate) fn compact_collection_with<'a, T: 'a + Sync + Send + Id, O: 'a + Send + Iterator<Item=&'a Indexed<Object<T>>>, C: ContextMut<T>, L: Loader>(items: O, active_context: Inversible<T, &'a C>, type_scoped_context: Inversible<T, &'a C>, active_property: Option<&'a str>, loader: &'a mut L, options: &'a Options) -> BoxFuture<'a, Result<JsonValue, Error>> where C: Sync + Send, C::LocalContext: Send + Sync + From<L::Output>, L: Sync + Send {
 	async move {
 		let mut result = Vec::new();
 
-		for item in items {
-			match item.compact_with(active_context.clone(), type_scoped_context.clone(), active_property, loader, options).await? {
-				JsonValue::Null => (),
-				compacted_item => {
-					result.push(compacted_item)
+		match options.concurrency {
+			Some(concurrency) => {
+				let loader: &mut (dyn Loader<Output = L::Output> + Send + Sync) = loader;
+				let loader = futures::lock::Mutex::new(loader);
+				let loader = &loader;
+
+				let mut compacted_items = stream::iter(items).map(|item| {
+					let active_context = active_context.clone();
+					let type_scoped_context = type_scoped_context.clone();
+					async move {
+						let mut loader = SharedLoader(loader);
+						item.compact_with(active_context, type_scoped_context, active_property, &mut loader, options).await
+					}
+				}).buffered(std::cmp::max(concurrency, 1));
+
+				while let Some(compacted_item) = compacted_items.next().await {
+					match compacted_item? {
+						JsonValue::Null => (),
+						compacted_item => {
+							result.push(compacted_item)
+						}
+					}
+				}
+			},
+			None => {
+				for item in items {
+					match item.compact_with(active_context.clone(), type_scoped_context.clone(), active_property, loader, options).await? {
+						JsonValue::Null => (),
+						compacted_item => {
+							result.push(compacted_item)
+						}
+					}
 				}
 			}
 		}
 
-		let mut list_or_set = false;
-		if let Some(active_property) = active_property {
-			if let Some(active_property_definition) = active_context.get(active_property) {
-				list_or_set = active_property_definition.container.contains(ContainerType::List) || active_property_definition.container.contains(ContainerType::Set);
-			}
-		}
+		Ok(collection_result(result, active_context, active_property, options))
+	}.boxed()
+}
 
-		if result.is_empty()
-		|| result.len() > 1
-		|| !options.compact_arrays
-		|| active_property == Some("@graph") || active_property == Some("@set")
-		|| list_or_set {
-			return Ok(JsonValue::Array(result))
+/// Document loader shared between concurrently compacted items.
+///
+/// Each load locks the underlying loader for the duration of the load.
+/// The underlying loader is a trait object so that nested concurrent collections
+/// share the same loader type, instead of wrapping it once more.
+struct SharedLoader<'m, O>(&'m futures::lock::Mutex<&'m mut (dyn Loader<Output = O> + Send + Sync)>);
+
+impl<'m, O> Loader for SharedLoader<'m, O> {
+	type Output = O;
+
+	fn load_context<'a>(&'a mut self, url: Iri) -> BoxFuture<'a, Result<context::RemoteContext<O>, Error>> {
+		let url = IriBuf::from(url);
+		async move {
+			let mut loader = self.0.lock().await;
+			loader.load_context(url.as_iri()).await
+		}.boxed()
+	}
+}
+
+/// Turn the compacted items of a collection into the compacted collection.
+///
+/// The collection is compacted into a single value if it contains only one item,
+/// unless the `compact_arrays` option is disabled or the active property requires an array
+/// (including through the `keep_graph_array` and `keep_arrays` options).
+fn collection_result<T: Id, C: ContextMut<T>>(mut result: Vec<JsonValue>, active_context: Inversible<T, &C>, active_property: Option<&str>, options: &Options) -> JsonValue {
+	let mut list_or_set = false;
+	if let Some(active_property) = active_property {
+		if let Some(active_property_definition) = active_context.get(active_property) {
+			list_or_set = active_property_definition.container.contains(ContainerType::List) || active_property_definition.container.contains(ContainerType::Set);
 		}
+	}
 
-		return Ok(result.into_iter().next().unwrap())
-	}.boxed()
+	if result.is_empty()
+	|| result.len() > 1
+	|| !options.compact_arrays
+	|| (active_property.is_none() && options.keep_graph_array)
+	|| active_property.map_or(false, |property| options.keeps_array(&active_context, property))
+	|| active_property == Some("@graph") || active_property == Some("@set")
+	|| list_or_set {
+		return JsonValue::Array(result)
+	}
+
+	result.pop().unwrap()
 }
 
 impl<T: Sync + Send + Id> Compact<T> for HashSet<Indexed<Object<T>>> {
-	fn compact_with<'a, C: ContextMut<T>, L: Loader>(&'a self, active_context: Inversible<T, &'a C>, type_scoped_context: Inversible<T, &'a C>, active_property: Option<&'a str>, loader: &'a mut L, options: Options) -> BoxFuture<'a, Result<JsonValue, Error>> where T: 'a, C: Sync + Send, C::LocalContext: Send + Sync + From<L::Output>, L: Sync + Send {
+	fn compact_with<'a, C: ContextMut<T>, L: Loader>(&'a self, active_context: Inversible<T, &'a C>, type_scoped_context: Inversible<T, &'a C>, active_property: Option<&'a str>, loader: &'a mut L, options: &'a Options) -> BoxFuture<'a, Result<JsonValue, Error>> where T: 'a, C: Sync + Send, C::LocalContext: Send + Sync + From<L::Output>, L: Sync + Send {
 		compact_collection_with(self.iter(), active_context, type_scoped_context, active_property, loader, options)
 	}
 }
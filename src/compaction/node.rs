@@ -1,11 +1,16 @@
+use std::collections::HashSet;
 use json::JsonValue;
 use crate::{
 	Id,
 	ContextMut,
+	Indexed,
+	Object,
 	Node,
+	Graph,
 	Reference,
 	Lenient,
 	Error,
+	ErrorCode,
 	ProcessingMode,
 	context::{
 		self,
@@ -30,14 +35,14 @@ use super::{
 };
 
 /// Compact the given indexed node.
-pub async fn compact_indexed_node_with<T: Sync + Send + Id, C: ContextMut<T>, L: Loader>(node: &Node<T>, index: Option<&str>, mut active_context: Inversible<T, &C>, type_scoped_context: Inversible<T, &C>, active_property: Option<&str>, loader: &mut L, options: Options) -> Result<JsonValue, Error> where C: Sync + Send, C::LocalContext: Send + Sync + From<L::Output>, L: Sync + Send {
+pub async fn compact_indexed_node_with<T: Sync + Send + Id, C: ContextMut<T>, L: Loader>(node: &Node<T>, index: Option<&str>, mut active_context: Inversible<T, &C>, type_scoped_context: Inversible<T, &C>, active_property: Option<&str>, loader: &mut L, options: &Options) -> Result<JsonValue, Error> where C: Sync + Send, C::LocalContext: Send + Sync + From<L::Output>, L: Sync + Send {
 	// If active context has a previous context, the active context is not propagated.
 	// If element does not contain an @value entry, and element does not consist of
 	// a single @id entry, set active context to previous context from active context,
 	// as the scope of a term-scoped context does not apply when processing new node objects.
 	if !(node.is_empty() && node.id().is_some()) { // does not consist of a single @id entry
-		if let Some(previous_context) = active_context.previous_context() {
-			active_context = Inversible::new(previous_context)
+		if let Some(previous_context) = active_context.previous() {
+			active_context = previous_context
 		}
 	}
 
@@ -81,15 +86,6 @@ pub async fn compact_indexed_node_with<T: Sync + Send + Id, C: ContextMut<T>, L:
 		}
 	}
 
-	// For each key expanded property and value expanded value in element, ordered
-	// lexicographically by expanded property if ordered is true:
-	let mut expanded_entries: Vec<_> = node.properties.iter().collect();
-	if options.ordered {
-		expanded_entries.sort_by(|(a, _), (b, _)| {
-			a.as_str().cmp(b.as_str())
-		})
-	}
-
 	// If expanded property is @id:
 	if let Some(id) = &node.id {
 		if node.is_empty() {
@@ -125,13 +121,88 @@ pub async fn compact_indexed_node_with<T: Sync + Send + Id, C: ContextMut<T>, L:
 				return Ok(compacted_value)
 			}
 		}
+	}
+
+	// For each key expanded property and value expanded value in element, ordered
+	// lexicographically by expanded property if ordered is true:
+	let mut expanded_entries: Vec<_> = node.properties.iter().collect();
+	if options.ordered {
+		expanded_entries.sort_by(|(a, _), (b, _)| {
+			a.as_str().cmp(b.as_str())
+		});
+
+		// Keywords come first in the lexicographic order:
+		// @annotation, @graph, @id, @included, @index, @reverse then @type.
+		compact_annotation(&mut result, node, active_context.as_ref(), loader, options).await?;
+		compact_graph(&mut result, node.graph.as_ref(), active_context.as_ref(), loader, options).await?;
+		compact_id(&mut result, node.id.as_ref(), active_context.as_ref(), options)?;
+		compact_included(&mut result, node, active_context.as_ref(), loader, options).await?;
+		compact_index(&mut result, index, active_context.as_ref(), active_property, options)?;
+		compact_reverse_properties(&mut result, node, active_context.as_ref(), loader, options).await?;
+		compact_types(&mut result, &node.types, active_context.as_ref(), type_scoped_context.clone(), options)?;
+	} else {
+		compact_id(&mut result, node.id.as_ref(), active_context.as_ref(), options)?;
+		compact_types(&mut result, &node.types, active_context.as_ref(), type_scoped_context.clone(), options)?;
+		compact_reverse_properties(&mut result, node, active_context.as_ref(), loader, options).await?;
+		compact_index(&mut result, index, active_context.as_ref(), active_property, options)?;
+		compact_graph(&mut result, node.graph.as_ref(), active_context.as_ref(), loader, options).await?;
+	}
 
+	for (expanded_property, expanded_value) in expanded_entries {
+		compact_property(&mut result, expanded_property.clone().into(), expanded_value, active_context.as_ref(), loader, false, options).await?
+	}
+
+	if !options.ordered {
+		compact_included(&mut result, node, active_context.as_ref(), loader, options).await?;
+		compact_annotation(&mut result, node, active_context.as_ref(), loader, options).await?;
+	}
+
+	Ok(JsonValue::Object(result))
+}
+
+/// Compact the given indexed graph object.
+///
+/// The result is the same as compacting a node object with only an `@id` and `@graph` entry.
+pub async fn compact_indexed_graph_with<T: Sync + Send + Id, C: ContextMut<T>, L: Loader>(graph: &Graph<T>, index: Option<&str>, mut active_context: Inversible<T, &C>, type_scoped_context: Inversible<T, &C>, active_property: Option<&str>, loader: &mut L, options: &Options) -> Result<JsonValue, Error> where C: Sync + Send, C::LocalContext: Send + Sync + From<L::Output>, L: Sync + Send {
+	// A graph object does not consist of a single @id entry,
+	// so the active context is not propagated.
+	if let Some(previous_context) = active_context.previous() {
+		active_context = previous_context
+	}
+
+	let mut active_context = active_context.into_borrowed();
+	if let Some(active_property) = active_property {
+		if let Some(active_property_definition) = type_scoped_context.get(active_property) {
+			if let Some(local_context) = &active_property_definition.context {
+				active_context = Inversible::new(local_context.process_with(*active_context.as_ref(), loader, active_property_definition.base_url(), context::ProcessingOptions::from(options).with_override()).await?.into_inner()).into_owned()
+			}
+		}
+	}
+
+	let mut result = json::object::Object::new();
+
+	if options.ordered {
+		compact_graph(&mut result, Some(graph.objects()), active_context.as_ref(), loader, options).await?;
+		compact_id(&mut result, graph.id(), active_context.as_ref(), options)?;
+		compact_index(&mut result, index, active_context.as_ref(), active_property, options)?;
+	} else {
+		compact_id(&mut result, graph.id(), active_context.as_ref(), options)?;
+		compact_index(&mut result, index, active_context.as_ref(), active_property, options)?;
+		compact_graph(&mut result, Some(graph.objects()), active_context.as_ref(), loader, options).await?;
+	}
+
+	Ok(JsonValue::Object(result))
+}
+
+/// Compact the `@id` entry of a node or graph into the given `result` compacted object.
+fn compact_id<T: Sync + Send + Id, C: ContextMut<T>>(result: &mut json::object::Object, id: Option<&Lenient<Reference<T>>>, active_context: Inversible<T, &C>, options: &Options) -> Result<(), Error> {
+	if let Some(id) = id {
 		// If expanded value is a string, then initialize compacted value by IRI
 		// compacting expanded value with vocab set to false.
-		let compacted_value = compact_iri(active_context.as_ref(), id, false, false, options)?;
+		let compacted_value = compact_iri(active_context.clone(), id, false, false, options)?;
 
 		// Initialize alias by IRI compacting expanded property.
-		let alias = compact_iri(active_context.as_ref(), Keyword::Id, true, false, options)?;
+		let alias = compact_iri(active_context, Keyword::Id, true, false, options)?;
 
 		// Add an entry alias to result whose value is set to compacted value and continue
 		// to the next expanded property.
@@ -140,13 +211,17 @@ pub async fn compact_indexed_node_with<T: Sync + Send + Id, C: ContextMut<T>, L:
 		}
 	}
 
-	compact_types(&mut result, &node.types, active_context.as_ref(), type_scoped_context.clone(), options)?;
+	Ok(())
+}
 
+/// Compact the reverse properties of the given node into the given `result` compacted object.
+async fn compact_reverse_properties<T: Sync + Send + Id, C: ContextMut<T>, L: Loader>(result: &mut json::object::Object, node: &Node<T>, active_context: Inversible<T, &C>, loader: &mut L, options: &Options) -> Result<(), Error> where C: Sync + Send, C::LocalContext: Send + Sync + From<L::Output>, L: Sync + Send {
 	// If expanded property is @reverse:
 	if !node.reverse_properties.is_empty() {
 		// Initialize compacted value to the result of using this algorithm recursively,
 		// passing active context, @reverse for active property,
 		// expanded value for element, and the compactArrays and ordered flags.
+		let mut active_context = active_context.into_borrowed();
 		let active_property = "@reverse";
 		if let Some(active_property_definition) = active_context.get(active_property) {
 			if let Some(local_context) = &active_property_definition.context {
@@ -154,8 +229,15 @@ pub async fn compact_indexed_node_with<T: Sync + Send + Id, C: ContextMut<T>, L:
 			}
 		}
 
+		let mut reverse_entries: Vec<_> = node.reverse_properties.iter().collect();
+		if options.ordered {
+			reverse_entries.sort_by(|(a, _), (b, _)| {
+				a.as_str().cmp(b.as_str())
+			})
+		}
+
 		let mut reverse_result = json::object::Object::new();
-		for (expanded_property, expanded_value) in &node.reverse_properties {
+		for (expanded_property, expanded_value) in reverse_entries {
 			compact_property(&mut reverse_result, expanded_property.clone().into(), expanded_value, active_context.as_ref(), loader, true, options).await?;
 		}
 
@@ -168,10 +250,10 @@ pub async fn compact_indexed_node_with<T: Sync + Send + Id, C: ContextMut<T>, L:
 				if term_definition.reverse_property {
 					// Initialize as array to true if the container mapping for property in
 					// the active context includes @set, otherwise the negation of compactArrays.
-					let as_array = term_definition.container.contains(ContainerType::Set) || !options.compact_arrays;
+					let as_array = term_definition.container.contains(ContainerType::Set) || !options.compact_arrays || options.keeps_array(&active_context.as_ref(), &property);
 
 					// Use add value to add value to the property entry in result using as array.
-					add_value(&mut result, &property, value, as_array);
+					add_value(result, &property, value, as_array);
 					continue
 				}
 			}
@@ -180,6 +262,10 @@ pub async fn compact_indexed_node_with<T: Sync + Send + Id, C: ContextMut<T>, L:
 		}
 
 		if !reverse_map.is_empty() {
+			if !options.allow_reverse_block {
+				return Err(ErrorCode::InvalidReverseProperty.into())
+			}
+
 			// Initialize alias by IRI compacting @reverse.
 			let alias = compact_iri(active_context.as_ref(), Keyword::Reverse, true, false, options)?;
 
@@ -188,6 +274,11 @@ pub async fn compact_indexed_node_with<T: Sync + Send + Id, C: ContextMut<T>, L:
 		}
 	}
 
+	Ok(())
+}
+
+/// Compact the `@index` entry of a node into the given `result` compacted object.
+fn compact_index<T: Sync + Send + Id, C: ContextMut<T>>(result: &mut json::object::Object, index: Option<&str>, active_context: Inversible<T, &C>, active_property: Option<&str>, options: &Options) -> Result<(), Error> {
 	// If expanded property is @index and active property has a container mapping in
 	// active context that includes @index,
 	if let Some(index) = index {
@@ -204,30 +295,50 @@ pub async fn compact_indexed_node_with<T: Sync + Send + Id, C: ContextMut<T>, L:
 
 		if !index_container {
 			// Initialize alias by IRI compacting expanded property.
-			let alias = compact_iri(active_context.as_ref(), Keyword::Index, true, false, options)?;
+			let alias = compact_iri(active_context, Keyword::Index, true, false, options)?;
 
 			// Add an entry alias to result whose value is set to expanded value and continue with the next expanded property.
 			result.insert(alias.as_str().unwrap(), index.as_json());
 		}
 	}
 
-	if let Some(graph) = &node.graph {
-		compact_property(&mut result, Term::Keyword(Keyword::Graph), graph, active_context.as_ref(), loader, false, options).await?
-	}
+	Ok(())
+}
 
-	for (expanded_property, expanded_value) in expanded_entries {
-		compact_property(&mut result, expanded_property.clone().into(), expanded_value, active_context.as_ref(), loader, false, options).await?
+/// Compact the `@graph` entry of a node or graph into the given `result` compacted object.
+async fn compact_graph<T: Sync + Send + Id, C: ContextMut<T>, L: Loader>(result: &mut json::object::Object, graph: Option<&HashSet<Indexed<Object<T>>>>, active_context: Inversible<T, &C>, loader: &mut L, options: &Options) -> Result<(), Error> where C: Sync + Send, C::LocalContext: Send + Sync + From<L::Output>, L: Sync + Send {
+	if let Some(graph) = graph {
+		compact_property(result, Term::Keyword(Keyword::Graph), graph, active_context, loader, false, options).await?
 	}
 
+	Ok(())
+}
+
+/// Compact the `@included` entry of the given node into the given `result` compacted object.
+async fn compact_included<T: Sync + Send + Id, C: ContextMut<T>, L: Loader>(result: &mut json::object::Object, node: &Node<T>, active_context: Inversible<T, &C>, loader: &mut L, options: &Options) -> Result<(), Error> where C: Sync + Send, C::LocalContext: Send + Sync + From<L::Output>, L: Sync + Send {
 	if let Some(included) = &node.included {
-		compact_property(&mut result, Term::Keyword(Keyword::Included), included, active_context.as_ref(), loader, false, options).await?
+		// `@included` is not available in json-ld-1.0.
+		if options.processing_mode == ProcessingMode::JsonLd1_0 {
+			return Err(ErrorCode::ProcessingModeConflict.into())
+		}
+
+		compact_property(result, Term::Keyword(Keyword::Included), included, active_context, loader, false, options).await?
 	}
 
-	Ok(JsonValue::Object(result))
+	Ok(())
+}
+
+/// Compact the `@annotation` entry of the given node into the given `result` compacted object.
+async fn compact_annotation<T: Sync + Send + Id, C: ContextMut<T>, L: Loader>(result: &mut json::object::Object, node: &Node<T>, active_context: Inversible<T, &C>, loader: &mut L, options: &Options) -> Result<(), Error> where C: Sync + Send, C::LocalContext: Send + Sync + From<L::Output>, L: Sync + Send {
+	if let Some(annotation) = &node.annotation {
+		compact_property(result, Term::Keyword(Keyword::Annotation), annotation, active_context, loader, false, options).await?
+	}
+
+	Ok(())
 }
 
 /// Compact the given list of types into the given `result` compacted object.
-fn compact_types<T: Sync + Send + Id, C: ContextMut<T>>(result: &mut json::object::Object, types: &[Lenient<Reference<T>>], active_context: Inversible<T, &C>, type_scoped_context: Inversible<T, &C>, options: Options) -> Result<(), Error> {
+fn compact_types<T: Sync + Send + Id, C: ContextMut<T>>(result: &mut json::object::Object, types: &[Lenient<Reference<T>>], active_context: Inversible<T, &C>, type_scoped_context: Inversible<T, &C>, options: &Options) -> Result<(), Error> {
 	// If expanded property is @type:
 	if !types.is_empty() {
 		// If expanded value is a string,
@@ -262,7 +373,8 @@ fn compact_types<T: Sync + Send + Id, C: ContextMut<T>>(result: &mut json::objec
 			Some(def) => def.container,
 			None => Container::None
 		};
-		let as_array = (options.processing_mode == ProcessingMode::JsonLd1_1 && container_mapping.contains(ContainerType::Set)) || !options.compact_arrays;
+		// The `type_as_array` option forces `@type` values into arrays.
+		let as_array = (options.processing_mode == ProcessingMode::JsonLd1_1 && container_mapping.contains(ContainerType::Set)) || !options.compact_arrays || options.type_as_array;
 
 		// Use add value to add compacted value to the alias entry in result using as array.
 		add_value(result, alias.as_str().unwrap(), compacted_value, as_array)
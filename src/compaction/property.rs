@@ -6,6 +6,7 @@ use crate::{
 	object,
 	Object,
 	Node,
+	Graph,
 	Lenient,
 	Error,
 	ErrorCode,
@@ -31,7 +32,7 @@ use super::{
 	value_value
 };
 
-async fn compact_property_list<T: Sync + Send + Id, C: ContextMut<T>, L: Loader>(list: &[Indexed<Object<T>>], expanded_index: Option<&str>, nest_result: &mut json::object::Object, container: Container, as_array: bool, item_active_property: &str, active_context: Inversible<T, &C>, loader: &mut L, options: Options) -> Result<(), Error> where C: Sync + Send, C::LocalContext: Send + Sync + From<L::Output>, L: Sync + Send {
+async fn compact_property_list<T: Sync + Send + Id, C: ContextMut<T>, L: Loader>(list: &[Indexed<Object<T>>], expanded_index: Option<&str>, nest_result: &mut json::object::Object, container: Container, as_array: bool, item_active_property: &str, active_context: Inversible<T, &C>, loader: &mut L, options: &Options) -> Result<(), Error> where C: Sync + Send, C::LocalContext: Send + Sync + From<L::Output>, L: Sync + Send {
 	// If expanded item is a list object:
 	let mut compacted_item = compact_collection_with(list.iter(), active_context.clone(), active_context.clone(), Some(item_active_property), loader, options).await?;
 
@@ -74,9 +75,9 @@ async fn compact_property_list<T: Sync + Send + Id, C: ContextMut<T>, L: Loader>
 	Ok(())
 }
 
-async fn compact_property_graph<T: Sync + Send + Id, C: ContextMut<T>, L: Loader>(node: &Node<T>, expanded_index: Option<&str>, nest_result: &mut json::object::Object, container: Container, as_array: bool, item_active_property: &str, active_context: Inversible<T, &C>, loader: &mut L, options: Options) -> Result<(), Error> where C: Sync + Send, C::LocalContext: Send + Sync + From<L::Output>, L: Sync + Send {
+async fn compact_property_graph<T: Sync + Send + Id, C: ContextMut<T>, L: Loader>(graph: &Graph<T>, expanded_index: Option<&str>, nest_result: &mut json::object::Object, container: Container, as_array: bool, item_active_property: &str, active_context: Inversible<T, &C>, loader: &mut L, options: &Options) -> Result<(), Error> where C: Sync + Send, C::LocalContext: Send + Sync + From<L::Output>, L: Sync + Send {
 	// If expanded item is a graph object
-	let mut compacted_item = node.graph.as_ref().unwrap().compact_with(active_context.clone(), active_context.clone(), Some(item_active_property), loader, options).await?;
+	let mut compacted_item = graph.objects().compact_with(active_context.clone(), active_context.clone(), Some(item_active_property), loader, options).await?;
 
 	// If `container` includes @graph and @id:
 	if container.contains(ContainerType::Graph) && container.contains(ContainerType::Id) {
@@ -96,7 +97,7 @@ async fn compact_property_graph<T: Sync + Send + Id, C: ContextMut<T>, L: Loader
 		// `expanded_item` or @none if no such value exists
 		// with `vocab` set to false if there is an @id entry in
 		// `expanded_item`.
-		let (id_value, vocab): (Lenient<Term<T>>, bool) = match node.id() {
+		let (id_value, vocab): (Lenient<Term<T>>, bool) = match graph.id() {
 			Some(term) => (term.clone().cast(), false),
 			None => (Lenient::Ok(Term::Keyword(Keyword::None)), true)
 		};
@@ -106,7 +107,7 @@ async fn compact_property_graph<T: Sync + Send + Id, C: ContextMut<T>, L: Loader
 		// Use `add_value` to add `compacted_item` to
 		// the `map_key` entry in `map_object` using `as_array`.
 		add_value(map_object, map_key.as_str().unwrap(), compacted_item, as_array)
-	} else if container.contains(ContainerType::Graph) && container.contains(ContainerType::Index) && node.is_simple_graph() {
+	} else if container.contains(ContainerType::Graph) && container.contains(ContainerType::Index) && graph.is_simple() {
 		// Initialize `map_object` to the value of `item_active_property`
 		// in `nest_result`, initializing it to a new empty map,
 		// if necessary.
@@ -120,16 +121,17 @@ async fn compact_property_graph<T: Sync + Send + Id, C: ContextMut<T>, L: Loader
 		};
 
 		// Initialize `map_key` the value of @index in `expanded_item`
-		// or @none, if no such value exists.
+		// or the result of IRI compacting @none, if no such value exists,
+		// so that an alias of @none is used consistently with the other maps.
 		let map_key = match expanded_index {
-			Some(index) => index,
-			None => "@none"
+			Some(index) => index.into(),
+			None => compact_iri(active_context, Keyword::None, true, false, options)?
 		};
 
 		// Use `add_value` to add `compacted_item` to
 		// the `map_key` entry in `map_object` using `as_array`.
-		add_value(map_object, map_key, compacted_item, as_array)
-	} else if container.contains(ContainerType::Graph) && node.is_simple_graph() {
+		add_value(map_object, map_key.as_str().unwrap(), compacted_item, as_array)
+	} else if container.contains(ContainerType::Graph) && graph.is_simple() {
 		// Otherwise, if `container` includes @graph and
 		// `expanded_item` is a simple graph object
 		// the value cannot be represented as a map object.
@@ -168,8 +170,8 @@ async fn compact_property_graph<T: Sync + Send + Id, C: ContextMut<T>, L: Loader
 		// IRI compacting @id using the value of
 		// IRI compacting the value of @id in `expanded_item` using
 		// false for vocab.
-		if let Some(id) = node.id() {
-			let key = compact_iri(active_context.clone(), Keyword::Id, false, false, options)?;
+		if let Some(id) = graph.id() {
+			let key = compact_iri(active_context.clone(), Keyword::Id, true, false, options)?;
 			let value = compact_iri(active_context.clone(), id, false, false, options)?;
 			map.insert(key.as_str().unwrap(), value);
 		}
@@ -191,7 +193,7 @@ async fn compact_property_graph<T: Sync + Send + Id, C: ContextMut<T>, L: Loader
 	Ok(())
 }
 
-fn select_nest_result<'a, T: Id, C: ContextMut<T>>(result: &'a mut json::object::Object, active_context: Inversible<T, &C>, item_active_property: &str, compact_arrays: bool) -> Result<(&'a mut json::object::Object, Container, bool), Error> {
+fn select_nest_result<'a, T: Id, C: ContextMut<T>>(result: &'a mut json::object::Object, active_context: Inversible<T, &C>, item_active_property: &str, options: &Options) -> Result<(&'a mut json::object::Object, Container, bool), Error> {
 	let (nest_result, container) = match active_context.get(item_active_property) {
 		Some(term_definition) => {
 			let nest_result = match &term_definition.nest {
@@ -240,17 +242,18 @@ fn select_nest_result<'a, T: Id, C: ContextMut<T>>(result: &'a mut json::object:
 	// Initialize `as_array` to true if `container` includes @set,
 	// or if `item_active_property` is @graph or @list,
 	// otherwise the negation of `options.compact_arrays`.
+	// Properties listed in the `keep_arrays` option are always compacted into arrays.
 	let as_array = if container.contains(ContainerType::Set) || item_active_property == "@graph" || item_active_property == "@list" {
 		true
 	} else {
-		!compact_arrays
+		!options.compact_arrays || options.keeps_array(&active_context, item_active_property)
 	};
 
 	Ok((nest_result, container, as_array))
 }
 
 /// Compact the given property into the `result` compacted object.
-pub async fn compact_property<'a, T: 'a + Sync + Send + Id, N: 'a + object::Any<T> + Sync + Send, O: IntoIterator<Item=&'a Indexed<N>>, C: ContextMut<T>, L: Loader>(result: &mut json::object::Object, expanded_property: Term<T>, expanded_value: O, active_context: Inversible<T, &C>, loader: &mut L, inside_reverse: bool, options: Options)
+pub async fn compact_property<'a, T: 'a + Sync + Send + Id, N: 'a + object::Any<T> + Sync + Send, O: IntoIterator<Item=&'a Indexed<N>>, C: ContextMut<T>, L: Loader>(result: &mut json::object::Object, expanded_property: Term<T>, expanded_value: O, active_context: Inversible<T, &C>, loader: &mut L, inside_reverse: bool, options: &Options)
 -> Result<(), Error> where C: Sync + Send, C::LocalContext: Send + Sync + From<L::Output>, L: Sync + Send {
 	let lenient_expanded_property: Lenient<Term<T>> = expanded_property.into();
 	let mut is_empty = true;
@@ -265,7 +268,7 @@ pub async fn compact_property<'a, T: 'a + Sync + Send + Id, N: 'a + object::Any<
 		// If the term definition for `item_active_property` in the active context
 		// has a nest value entry (nest term)
 		if let Some(item_active_property) = item_active_property.as_str() {
-			let (nest_result, container, as_array) = select_nest_result(result, active_context.clone(), item_active_property, options.compact_arrays)?;
+			let (nest_result, container, as_array) = select_nest_result(result, active_context.clone(), item_active_property, options)?;
 
 			// Initialize `compacted_item` to the result of using this algorithm
 			// recursively, passing `active_context`, `item_active_property` for
@@ -278,10 +281,36 @@ pub async fn compact_property<'a, T: 'a + Sync + Send + Id, N: 'a + object::Any<
 				object::Ref::List(list) => {
 					compact_property_list(list, expanded_item.index(), nest_result, container, as_array, item_active_property, active_context.clone(), loader, options).await?
 				},
-				object::Ref::Node(node) if node.is_graph() => {
-					compact_property_graph(node, expanded_item.index(), nest_result, container, as_array, item_active_property, active_context.clone(), loader, options).await?
+				object::Ref::Graph(graph) => {
+					compact_property_graph(graph, expanded_item.index(), nest_result, container, as_array, item_active_property, active_context.clone(), loader, options).await?
 				},
 				_ => {
+					// Language maps can only hold strings, losing the base direction of the values.
+					// If the base direction of the value differs from the direction mapping of the
+					// term (or the default base direction), the value is compacted into a value
+					// object under another key.
+					// The key is selected by IRI compacting the property without considering the
+					// value, which never selects a language map (the value-based selection
+					// is the one that selected this language map in the first place).
+					if container.contains(ContainerType::Language) && !container.contains(ContainerType::Graph) {
+						if let object::Ref::Value(value) = expanded_item.inner().as_ref() {
+							let term_direction = match active_context.get(item_active_property).and_then(|def| def.direction) {
+								Some(direction) => direction.option(),
+								None => active_context.default_base_direction()
+							};
+
+							if value.direction() != term_direction {
+								let key = compact_iri(active_context.clone(), &lenient_expanded_property, true, inside_reverse, options)?;
+								if let Some(key) = key.as_str() {
+									let (nest_result, _, as_array) = select_nest_result(result, active_context.clone(), key, options)?;
+									let compacted_item = expanded_item.compact_with(active_context.clone(), active_context.clone(), Some(key), loader, options).await?;
+									add_value(nest_result, key, compacted_item, as_array);
+									continue
+								}
+							}
+						}
+					}
+
 					let mut compacted_item = expanded_item.compact_with(active_context.clone(), active_context.clone(), Some(item_active_property), loader, options).await?;
 
 					// if container includes @language, @index, @id,
@@ -389,9 +418,7 @@ pub async fn compact_property<'a, T: 'a + Sync + Send + Id, N: 'a + object::Any<
 								if !remaining_values.is_empty() {
 									match &mut compacted_item {
 										JsonValue::Object(map) => {
-											for value in remaining_values {
-												add_value(map, container_key.as_str().unwrap(), value, false)
-											}
+											add_value(map, container_key.as_str().unwrap(), JsonValue::Array(remaining_values), false)
 										},
 										_ => ()
 									}
@@ -451,9 +478,7 @@ pub async fn compact_property<'a, T: 'a + Sync + Send + Id, N: 'a + object::Any<
 							if !remaining_values.is_empty() {
 								match &mut compacted_item {
 									JsonValue::Object(map) => {
-										for value in remaining_values {
-											add_value(map, container_key.as_str().unwrap(), value, false)
-										}
+										add_value(map, container_key.as_str().unwrap(), JsonValue::Array(remaining_values), false)
 									},
 									_ => ()
 								}
@@ -479,7 +504,11 @@ pub async fn compact_property<'a, T: 'a + Sync + Send + Id, N: 'a + object::Any<
 
 						// If `map_key` is null, set it to the result of
 						// IRI compacting @none.
+						// Language keys are lowercased if the `lowercase_language_keys` option is set,
+						// and normalized following the `language_normalization` option.
 						let map_key = match map_key {
+							Some(key) if container_type == ContainerType::Language && options.lowercase_language_keys => key.to_lowercase(),
+							Some(key) if container_type == ContainerType::Language => options.language_normalization.apply(&key),
 							Some(key) => key,
 							None => {
 								let key = compact_iri(active_context.clone(), Keyword::None, true, false, options)?;
@@ -510,7 +539,7 @@ pub async fn compact_property<'a, T: 'a + Sync + Send + Id, N: 'a + object::Any<
 		// If the term definition for `item_active_property` in the active context
 		// has a nest value entry (nest term):
 		if let Some(item_active_property) = item_active_property.as_str() {
-			let (nest_result, _, _) = select_nest_result(result, active_context.clone(), item_active_property, options.compact_arrays)?;
+			let (nest_result, _, _) = select_nest_result(result, active_context.clone(), item_active_property, options)?;
 
 			// Use `add_value` to add an empty array to the `item_active_property` entry in
 			// `nest_result` using true for `as_array`.
@@ -4,6 +4,8 @@ use crate::{
 	ContextMut,
 	Value,
 	Error,
+	ErrorCode,
+	ProcessingMode,
 	context::{
 		self,
 		Loader,
@@ -24,7 +26,7 @@ use super::{
 };
 
 /// Compact the given indexed value.
-pub async fn compact_indexed_value_with<T: Sync + Send + Id, C: ContextMut<T>, L: Loader>(value: &Value<T>, index: Option<&str>, active_context: Inversible<T, &C>, active_property: Option<&str>, loader: &mut L, options: Options) -> Result<JsonValue, Error> where C: Sync + Send, C::LocalContext: Send + Sync + From<L::Output>, L: Sync + Send {
+pub async fn compact_indexed_value_with<T: Sync + Send + Id, C: ContextMut<T>, L: Loader>(value: &Value<T>, index: Option<&str>, active_context: Inversible<T, &C>, active_property: Option<&str>, loader: &mut L, options: &Options) -> Result<JsonValue, Error> where C: Sync + Send, C::LocalContext: Send + Sync + From<L::Output>, L: Sync + Send {
 	// If the term definition for active property in active context has a local context:
 	let mut active_context = active_context.into_borrowed();
 	if let Some(active_property) = active_property {
@@ -140,6 +142,11 @@ pub async fn compact_indexed_value_with<T: Sync + Send + Id, C: ContextMut<T>, L
 			let ls_language = ls.language();//.map(|l| Nullable::Some(l));
 			let ls_direction = ls.direction();//.map(|d| Nullable::Some(d));
 
+			// `@direction` is not available in json-ld-1.0.
+			if options.processing_mode == ProcessingMode::JsonLd1_0 && ls_direction.is_some() {
+				return Err(ErrorCode::ProcessingModeConflict.into())
+			}
+
 			if remove_index
 			&& (ls_language.is_none() || language == ls_language) // || (ls.language().is_none() && language.is_none()))
 			&& (ls_direction.is_none() || direction == ls_direction) { // || (ls.direction().is_none() && direction.is_none())) {
@@ -150,7 +157,7 @@ pub async fn compact_indexed_value_with<T: Sync + Send + Id, C: ContextMut<T>, L
 
 				if let Some(language) = ls.language() {
 					let compact_key  = compact_iri(active_context.as_ref(), Keyword::Language, true, false, options)?;
-					result.insert(compact_key.as_str().unwrap(), language.as_json());
+					result.insert(compact_key.as_str().unwrap(), options.language_normalization.apply(language.as_str()).as_str().into());
 				}
 
 				if let Some(direction) = ls.direction() {
@@ -161,10 +168,10 @@ pub async fn compact_indexed_value_with<T: Sync + Send + Id, C: ContextMut<T>, L
 		},
 		Value::Json(value) => {
 			if type_mapping == Some(Type::Json) && remove_index {
-				return Ok(value.clone())
+				return Ok(value.as_json_value().clone())
 			} else {
 				let compact_key  = compact_iri(active_context.as_ref(), Keyword::Value, true, false, options)?;
-				result.insert(compact_key.as_str().unwrap(), value.clone());
+				result.insert(compact_key.as_str().unwrap(), value.as_json_value().clone());
 
 				let compact_key = compact_iri(active_context.as_ref(), Keyword::Type, true, false, options)?;
 				let compact_ty = compact_iri(active_context.as_ref(), Keyword::Json, true, false, options)?;
@@ -0,0 +1,79 @@
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::sync::Mutex;
+use iref::{Iri, IriBuf};
+use json::JsonValue;
+use crate::util;
+use super::ProcessingOptions;
+
+/// Key of a processed local context in the cache.
+#[derive(PartialEq, Eq, Hash)]
+struct Key {
+	/// Hash of the local context.
+	hash: u64,
+
+	/// Base URL used to process the local context.
+	base_url: Option<IriBuf>,
+
+	/// Options used to process the local context.
+	options: ProcessingOptions
+}
+
+/// Cache of the contexts obtained by processing local JSON contexts on top of a given active context.
+///
+/// Entries are indexed by the content of the local context, the base URL and the processing options.
+/// The cache belongs to a single active context: it is not shared between clones,
+/// and must be cleared when the active context is modified.
+pub struct ProcessedCache<C> {
+	map: Mutex<HashMap<Key, Vec<(JsonValue, C)>>>
+}
+
+impl<C> ProcessedCache<C> {
+	/// Creates a new empty cache.
+	pub fn new() -> ProcessedCache<C> {
+		ProcessedCache {
+			map: Mutex::new(HashMap::new())
+		}
+	}
+
+	/// Checks if the cache is empty.
+	pub fn is_empty(&self) -> bool {
+		self.map.lock().unwrap().is_empty()
+	}
+
+	fn key(local_context: &JsonValue, base_url: Option<Iri>, options: ProcessingOptions) -> Key {
+		let mut h = std::collections::hash_map::DefaultHasher::new();
+		util::hash_json(local_context, &mut h);
+
+		Key {
+			hash: h.finish(),
+			base_url: base_url.map(IriBuf::from),
+			options
+		}
+	}
+
+	/// Get the context previously obtained by processing the given local context, if any.
+	pub fn get(&self, local_context: &JsonValue, base_url: Option<Iri>, options: ProcessingOptions) -> Option<C> where C: Clone {
+		let key = Self::key(local_context, base_url, options);
+		let map = self.map.lock().unwrap();
+		map.get(&key).and_then(|entries| {
+			entries.iter().find(|(local, _)| local == local_context).map(|(_, processed)| processed.clone())
+		})
+	}
+
+	/// Remember the context obtained by processing the given local context.
+	pub fn insert(&self, local_context: &JsonValue, base_url: Option<Iri>, options: ProcessingOptions, processed: C) {
+		let key = Self::key(local_context, base_url, options);
+		let mut map = self.map.lock().unwrap();
+		let entries = map.entry(key).or_insert_with(Vec::new);
+		if !entries.iter().any(|(local, _)| local == local_context) {
+			entries.push((local_context.clone(), processed))
+		}
+	}
+}
+
+impl<C> Default for ProcessedCache<C> {
+	fn default() -> ProcessedCache<C> {
+		ProcessedCache::new()
+	}
+}
@@ -0,0 +1,302 @@
+use std::fmt::Write;
+use std::collections::HashSet;
+use crate::{
+	Id,
+	Reference,
+	Nullable,
+	syntax::{
+		Term,
+		Type
+	}
+};
+use super::{
+	Context,
+	JsonContext
+};
+
+fn iri_code(iri: &str) -> String {
+	format!("IriBuf::new({:?}).unwrap()", iri)
+}
+
+fn term_code<T: Id>(term: &Term<T>) -> String {
+	match term {
+		Term::Null => "Term::Null".to_string(),
+		Term::Ref(Reference::Id(id)) => format!("Term::Ref(Reference::Id({}))", iri_code(id.as_iri().into_str())),
+		Term::Ref(Reference::Blank(id)) => format!("Term::Ref(Reference::Blank(BlankId::new({:?})))", id.name()),
+		Term::Keyword(kw) => format!("Term::Keyword(Keyword::try_from({:?}).unwrap())", kw.into_str())
+	}
+}
+
+fn type_code<T: Id>(typ: &Type<T>) -> String {
+	match typ {
+		Type::Id => "Type::Id".to_string(),
+		Type::Json => "Type::Json".to_string(),
+		Type::None => "Type::None".to_string(),
+		Type::Vocab => "Type::Vocab".to_string(),
+		Type::Ref(id) => format!("Type::Ref({})", iri_code(id.as_iri().into_str()))
+	}
+}
+
+/// Generate the Rust source code of a function building the given context.
+///
+/// This is meant to be used at build time (for instance in a `build.rs` script)
+/// to embed a context in an application with a fixed vocabulary:
+/// the context document is processed once, and the generated function
+/// rebuilds the processed context without any context processing or remote loading.
+///
+/// The generated function is named `name` and returns a `JsonContext`.
+/// It requires the `json-ld`, `iref`, `langtag` and `json` crates as dependencies.
+pub fn generate_rust<T: Id>(context: &JsonContext<T>, name: &str) -> String {
+	let mut code = String::new();
+
+	writeln!(code, "/// Precompiled JSON-LD context.").unwrap();
+	writeln!(code, "pub fn {}() -> ::json_ld::context::JsonContext {{", name).unwrap();
+	writeln!(code, "\t#[allow(unused_imports)]").unwrap();
+	writeln!(code, "\tuse ::std::convert::TryFrom;").unwrap();
+	writeln!(code, "\t#[allow(unused_imports)]").unwrap();
+	writeln!(code, "\tuse ::iref::{{Iri, IriBuf}};").unwrap();
+	writeln!(code, "\t#[allow(unused_imports)]").unwrap();
+	writeln!(code, "\tuse ::json_ld::{{Reference, BlankId, Nullable, Direction, context::{{JsonContext, ContextMut, TermDefinition}}, syntax::{{Term, Type, Container, ContainerType, Keyword}}}};").unwrap();
+
+	match context.original_base_url() {
+		Some(iri) => writeln!(code, "\tlet mut context = JsonContext::new(Some(Iri::new({:?}).unwrap()));", iri.as_str()).unwrap(),
+		None => writeln!(code, "\tlet mut context = JsonContext::new(None);").unwrap()
+	}
+
+	if context.base_iri() != context.original_base_url() {
+		match context.base_iri() {
+			Some(iri) => writeln!(code, "\tcontext.set_base_iri(Some(Iri::new({:?}).unwrap()));", iri.as_str()).unwrap(),
+			None => writeln!(code, "\tcontext.set_base_iri(None);").unwrap()
+		}
+	}
+
+	if let Some(vocab) = context.vocabulary() {
+		writeln!(code, "\tcontext.set_vocabulary(Some({}));", term_code(vocab)).unwrap()
+	}
+
+	if let Some(language) = context.default_language() {
+		writeln!(code, "\tcontext.set_default_language(Some(::langtag::LanguageTagBuf::parse_copy({:?}).unwrap()));", language.as_str()).unwrap()
+	}
+
+	if let Some(direction) = context.default_base_direction() {
+		writeln!(code, "\tcontext.set_default_base_direction(Some(Direction::{:?}));", direction).unwrap()
+	}
+
+	let mut definitions: Vec<_> = context.definitions().collect();
+	definitions.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+	for (term, definition) in definitions {
+		writeln!(code, "\t{{").unwrap();
+		writeln!(code, "\t\tlet mut definition = TermDefinition::default();").unwrap();
+
+		if let Some(value) = &definition.value {
+			writeln!(code, "\t\tdefinition.value = Some({});", term_code(value)).unwrap()
+		}
+
+		if definition.prefix {
+			writeln!(code, "\t\tdefinition.prefix = true;").unwrap()
+		}
+
+		if definition.protected {
+			writeln!(code, "\t\tdefinition.protected = true;").unwrap()
+		}
+
+		if definition.reverse_property {
+			writeln!(code, "\t\tdefinition.reverse_property = true;").unwrap()
+		}
+
+		if let Some(base_url) = definition.base_url() {
+			writeln!(code, "\t\tdefinition.base_url = Some({});", iri_code(base_url.as_str())).unwrap()
+		}
+
+		if let Some(local) = &definition.context {
+			writeln!(code, "\t\tdefinition.context = Some(::json::parse({:?}).unwrap());", local.dump()).unwrap()
+		}
+
+		if !definition.container.is_empty() {
+			let types: Vec<_> = definition.container.iter().map(|c| format!("ContainerType::{:?}", c)).collect();
+			writeln!(code, "\t\tdefinition.container = Container::from(&[{}]).unwrap();", types.join(", ")).unwrap()
+		}
+
+		match &definition.direction {
+			Some(Nullable::Some(direction)) => writeln!(code, "\t\tdefinition.direction = Some(Nullable::Some(Direction::{:?}));", direction).unwrap(),
+			Some(Nullable::Null) => writeln!(code, "\t\tdefinition.direction = Some(Nullable::Null);").unwrap(),
+			None => ()
+		}
+
+		if let Some(index) = &definition.index {
+			writeln!(code, "\t\tdefinition.index = Some({:?}.to_string());", index).unwrap()
+		}
+
+		match &definition.language {
+			Some(Nullable::Some(language)) => writeln!(code, "\t\tdefinition.language = Some(Nullable::Some(::langtag::LanguageTagBuf::parse_copy({:?}).unwrap()));", language.as_str()).unwrap(),
+			Some(Nullable::Null) => writeln!(code, "\t\tdefinition.language = Some(Nullable::Null);").unwrap(),
+			None => ()
+		}
+
+		if let Some(nest) = &definition.nest {
+			writeln!(code, "\t\tdefinition.nest = Some({:?}.to_string());", nest).unwrap()
+		}
+
+		if let Some(typ) = &definition.typ {
+			writeln!(code, "\t\tdefinition.typ = Some({});", type_code(typ)).unwrap()
+		}
+
+		writeln!(code, "\t\tcontext.set({:?}, Some(definition));", term).unwrap();
+		writeln!(code, "\t}}").unwrap();
+	}
+
+	writeln!(code, "\tcontext").unwrap();
+	writeln!(code, "}}").unwrap();
+
+	code
+}
+
+/// Split a term into words, on non-alphanumeric characters and lower-to-upper case boundaries.
+fn words(term: &str) -> Vec<String> {
+	let mut words = Vec::new();
+	let mut current = String::new();
+	let mut previous_lowercase = false;
+
+	for c in term.chars() {
+		if !c.is_ascii_alphanumeric() {
+			if !current.is_empty() {
+				words.push(std::mem::take(&mut current))
+			}
+
+			previous_lowercase = false;
+			continue
+		}
+
+		if c.is_ascii_uppercase() && previous_lowercase && !current.is_empty() {
+			words.push(std::mem::take(&mut current))
+		}
+
+		previous_lowercase = c.is_ascii_lowercase() || c.is_ascii_digit();
+		current.push(c)
+	}
+
+	if !current.is_empty() {
+		words.push(current)
+	}
+
+	words
+}
+
+/// Make sure the identifier is valid and not already used.
+fn unique_ident(ident: String, used: &mut HashSet<String>) -> String {
+	let ident = match ident.chars().next() {
+		None => "_".to_string(),
+		Some(c) if c.is_ascii_digit() => format!("_{}", ident),
+		_ => ident
+	};
+
+	let mut candidate = ident.clone();
+	let mut n = 2;
+	while used.contains(&candidate) || candidate == "Self" {
+		candidate = format!("{}_{}", ident, n);
+		n += 1
+	}
+
+	used.insert(candidate.clone());
+	candidate
+}
+
+fn const_ident(term: &str, used: &mut HashSet<String>) -> String {
+	let ident: Vec<_> = words(term).into_iter().map(|w| w.to_ascii_uppercase()).collect();
+	unique_ident(ident.join("_"), used)
+}
+
+fn variant_ident(term: &str, used: &mut HashSet<String>) -> String {
+	let ident: String = words(term).into_iter().map(|w| {
+		let mut chars = w.chars();
+		match chars.next() {
+			Some(c) => c.to_ascii_uppercase().to_string() + chars.as_str(),
+			None => String::new()
+		}
+	}).collect();
+	unique_ident(ident, used)
+}
+
+/// Generate the Rust source code of a vocabulary module for the terms of the given context.
+///
+/// The generated module, named `name`, contains:
+///  - a `&str` constant holding the IRI of each term mapped to an IRI, named after the term
+///    (for instance `FIRST_NAME` for the `firstName` term);
+///  - a `Vocab` enum with one variant per distinct IRI, implementing `AsIri` and `TryFrom<Iri>`,
+///    and hence [`Vocab`](crate::Vocab);
+///  - an `Id` type alias for the [`Lexicon`](crate::Lexicon) built from this vocabulary.
+///
+/// Terms mapped to keywords, blank node identifiers or `null` are ignored.
+/// Like [`generate_rust`], this is meant to be used in a `build.rs` script.
+/// The generated module requires the `json-ld` and `iref` crates as dependencies.
+pub fn generate_vocabulary<T: Id, C: Context<T>>(context: &C, name: &str) -> String {
+	let mut definitions: Vec<_> = context.definitions().filter_map(|(term, definition)| match &definition.value {
+		Some(Term::Ref(Reference::Id(id))) if !definition.reverse_property => Some((term.as_str(), id.as_iri().as_str().to_string())),
+		_ => None
+	}).collect();
+	definitions.sort();
+
+	let mut code = String::new();
+	let mut consts = HashSet::new();
+	let mut variants = HashSet::new();
+	let mut iris = HashSet::new();
+	let mut entries = Vec::new();
+
+	writeln!(code, "/// Vocabulary of a JSON-LD context.").unwrap();
+	writeln!(code, "pub mod {} {{", name).unwrap();
+	writeln!(code, "\tuse ::std::convert::TryFrom;").unwrap();
+	writeln!(code, "\tuse ::iref::{{Iri, AsIri}};").unwrap();
+
+	for (term, iri) in &definitions {
+		let ident = const_ident(term, &mut consts);
+		writeln!(code).unwrap();
+		writeln!(code, "\t/// IRI of the `{}` term.", term).unwrap();
+		writeln!(code, "\tpub const {}: &str = {:?};", ident, iri).unwrap();
+
+		if iris.insert(iri.clone()) {
+			entries.push((variant_ident(term, &mut variants), ident))
+		}
+	}
+
+	writeln!(code).unwrap();
+	writeln!(code, "\t/// Vocabulary terms.").unwrap();
+	writeln!(code, "\t#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]").unwrap();
+	writeln!(code, "\tpub enum Vocab {{").unwrap();
+	for (variant, _) in &entries {
+		writeln!(code, "\t\t{},", variant).unwrap()
+	}
+	writeln!(code, "\t}}").unwrap();
+
+	writeln!(code).unwrap();
+	writeln!(code, "\timpl AsIri for Vocab {{").unwrap();
+	writeln!(code, "\t\tfn as_iri(&self) -> Iri {{").unwrap();
+	writeln!(code, "\t\t\tmatch *self {{").unwrap();
+	for (variant, ident) in &entries {
+		writeln!(code, "\t\t\t\tVocab::{} => Iri::new({}).unwrap(),", variant, ident).unwrap()
+	}
+	writeln!(code, "\t\t\t}}").unwrap();
+	writeln!(code, "\t\t}}").unwrap();
+	writeln!(code, "\t}}").unwrap();
+
+	writeln!(code).unwrap();
+	writeln!(code, "\timpl<'a> TryFrom<Iri<'a>> for Vocab {{").unwrap();
+	writeln!(code, "\t\ttype Error = Iri<'a>;").unwrap();
+	writeln!(code).unwrap();
+	writeln!(code, "\t\tfn try_from(iri: Iri<'a>) -> Result<Vocab, Iri<'a>> {{").unwrap();
+	writeln!(code, "\t\t\tmatch iri.as_str() {{").unwrap();
+	for (variant, ident) in &entries {
+		writeln!(code, "\t\t\t\t{} => Ok(Vocab::{}),", ident, variant).unwrap()
+	}
+	writeln!(code, "\t\t\t\t_ => Err(iri)").unwrap();
+	writeln!(code, "\t\t\t}}").unwrap();
+	writeln!(code, "\t\t}}").unwrap();
+	writeln!(code, "\t}}").unwrap();
+
+	writeln!(code).unwrap();
+	writeln!(code, "\t/// Identifier type built from this vocabulary.").unwrap();
+	writeln!(code, "\tpub type Id = ::json_ld::Lexicon<Vocab>;").unwrap();
+	writeln!(code, "}}").unwrap();
+
+	code
+}
@@ -1,14 +1,17 @@
 use iref::{Iri, IriBuf};
 use langtag::LanguageTagBuf;
+use json::JsonValue;
 use crate::{
 	Nullable,
 	Id,
 	Direction,
 	syntax::{
+		Keyword,
 		Term,
 		Type,
 		Container
-	}
+	},
+	util::AsJson
 };
 use super::Context;
 
@@ -56,6 +59,80 @@ impl<T: Id, C: Context<T>> TermDefinition<T, C> {
 	pub fn base_url(&self) -> Option<Iri> {
 		self.base_url.as_ref().map(|iri| iri.as_iri())
 	}
+
+	/// Checks if the definition can be written in its simple form (just the IRI mapping).
+	pub fn is_simple(&self) -> bool {
+		!self.protected &&
+		!self.reverse_property &&
+		self.typ.is_none() &&
+		self.language.is_none() &&
+		self.direction.is_none() &&
+		self.context.is_none() &&
+		self.nest.is_none() &&
+		self.index.is_none() &&
+		self.container.is_empty()
+	}
+}
+
+/// Expanded (or simple, when possible) JSON form of the term definition,
+/// as it may appear in a `@context` entry.
+impl<T: Id, C: Context<T>> AsJson for TermDefinition<T, C> where C::LocalContext: AsJson {
+	fn as_json(&self) -> JsonValue {
+		let value = match &self.value {
+			Some(value) => value.as_json(),
+			None => JsonValue::Null
+		};
+
+		if self.is_simple() {
+			return value
+		}
+
+		let mut obj = json::object::Object::new();
+
+		if self.reverse_property {
+			obj.insert(Keyword::Reverse.into(), value)
+		} else {
+			obj.insert(Keyword::Id.into(), value)
+		}
+
+		if let Some(typ) = &self.typ {
+			obj.insert(Keyword::Type.into(), typ.as_json())
+		}
+
+		if !self.container.is_empty() {
+			obj.insert(Keyword::Container.into(), self.container.as_json())
+		}
+
+		if let Some(context) = &self.context {
+			obj.insert(Keyword::Context.into(), context.as_json())
+		}
+
+		if let Some(language) = &self.language {
+			obj.insert(Keyword::Language.into(), language.as_json())
+		}
+
+		if let Some(direction) = &self.direction {
+			obj.insert(Keyword::Direction.into(), direction.as_json())
+		}
+
+		if let Some(index) = &self.index {
+			obj.insert(Keyword::Index.into(), index.as_json())
+		}
+
+		if let Some(nest) = &self.nest {
+			obj.insert(Keyword::Nest.into(), nest.as_json())
+		}
+
+		if self.prefix {
+			obj.insert(Keyword::Prefix.into(), true.into())
+		}
+
+		if self.protected {
+			obj.insert(Keyword::Protected.into(), true.into())
+		}
+
+		JsonValue::Object(obj)
+	}
 }
 
 impl<T: Id, C: Context<T>> Default for TermDefinition<T, C> {
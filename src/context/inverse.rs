@@ -1,6 +1,7 @@
 use std::{
 	cmp::Ordering,
 	collections::HashMap,
+	sync::Mutex,
 	fmt
 };
 use once_cell::sync::OnceCell;
@@ -10,10 +11,14 @@ use langtag::{
 };
 use mown::Mown;
 use std::sync::Arc;
+use iref::IriBuf;
+use json::JsonValue;
 use crate::{
 	Id,
+	Lenient,
 	Nullable,
 	Direction,
+	ProcessingMode,
 	syntax::{
 		Term,
 		Container,
@@ -21,19 +26,47 @@ use crate::{
 	}
 };
 use super::{
-	Context
+	Context,
+	TermDefinition
 };
 
 /// Context that can be inverted.
 /// 
 /// This type keeps an inversion of the underlying context which is computed
 /// when [`inverse`] is called and reset when the context is mutabily accessed.
+/// The inverse definition of each IRI mapping is itself only computed when requested
+/// (see [`inverse_definition`](Inversible::inverse_definition)).
+/// The inversion is shared among clones, so it is computed at most once per context.
+/// The inversion of the previous context (see [`previous`](Inversible::previous)) is cached
+/// in the same way.
 pub struct Inversible<T: Id, C> {
 	/// Underlying context.
 	context: C,
 
 	/// Inverse context.
-	inverse: Arc<OnceCell<InverseContext<T>>>
+	inverse: Arc<OnceCell<InverseContext<T>>>,
+
+	/// Inverse of the previous context.
+	previous_inverse: Arc<OnceCell<InverseContext<T>>>,
+
+	/// Memoized IRI compaction results.
+	compacted_iris: Arc<Mutex<HashMap<CompactedIriKey<T>, JsonValue>>>
+}
+
+/// Key of a memoized IRI compaction result.
+///
+/// Along with the compacted term and the `vocab` and `reverse` flags,
+/// it holds every compaction option the result depends on,
+/// so that a memoized result is never reused with different options.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub(crate) struct CompactedIriKey<T: Id> {
+	pub term: Lenient<Term<T>>,
+	pub vocab: bool,
+	pub reverse: bool,
+	pub processing_mode: ProcessingMode,
+
+	/// Base IRI against which the term is made relative, if any.
+	pub relative_to: Option<IriBuf>
 }
 
 impl<T: Id, C: Clone> Clone for Inversible<T, C> {
@@ -41,7 +74,9 @@ impl<T: Id, C: Clone> Clone for Inversible<T, C> {
 	fn clone(&self) -> Self {
 		Inversible {
 			context: self.context.clone(),
-			inverse: self.inverse.clone()
+			inverse: self.inverse.clone(),
+			previous_inverse: self.previous_inverse.clone(),
+			compacted_iris: self.compacted_iris.clone()
 		}
 	}
 }
@@ -59,6 +94,8 @@ impl<T: Id, C> std::ops::DerefMut for Inversible<T, C> {
 	#[inline]
 	fn deref_mut(&mut self) -> &mut C {
 		self.inverse = Arc::new(OnceCell::new());
+		self.previous_inverse = Arc::new(OnceCell::new());
+		self.compacted_iris = Arc::new(Mutex::new(HashMap::new()));
 		&mut self.context
 	}
 }
@@ -67,20 +104,44 @@ impl<T: Id, C> Inversible<T, C> {
 	pub fn new(context: C) -> Inversible<T, C> {
 		Inversible {
 			context,
-			inverse: Arc::new(OnceCell::new())
+			inverse: Arc::new(OnceCell::new()),
+			previous_inverse: Arc::new(OnceCell::new()),
+			compacted_iris: Arc::new(Mutex::new(HashMap::new()))
 		}
 	}
 
 	pub fn inverse(&self) -> &InverseContext<T> where C: std::ops::Deref, C::Target: Context<T> {
-		self.inverse.get_or_init(|| {
+		let cache = self.context.inverse_cache().unwrap_or(&*self.inverse);
+		cache.get_or_init(|| {
 			InverseContext::from(&*self.context)
 		})
 	}
 
+	/// Get the inverse definition of the given IRI mapping.
+	///
+	/// Only the inverse definition of this IRI mapping is computed, if it was not already.
+	pub fn inverse_definition(&self, term: &Term<T>) -> Option<&InverseDefinition<T>> where C: std::ops::Deref, C::Target: Context<T> {
+		self.inverse().get(&*self.context, term)
+	}
+
+	/// Returns the memoized result of the compaction of the given term, if any.
+	pub(crate) fn compacted_iri(&self, key: &CompactedIriKey<T>) -> Option<JsonValue> {
+		let compacted_iris = self.compacted_iris.lock().unwrap();
+		compacted_iris.get(key).cloned()
+	}
+
+	/// Memoize the result of the compaction of the given term.
+	pub(crate) fn set_compacted_iri(&self, key: CompactedIriKey<T>, value: JsonValue) {
+		let mut compacted_iris = self.compacted_iris.lock().unwrap();
+		compacted_iris.insert(key, value);
+	}
+
 	pub fn into_owned<'a>(self) -> Inversible<T, Mown<'a, C>> {
 		Inversible {
 			context: Mown::Owned(self.context),
-			inverse: self.inverse
+			inverse: self.inverse,
+			previous_inverse: self.previous_inverse,
+			compacted_iris: self.compacted_iris
 		}
 	}
 }
@@ -89,7 +150,25 @@ impl<'a, T: Id, C> Inversible<T, &'a C> {
 	pub fn into_borrowed(self) -> Inversible<T, Mown<'a, C>> {
 		Inversible {
 			context: Mown::Borrowed(self.context),
-			inverse: self.inverse
+			inverse: self.inverse,
+			previous_inverse: self.previous_inverse,
+			compacted_iris: self.compacted_iris
+		}
+	}
+
+	/// Returns the previous context, if any.
+	///
+	/// Unlike `Inversible::new(context.previous_context())`, the inverse of the previous context
+	/// is shared with every clone of this context, and hence computed only once.
+	pub fn previous(&self) -> Option<Inversible<T, &'a C>> where C: Context<T> {
+		match self.context.previous_context() {
+			Some(previous_context) => Some(Inversible {
+				context: previous_context,
+				inverse: self.previous_inverse.clone(),
+				previous_inverse: Arc::new(OnceCell::new()),
+				compacted_iris: Arc::new(Mutex::new(HashMap::new()))
+			}),
+			None => None
 		}
 	}
 }
@@ -98,7 +177,9 @@ impl<'a, T: Id, C> Inversible<T, Mown<'a, C>> {
 	pub fn as_ref(&self) -> Inversible<T, &C> {
 		Inversible {
 			context: self.context.as_ref(),
-			inverse: self.inverse.clone()
+			inverse: self.inverse.clone(),
+			previous_inverse: self.previous_inverse.clone(),
+			compacted_iris: self.compacted_iris.clone()
 		}
 	}
 }
@@ -251,6 +332,87 @@ impl<T: Id> InverseDefinition<T> {
 		self.map.get_mut(container).unwrap()
 	}
 
+	/// Add the given term to the inversion.
+	///
+	/// Terms must be added in order: shortest first, then least in lexicographical order.
+	fn add<C: Context<T>>(&mut self, term: &str, term_definition: &TermDefinition<T, C>, context: &C) {
+		let container = &term_definition.container;
+		let type_lang_map = self.reference_mut(container, || InverseContainer::new(term));
+
+		let type_map = &mut type_lang_map.typ;
+		let lang_map = &mut type_lang_map.language;
+
+		if term_definition.reverse_property {
+			// If the term definition indicates that the term represents a reverse property:
+			if type_map.reverse.is_none() {
+				type_map.reverse = Some(term.to_string())
+			}
+		} else {
+			match &term_definition.typ {
+				Some(Type::None) => {
+					// Otherwise, if term definition has a type mapping which is @none:
+					type_map.set_any(term);
+					lang_map.set_any(term);
+				},
+				Some(typ) => {
+					// Otherwise, if term definition has a type mapping:
+					type_map.set(typ, term)
+				},
+				None => {
+					match (&term_definition.language, &term_definition.direction) {
+						(Some(language), Some(direction)) => {
+							// Otherwise, if term definition has both a language mapping
+							// and a direction mapping:
+							match (language, direction) {
+								(Nullable::Some(language), Nullable::Some(direction)) => {
+									lang_map.set(Nullable::Some((Some(language.as_ref()), Some(*direction))), term)
+								},
+								(Nullable::Some(language), Nullable::Null) => {
+									lang_map.set(Nullable::Some((Some(language.as_ref()), None)), term)
+								},
+								(Nullable::Null, Nullable::Some(direction)) => {
+									lang_map.set(Nullable::Some((None, Some(*direction))), term)
+								},
+								(Nullable::Null, Nullable::Null) => {
+									lang_map.set(Nullable::Null, term)
+								}
+							}
+						},
+						(Some(language), None) => {
+							// Otherwise, if term definition has a language mapping (might
+							// be null):
+							match language {
+								Nullable::Some(language) => {
+									lang_map.set(Nullable::Some((Some(language.as_ref()), None)), term)
+								},
+								Nullable::Null => {
+									lang_map.set(Nullable::Null, term)
+								}
+							}
+						},
+						(None, Some(direction)) => {
+							// Otherwise, if term definition has a direction mapping (might
+							// be null):
+							match direction {
+								Nullable::Some(direction) => {
+									lang_map.set(Nullable::Some((None, Some(*direction))), term)
+								},
+								Nullable::Null => {
+									lang_map.set(Nullable::Some((None, None)), term)
+								}
+							}
+						},
+						(None, None) => {
+							lang_map.set(Nullable::Some((context.default_language(), context.default_base_direction())), term);
+							lang_map.set_none(term);
+							type_map.set_none(term);
+						}
+					}
+				}
+			}
+		}
+	}
+
 	pub fn select(&self, containers: &[Container], selection: &Selection<T>) -> Option<&str> {
 		for container in containers {
 			if let Some(type_lang_map) = self.get(container) {
@@ -280,8 +442,22 @@ impl<T: Id> InverseDefinition<T> {
 	}
 }
 
+/// Inverse context.
+///
+/// The inversion is computed lazily: building the inverse context only groups the terms
+/// of the context by IRI mapping, and the inverse definition of each IRI mapping
+/// is computed the first time it is requested with [`get`](InverseContext::get).
 pub struct InverseContext<T: Id> {
-	map: HashMap<Term<T>, InverseDefinition<T>>
+	map: HashMap<Term<T>, LazyDefinition<T>>
+}
+
+/// Inverse definition of an IRI mapping, computed on demand.
+struct LazyDefinition<T: Id> {
+	/// Terms having this IRI mapping, shortest first, then in lexicographical order.
+	terms: Vec<String>,
+
+	/// Inverse definition, once computed.
+	definition: OnceCell<InverseDefinition<T>>
 }
 
 pub enum Selection<'a, T: Id> {
@@ -311,27 +487,28 @@ impl<T: Id> InverseContext<T> {
 		self.map.contains_key(term)
 	}
 
-	pub fn insert(&mut self, term: Term<T>, value: InverseDefinition<T>) {
-		self.map.insert(term, value);
-	}
+	/// Get the inverse definition of the given IRI mapping.
+	///
+	/// The inverse definition is computed from the given context the first time it is requested.
+	/// The given context must be the context this inverse context has been built from.
+	pub fn get<C: Context<T>>(&self, context: &C, term: &Term<T>) -> Option<&InverseDefinition<T>> {
+		self.map.get(term).map(|lazy| {
+			lazy.definition.get_or_init(|| {
+				let mut result = InverseDefinition::new();
 
-	pub fn get(&self, term: &Term<T>) -> Option<&InverseDefinition<T>> {
-		self.map.get(term)
-	}
-
-	pub fn get_mut(&mut self, term: &Term<T>) -> Option<&mut InverseDefinition<T>> {
-		self.map.get_mut(term)
-	}
+				for term in &lazy.terms {
+					if let Some(term_definition) = context.get(term) {
+						result.add(term, term_definition, context)
+					}
+				}
 
-	fn reference_mut<F: FnOnce() -> InverseDefinition<T>>(&mut self, term: &Term<T>, insert: F) -> &mut InverseDefinition<T> {
-		if !self.contains(term) {
-			self.insert(term.clone(), insert());
-		}
-		self.map.get_mut(term).unwrap()
+				result
+			})
+		})
 	}
 
-	pub fn select(&self, var: &Term<T>, containers: &[Container], selection: &Selection<T>) -> Option<&str> {
-		match self.get(var) {
+	pub fn select<C: Context<T>>(&self, context: &C, var: &Term<T>, containers: &[Container], selection: &Selection<T>) -> Option<&str> {
+		match self.get(context, var) {
 			Some(container_map) => container_map.select(containers, selection),
 			None => None
 		}
@@ -340,99 +517,35 @@ impl<T: Id> InverseContext<T> {
 
 impl<'a, T: Id, C: Context<T>> From<&'a C> for InverseContext<T> {
 	fn from(context: &'a C) -> InverseContext<T> {
-		let mut result = InverseContext::new();
-
-		let mut definitions: Vec<_> = context.definitions().collect();
-		definitions.sort_by(|(a, _), (b, _)| {
-			let ord = a.len().cmp(&b.len());
-			if ord == Ordering::Equal {
-				a.cmp(b)
-			} else {
-				ord
-			}
-		});
+		let mut map: HashMap<Term<T>, LazyDefinition<T>> = HashMap::new();
 
-		for (term, term_definition) in definitions {
+		for (term, term_definition) in context.definitions() {
 			if let Some(var) = term_definition.value.as_ref() {
-				let container = &term_definition.container;
-				let container_map = result.reference_mut(var, || InverseDefinition::new());
-				let type_lang_map = container_map.reference_mut(container, || InverseContainer::new(term));
-
-				let type_map = &mut type_lang_map.typ;
-				let lang_map = &mut type_lang_map.language;
-
-				if term_definition.reverse_property {
-					// If the term definition indicates that the term represents a reverse property:
-					if type_map.reverse.is_none() {
-						type_map.reverse = Some(term.to_string())
-					}
-				} else {
-					match &term_definition.typ {
-						Some(Type::None) => {
-							// Otherwise, if term definition has a type mapping which is @none:
-							type_map.set_any(term);
-							lang_map.set_any(term);
-						},
-						Some(typ) => {
-							// Otherwise, if term definition has a type mapping:
-							type_map.set(typ, term)
-						},
-						None => {
-							match (&term_definition.language, &term_definition.direction) {
-								(Some(language), Some(direction)) => {
-									// Otherwise, if term definition has both a language mapping
-									// and a direction mapping:
-									match (language, direction) {
-										(Nullable::Some(language), Nullable::Some(direction)) => {
-											lang_map.set(Nullable::Some((Some(language.as_ref()), Some(*direction))), term)
-										},
-										(Nullable::Some(language), Nullable::Null) => {
-											lang_map.set(Nullable::Some((Some(language.as_ref()), None)), term)
-										},
-										(Nullable::Null, Nullable::Some(direction)) => {
-											lang_map.set(Nullable::Some((None, Some(*direction))), term)
-										},
-										(Nullable::Null, Nullable::Null) => {
-											lang_map.set(Nullable::Null, term)
-										}
-									}
-								},
-								(Some(language), None) => {
-									// Otherwise, if term definition has a language mapping (might
-									// be null):
-									match language {
-										Nullable::Some(language) => {
-											lang_map.set(Nullable::Some((Some(language.as_ref()), None)), term)
-										},
-										Nullable::Null => {
-											lang_map.set(Nullable::Null, term)
-										}
-									}
-								},
-								(None, Some(direction)) => {
-									// Otherwise, if term definition has a direction mapping (might
-									// be null):
-									match direction {
-										Nullable::Some(direction) => {
-											lang_map.set(Nullable::Some((None, Some(*direction))), term)
-										},
-										Nullable::Null => {
-											lang_map.set(Nullable::Some((None, None)), term)
-										}
-									}
-								},
-								(None, None) => {
-									lang_map.set(Nullable::Some((context.default_language(), context.default_base_direction())), term);
-									lang_map.set_none(term);
-									type_map.set_none(term);
-								}
-							}
-						}
+				match map.get_mut(var) {
+					Some(lazy) => lazy.terms.push(term.clone()),
+					None => {
+						map.insert(var.clone(), LazyDefinition {
+							terms: vec![term.clone()],
+							definition: OnceCell::new()
+						});
 					}
 				}
 			}
 		}
 
-		result
+		for lazy in map.values_mut() {
+			lazy.terms.sort_by(|a, b| {
+				let ord = a.len().cmp(&b.len());
+				if ord == Ordering::Equal {
+					a.cmp(b)
+				} else {
+					ord
+				}
+			});
+		}
+
+		InverseContext {
+			map
+		}
 	}
 }
@@ -0,0 +1,134 @@
+use langtag::LanguageTagBuf;
+use crate::{
+	Error,
+	ErrorCode,
+	Id
+};
+use super::{
+	Context,
+	ContextMut,
+	TermDefinition
+};
+
+/// Policy used to resolve conflicts when merging two contexts.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum MergePolicy {
+	/// Definitions of the merged context replace existing definitions,
+	/// unless they are protected.
+	///
+	/// Merging fails with a `ProtectedTermRedefinition` error
+	/// if a protected term would be redefined differently.
+	Override,
+
+	/// Definitions of the merged context replace existing definitions,
+	/// even protected ones.
+	OverrideProtected,
+
+	/// Existing definitions are kept,
+	/// only the terms undefined in the current context are added.
+	Keep
+}
+
+/// Differences between two contexts.
+///
+/// Created with [`Context::diff`].
+pub struct ContextDiff<'a, T: Id, C: Context<T>> {
+	/// Terms only defined in the new context.
+	pub added: Vec<(&'a str, &'a TermDefinition<T, C>)>,
+
+	/// Terms only defined in the old context.
+	pub removed: Vec<(&'a str, &'a TermDefinition<T, C>)>,
+
+	/// Terms defined in both contexts, with different definitions (old, new).
+	pub changed: Vec<(&'a str, &'a TermDefinition<T, C>, &'a TermDefinition<T, C>)>
+}
+
+impl<'a, T: Id, C: Context<T>> ContextDiff<'a, T, C> {
+	/// Compute the differences from the `old` context to the `new` context.
+	///
+	/// Terms are sorted in each list.
+	pub fn new(old: &'a C, new: &'a C) -> ContextDiff<'a, T, C> {
+		let mut added = Vec::new();
+		let mut removed = Vec::new();
+		let mut changed = Vec::new();
+
+		for (term, old_definition) in old.terms() {
+			match new.get(term) {
+				Some(new_definition) => {
+					if old_definition != new_definition || old_definition.protected != new_definition.protected {
+						changed.push((term, old_definition, new_definition))
+					}
+				},
+				None => removed.push((term, old_definition))
+			}
+		}
+
+		for (term, new_definition) in new.terms() {
+			if !old.contains(term) {
+				added.push((term, new_definition))
+			}
+		}
+
+		added.sort_by(|(a, _), (b, _)| a.cmp(b));
+		removed.sort_by(|(a, _), (b, _)| a.cmp(b));
+		changed.sort_by(|(a, _, _), (b, _, _)| a.cmp(b));
+
+		ContextDiff {
+			added,
+			removed,
+			changed
+		}
+	}
+
+	/// Checks if both contexts define the same terms, with the same definitions.
+	pub fn is_empty(&self) -> bool {
+		self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+	}
+
+	/// Checks if the new context is backward compatible with the old one,
+	/// meaning that no term has been removed or redefined.
+	pub fn is_backward_compatible(&self) -> bool {
+		self.removed.is_empty() && self.changed.is_empty()
+	}
+}
+
+/// Merge the term definitions of `other` into `context`.
+pub(crate) fn merge<T: Id, C: ContextMut<T>>(context: &mut C, other: &C, policy: MergePolicy) -> Result<(), Error> where TermDefinition<T, C>: Clone {
+	let mut definitions = Vec::new();
+	for (term, definition) in other.terms() {
+		match context.get(term) {
+			Some(current) => match policy {
+				MergePolicy::Keep => (),
+				MergePolicy::Override => {
+					if current.protected && current != definition {
+						return Err(ErrorCode::ProtectedTermRedefinition.into())
+					}
+
+					definitions.push((term, definition.clone()))
+				},
+				MergePolicy::OverrideProtected => definitions.push((term, definition.clone()))
+			},
+			None => definitions.push((term, definition.clone()))
+		}
+	}
+
+	let override_scalars = policy != MergePolicy::Keep;
+
+	if other.vocabulary().is_some() && (override_scalars || context.vocabulary().is_none()) {
+		context.set_vocabulary(other.vocabulary().cloned())
+	}
+
+	if other.default_language().is_some() && (override_scalars || context.default_language().is_none()) {
+		context.set_default_language(other.default_language().and_then(|lang| LanguageTagBuf::parse_copy(lang.as_str()).ok()))
+	}
+
+	if other.default_base_direction().is_some() && (override_scalars || context.default_base_direction().is_none()) {
+		context.set_default_base_direction(other.default_base_direction())
+	}
+
+	for (term, definition) in definitions {
+		context.set(term, Some(definition));
+	}
+
+	Ok(())
+}
@@ -3,9 +3,15 @@
 mod definition;
 mod loader;
 mod processing;
+mod merge;
+mod codegen;
+mod validate;
+mod cache;
 pub mod inverse;
 
 use std::collections::HashMap;
+use std::sync::Arc;
+use once_cell::sync::OnceCell;
 use futures::{
 	FutureExt,
 	future::BoxFuture
@@ -19,22 +25,66 @@ use json::JsonValue;
 use crate::{
 	ProcessingMode,
 	Error,
+	OptionsError,
 	Direction,
 	Id,
-	syntax::Term,
-	util
+	Reference,
+	syntax::{
+		Keyword,
+		Term
+	},
+	util::{
+		self,
+		AsJson
+	}
 };
 
 pub use definition::*;
 pub use loader::*;
 pub use processing::*;
+pub use codegen::{
+	generate_rust,
+	generate_vocabulary
+};
+pub use validate::{
+	validate,
+	Report,
+	Warning
+};
+pub use merge::{
+	MergePolicy,
+	ContextDiff
+};
+pub use cache::ProcessedCache;
 pub use inverse::{
 	InverseContext,
 	Inversible
 };
 
+/// Handling of terms having the form of a keyword (such as `@foo`).
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum ReservedTerms {
+	/// Ignore the term definition, as required by the specification.
+	Ignore,
+
+	/// Abort the processing with a `reserved term` error.
+	Reject,
+
+	/// Define the term like any other term.
+	///
+	/// This is not conformant to the specification,
+	/// but may be required to process some legacy contexts.
+	Allow
+}
+
+impl Default for ReservedTerms {
+	fn default() -> ReservedTerms {
+		ReservedTerms::Ignore
+	}
+}
+
 /// Options of the Context Processing Algorithm.
-#[derive(Clone, Copy, PartialEq, Eq)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
 pub struct ProcessingOptions {
 	/// The processing mode
 	pub processing_mode: ProcessingMode,
@@ -43,10 +93,25 @@ pub struct ProcessingOptions {
 	pub override_protected: bool,
 
 	/// Propagate the processed context.
-	pub propagate: bool
+	pub propagate: bool,
+
+	/// Ignore non-propagated contexts (JSON-LD 1.0 compatibility).
+	///
+	/// If true, every processed context propagates to nested nodes,
+	/// regardless of `propagate` and `@propagate` entries:
+	/// type-scoped contexts are never reverted.
+	pub ignore_propagation: bool,
+
+	/// Handling of terms having the form of a keyword.
+	pub reserved_terms: ReservedTerms
 }
 
 impl ProcessingOptions {
+	/// Create a new options builder, starting from the default options.
+	pub fn builder() -> ProcessingOptionsBuilder {
+		ProcessingOptionsBuilder::new()
+	}
+
 	/// Return the same set of options, but with `override_protected` set to `true`.
 	pub fn with_override(&self) -> ProcessingOptions {
 		let mut opt = *self;
@@ -74,11 +139,74 @@ impl Default for ProcessingOptions {
 		ProcessingOptions {
 			processing_mode: ProcessingMode::default(),
 			override_protected: false,
-			propagate: true
+			propagate: true,
+			ignore_propagation: false,
+			reserved_terms: ReservedTerms::Ignore
 		}
 	}
 }
 
+/// Builder of [`ProcessingOptions`].
+pub struct ProcessingOptionsBuilder {
+	options: ProcessingOptions
+}
+
+impl ProcessingOptionsBuilder {
+	/// Create a new builder, starting from the default options.
+	pub fn new() -> ProcessingOptionsBuilder {
+		ProcessingOptionsBuilder {
+			options: ProcessingOptions::default()
+		}
+	}
+
+	/// Set the processing mode.
+	pub fn processing_mode(mut self, mode: ProcessingMode) -> Self {
+		self.options.processing_mode = mode;
+		self
+	}
+
+	/// Override protected definitions.
+	pub fn override_protected(mut self, value: bool) -> Self {
+		self.options.override_protected = value;
+		self
+	}
+
+	/// Propagate the processed context.
+	pub fn propagate(mut self, value: bool) -> Self {
+		self.options.propagate = value;
+		self
+	}
+
+	/// Ignore non-propagated contexts.
+	pub fn ignore_propagation(mut self, value: bool) -> Self {
+		self.options.ignore_propagation = value;
+		self
+	}
+
+	/// Set the handling of terms having the form of a keyword.
+	pub fn reserved_terms(mut self, policy: ReservedTerms) -> Self {
+		self.options.reserved_terms = policy;
+		self
+	}
+
+	/// Validate and build the options.
+	///
+	/// Disabling the propagation requires the `json-ld-1.1` processing mode.
+	pub fn build(self) -> Result<ProcessingOptions, OptionsError> {
+		if !self.options.propagate && self.options.processing_mode == ProcessingMode::JsonLd1_0 {
+			return Err(OptionsError::PropagationRequiresJsonLd1_1)
+		}
+
+		Ok(self.options)
+	}
+}
+
+impl Default for ProcessingOptionsBuilder {
+	fn default() -> ProcessingOptionsBuilder {
+		ProcessingOptionsBuilder::new()
+	}
+}
+
 /// JSON-LD context.
 ///
 /// A context holds all the term definitions used to expand a JSON-LD value.
@@ -126,7 +254,61 @@ pub trait Context<T: Id = IriBuf> : Clone {
 	/// Get the previous context.
 	fn previous_context(&self) -> Option<&Self>;
 
+	/// Cache of the inverse of this context, if the context type provides one.
+	///
+	/// When provided, the inverse context is computed at most once and reused by every
+	/// [`Inversible`] wrapping this context, until the context is modified.
+	/// The default implementation provides no cache.
+	fn inverse_cache(&self) -> Option<&OnceCell<InverseContext<T>>> {
+		None
+	}
+
+	/// Cache of the contexts obtained by processing local JSON contexts on top of this context,
+	/// if the context type provides one.
+	///
+	/// When provided, the same scoped context is processed at most once on top of this context
+	/// for a given base URL and set of options.
+	/// The default implementation provides no cache.
+	fn processed_cache(&self) -> Option<&ProcessedCache<Self>> {
+		None
+	}
+
+	/// Iterate through the term definitions of the context.
 	fn definitions<'a>(&'a self) -> Box<dyn 'a + Iterator<Item = (&'a String, &'a TermDefinition<T, Self>)>>;
+
+	/// Iterate through the defined terms of the context, along with their definition.
+	fn terms<'a>(&'a self) -> Box<dyn 'a + Iterator<Item = (&'a str, &'a TermDefinition<T, Self>)>> {
+		Box::new(self.definitions().map(|(term, definition)| (term.as_str(), definition)))
+	}
+
+	/// Number of term definitions in the context.
+	fn definitions_len(&self) -> usize {
+		self.definitions().count()
+	}
+
+	/// Iterate through the terms whose IRI mapping is the given IRI.
+	///
+	/// Container, type, language and direction mappings are ignored,
+	/// as well as the reverse property flag.
+	fn terms_for_iri<'a: 'b, 'b>(&'a self, iri: Iri<'b>) -> Box<dyn 'b + Iterator<Item = (&'a str, &'a TermDefinition<T, Self>)>> {
+		Box::new(self.terms().filter(move |(_, definition)| match &definition.value {
+			Some(Term::Ref(Reference::Id(id))) => id.as_iri() == iri,
+			_ => false
+		}))
+	}
+
+	/// Returns the preferred term whose IRI mapping is the given IRI, if any.
+	///
+	/// Following the term selection algorithm, the shortest term is preferred,
+	/// then the least in lexicographical order.
+	fn term_for_iri(&self, iri: Iri) -> Option<&str> {
+		self.terms_for_iri(iri).map(|(term, _)| term).min_by(|a, b| a.len().cmp(&b.len()).then_with(|| a.cmp(b)))
+	}
+
+	/// Compute the term definitions added, removed and changed in `other` relative to this context.
+	fn diff<'a>(&'a self, other: &'a Self) -> ContextDiff<'a, T, Self> {
+		ContextDiff::new(self, other)
+	}
 }
 
 /// Mutable JSON-LD context.
@@ -148,6 +330,14 @@ pub trait ContextMut<T: Id = IriBuf>: Context<T> {
 
 	/// Sets the previous context.
 	fn set_previous_context(&mut self, previous: Self);
+
+	/// Merge the term definitions of `other` into this context, following the given policy.
+	///
+	/// The vocabulary mapping, default language and default base direction of `other`
+	/// are also merged, if defined.
+	fn merge(&mut self, other: &Self, policy: MergePolicy) -> Result<(), Error> where TermDefinition<T, Self>: Clone {
+		merge::merge(self, other, policy)
+	}
 }
 
 /// Trait for types that are or wrap a mutable context.
@@ -256,17 +446,65 @@ impl<L, C> std::convert::AsRef<C> for Processed<L, C> {
 	}
 }
 
-#[derive(Clone, PartialEq, Eq)]
+/// JSON context.
+///
+/// The previous context and the term definitions are shared between clones,
+/// and only copied when modified, so cloning a context (as done for every
+/// scoped context processed) is cheap.
+/// Each term definition is also shared individually, so modifying a shared context
+/// only copies the definition table, not the definitions themselves.
+/// This copy still takes time linear in the number of defined terms:
+/// it happens on the first modification following a clone,
+/// and subsequent modifications of the same context do not copy the table again.
+///
+/// The inverse context is also cached, and shared between clones until the context is modified.
+/// Local contexts processed on top of this context are cached as well,
+/// but this cache is not shared between clones.
 pub struct JsonContext<T: Id = IriBuf> {
 	original_base_url: Option<IriBuf>,
 	base_iri: Option<IriBuf>,
 	vocabulary: Option<Term<T>>,
 	default_language: Option<LanguageTagBuf>,
 	default_base_direction: Option<Direction>,
-	previous_context: Option<Box<Self>>,
-	definitions: HashMap<String, TermDefinition<T, Self>>
+	previous_context: Option<Arc<Self>>,
+	definitions: Arc<HashMap<String, Arc<TermDefinition<T, Self>>>>,
+	inverse: Arc<OnceCell<InverseContext<T>>>,
+	processed: ProcessedCache<Self>
+}
+
+impl<T: Id> Clone for JsonContext<T> {
+	fn clone(&self) -> Self {
+		// NOTE the processed contexts cache is not cloned,
+		//      so a context never ends up in its own cache.
+		JsonContext {
+			original_base_url: self.original_base_url.clone(),
+			base_iri: self.base_iri.clone(),
+			vocabulary: self.vocabulary.clone(),
+			default_language: self.default_language.clone(),
+			default_base_direction: self.default_base_direction,
+			previous_context: self.previous_context.clone(),
+			definitions: self.definitions.clone(),
+			inverse: self.inverse.clone(),
+			processed: ProcessedCache::new()
+		}
+	}
 }
 
+impl<T: Id> PartialEq for JsonContext<T> {
+	fn eq(&self, other: &Self) -> bool {
+		// NOTE we ignore the cached inverse and processed contexts.
+		self.original_base_url == other.original_base_url &&
+		self.base_iri == other.base_iri &&
+		self.vocabulary == other.vocabulary &&
+		self.default_language == other.default_language &&
+		self.default_base_direction == other.default_base_direction &&
+		self.previous_context == other.previous_context &&
+		self.definitions == other.definitions
+	}
+}
+
+impl<T: Id> Eq for JsonContext<T> {}
+
 impl<T: Id> JsonContext<T> {
 	pub fn new(base_iri: Option<Iri>) -> JsonContext<T> {
 		JsonContext {
@@ -276,7 +514,9 @@ impl<T: Id> JsonContext<T> {
 			default_language: None,
 			default_base_direction: None,
 			previous_context: None,
-			definitions: HashMap::new()
+			definitions: Arc::new(HashMap::new()),
+			inverse: Arc::new(OnceCell::new()),
+			processed: ProcessedCache::new()
 		}
 	}
 }
@@ -298,7 +538,9 @@ impl<T: Id> Default for JsonContext<T> {
 			default_language: None,
 			default_base_direction: None,
 			previous_context: None,
-			definitions: HashMap::new()
+			definitions: Arc::new(HashMap::new()),
+			inverse: Arc::new(OnceCell::new()),
+			processed: ProcessedCache::new()
 		}
 	}
 }
@@ -311,7 +553,7 @@ impl<T: Id> Context<T> for JsonContext<T> {
 	}
 
 	fn get(&self, term: &str) -> Option<&TermDefinition<T, Self>> {
-		self.definitions.get(term)
+		self.definitions.get(term).map(|definition| &**definition)
 	}
 
 	fn original_base_url(&self) -> Option<Iri> {
@@ -354,23 +596,94 @@ impl<T: Id> Context<T> for JsonContext<T> {
 	}
 
 	fn definitions<'a>(&'a self) -> Box<dyn 'a + Iterator<Item = (&'a String, &'a TermDefinition<T, Self>)>> {
-		Box::new(self.definitions.iter())
+		Box::new(self.definitions.iter().map(|(term, definition)| (term, &**definition)))
+	}
+
+	fn definitions_len(&self) -> usize {
+		self.definitions.len()
+	}
+
+	fn inverse_cache(&self) -> Option<&OnceCell<InverseContext<T>>> {
+		Some(&self.inverse)
+	}
+
+	fn processed_cache(&self) -> Option<&ProcessedCache<Self>> {
+		Some(&self.processed)
+	}
+}
+
+impl<T: Id> JsonContext<T> {
+	/// Invalidate the cached inverse and processed contexts.
+	fn invalidate_caches(&mut self) {
+		if self.inverse.get().is_some() {
+			self.inverse = Arc::new(OnceCell::new())
+		}
+
+		if !self.processed.is_empty() {
+			self.processed = ProcessedCache::new()
+		}
+	}
+}
+
+/// Reconstructed `@context` JSON object defining this context.
+///
+/// Term definitions are emitted in order.
+/// The previous context (used to revert non-propagated contexts) is not represented.
+impl<T: Id> util::AsJson for JsonContext<T> {
+	fn as_json(&self) -> JsonValue {
+		let mut obj = json::object::Object::new();
+
+		if self.base_iri != self.original_base_url {
+			match &self.base_iri {
+				Some(base_iri) => obj.insert(Keyword::Base.into(), base_iri.as_str().into()),
+				None => obj.insert(Keyword::Base.into(), JsonValue::Null)
+			}
+		}
+
+		if let Some(vocabulary) = &self.vocabulary {
+			obj.insert(Keyword::Vocab.into(), vocabulary.as_json())
+		}
+
+		if let Some(language) = &self.default_language {
+			obj.insert(Keyword::Language.into(), language.as_json())
+		}
+
+		if let Some(direction) = self.default_base_direction {
+			obj.insert(Keyword::Direction.into(), direction.as_json())
+		}
+
+		let mut definitions: Vec<_> = self.definitions.iter().collect();
+		definitions.sort_by(|(a, _), (b, _)| a.cmp(b));
+		for (term, definition) in definitions {
+			obj.insert(term, definition.as_json())
+		}
+
+		JsonValue::Object(obj)
 	}
 }
 
 impl<T: Id> ContextMut<T> for JsonContext<T> {
+	/// Defines the given term.
+	///
+	/// If the definition table is shared with a clone of this context,
+	/// it is first copied (see [`JsonContext`]), which is linear in the number of defined terms.
 	fn set(&mut self, term: &str, definition: Option<TermDefinition<T, Self>>) -> Option<TermDefinition<T, Self>> {
-		match definition {
+		self.invalidate_caches();
+		let previous = match definition {
 			Some(def) => {
-				self.definitions.insert(term.to_string(), def)
+				Arc::make_mut(&mut self.definitions).insert(term.to_string(), Arc::new(def))
 			},
 			None => {
-				self.definitions.remove(term)
+				Arc::make_mut(&mut self.definitions).remove(term)
 			}
-		}
+		};
+
+		// The previous definition is only copied if it is still shared.
+		previous.map(|def| Arc::try_unwrap(def).unwrap_or_else(|def| (*def).clone()))
 	}
 
 	fn set_base_iri(&mut self, iri: Option<Iri>) {
+		self.invalidate_caches();
 		self.base_iri = match iri {
 			Some(iri) => {
 				let mut iri_buf: IriBuf = iri.into();
@@ -382,18 +695,22 @@ impl<T: Id> ContextMut<T> for JsonContext<T> {
 	}
 
 	fn set_vocabulary(&mut self, vocab: Option<Term<T>>) {
+		self.invalidate_caches();
 		self.vocabulary = vocab;
 	}
 
 	fn set_default_language(&mut self, lang: Option<LanguageTagBuf>) {
+		self.invalidate_caches();
 		self.default_language = lang;
 	}
 
 	fn set_default_base_direction(&mut self, dir: Option<Direction>) {
+		self.invalidate_caches();
 		self.default_base_direction = dir;
 	}
 
 	fn set_previous_context(&mut self, previous: Self) {
-		self.previous_context = Some(Box::new(previous))
+		self.invalidate_caches();
+		self.previous_context = Some(Arc::new(previous))
 	}
 }
@@ -30,6 +30,7 @@ use crate::{
 };
 use super::{
 	ProcessingOptions,
+	ReservedTerms,
 	Local,
 	Context,
 	ContextMut,
@@ -42,7 +43,27 @@ impl<T: Id> Local<T> for JsonValue {
 	/// Load a local context.
 	fn process_full<'a, 's: 'a, C: Send + Sync + ContextMut<T>, L: Send + Sync + Loader>(&'s self, active_context: &'a C, stack: ProcessingStack, loader: &'a mut L, base_url: Option<Iri<'a>>, options: ProcessingOptions) -> BoxFuture<'a, Result<Processed<&'s Self, C>, Error>> where C::LocalContext: Send + Sync + From<L::Output> + From<Self>, L::Output: Into<Self>, T: Send + Sync {
 		async move {
-			Ok(Processed::new(self, process_context(active_context, self, stack, loader, base_url, options).await?))
+			// Processed contexts are only cached from the top of the processing stack,
+			// where the result does not depend on the remote contexts being loaded.
+			let cache = if stack.is_empty() {
+				active_context.processed_cache()
+			} else {
+				None
+			};
+
+			if let Some(cache) = cache {
+				if let Some(processed) = cache.get(self, base_url, options) {
+					return Ok(Processed::new(self, processed))
+				}
+			}
+
+			let processed = process_context(active_context, self, stack, loader, base_url, options).await?;
+
+			if let Some(cache) = cache {
+				cache.insert(self, base_url, options, processed.clone())
+			}
+
+			Ok(Processed::new(self, processed))
 		}.boxed()
 	}
 }
@@ -183,6 +204,11 @@ fn process_context<'a, T: Send + Sync + Id, C: Send + Sync + ContextMut<T>, L: S
 			}
 		}
 
+		// Non-propagated contexts are ignored on demand, for JSON-LD 1.0 compatibility.
+		if options.ignore_propagation {
+			options.propagate = true
+		}
+
 		// 3) If propagate is false, and result does not have a previous context,
 		// set previous context in result to active context.
 		if !options.propagate && result.previous_context().is_none() {
@@ -226,10 +252,9 @@ fn process_context<'a, T: Send + Sync + Id, C: Send + Sync + ContextMut<T>, L: S
 					// Initialize `context` to the result of resolving context against base URL.
 					// If base URL is not a valid IRI, then context MUST be a valid IRI, otherwise
 					// a loading document failed error has been detected and processing is aborted.
-					let context = if let Ok(iri_ref) = IriRef::new(context.as_str().unwrap()) {
-						resolve_iri(iri_ref, base_url).ok_or(Error::from(ErrorCode::LoadingRemoteContextFailed))?
-					} else {
-						return Err(ErrorCode::LoadingDocumentFailed.into())
+					let context = match IriRef::new(context.as_str().unwrap()) {
+						Ok(iri_ref) => resolve_iri(iri_ref, base_url).ok_or_else(|| Error::from(ErrorCode::LoadingRemoteContextFailed).with_subject(context.as_str().unwrap()))?,
+						Err(_) => return Err(Error::from(ErrorCode::LoadingDocumentFailed).with_subject(context.as_str().unwrap()))
 					};
 
 					// If the number of entries in the `remote_contexts` array exceeds a processor
@@ -253,7 +278,7 @@ fn process_context<'a, T: Send + Sync + Id, C: Send + Sync + ContextMut<T>, L: S
 					// context has been detected and processing is aborted.
 					// Set loaded context to the value of that entry.
 					if remote_contexts.push(context.as_iri()) {
-						let context_document = loader.load_context(context.as_iri()).await?.cast::<JsonValue>();
+						let context_document = loader.load_context(context.as_iri()).await.map_err(|e| e.with_subject(context.as_str()))?.cast::<JsonValue>();
 						let loaded_context = context_document.context();
 
 
@@ -263,7 +288,9 @@ fn process_context<'a, T: Send + Sync + Id, C: Send + Sync + ContextMut<T>, L: S
 						let new_options = ProcessingOptions {
 							processing_mode: options.processing_mode,
 							override_protected: false,
-							propagate: true
+							propagate: true,
+							ignore_propagation: options.ignore_propagation,
+							reserved_terms: options.reserved_terms
 						};
 
 						result = loaded_context.process_full(&result, remote_contexts.clone(), loader, Some(context_document.url()), new_options).await?.into_inner();
@@ -448,10 +475,19 @@ fn process_context<'a, T: Send + Sync + Id, C: Send + Sync + ContextMut<T>, L: S
 					// has already been defined or is currently being defined during recursion.
 					let mut defined = HashMap::new();
 
-					let protected = if let Some(JsonValue::Boolean(protected)) = context.get(Keyword::Protected.into()) {
-						*protected
-					} else {
-						false
+					let protected = match context.get(Keyword::Protected.into()) {
+						Some(protected_value) => {
+							// `@protected` is not available in json-ld-1.0.
+							if options.processing_mode == ProcessingMode::JsonLd1_0 {
+								return Err(ErrorCode::ProcessingModeConflict.into())
+							}
+
+							match protected_value {
+								JsonValue::Boolean(protected) => *protected,
+								_ => false
+							}
+						},
+						None => false
 					};
 
 					// 5.13) For each key-value pair in context where key is not
@@ -465,7 +501,7 @@ fn process_context<'a, T: Send + Sync + Id, C: Send + Sync + ContextMut<T>, L: S
 						match key {
 							"@base" | "@direction" | "@import" | "@language" | "@propagate" | "@protected" | "@version" | "@vocab" => (),
 							_ => {
-								define(&mut result, context.as_ref(), key, &mut defined, remote_contexts.clone(), loader, base_url, protected, options).await?
+								define(&mut result, context.as_ref(), key, &mut defined, remote_contexts.clone(), loader, base_url, protected, options).await.map_err(|e| e.with_subject(key))?
 							}
 						}
 					}
@@ -612,9 +648,14 @@ pub fn define<'a, T: Send + Sync + Id, C: Send + Sync + ContextMut<T>, L: Send +
 							// If term has the form of a keyword (i.e., it matches the ABNF rule "@"1*ALPHA
 							// from [RFC5234]), return; processors SHOULD generate a warning.
 							if is_keyword_like(term) {
-
-								// TODO warning
-								return Ok(())
+								match options.reserved_terms {
+									ReservedTerms::Ignore => {
+										// TODO warning
+										return Ok(())
+									},
+									ReservedTerms::Reject => return Err(Error::from(ErrorCode::ReservedTerm).with_subject(term)),
+									ReservedTerms::Allow => ()
+								}
 							}
 						}
 					}
@@ -1081,6 +1122,11 @@ pub fn define<'a, T: Send + Sync + Id, C: Send + Sync + ContextMut<T>, L: Send +
 						// If `value` contains the entry `@direction` and does not contain the
 						// entry `@type`:
 						if let Some(direction_value) = value.get("@direction") {
+							// `@direction` is not available in json-ld-1.0.
+							if options.processing_mode == ProcessingMode::JsonLd1_0 {
+								return Err(ErrorCode::ProcessingModeConflict.into())
+							}
+
 							// Initialize `direction` to the value associated with the `@direction`
 							// entry, which MUST be either null, "ltr", or "rtl".
 							definition.direction = Some(match direction_value.as_str() {
@@ -1202,7 +1248,8 @@ pub fn expand_iri<'a, T: Send + Sync + Id, C: Send + Sync + ContextMut<T>, L: Se
 		} else {
 			// If value has the form of a keyword, a processor SHOULD generate a warning and return
 			// null.
-			if is_keyword_like(value.as_ref()) {
+			// Terms having the form of a keyword may only be defined on demand.
+			if is_keyword_like(value.as_ref()) && (options.reserved_terms != ReservedTerms::Allow || (local_context.get(value.as_ref()).is_none() && active_context.get(value.as_ref()).is_none())) {
 				// TODO warning
 				return Ok(Term::Null.into())
 			}
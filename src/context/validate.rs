@@ -0,0 +1,154 @@
+use std::convert::TryFrom;
+use futures::future::{BoxFuture, FutureExt};
+use iref::Iri;
+use json::JsonValue;
+use crate::{
+	Error,
+	Id,
+	syntax::Keyword,
+	util
+};
+use super::{
+	Context,
+	JsonContext,
+	Local,
+	Loader,
+	ProcessingOptions
+};
+
+/// IRI schemes that are not reported as unresolvable prefixes.
+const KNOWN_SCHEMES: &[&str] = &[
+	"http", "https", "ftp", "file", "urn", "mailto", "tel", "tag", "data", "did"
+];
+
+/// Warning raised by the context linter.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Warning {
+	/// The IRI mapping of a term uses a compact IRI whose prefix is not defined.
+	///
+	/// The IRI is then interpreted as an absolute IRI with the prefix as scheme.
+	UnresolvablePrefix {
+		term: String,
+		prefix: String
+	},
+
+	/// A term has the form of a keyword (`@` followed by letters) without being a keyword.
+	///
+	/// Such terms are ignored by the context processing algorithm.
+	KeywordLikeTerm(String),
+
+	/// A `@protected` entry has no effect.
+	///
+	/// This is either a context-level `@protected` entry in a context without term definitions,
+	/// or a term-level `@protected` entry with the same value as the context-level entry.
+	/// The term is `None` for context-level entries.
+	UnusedProtected(Option<String>)
+}
+
+/// Result of the validation of a context.
+pub struct Report<T: Id> {
+	/// Processed context, if the processing succeeded.
+	pub context: Option<JsonContext<T>>,
+
+	/// Processing error, if any.
+	pub error: Option<Error>,
+
+	/// Warnings.
+	pub warnings: Vec<Warning>
+}
+
+impl<T: Id> Report<T> {
+	/// Checks if the context has been processed successfully.
+	pub fn is_valid(&self) -> bool {
+		self.error.is_none()
+	}
+
+	/// Checks if the context has been processed successfully, without warnings.
+	pub fn is_clean(&self) -> bool {
+		self.is_valid() && self.warnings.is_empty()
+	}
+}
+
+/// Validate the given local context in isolation.
+///
+/// The context is processed against an empty active context with the actual context processing
+/// algorithm, so any processing error is reported as it would be during expansion or compaction.
+/// The local context is then inspected for likely mistakes, reported as [`Warning`]s.
+pub fn validate<'a, T: Id + Send + Sync, L: Send + Sync + Loader>(local_context: &'a JsonValue, loader: &'a mut L, base_url: Option<Iri<'a>>, options: ProcessingOptions) -> BoxFuture<'a, Report<T>> where JsonValue: From<L::Output>, L::Output: Into<JsonValue> {
+	async move {
+		let active_context = JsonContext::new(base_url);
+		let (context, error) = match local_context.process_with(&active_context, loader, base_url, options).await {
+			Ok(processed) => (Some(processed.into_inner()), None),
+			Err(e) => (None, Some(e))
+		};
+
+		let mut warnings = Vec::new();
+		lint(local_context, context.as_ref(), &mut warnings);
+
+		Report {
+			context,
+			error,
+			warnings
+		}
+	}.boxed()
+}
+
+fn is_keyword_like(key: &str) -> bool {
+	key.len() > 1 && key.starts_with('@') && key[1..].chars().all(|c| c.is_ascii_alphabetic())
+}
+
+fn lint<T: Id>(local_context: &JsonValue, processed: Option<&JsonContext<T>>, warnings: &mut Vec<Warning>) {
+	for context in util::as_array(local_context) {
+		if let JsonValue::Object(context) = context {
+			let protected = context.get(Keyword::Protected.into()).and_then(JsonValue::as_bool);
+			let mut has_terms = false;
+
+			for (term, definition) in context.iter() {
+				if Keyword::try_from(term).is_ok() {
+					continue
+				}
+
+				if is_keyword_like(term) {
+					warnings.push(Warning::KeywordLikeTerm(term.to_string()));
+					continue
+				}
+
+				has_terms = true;
+
+				let iri = match definition {
+					JsonValue::Object(definition) => {
+						if let Some(term_protected) = definition.get(Keyword::Protected.into()).and_then(JsonValue::as_bool) {
+							if term_protected == protected.unwrap_or(false) {
+								warnings.push(Warning::UnusedProtected(Some(term.to_string())))
+							}
+						}
+
+						if let Some(scoped) = definition.get(Keyword::Context.into()) {
+							lint(scoped, processed, warnings)
+						}
+
+						definition.get(Keyword::Id.into()).or_else(|| definition.get(Keyword::Reverse.into())).and_then(JsonValue::as_str)
+					},
+					definition => definition.as_str()
+				};
+
+				if let Some(iri) = iri {
+					if let Some(i) = iri.find(':') {
+						let (prefix, suffix) = (&iri[..i], &iri[(i+1)..]);
+						let defined = context.get(prefix).is_some() || processed.map(|c| c.contains(prefix)).unwrap_or(false);
+						if prefix != "_" && !suffix.starts_with("//") && !defined && !KNOWN_SCHEMES.contains(&prefix) {
+							warnings.push(Warning::UnresolvablePrefix {
+								term: term.to_string(),
+								prefix: prefix.to_string()
+							})
+						}
+					}
+				}
+			}
+
+			if protected.is_some() && !has_terms {
+				warnings.push(Warning::UnusedProtected(None))
+			}
+		}
+	}
+}
@@ -0,0 +1,191 @@
+//! Diagnostics reports.
+//!
+//! The functions of this module run the expansion and compaction algorithms in *analysis* mode:
+//! every recoverable issue is collected instead of aborting the processing,
+//! and a structured [`Report`] is returned along with the result.
+use std::sync::{Arc, Mutex};
+use futures::future::{BoxFuture, FutureExt};
+use iref::Iri;
+use json::JsonValue;
+use crate::{
+	Id,
+	Error,
+	Issue,
+	IssueHandler,
+	Action,
+	Severity,
+	Document,
+	ExpandedDocument,
+	ContextMutProxy,
+	Context,
+	ContextMut,
+	context::Loader,
+	expansion,
+	compaction,
+	util::AsJson
+};
+
+/// Issue handler collecting every issue, and never aborting the processing.
+#[derive(Default)]
+struct Collector {
+	issues: Mutex<Vec<Issue>>
+}
+
+impl IssueHandler for Collector {
+	fn handle(&self, issue: &Issue) -> Action {
+		self.issues.lock().unwrap().push(issue.clone());
+		Action::Continue
+	}
+}
+
+impl Collector {
+	fn take(&self) -> Vec<Issue> {
+		std::mem::take(&mut *self.issues.lock().unwrap())
+	}
+}
+
+/// Diagnostics report.
+///
+/// Lists every issue encountered during the processing of a document,
+/// and the error that aborted the processing, if any.
+/// Use the [`AsJson`] implementation to get a JSON representation of the report.
+pub struct Report {
+	/// Issues encountered during the processing, in order.
+	issues: Vec<Issue>,
+
+	/// Error that aborted the processing, if any.
+	error: Option<Error>
+}
+
+impl Report {
+	/// Checks if the processing succeeded.
+	pub fn is_success(&self) -> bool {
+		self.error.is_none()
+	}
+
+	/// Get the error that aborted the processing, if any.
+	pub fn error(&self) -> Option<&Error> {
+		self.error.as_ref()
+	}
+
+	/// Get all the issues encountered during the processing.
+	pub fn issues(&self) -> &[Issue] {
+		&self.issues
+	}
+
+	/// Iterate through the issues with the given severity.
+	pub fn with_severity(&self, severity: Severity) -> impl Iterator<Item = &Issue> {
+		self.issues.iter().filter(move |issue| issue.severity == severity)
+	}
+
+	/// Iterate through the values dropped during the processing.
+	///
+	/// A value is dropped when the processing recovers from an error.
+	pub fn dropped(&self) -> impl Iterator<Item = &JsonValue> {
+		self.with_severity(Severity::Error).filter_map(|issue| issue.fragment.as_ref())
+	}
+}
+
+impl AsJson for Issue {
+	fn as_json(&self) -> JsonValue {
+		let mut obj = json::object::Object::new();
+		obj.insert("severity", self.severity.as_str().into());
+		obj.insert("code", self.code.as_str().into());
+
+		if let Some(subject) = &self.subject {
+			obj.insert("subject", subject.as_str().into())
+		}
+
+		if let Some(fragment) = &self.fragment {
+			obj.insert("fragment", fragment.clone())
+		}
+
+		JsonValue::Object(obj)
+	}
+}
+
+/// JSON representation of the report.
+///
+/// The report is an object with a `success` boolean entry,
+/// an `errors` and a `warnings` entries listing the issues of each severity,
+/// a `dropped` entry listing the dropped values,
+/// and an `error` entry describing the error that aborted the processing, if any.
+impl AsJson for Report {
+	fn as_json(&self) -> JsonValue {
+		let mut obj = json::object::Object::new();
+		obj.insert("success", self.is_success().into());
+
+		if let Some(error) = &self.error {
+			let mut error_obj = json::object::Object::new();
+			error_obj.insert("code", error.code().as_str().into());
+
+			if let Some(subject) = error.subject() {
+				error_obj.insert("subject", subject.into())
+			}
+
+			if let Some(fragment) = error.fragment() {
+				error_obj.insert("fragment", fragment.clone())
+			}
+
+			obj.insert("error", JsonValue::Object(error_obj))
+		}
+
+		obj.insert("errors", JsonValue::Array(self.with_severity(Severity::Error).map(Issue::as_json).collect()));
+		obj.insert("warnings", JsonValue::Array(self.with_severity(Severity::Warning).map(Issue::as_json).collect()));
+		obj.insert("dropped", JsonValue::Array(self.dropped().cloned().collect()));
+
+		JsonValue::Object(obj)
+	}
+}
+
+/// Expand the given document in analysis mode.
+///
+/// Any issue handler set in the options is replaced.
+/// Returns the expanded document, if the expansion succeeded, along with the diagnostics report.
+pub fn analyze_expansion<'a, T: 'a + Send + Sync + Id, D: Sync + Document<T>, C: Send + Sync + ContextMut<T>, L: Send + Sync + Loader>(document: &'a D, base_url: Option<Iri<'a>>, context: &'a C, loader: &'a mut L, mut options: expansion::Options) -> BoxFuture<'a, (Option<ExpandedDocument<T>>, Report)> where
+	C::LocalContext: Send + Sync + From<L::Output> + From<D::LocalContext>,
+	L::Output: Into<D::LocalContext>
+{
+	async move {
+		let collector = Arc::new(Collector::default());
+		options.issue_handler = Some(collector.clone());
+
+		let result = document.expand_with(base_url, context, loader, options).await;
+		let issues = collector.take();
+
+		match result {
+			Ok(expanded) => (Some(expanded), Report { issues, error: None }),
+			Err(e) => (None, Report { issues, error: Some(e) })
+		}
+	}.boxed()
+}
+
+/// Compact the given document in analysis mode.
+///
+/// The document is first expanded in analysis mode, then compacted with the given context.
+/// Returns the compacted document, if the compaction succeeded, along with the diagnostics report.
+pub fn analyze_compaction<'a, T: 'a + Send + Sync + Id, D: Sync + Document<T>, C: ContextMutProxy<T> + Send + Sync + AsJson, L: Send + Sync + Loader>(document: &'a D, base_url: Option<Iri<'a>>, context: &'a C, loader: &'a mut L, options: compaction::Options) -> BoxFuture<'a, (Option<JsonValue>, Report)> where
+	C::Target: Send + Sync,
+	<C::Target as Context<T>>::LocalContext: Send + Sync + From<L::Output> + From<D::LocalContext>,
+	L::Output: Into<D::LocalContext>
+{
+	async move {
+		let base_url = match &options.base {
+			Some(base) => Some(base.as_iri()),
+			None => base_url
+		};
+		let expansion_context = C::Target::new(base_url);
+		let (expanded, mut report) = analyze_expansion(document, base_url, &expansion_context, loader, options.clone().into()).await;
+
+		match expanded {
+			Some(expanded) => match expanded.compact(context, loader, options).await {
+				Ok(compacted) => (Some(compacted), report),
+				Err(e) => {
+					report.error = Some(e);
+					(None, report)
+				}
+			},
+			None => (None, report)
+		}
+	}.boxed()
+}
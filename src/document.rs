@@ -1,4 +1,10 @@
-use std::collections::HashSet;
+use std::collections::{
+	HashMap,
+	HashSet
+};
+use std::fmt;
+use std::borrow::Borrow;
+use std::convert::TryFrom;
 use std::ops::{
 	Deref,
 	DerefMut
@@ -6,29 +12,678 @@ use std::ops::{
 use futures::future::{BoxFuture, FutureExt};
 use iref::{
 	Iri,
-	IriBuf
+	IriBuf,
+	IriRef
 };
 use json::JsonValue;
 use crate::{
 	Error,
+	ErrorCode,
 	Id,
 	Indexed,
 	Object,
+	Node,
+	Reference,
+	ToReference,
+	Lenient,
 	Context,
 	ContextMut,
 	ContextMutProxy,
 	context::{
 		self,
-		Loader
+		Loader,
+		Local
 	},
+	object,
 	expansion,
 	compaction
 };
 
 /// Result of the document expansion algorithm.
 ///
-/// It is just an alias for a set of (indexed) objects.
-pub type ExpandedDocument<T> = HashSet<Indexed<Object<T>>>;
+/// It is a set of (indexed) objects, where top-level nodes are indexed by identifier.
+pub struct ExpandedDocument<T: Id> {
+	/// Objects of the document.
+	objects: Vec<Indexed<Object<T>>>,
+
+	/// Position of the top-level nodes in `objects`, by identifier.
+	index: HashMap<Reference<T>, usize>
+}
+
+impl<T: Id> ExpandedDocument<T> {
+	/// Creates a new expanded document from its objects.
+	pub fn new(objects: HashSet<Indexed<Object<T>>>) -> ExpandedDocument<T> {
+		Self::from_vec(objects.into_iter().collect())
+	}
+
+	/// Creates a new expanded document from its objects, preserving their order.
+	pub(crate) fn from_vec(objects: Vec<Indexed<Object<T>>>) -> ExpandedDocument<T> {
+		let mut doc = ExpandedDocument {
+			objects,
+			index: HashMap::new()
+		};
+
+		doc.reindex();
+		doc
+	}
+
+	/// Rebuild the index of the top-level nodes and named graphs.
+	fn reindex(&mut self) {
+		self.index.clear();
+		for (i, object) in self.objects.iter().enumerate() {
+			if let Some(Lenient::Ok(id)) = object.id() {
+				self.index.entry(id.clone()).or_insert(i);
+			}
+		}
+	}
+
+	/// Returns the number of top-level objects in the document.
+	pub fn len(&self) -> usize {
+		self.objects.len()
+	}
+
+	/// Checks if the document has no top-level objects.
+	pub fn is_empty(&self) -> bool {
+		self.objects.is_empty()
+	}
+
+	/// Returns the top-level objects of the document.
+	pub fn objects(&self) -> &[Indexed<Object<T>>] {
+		&self.objects
+	}
+
+	/// Returns an iterator over the top-level objects of the document.
+	pub fn iter(&self) -> std::slice::Iter<Indexed<Object<T>>> {
+		self.objects.iter()
+	}
+
+	/// Get the top-level node with the given identifier, if any.
+	///
+	/// If multiple top-level nodes share the same identifier,
+	/// there are no guaranties on which node will be returned.
+	pub fn get<'a, Q: ToReference<T>>(&self, id: Q) -> Option<&Indexed<Object<T>>> where T: 'a {
+		match self.index.get(id.to_ref().borrow()) {
+			Some(i) => Some(&self.objects[*i]),
+			None => None
+		}
+	}
+
+	/// Get the top-level node with the given identifier, if any.
+	///
+	/// Same as [`get`](ExpandedDocument::get), but returns the node itself.
+	pub fn get_node<'a, Q: ToReference<T>>(&self, id: Q) -> Option<&Node<T>> where T: 'a {
+		match self.get(id).map(|object| object.inner()) {
+			Some(Object::Node(node)) => Some(node),
+			_ => None
+		}
+	}
+
+	/// Find the node with the given identifier, at any depth of the document.
+	///
+	/// Unlike [`get_node`](ExpandedDocument::get_node), this also searches through
+	/// graphs, included nodes and nested node objects.
+	/// Top-level nodes are found first.
+	pub fn find<'a, Q: ToReference<T>>(&self, id: Q) -> Option<&Node<T>> where T: 'a {
+		let id = id.to_ref();
+		let id: &Reference<T> = id.borrow();
+
+		if let Some(node) = self.get_node(id) {
+			return Some(node)
+		}
+
+		self.traverse().filter_map(|(_, item)| item.as_node()).find(|node| match node.id() {
+			Some(Lenient::Ok(node_id)) => node_id == id,
+			_ => false
+		})
+	}
+
+	/// Returns all the nodes of the given type, at any depth of the document.
+	///
+	/// This searches through graphs, included nodes and nested node objects.
+	pub fn nodes_with_type<'b, U>(&'b self, ty: &'b U) -> impl 'b + Iterator<Item = &'b Node<T>> where Lenient<Reference<T>>: PartialEq<U> {
+		self.traverse().filter_map(|(_, item)| item.as_node()).filter(move |node| node.has_type(ty))
+	}
+
+	/// Returns every node identifier and type of the document that is neither an IRI
+	/// nor a blank node identifier, at any depth, with the path of the node using it.
+	pub fn unresolved(&self) -> impl '_ + Iterator<Item = (crate::Path<T>, crate::Unresolved)> {
+		self.traverse().flat_map(|(path, item)| {
+			let (id, types) = match item {
+				crate::Visited::Object(object) => match object.inner() {
+					Object::Node(node) => (node.id(), node.types()),
+					Object::Graph(graph) => (graph.id(), &[][..]),
+					_ => (None, &[][..])
+				},
+				crate::Visited::Node(node) => (node.id(), node.types())
+			};
+
+			let id = match id {
+				Some(Lenient::Unknown(id)) => Some(crate::Unresolved::Id(id.as_str())),
+				_ => None
+			};
+
+			let types = types.iter().filter_map(|ty| match ty {
+				Lenient::Unknown(ty) => Some(crate::Unresolved::Type(ty.as_str())),
+				_ => None
+			});
+
+			id.into_iter().chain(types).map(move |unresolved| (path.clone(), unresolved))
+		})
+	}
+
+	/// Returns an iterator over the top-level nodes of the document.
+	pub fn nodes(&self) -> Nodes<T> {
+		Nodes(self.objects.iter())
+	}
+
+	/// Returns an iterator over the top-level values of the document.
+	pub fn values(&self) -> Values<T> {
+		Values(self.objects.iter())
+	}
+
+	/// Returns an iterator over the top-level graph objects of the document.
+	pub fn graphs(&self) -> Graphs<T> {
+		Graphs(self.objects.iter())
+	}
+
+	/// Checks if this document is equivalent to `other` up to blank node renaming.
+	///
+	/// Unlike `==`, which compares blank node identifiers literally,
+	/// this searches for a bijection between the blank node identifiers of both documents
+	/// under which they are equal.
+	/// The order of non-list arrays (such as property values) is also ignored.
+	pub fn equivalent(&self, other: &ExpandedDocument<T>) -> bool {
+		crate::equivalence::objects(&self.objects, &other.objects, Default::default()).is_some()
+	}
+
+	/// Depth-first traversal of every object of the document, with its path from the root.
+	///
+	/// This includes nested node objects, list items, graph members,
+	/// included nodes and reverse property values.
+	pub fn traverse(&self) -> crate::Traverse<T> {
+		crate::Traverse::new(self)
+	}
+
+	/// Returns an iterator over the quads of the document.
+	///
+	/// Quads borrow the document: no intermediate dataset is built.
+	/// See [`Quad`](`crate::Quad`) for how graphs, subjects and objects are represented.
+	pub fn quads(&self) -> crate::Quads<T> {
+		crate::Quads::new(self)
+	}
+
+	/// Returns an adjacency view of the document, to walk the graph of its nodes.
+	pub fn adjacency(&self) -> crate::Adjacency<T> {
+		crate::Adjacency::new(self)
+	}
+
+	/// Merge the top-level nodes sharing the same identifier.
+	///
+	/// Every top-level node is merged into the first top-level node with the same identifier
+	/// using [`Node::merge_with`].
+	/// Named graph objects are merged as node objects.
+	/// Nested nodes are left untouched.
+	pub fn merge_duplicates(&mut self) {
+		let mut objects = Vec::with_capacity(self.objects.len());
+		let mut index: HashMap<Reference<T>, usize> = HashMap::new();
+
+		for object in std::mem::take(&mut self.objects) {
+			let id = match object.id() {
+				Some(Lenient::Ok(id)) => Some(id.clone()),
+				_ => None
+			};
+
+			if let Some(id) = id {
+				if let Some(i) = index.get(&id) {
+					let target: &mut Indexed<Object<T>> = &mut objects[*i];
+					if let (Some(target), Ok(node)) = (target.inner_mut().node_mut(), Node::try_from(object.into_inner())) {
+						target.merge_with(node)
+					}
+
+					continue
+				}
+
+				index.insert(id, objects.len());
+			}
+
+			objects.push(object)
+		}
+
+		self.objects = objects;
+		self.index = index;
+	}
+
+	/// Relabel every blank node identifier of the document using the given generator.
+	///
+	/// This includes node identifiers and references, types, properties,
+	/// reverse properties and graph names, at any depth.
+	/// Two occurrences of the same blank node identifier are given the same new label.
+	/// Returns the mapping from the old identifiers to the new ones.
+	///
+	/// # Example
+	/// ```
+	/// # let mut doc: json_ld::ExpandedDocument<iref::IriBuf> = json_ld::ExpandedDocument::new(Default::default());
+	/// use json_ld::BlankIdCounter;
+	///
+	/// let mut generator = BlankIdCounter::with_prefix("n");
+	/// let labels = doc.relabel_blank_nodes(&mut generator);
+	/// ```
+	pub fn relabel_blank_nodes<G: crate::BlankIdGenerator>(&mut self, generator: G) -> HashMap<crate::BlankId, crate::BlankId> {
+		let mut relabeling = crate::relabeling::Relabeling::new(generator);
+		for object in &mut self.objects {
+			relabeling.object(object.inner_mut())
+		}
+
+		self.reindex();
+		relabeling.labels
+	}
+
+	/// Flatten the document.
+	///
+	/// Every node of the document, at any depth, is collected at the top-level,
+	/// nodes sharing the same identifier being merged.
+	/// Nested node objects are replaced with node references,
+	/// and the nodes of each named graph are put in the `@graph` entry of the top-level
+	/// node with the graph name.
+	/// Free-floating values are dropped.
+	///
+	/// Nodes without identifier are labeled, and existing blank node identifiers relabeled,
+	/// using the given generator.
+	/// Fails with a `conflicting indexes` error if the same node is given different indexes.
+	pub fn flatten<G: crate::BlankIdGenerator>(self, generator: G) -> Result<ExpandedDocument<T>, Error> {
+		Ok(ExpandedDocument::from_vec(crate::flattening::flatten(self.objects, generator)?))
+	}
+
+	/// Move every included node to the top-level, at any depth.
+	///
+	/// Each node found in an `@included` entry (recursively) is moved to the top-level of
+	/// the document, or to the top-level of the named graph containing it.
+	/// If it has an identifier, a node reference is left in the `@included` entry.
+	/// Unlike [`flatten`](ExpandedDocument::flatten), other nested nodes are left in place.
+	pub fn lift_included(&mut self) {
+		let mut lifted = Vec::new();
+		for object in &mut self.objects {
+			lift_included_in_object(object.inner_mut(), &mut lifted)
+		}
+
+		self.objects.extend(lifted.into_iter().map(Indexed::cast));
+		self.reindex();
+	}
+
+	/// Replace every reverse property with the equivalent forward property, at any depth.
+	///
+	/// Each node found in a `@reverse` entry is moved to the top-level of the document,
+	/// or to the top-level of the named graph containing it,
+	/// and given a forward property referencing the node it was the reverse value of.
+	/// If a top-level node of the same graph shares its identifier, both nodes are merged.
+	/// Nodes referenced through a reverse property but without identifier are labeled
+	/// using the given generator.
+	/// Other nested nodes are left in place.
+	pub fn apply_reverse_properties<G: crate::BlankIdGenerator>(&mut self, mut generator: G) {
+		let mut lifted = Vec::new();
+		for object in &mut self.objects {
+			apply_reverse_properties_in_object(object.inner_mut(), &mut generator, &mut lifted)
+		}
+
+		merge_lifted(&mut self.objects, lifted);
+		self.reindex();
+	}
+
+	/// Consumes the document and returns its node map.
+	///
+	/// Nodes without identifier are labeled, and existing blank node identifiers relabeled,
+	/// using the given generator.
+	/// The node map can be converted back into a (flattened) document
+	/// with [`NodeMap::into_document`](`crate::flattening::NodeMap::into_document`).
+	/// Fails with a `conflicting indexes` error if the same node is given different indexes.
+	pub fn into_node_map<G: crate::BlankIdGenerator>(self, generator: G) -> Result<crate::flattening::NodeMap<T>, Error> {
+		crate::flattening::NodeMap::generate(self.objects, generator)
+	}
+
+	/// Consumes the document and returns its set of objects.
+	pub fn into_objects(self) -> HashSet<Indexed<Object<T>>> {
+		self.objects.into_iter().collect()
+	}
+}
+
+fn lift_included_in_object<T: Id>(object: &mut Object<T>, lifted: &mut Vec<Indexed<Node<T>>>) {
+	match object {
+		Object::Value(_) => (),
+		Object::List(items) => for item in items {
+			lift_included_in_object(item.inner_mut(), lifted)
+		},
+		Object::Node(node) => lift_included_in_node(node, lifted),
+		Object::Graph(graph) => {
+			let mut graph_lifted = Vec::new();
+			let mut objects: HashSet<_> = std::mem::take(graph.objects_mut()).into_iter().map(|mut object| {
+				lift_included_in_object(object.inner_mut(), &mut graph_lifted);
+				object
+			}).collect();
+
+			objects.extend(graph_lifted.into_iter().map(Indexed::cast));
+			*graph.objects_mut() = objects
+		}
+	}
+}
+
+fn lift_included_in_node<T: Id>(node: &mut Node<T>, lifted: &mut Vec<Indexed<Node<T>>>) {
+	if let Some(included) = node.take_included() {
+		let mut references = HashSet::new();
+		for mut included_node in included {
+			if let Some(id) = included_node.id() {
+				references.insert(Indexed::new(Node::with_id(id.clone()), None));
+			}
+
+			if !included_node.is_empty() {
+				lift_included_in_node(included_node.inner_mut(), lifted);
+				lifted.push(included_node)
+			}
+		}
+
+		if !references.is_empty() {
+			node.set_included(Some(references))
+		}
+	}
+
+	if let Some(graph) = node.take_graph() {
+		let mut graph_lifted = Vec::new();
+		let mut objects: HashSet<_> = graph.into_iter().map(|mut object| {
+			lift_included_in_object(object.inner_mut(), &mut graph_lifted);
+			object
+		}).collect();
+
+		objects.extend(graph_lifted.into_iter().map(Indexed::cast));
+		node.set_graph(Some(objects))
+	}
+
+	if let Some(annotation) = node.take_annotation() {
+		node.set_annotation(Some(annotation.into_iter().map(|mut annotation_node| {
+			lift_included_in_node(annotation_node.inner_mut(), lifted);
+			annotation_node
+		}).collect()))
+	}
+
+	for values in node.properties.values_mut() {
+		for value in values {
+			lift_included_in_object(value.inner_mut(), lifted)
+		}
+	}
+
+	for nodes in node.reverse_properties.values_mut() {
+		for reverse_node in nodes {
+			lift_included_in_node(reverse_node.inner_mut(), lifted)
+		}
+	}
+}
+
+fn apply_reverse_properties_in_object<T: Id, G: crate::BlankIdGenerator>(object: &mut Object<T>, generator: &mut G, lifted: &mut Vec<Indexed<Node<T>>>) {
+	match object {
+		Object::Value(_) => (),
+		Object::List(items) => for item in items {
+			apply_reverse_properties_in_object(item.inner_mut(), generator, lifted)
+		},
+		Object::Node(node) => apply_reverse_properties_in_node(node, generator, lifted),
+		Object::Graph(graph) => {
+			let objects = std::mem::take(graph.objects_mut()).into_iter().collect();
+			*graph.objects_mut() = apply_reverse_properties_in_graph(objects, generator).into_iter().collect()
+		}
+	}
+}
+
+fn apply_reverse_properties_in_graph<T: Id, G: crate::BlankIdGenerator>(mut objects: Vec<Indexed<Object<T>>>, generator: &mut G) -> Vec<Indexed<Object<T>>> {
+	let mut lifted = Vec::new();
+	for object in &mut objects {
+		apply_reverse_properties_in_object(object.inner_mut(), generator, &mut lifted)
+	}
+
+	merge_lifted(&mut objects, lifted);
+	objects
+}
+
+fn apply_reverse_properties_in_node<T: Id, G: crate::BlankIdGenerator>(node: &mut Node<T>, generator: &mut G, lifted: &mut Vec<Indexed<Node<T>>>) {
+	if !node.reverse_properties.is_empty() {
+		let id = node.id.get_or_insert_with(|| Lenient::Ok(Reference::Blank(generator.next_blank_id()))).clone();
+
+		for (prop, subjects) in std::mem::take(&mut node.reverse_properties) {
+			for mut subject in subjects {
+				subject.insert(prop.clone(), Indexed::new(Object::Node(Node::with_id(id.clone())), None));
+				apply_reverse_properties_in_node(subject.inner_mut(), generator, lifted);
+				lifted.push(subject)
+			}
+		}
+	}
+
+	if let Some(graph) = node.take_graph() {
+		let objects = apply_reverse_properties_in_graph(graph.into_iter().collect(), generator);
+		node.set_graph(Some(objects.into_iter().collect()))
+	}
+
+	if let Some(included) = node.take_included() {
+		node.set_included(Some(included.into_iter().map(|mut included_node| {
+			apply_reverse_properties_in_node(included_node.inner_mut(), generator, lifted);
+			included_node
+		}).collect()))
+	}
+
+	if let Some(annotation) = node.take_annotation() {
+		node.set_annotation(Some(annotation.into_iter().map(|mut annotation_node| {
+			apply_reverse_properties_in_node(annotation_node.inner_mut(), generator, lifted);
+			annotation_node
+		}).collect()))
+	}
+
+	for values in node.properties.values_mut() {
+		for value in values {
+			apply_reverse_properties_in_object(value.inner_mut(), generator, lifted)
+		}
+	}
+}
+
+/// Add the given nodes to `objects`,
+/// merging them with the node (or graph) object sharing their identifier, if any.
+fn merge_lifted<T: Id>(objects: &mut Vec<Indexed<Object<T>>>, lifted: Vec<Indexed<Node<T>>>) {
+	for node in lifted {
+		let target = match node.id() {
+			Some(id) => objects.iter().position(|object| object.id() == Some(id)),
+			None => None
+		};
+
+		match target.and_then(|i| objects[i].inner_mut().node_mut()) {
+			Some(target) => target.merge_with(node.into_inner()),
+			None => objects.push(node.cast())
+		}
+	}
+}
+
+/// Iterator over the top-level nodes of an expanded document.
+pub struct Nodes<'a, T: Id>(std::slice::Iter<'a, Indexed<Object<T>>>);
+
+impl<'a, T: Id> Iterator for Nodes<'a, T> {
+	type Item = &'a Indexed<Object<T>>;
+
+	fn next(&mut self) -> Option<&'a Indexed<Object<T>>> {
+		self.0.find(|object| object.is_node())
+	}
+}
+
+/// Iterator over the top-level values of an expanded document.
+pub struct Values<'a, T: Id>(std::slice::Iter<'a, Indexed<Object<T>>>);
+
+impl<'a, T: Id> Iterator for Values<'a, T> {
+	type Item = &'a Indexed<Object<T>>;
+
+	fn next(&mut self) -> Option<&'a Indexed<Object<T>>> {
+		self.0.find(|object| object.is_value())
+	}
+}
+
+/// Iterator over the top-level graph objects of an expanded document.
+pub struct Graphs<'a, T: Id>(std::slice::Iter<'a, Indexed<Object<T>>>);
+
+impl<'a, T: Id> Iterator for Graphs<'a, T> {
+	type Item = &'a Indexed<Object<T>>;
+
+	fn next(&mut self) -> Option<&'a Indexed<Object<T>>> {
+		self.0.find(|object| object.is_graph())
+	}
+}
+
+impl<T: Sync + Send + Id> ExpandedDocument<T> {
+	/// Compact the expanded document with the given context, document loader and
+	/// compaction options.
+	///
+	/// The JSON representation of the context is emitted as the `@context` entry of the
+	/// compacted document, and multiple top-level objects are wrapped in a `@graph` entry,
+	/// exactly like [`Document::compact_with`] does.
+	///
+	/// This is an asynchronous method since processing scoped contexts may require loading
+	/// remote ressources. It returns a boxed [`Future`](`std::future::Future`) to the result.
+	pub fn compact<'a, C: ContextMutProxy<T> + Send + Sync + crate::util::AsJson, L: Send + Sync + Loader>(&'a self, context: &'a C, loader: &'a mut L, options: compaction::Options) -> BoxFuture<'a, Result<JsonValue, Error>> where
+		C::Target: Send + Sync,
+		<C::Target as Context<T>>::LocalContext: Send + Sync + From<L::Output>,
+		T: 'a
+	{
+		use compaction::Compact;
+		async move {
+			let json_context = context.as_json();
+			let context = context::Inversible::new(context.deref());
+
+			let compacted = if self.objects.len() == 1 && options.compact_arrays && !options.keep_graph_array {
+				self.objects.iter().next().unwrap().compact_with(context.clone(), context.clone(), None, loader, &options).await?
+			} else {
+				compaction::compact_collection_with(self.objects.iter(), context.clone(), context.clone(), None, loader, &options).await?
+			};
+
+			let mut map = match compacted {
+				JsonValue::Array(items) => {
+					let mut map = json::object::Object::new();
+					if !items.is_empty() {
+						use crate::{
+							Lenient,
+							syntax::{
+								Term,
+								Keyword
+							}
+						};
+						let key = crate::compaction::compact_iri(context.clone(), &Lenient::Ok(Term::Keyword(Keyword::Graph)), true, false, &options)?;
+						map.insert(key.as_str().unwrap(), JsonValue::Array(items));
+					}
+
+					map
+				},
+				JsonValue::Object(map) => map,
+				_ => panic!("invalid compact document")
+			};
+
+			if !map.is_empty() && !json_context.is_null() && !json_context.is_empty() {
+				map.insert("@context", json_context)
+			}
+
+			Ok(JsonValue::Object(map))
+		}.boxed()
+	}
+}
+
+impl<T: 'static + Sync + Send + Id> ExpandedDocument<T> {
+	/// Compact the expanded document, taking ownership of the document, context and loader.
+	///
+	/// This is equivalent to [`compact`](`ExpandedDocument::compact`), but the returned future
+	/// does not borrow anything, so it can be stored or spawned.
+	pub fn into_compact<C: 'static + ContextMutProxy<T> + Send + Sync + crate::util::AsJson, L: 'static + Send + Sync + Loader>(self, context: C, mut loader: L, options: compaction::Options) -> BoxFuture<'static, Result<JsonValue, Error>> where
+		C::Target: Send + Sync,
+		<C::Target as Context<T>>::LocalContext: Send + Sync + From<L::Output>
+	{
+		async move {
+			self.compact(&context, &mut loader, options).await
+		}.boxed()
+	}
+}
+
+impl<T: Id> From<HashSet<Indexed<Object<T>>>> for ExpandedDocument<T> {
+	fn from(objects: HashSet<Indexed<Object<T>>>) -> ExpandedDocument<T> {
+		ExpandedDocument::new(objects)
+	}
+}
+
+/// Parse an already expanded JSON-LD document.
+///
+/// The input must be in expanded form: an array of node, value or list objects
+/// whose keys are either keywords or absolute IRIs/blank node identifiers.
+/// No context processing is performed.
+impl<'a, T: Id> TryFrom<&'a JsonValue> for ExpandedDocument<T> {
+	type Error = Error;
+
+	fn try_from(json: &'a JsonValue) -> Result<ExpandedDocument<T>, Error> {
+		Ok(ExpandedDocument::new(object::parse_document(json)?))
+	}
+}
+
+/// Canonical expanded JSON-LD representation of the document.
+///
+/// Entries and unordered arrays are sorted so that the output is deterministic.
+/// Use the alternate flag (`{:#}`) for pretty-printing.
+impl<T: Id> fmt::Display for ExpandedDocument<T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		crate::util::fmt_canonical(&crate::util::AsJson::as_json(self), f)
+	}
+}
+
+/// Two expanded documents are equal if they contain the same objects,
+/// the same number of times, in any order.
+impl<T: Id> PartialEq for ExpandedDocument<T> {
+	fn eq(&self, other: &Self) -> bool {
+		if self.objects.len() != other.objects.len() {
+			return false
+		}
+
+		let mut counts: HashMap<&Indexed<Object<T>>, usize> = HashMap::new();
+		for object in &other.objects {
+			*counts.entry(object).or_default() += 1
+		}
+
+		self.objects.iter().all(|object| {
+			match counts.get_mut(object) {
+				Some(count) if *count > 0 => {
+					*count -= 1;
+					true
+				},
+				_ => false
+			}
+		})
+	}
+}
+
+impl<T: Id> Eq for ExpandedDocument<T> {}
+
+impl<T: Id> IntoIterator for ExpandedDocument<T> {
+	type Item = Indexed<Object<T>>;
+	type IntoIter = std::vec::IntoIter<Indexed<Object<T>>>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		self.objects.into_iter()
+	}
+}
+
+impl<'a, T: Id> IntoIterator for &'a ExpandedDocument<T> {
+	type Item = &'a Indexed<Object<T>>;
+	type IntoIter = std::slice::Iter<'a, Indexed<Object<T>>>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		self.objects.iter()
+	}
+}
+
+impl<T: Sync + Send + Id> compaction::Compact<T> for ExpandedDocument<T> {
+	fn compact_with<'a, C: ContextMut<T>, L: Loader>(&'a self, active_context: context::Inversible<T, &'a C>, type_scoped_context: context::Inversible<T, &'a C>, active_property: Option<&'a str>, loader: &'a mut L, options: &'a compaction::Options) -> BoxFuture<'a, Result<JsonValue, Error>> where T: 'a, C: Sync + Send, C::LocalContext: Send + Sync + From<L::Output>, L: Sync + Send {
+		compaction::compact_collection_with(self.objects.iter(), active_context, type_scoped_context, active_property, loader, options)
+	}
+}
+
+impl<T: Id> crate::util::AsJson for ExpandedDocument<T> {
+	fn as_json(&self) -> JsonValue {
+		crate::util::AsJson::as_json(&self.objects)
+	}
+}
 
 /// JSON-LD document.
 ///
@@ -46,6 +701,9 @@ pub trait Document<T: Id> {
 	/// Expand the document with a custom base URL, initial context, document loader and
 	/// expansion options.
 	///
+	/// If the [`base`](`expansion::Options::base`) option is set, it takes precedence over the
+	/// given base URL.
+	///
 	/// If you do not wish to set the base URL and expansion options yourself, the
 	/// [`expand`](`Document::expand`) method is more appropriate.
 	///
@@ -101,6 +759,97 @@ pub trait Document<T: Id> {
 		}.boxed()
 	}
 
+	/// Compare this document with `other` semantically.
+	///
+	/// Both documents are expanded like [`expand`](`Document::expand`) does,
+	/// and their expanded forms are compared with [`json_ld_eq`](`crate::util::json_ld_eq`):
+	/// the order of array items is ignored, except in lists.
+	/// Blank node identifiers are compared literally.
+	fn compare<'a, D: Document<T> + Sync, C: 'a + Send + Sync + ContextMut<T>, L: Send + Sync + Loader>(&'a self, other: &'a D, loader: &'a mut L) -> BoxFuture<'a, Result<bool, Error>> where
+		C::LocalContext: Send + Sync + From<L::Output> + From<Self::LocalContext> + From<D::LocalContext>,
+		L::Output: Into<Self::LocalContext> + Into<D::LocalContext>,
+		T: 'a + Send + Sync,
+		Self: Sync
+	{
+		async move {
+			let a = self.expand::<C, L>(loader).await?;
+			let b = other.expand::<C, L>(loader).await?;
+			Ok(crate::util::json_ld_eq(&crate::util::AsJson::as_json(&a), &crate::util::AsJson::as_json(&b)))
+		}.boxed()
+	}
+
+	/// Compare this document with `other` semantically, using the given number comparison mode.
+	///
+	/// Same as [`compare`](`Document::compare`), but the expanded forms are compared with
+	/// [`json_ld_eq_with`](`crate::util::json_ld_eq_with`).
+	/// With [`NumberComparison::Value`](`crate::NumberComparison::Value`),
+	/// numbers are compared by value (`1` equals `1.0`) whatever the JSON backend.
+	fn compare_with<'a, D: Document<T> + Sync, C: 'a + Send + Sync + ContextMut<T>, L: Send + Sync + Loader>(&'a self, other: &'a D, loader: &'a mut L, numbers: crate::NumberComparison) -> BoxFuture<'a, Result<bool, Error>> where
+		C::LocalContext: Send + Sync + From<L::Output> + From<Self::LocalContext> + From<D::LocalContext>,
+		L::Output: Into<Self::LocalContext> + Into<D::LocalContext>,
+		T: 'a + Send + Sync,
+		Self: Sync
+	{
+		async move {
+			let a = self.expand::<C, L>(loader).await?;
+			let b = other.expand::<C, L>(loader).await?;
+			Ok(crate::util::json_ld_eq_with(&crate::util::AsJson::as_json(&a), &crate::util::AsJson::as_json(&b), numbers))
+		}.boxed()
+	}
+
+	/// Expand the document, taking ownership of the document, initial context and loader.
+	///
+	/// This is equivalent to [`expand`](`Document::expand`) with the given initial context and
+	/// options, but the returned future does not borrow anything,
+	/// so it can be stored or spawned.
+	fn expand_owned<C: 'static + Send + Sync + ContextMut<T>, L: 'static + Send + Sync + Loader>(self, context: C, mut loader: L, options: expansion::Options) -> BoxFuture<'static, Result<ExpandedDocument<T>, Error>> where
+		C::LocalContext: Send + Sync + From<L::Output> + From<Self::LocalContext>,
+		L::Output: Into<Self::LocalContext>,
+		T: 'static + Send + Sync,
+		Self: 'static + Sized + Send + Sync
+	{
+		async move {
+			self.expand_with(self.base_url(), &context, &mut loader, options).await
+		}.boxed()
+	}
+
+	/// Compact the document with a custom base URL, context, document loader and
+	/// compaction options.
+	///
+	/// The context is a processed context attached to its original local representation
+	/// (see [`Processed`](`crate::context::Processed`)), which is emitted verbatim as the
+	/// `@context` entry of the compacted document.
+	/// The local context may be an array of contexts (context definitions or remote context
+	/// IRIs), in which case they are processed in sequence into the active context.
+	///
+	/// If the [`base`](`compaction::Options::base`) option is set, it takes precedence over the
+	/// given base URL.
+	///
+	/// This is an asynchronous method since expanding the document or processing the contexts
+	/// may require loading remote ressources. It returns a boxed [`Future`](`std::future::Future`)
+	/// to the result.
+	///
+	/// # Example
+	/// ```
+	/// # fn main() -> Result<(), json_ld::Error> {
+	/// use async_std::task;
+	/// use json_ld::{Document, JsonContext, NoLoader, context::Local};
+	///
+	/// let doc = json::parse("{
+	/// 	\"http://xmlns.com/foaf/0.1/name\": \"Timothée Haudebourg\",
+	/// 	\"http://xmlns.com/foaf/0.1/homepage\": { \"@id\": \"http://timothee.haudebourg.net/\" }
+	/// }").unwrap();
+	///
+	/// let context = json::parse("[
+	/// 	{ \"name\": \"http://xmlns.com/foaf/0.1/name\" },
+	/// 	{ \"homepage\": { \"@id\": \"http://xmlns.com/foaf/0.1/homepage\", \"@type\": \"@id\" } }
+	/// ]").unwrap();
+	/// let processed_context = task::block_on(context.process::<JsonContext, _>(&mut NoLoader, None))?;
+	///
+	/// let compacted_doc = task::block_on(doc.compact_with(None, &processed_context, &mut NoLoader, json_ld::compaction::Options::default()))?;
+	/// # Ok(())
+	/// # }
+	/// ```
 	fn compact_with<'a, C: ContextMutProxy<T> + Send + Sync + crate::util::AsJson, L: Send + Sync + Loader>(&'a self, base_url: Option<Iri<'a>>, context: &'a C, loader: &'a mut L, options: compaction::Options) -> BoxFuture<'a, Result<JsonValue, Error>> where
 		C::Target: Send + Sync + Default,
 		<C::Target as Context<T>>::LocalContext: Send + Sync + From<L::Output> + From<Self::LocalContext>,
@@ -108,47 +857,21 @@ pub trait Document<T: Id> {
 		T: 'a + Send + Sync,
 		Self: Sync
 	{
-		use compaction::Compact;
 		async move {
-			let json_context = context.as_json();
-			let context = context::Inversible::new(context.deref());
-			let expanded = self.expand_with(base_url, &C::Target::new(base_url), loader, options.into()).await?;
-
-			let compacted = if expanded.len() == 1 && options.compact_arrays {
-				expanded.into_iter().next().unwrap().compact_with(context.clone(), context.clone(), None, loader, options.into()).await?
-			} else {
-				expanded.compact_with(context.clone(), context.clone(), None, loader, options.into()).await?
+			let base_url = match &options.base {
+				Some(base) => Some(base.as_iri()),
+				None => base_url
 			};
-
-			let mut map = match compacted {
-				JsonValue::Array(items) => {
-					let mut map = json::object::Object::new();
-					if !items.is_empty() {
-						use crate::{
-							Lenient,
-							syntax::{
-								Term,
-								Keyword
-							}
-						};
-						let key = crate::compaction::compact_iri(context.clone(), &Lenient::Ok(Term::Keyword(Keyword::Graph)), true, false, options.into())?;
-						map.insert(key.as_str().unwrap(), JsonValue::Array(items));
-					}
-
-					map
-				},
-				JsonValue::Object(map) => map,
-				_ => panic!("invalid compact document")
-			};
-
-			if !map.is_empty() && !json_context.is_null() && !json_context.is_empty() {
-				map.insert("@context", json_context)
-			}
-
-			Ok(JsonValue::Object(map))
+			let expanded = self.expand_with(base_url, &C::Target::new(base_url), loader, options.clone().into()).await?;
+			expanded.compact(context, loader, options).await
 		}.boxed()
 	}
 
+	/// Compact the document.
+	///
+	/// The default implementation is equivalent to [`compact_with`](`Document::compact_with`), but
+	/// uses the document [`base_url`](`Document::base_url`), with the default
+	/// options.
 	fn compact<'a, C: ContextMutProxy<T> + Send + Sync + crate::util::AsJson, L: Send + Sync + Loader>(&'a self, context: &'a C, loader: &'a mut L) -> BoxFuture<'a, Result<JsonValue, Error>> where
 		C::Target: Send + Sync + Default,	
 		<C::Target as Context<T>>::LocalContext: Send + Sync + From<L::Output> + From<Self::LocalContext>,
@@ -158,6 +881,63 @@ pub trait Document<T: Id> {
 	{
 		self.compact_with(self.base_url(), context, loader, compaction::Options::default())
 	}
+
+	/// Flatten the document, and compact it if a context is given.
+	///
+	/// The document is expanded using its [`base_url`](`Document::base_url`)
+	/// (unless the [`base`](`compaction::Options::base`) option is set),
+	/// then flattened with [`ExpandedDocument::flatten`], using a [`BlankIdCounter`](`crate::BlankIdCounter`)
+	/// to label blank nodes.
+	///
+	/// If no context is given, the flattened document is returned in expanded form.
+	/// Otherwise it is compacted like [`compact_with`](`Document::compact_with`) does,
+	/// except that the top-level nodes are always put in a `@graph` entry,
+	/// even if there is only one.
+	fn flatten_with<'a, C: ContextMutProxy<T> + Send + Sync + crate::util::AsJson, L: Send + Sync + Loader>(&'a self, context: Option<&'a C>, loader: &'a mut L, options: compaction::Options) -> BoxFuture<'a, Result<JsonValue, Error>> where
+		C::Target: Send + Sync + Default,
+		<C::Target as Context<T>>::LocalContext: Send + Sync + From<L::Output> + From<Self::LocalContext>,
+		L::Output: Into<Self::LocalContext>,
+		T: 'a + Send + Sync,
+		Self: Sync
+	{
+		async move {
+			let base_url = match &options.base {
+				Some(base) => Some(base.as_iri()),
+				None => self.base_url()
+			};
+			let expanded = self.expand_with(base_url, &C::Target::new(base_url), loader, options.clone().into()).await?;
+			let flattened = expanded.flatten(crate::BlankIdCounter::new())?;
+
+			match context {
+				Some(context) => {
+					let options = compaction::Options {
+						keep_graph_array: true,
+						..options
+					};
+
+					flattened.compact(context, loader, options).await
+				},
+				None => Ok(crate::util::AsJson::as_json(&flattened))
+			}
+		}.boxed()
+	}
+
+	/// Compact the document, taking ownership of the document, context and loader.
+	///
+	/// This is equivalent to [`compact_with`](`Document::compact_with`) using the document
+	/// [`base_url`](`Document::base_url`), but the returned future does not borrow anything,
+	/// so it can be stored or spawned.
+	fn into_compact<C: 'static + ContextMutProxy<T> + Send + Sync + crate::util::AsJson, L: 'static + Send + Sync + Loader>(self, context: C, mut loader: L, options: compaction::Options) -> BoxFuture<'static, Result<JsonValue, Error>> where
+		C::Target: Send + Sync + Default,
+		<C::Target as Context<T>>::LocalContext: Send + Sync + From<L::Output> + From<Self::LocalContext>,
+		L::Output: Into<Self::LocalContext>,
+		T: 'static + Send + Sync,
+		Self: 'static + Sized + Send + Sync
+	{
+		async move {
+			self.compact_with(self.base_url(), &context, &mut loader, options).await
+		}.boxed()
+	}
 }
 
 /// Default JSON document implementation.
@@ -176,7 +956,61 @@ impl<T: Id> Document<T> for JsonValue {
 		L::Output: Into<JsonValue>,
 		T: 'a + Send + Sync
 	{
-		expansion::expand(context, self, base_url, loader, options).boxed()
+		let base_url = base_url.map(|url| IriBuf::from(url));
+		async move {
+			let objects = expansion::expand(context, self, base_url.as_ref().map(|url| url.as_iri()), loader, &options).await?;
+			Ok(ExpandedDocument::new(objects))
+		}.boxed()
+	}
+}
+
+/// Raw JSON text document.
+///
+/// The text is parsed when the document is expanded.
+/// A `loading document failed` error is returned if the text is not valid JSON,
+/// and an `input too large` error if it exceeds the [`max_input_bytes`](`crate::Limits::max_input_bytes`) limit.
+impl<T: Id> Document<T> for str {
+	type LocalContext = JsonValue;
+
+	/// Returns `None`.
+	///
+	/// Use [`RemoteDocument`] to attach a base URL to a text document.
+	fn base_url(&self) -> Option<Iri> {
+		None
+	}
+
+	fn expand_with<'a, C: Send + Sync + ContextMut<T>, L: Send + Sync + Loader>(&'a self, base_url: Option<Iri>, context: &'a C, loader: &'a mut L, options: expansion::Options) -> BoxFuture<'a, Result<ExpandedDocument<T>, Error>> where
+		C::LocalContext: Send + Sync + From<L::Output> + From<JsonValue>,
+		L::Output: Into<JsonValue>,
+		T: 'a + Send + Sync
+	{
+		let base_url = base_url.map(|url| IriBuf::from(url));
+		async move {
+			options.limits.check_input(self.len())?;
+			let doc = json::parse(self).map_err(|e| Error::new(ErrorCode::LoadingDocumentFailed, e))?;
+			let objects = expansion::expand(context, &doc, base_url.as_ref().map(|url| url.as_iri()), loader, &options).await?;
+			Ok(ExpandedDocument::new(objects))
+		}.boxed()
+	}
+}
+
+/// Raw JSON text document.
+///
+/// See the `str` implementation.
+impl<T: Id> Document<T> for String {
+	type LocalContext = JsonValue;
+
+	/// Returns `None`.
+	fn base_url(&self) -> Option<Iri> {
+		None
+	}
+
+	fn expand_with<'a, C: Send + Sync + ContextMut<T>, L: Send + Sync + Loader>(&'a self, base_url: Option<Iri>, context: &'a C, loader: &'a mut L, options: expansion::Options) -> BoxFuture<'a, Result<ExpandedDocument<T>, Error>> where
+		C::LocalContext: Send + Sync + From<L::Output> + From<JsonValue>,
+		L::Output: Into<JsonValue>,
+		T: 'a + Send + Sync
+	{
+		Document::<T>::expand_with(self.as_str(), base_url, context, loader, options)
 	}
 }
 
@@ -212,19 +1046,133 @@ pub struct RemoteDocument<D = JsonValue> {
 	/// The base URL of the document.
 	base_url: IriBuf,
 
+	/// The media type of the document, if known.
+	content_type: Option<String>,
+
+	/// The `profile` parameter of the document media type, if any.
+	profile: Option<String>,
+
+	/// The URL of a context discovered through an HTTP `Link` header, if any.
+	context_url: Option<IriBuf>,
+
 	/// The document contents.
 	doc: D,
 }
 
+/// Link relation of contexts referenced through an HTTP `Link` header.
+const CONTEXT_LINK_REL: &str = "http://www.w3.org/ns/json-ld#context";
+
+/// Checks that the given media type (or `Content-Type` header value) denotes a JSON document.
+///
+/// This is the case of `application/json`, `application/ld+json`,
+/// and any other media type with the `+json` suffix.
+/// Parameters (such as `profile`) are ignored.
+pub fn is_json_media_type(ty: &str) -> bool {
+	let ty = ty.split(';').next().unwrap_or("").trim().to_lowercase();
+	ty == "application/json" || ty.ends_with("+json")
+}
+
 impl<D> RemoteDocument<D> {
 	/// Create a new remote document from the document contents and base URL.
 	pub fn new(doc: D, base_url: Iri) -> RemoteDocument<D> {
 		RemoteDocument {
 			base_url: base_url.into(),
+			content_type: None,
+			profile: None,
+			context_url: None,
 			doc: doc
 		}
 	}
 
+	/// The media type of the document, if known.
+	pub fn content_type(&self) -> Option<&str> {
+		self.content_type.as_ref().map(|ty| ty.as_str())
+	}
+
+	/// The `profile` parameter of the document media type, if any.
+	pub fn profile(&self) -> Option<&str> {
+		self.profile.as_ref().map(|profile| profile.as_str())
+	}
+
+	/// The URL of the context referenced by the HTTP `Link` header of the document, if any.
+	///
+	/// If set, this context is processed before expanding the document.
+	pub fn context_url(&self) -> Option<Iri> {
+		self.context_url.as_ref().map(|url| url.as_iri())
+	}
+
+	/// Set the media type of the document.
+	pub fn with_content_type(mut self, content_type: Option<String>) -> Self {
+		self.content_type = content_type;
+		self
+	}
+
+	/// Set the `profile` parameter of the document media type.
+	pub fn with_profile(mut self, profile: Option<String>) -> Self {
+		self.profile = profile;
+		self
+	}
+
+	/// Set the URL of the context to process before expanding the document.
+	pub fn with_context_url(mut self, context_url: Option<IriBuf>) -> Self {
+		self.context_url = context_url;
+		self
+	}
+
+	/// Set the media type and profile of the document from the value of an HTTP `Content-Type` header.
+	pub fn with_content_type_header(mut self, header: &str) -> Self {
+		let mut parts = header.split(';');
+		self.content_type = parts.next().map(|ty| ty.trim().to_lowercase()).filter(|ty| !ty.is_empty());
+		self.profile = parts.filter_map(|param| {
+			let mut kv = param.splitn(2, '=');
+			match (kv.next(), kv.next()) {
+				(Some(key), Some(value)) if key.trim().eq_ignore_ascii_case("profile") => Some(value.trim().trim_matches('"').to_string()),
+				_ => None
+			}
+		}).next();
+		self
+	}
+
+	/// Set the context URL of the document from the value of an HTTP `Link` header.
+	///
+	/// Links with the `http://www.w3.org/ns/json-ld#context` relation are resolved against the
+	/// document base URL.
+	/// As specified, they are ignored if the document media type is `application/ld+json`.
+	pub fn with_link_header(mut self, header: &str) -> Self {
+		if self.content_type() == Some("application/ld+json") {
+			return self
+		}
+
+		for link in header.split(',') {
+			let mut parts = link.split(';');
+			let target = match parts.next() {
+				Some(target) => target.trim(),
+				None => continue
+			};
+
+			if !target.starts_with('<') || !target.ends_with('>') {
+				continue
+			}
+
+			let is_context = parts.any(|param| {
+				let mut kv = param.splitn(2, '=');
+				match (kv.next(), kv.next()) {
+					(Some(key), Some(value)) if key.trim() == "rel" => value.trim().trim_matches('"').split_whitespace().any(|rel| rel == CONTEXT_LINK_REL),
+					_ => false
+				}
+			});
+
+			if is_context {
+				if let Ok(iri_ref) = IriRef::new(&target[1..(target.len()-1)]) {
+					self.context_url = Some(iri_ref.resolved(self.base_url.as_iri()));
+					break
+				}
+			}
+		}
+
+		self
+	}
+
 	/// Consume the remote document and return the inner document.
 	pub fn into_document(self) -> D {
 		self.doc
@@ -237,7 +1185,10 @@ impl<D> RemoteDocument<D> {
 }
 
 /// A Remote document is a document.
-impl<T: Id, D: Document<T>> Document<T> for RemoteDocument<D> {
+///
+/// If the remote document has a [context URL](`RemoteDocument::context_url`),
+/// the referenced context is processed on top of the given context before expansion.
+impl<T: Id, D: Sync + Document<T>> Document<T> for RemoteDocument<D> where D::LocalContext: Send + Sync + From<JsonValue> {
 	type LocalContext = D::LocalContext;
 
 	fn base_url(&self) -> Option<Iri> {
@@ -249,7 +1200,17 @@ impl<T: Id, D: Document<T>> Document<T> for RemoteDocument<D> {
 		L::Output: Into<Self::LocalContext>,
 		T: 'a + Send + Sync
 	{
-		self.doc.expand_with(base_url, context, loader, options)
+		match &self.context_url {
+			None => self.doc.expand_with(base_url, context, loader, options),
+			Some(context_url) => {
+				let base_url = base_url.map(|url| IriBuf::from(url));
+				async move {
+					let local: D::LocalContext = JsonValue::from(context_url.as_str()).into();
+					let processed = local.process_with(context, loader, Some(context_url.as_iri()), (&options).into()).await?.into_inner();
+					self.doc.expand_with(base_url.as_ref().map(|url| url.as_iri()), &processed, loader, options).await
+				}.boxed()
+			}
+		}
 	}
 }
 
@@ -0,0 +1,335 @@
+//! Comparison of objects up to blank node renaming.
+
+use std::{
+	collections::{
+		HashMap,
+		hash_map::DefaultHasher
+	},
+	hash::{
+		Hash,
+		Hasher
+	}
+};
+use crate::{
+	Id,
+	BlankId,
+	Reference,
+	Lenient,
+	Indexed,
+	Object,
+	Node,
+	Graph
+};
+
+/// Partial bijection between the blank node identifiers of two documents.
+#[derive(Clone, Default)]
+pub(crate) struct BlankMapping<'a> {
+	forward: HashMap<&'a BlankId, &'a BlankId>,
+	backward: HashMap<&'a BlankId, &'a BlankId>
+}
+
+impl<'a> BlankMapping<'a> {
+	/// Try to map `a` to `b`, returning the extended mapping on success.
+	fn bind(mut self, a: &'a BlankId, b: &'a BlankId) -> Option<Self> {
+		match (self.forward.get(a), self.backward.get(b)) {
+			(Some(a_image), Some(_)) => if *a_image == b { Some(self) } else { None },
+			(None, None) => {
+				self.forward.insert(a, b);
+				self.backward.insert(b, a);
+				Some(self)
+			},
+			_ => None
+		}
+	}
+}
+
+fn reference<'a, T: Id>(a: &'a Reference<T>, b: &'a Reference<T>, m: BlankMapping<'a>) -> Option<BlankMapping<'a>> {
+	match (a, b) {
+		(Reference::Id(a), Reference::Id(b)) => if a == b { Some(m) } else { None },
+		(Reference::Blank(a), Reference::Blank(b)) => m.bind(a, b),
+		_ => None
+	}
+}
+
+fn lenient_reference<'a, T: Id>(a: &'a Lenient<Reference<T>>, b: &'a Lenient<Reference<T>>, m: BlankMapping<'a>) -> Option<BlankMapping<'a>> {
+	match (a, b) {
+		(Lenient::Ok(a), Lenient::Ok(b)) => reference(a, b, m),
+		(Lenient::Unknown(a), Lenient::Unknown(b)) => if a == b { Some(m) } else { None },
+		_ => None
+	}
+}
+
+/// Hash of an item ignoring blank node identifiers.
+///
+/// Items that are equal up to blank node renaming have the same fingerprint.
+/// Unordered collections are fingerprinted independently of the order of their items.
+trait Fingerprint {
+	fn fingerprint<H: Hasher>(&self, state: &mut H);
+
+	fn fingerprint_u64(&self) -> u64 {
+		let mut hasher = DefaultHasher::new();
+		self.fingerprint(&mut hasher);
+		hasher.finish()
+	}
+}
+
+/// Fingerprint of the given items, in any order.
+fn unordered_fingerprint<I: Fingerprint>(items: impl IntoIterator<Item = I>) -> u64 {
+	let mut len = 0usize;
+	let mut sum = 0u64;
+	for item in items {
+		len += 1;
+		sum = sum.wrapping_add(item.fingerprint_u64())
+	}
+
+	let mut hasher = DefaultHasher::new();
+	len.hash(&mut hasher);
+	sum.hash(&mut hasher);
+	hasher.finish()
+}
+
+impl<'a, F: Fingerprint + ?Sized> Fingerprint for &'a F {
+	fn fingerprint<H: Hasher>(&self, state: &mut H) {
+		(*self).fingerprint(state)
+	}
+}
+
+impl<K: Fingerprint, V: Fingerprint> Fingerprint for (K, V) {
+	fn fingerprint<H: Hasher>(&self, state: &mut H) {
+		self.0.fingerprint(state);
+		self.1.fingerprint(state)
+	}
+}
+
+/// Property values, in any order.
+impl<F: Fingerprint> Fingerprint for Vec<F> {
+	fn fingerprint<H: Hasher>(&self, state: &mut H) {
+		unordered_fingerprint(self).hash(state)
+	}
+}
+
+impl<T: Id> Fingerprint for Reference<T> {
+	fn fingerprint<H: Hasher>(&self, state: &mut H) {
+		match self {
+			Reference::Id(id) => {
+				0u8.hash(state);
+				id.hash(state)
+			},
+			Reference::Blank(_) => 1u8.hash(state)
+		}
+	}
+}
+
+impl<T: Id> Fingerprint for Lenient<Reference<T>> {
+	fn fingerprint<H: Hasher>(&self, state: &mut H) {
+		match self {
+			Lenient::Ok(r) => r.fingerprint(state),
+			Lenient::Unknown(u) => {
+				2u8.hash(state);
+				u.hash(state)
+			}
+		}
+	}
+}
+
+impl<F: Fingerprint> Fingerprint for Indexed<F> {
+	fn fingerprint<H: Hasher>(&self, state: &mut H) {
+		self.index().hash(state);
+		self.inner().fingerprint(state)
+	}
+}
+
+impl<T: Id> Fingerprint for Object<T> {
+	fn fingerprint<H: Hasher>(&self, state: &mut H) {
+		match self {
+			Object::Value(value) => {
+				0u8.hash(state);
+				value.hash(state)
+			},
+			Object::Node(node) => {
+				1u8.hash(state);
+				node.fingerprint(state)
+			},
+			Object::List(list) => {
+				2u8.hash(state);
+				list.len().hash(state);
+				for item in list.as_slice() {
+					item.fingerprint(state)
+				}
+			},
+			Object::Graph(graph) => {
+				3u8.hash(state);
+				graph.id().map(|id| id.fingerprint_u64()).hash(state);
+				unordered_fingerprint(graph).hash(state)
+			}
+		}
+	}
+}
+
+impl<T: Id> Fingerprint for Node<T> {
+	fn fingerprint<H: Hasher>(&self, state: &mut H) {
+		self.id.as_ref().map(|id| id.fingerprint_u64()).hash(state);
+		unordered_fingerprint(&self.types).hash(state);
+		self.graph.as_ref().map(unordered_fingerprint).hash(state);
+		self.included.as_ref().map(unordered_fingerprint).hash(state);
+		self.annotation.as_ref().map(unordered_fingerprint).hash(state);
+		unordered_fingerprint(self.properties.iter()).hash(state);
+		unordered_fingerprint(self.reverse_properties.iter()).hash(state)
+	}
+}
+
+/// Match the items of `a` against the items of `b`, in any order.
+///
+/// This performs a backtracking search for a one-to-one matching compatible with
+/// the current blank node mapping.
+/// Items are first bucketed by [`Fingerprint`],
+/// so that each item of `a` is only tried against the items of `b` with the same fingerprint.
+fn unordered<'a, A: Copy + Fingerprint, B: Copy + Fingerprint, F>(a: &[A], b: &[B], m: BlankMapping<'a>, eq: &F) -> Option<BlankMapping<'a>> where F: Fn(A, B, BlankMapping<'a>) -> Option<BlankMapping<'a>> {
+	if a.len() != b.len() {
+		return None
+	}
+
+	let mut buckets: HashMap<u64, Vec<B>> = HashMap::new();
+	for item in b {
+		buckets.entry(item.fingerprint_u64()).or_default().push(*item)
+	}
+
+	let mut a: Vec<(u64, A)> = a.iter().map(|item| (item.fingerprint_u64(), *item)).collect();
+	let mut a_counts: HashMap<u64, usize> = HashMap::new();
+	for (fingerprint, _) in &a {
+		*a_counts.entry(*fingerprint).or_default() += 1
+	}
+
+	if a_counts.len() != buckets.len() || a_counts.iter().any(|(fingerprint, count)| buckets.get(fingerprint).map(Vec::len) != Some(*count)) {
+		return None
+	}
+
+	// Items with few candidates first, to fail early.
+	a.sort_by_key(|(fingerprint, _)| buckets[fingerprint].len());
+	unordered_in_buckets(&a, &mut buckets, m, eq)
+}
+
+fn unordered_in_buckets<'a, A: Copy, B: Copy, F>(a: &[(u64, A)], buckets: &mut HashMap<u64, Vec<B>>, m: BlankMapping<'a>, eq: &F) -> Option<BlankMapping<'a>> where F: Fn(A, B, BlankMapping<'a>) -> Option<BlankMapping<'a>> {
+	match a.split_first() {
+		Some(((fingerprint, first), rest)) => {
+			for i in 0..buckets[fingerprint].len() {
+				let candidate = buckets[fingerprint][i];
+				if let Some(m) = eq(*first, candidate, m.clone()) {
+					buckets.get_mut(fingerprint).unwrap().swap_remove(i);
+					let result = unordered_in_buckets(rest, buckets, m, eq);
+
+					// Put the candidate back where it was.
+					let bucket = buckets.get_mut(fingerprint).unwrap();
+					bucket.push(candidate);
+					let last = bucket.len() - 1;
+					bucket.swap(i, last);
+
+					if result.is_some() {
+						return result
+					}
+				}
+			}
+
+			None
+		},
+		None => Some(m)
+	}
+}
+
+fn ordered<'a, A, B, F>(a: &'a [A], b: &'a [B], mut m: BlankMapping<'a>, eq: &F) -> Option<BlankMapping<'a>> where F: Fn(&'a A, &'a B, BlankMapping<'a>) -> Option<BlankMapping<'a>> {
+	if a.len() != b.len() {
+		return None
+	}
+
+	for (a, b) in a.iter().zip(b) {
+		m = eq(a, b, m)?
+	}
+
+	Some(m)
+}
+
+pub(crate) fn objects<'a, T: Id, A: IntoIterator<Item = &'a Indexed<Object<T>>>, B: IntoIterator<Item = &'a Indexed<Object<T>>>>(a: A, b: B, m: BlankMapping<'a>) -> Option<BlankMapping<'a>> {
+	let a: Vec<_> = a.into_iter().collect();
+	let b: Vec<_> = b.into_iter().collect();
+	unordered(&a, &b, m, &object)
+}
+
+fn nodes<'a, T: Id, A: IntoIterator<Item = &'a Indexed<Node<T>>>, B: IntoIterator<Item = &'a Indexed<Node<T>>>>(a: A, b: B, m: BlankMapping<'a>) -> Option<BlankMapping<'a>> {
+	let a: Vec<_> = a.into_iter().collect();
+	let b: Vec<_> = b.into_iter().collect();
+	unordered(&a, &b, m, &indexed_node)
+}
+
+fn object<'a, T: Id>(a: &'a Indexed<Object<T>>, b: &'a Indexed<Object<T>>, m: BlankMapping<'a>) -> Option<BlankMapping<'a>> {
+	if a.index() != b.index() {
+		return None
+	}
+
+	match (a.inner(), b.inner()) {
+		(Object::Value(a), Object::Value(b)) => if a == b { Some(m) } else { None },
+		(Object::Node(a), Object::Node(b)) => node(a, b, m),
+		(Object::List(a), Object::List(b)) => ordered(a.as_slice(), b.as_slice(), m, &object),
+		(Object::Graph(a), Object::Graph(b)) => graph(a, b, m),
+		_ => None
+	}
+}
+
+fn indexed_node<'a, T: Id>(a: &'a Indexed<Node<T>>, b: &'a Indexed<Node<T>>, m: BlankMapping<'a>) -> Option<BlankMapping<'a>> {
+	if a.index() != b.index() {
+		return None
+	}
+
+	node(a.inner(), b.inner(), m)
+}
+
+fn graph<'a, T: Id>(a: &'a Graph<T>, b: &'a Graph<T>, mut m: BlankMapping<'a>) -> Option<BlankMapping<'a>> {
+	m = match (a.id(), b.id()) {
+		(Some(a), Some(b)) => lenient_reference(a, b, m)?,
+		(None, None) => m,
+		_ => return None
+	};
+
+	objects(a, b, m)
+}
+
+fn node<'a, T: Id>(a: &'a Node<T>, b: &'a Node<T>, mut m: BlankMapping<'a>) -> Option<BlankMapping<'a>> {
+	m = match (&a.id, &b.id) {
+		(Some(a), Some(b)) => lenient_reference(a, b, m)?,
+		(None, None) => m,
+		_ => return None
+	};
+
+	let a_types: Vec<_> = a.types.iter().collect();
+	let b_types: Vec<_> = b.types.iter().collect();
+	m = unordered(&a_types, &b_types, m, &lenient_reference)?;
+
+	m = match (&a.graph, &b.graph) {
+		(Some(a), Some(b)) => objects(a, b, m)?,
+		(None, None) => m,
+		_ => return None
+	};
+
+	m = match (&a.included, &b.included) {
+		(Some(a), Some(b)) => nodes(a, b, m)?,
+		(None, None) => m,
+		_ => return None
+	};
+
+	m = match (&a.annotation, &b.annotation) {
+		(Some(a), Some(b)) => nodes(a, b, m)?,
+		(None, None) => m,
+		_ => return None
+	};
+
+	let a_props: Vec<_> = a.properties.iter().collect();
+	let b_props: Vec<_> = b.properties.iter().collect();
+	m = unordered(&a_props, &b_props, m, &|(a_prop, a_values), (b_prop, b_values), m| {
+		objects(a_values, b_values, reference(a_prop, b_prop, m)?)
+	})?;
+
+	let a_props: Vec<_> = a.reverse_properties.iter().collect();
+	let b_props: Vec<_> = b.reverse_properties.iter().collect();
+	unordered(&a_props, &b_props, m, &|(a_prop, a_nodes), (b_prop, b_nodes), m| {
+		nodes(a_nodes, b_nodes, reference(a_prop, b_prop, m)?)
+	})
+}
@@ -1,25 +1,40 @@
 use std::convert::TryFrom;
 use std::fmt;
+use json::JsonValue;
 
 /// Error type.
 ///
 /// This is the type of all the errors that may occur during a JSON-LD document processing.
 /// Each error is described by an error code.
 /// See [`ErrorCode`] for more informations about all the different possible errors.
+///
+/// An error may also refer to the term, keyword or IRI it is about (its *subject*),
+/// which is included in its `Display` message,
+/// and to the lower-level error that caused it, available through [`std::error::Error::source`].
+/// When relevant, the offending JSON fragment is attached to the error (see [`Error::fragment`]),
+/// but is not part of its `Display` message.
 #[derive(Debug)]
 pub struct Error {
 	/// Error code.
 	code: ErrorCode,
 
+	/// The term, keyword or IRI this error is about, if any.
+	subject: Option<String>,
+
+	/// The offending JSON fragment, if any.
+	fragment: Option<JsonValue>,
+
 	/// The lower-level source of this error, if any.
-	source: Option<Box<dyn std::error::Error + 'static>>
+	source: Option<Box<dyn std::error::Error + Send + Sync + 'static>>
 }
 
 impl Error {
 	/// Create a new error with a given error source.
-	pub fn new<S: std::error::Error + 'static>(code: ErrorCode, source: S) -> Error {
+	pub fn new<S: std::error::Error + Send + Sync + 'static>(code: ErrorCode, source: S) -> Error {
 		Error {
 			code,
+			subject: None,
+			fragment: None,
 			source: Some(Box::new(source))
 		}
 	}
@@ -28,6 +43,40 @@ impl Error {
 	pub fn code(&self) -> ErrorCode {
 		self.code
 	}
+
+	/// Get the term, keyword or IRI this error is about, if any.
+	pub fn subject(&self) -> Option<&str> {
+		self.subject.as_ref().map(String::as_str)
+	}
+
+	/// Set the term, keyword or IRI this error is about.
+	///
+	/// The subject is only set if the error does not already have one,
+	/// so that the most specific subject is kept when an error is propagated.
+	pub fn with_subject<S: Into<String>>(mut self, subject: S) -> Error {
+		if self.subject.is_none() {
+			self.subject = Some(subject.into())
+		}
+
+		self
+	}
+
+	/// Get the offending JSON fragment, if any.
+	pub fn fragment(&self) -> Option<&JsonValue> {
+		self.fragment.as_ref()
+	}
+
+	/// Attach the offending JSON fragment to the error.
+	///
+	/// As with [`with_subject`](Error::with_subject), the fragment is only set
+	/// if the error does not already have one.
+	pub fn with_fragment(mut self, fragment: JsonValue) -> Error {
+		if self.fragment.is_none() {
+			self.fragment = Some(fragment)
+		}
+
+		self
+	}
 }
 
 impl std::error::Error for Error {
@@ -41,7 +90,10 @@ impl std::error::Error for Error {
 
 impl fmt::Display for Error {
 	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-		write!(f, "{}", self.code.as_str())
+		match &self.subject {
+			Some(subject) => write!(f, "{} `{}`", self.code.as_str(), subject),
+			None => write!(f, "{}", self.code.as_str())
+		}
 	}
 }
 
@@ -49,6 +101,8 @@ impl From<ErrorCode> for Error {
 	fn from(code: ErrorCode) -> Error {
 		Error {
 			code,
+			subject: None,
+			fragment: None,
 			source: None
 		}
 	}
@@ -57,6 +111,10 @@ impl From<ErrorCode> for Error {
 /// Error code.
 #[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Debug)]
 pub enum ErrorCode {
+	/// The processing has been cancelled through a cancellation token.
+	/// Note: this error is not defined in the JSON-LD API specification.
+	Cancelled,
+
 	/// Two properties which expand to the same keyword have been detected.
 	/// This might occur if a keyword and an alias thereof are used at the same time.
 	CollidingKeywords,
@@ -70,12 +128,24 @@ pub enum ErrorCode {
 	/// A cycle in IRI mappings has been detected.
 	CyclicIriMapping,
 
+	/// The input document is nested deeper than the configured maximum depth.
+	/// Note: this error is not defined in the JSON-LD API specification.
+	DocumentTooDeep,
+
+	/// The input document exceeds the configured maximum size.
+	/// Note: this error is not defined in the JSON-LD API specification.
+	InputTooLarge,
+
 	/// An `@id` entry was encountered whose value was not a string.
 	InvalidIdValue,
 
 	/// An invalid value for `@import` has been found.
 	InvalidImportValue,
 
+	/// An `@annotation` entry has been found in an invalid position,
+	/// or its value is not a set of node objects without `@id`.
+	InvalidAnnotation,
+
 	/// An included block contains an invalid value.
 	InvalidIncludedValue,
 
@@ -210,6 +280,10 @@ pub enum ErrorCode {
 	/// There was a problem encountered loading a remote context.
 	LoadingRemoteContextFailed,
 
+	/// A node identifier or type is not a well-formed IRI.
+	/// Note: this error is not defined in the JSON-LD API specification.
+	MalformedIri,
+
 	/// Multiple HTTP Link Headers [RFC8288](https://tools.ietf.org/html/rfc8288) using the http://www.w3.org/ns/json-ld#context link
 	/// relation have been detected.
 	MultipleContextLinkHeaders,
@@ -219,7 +293,24 @@ pub enum ErrorCode {
 	ProcessingModeConflict,
 
 	/// An attempt was made to redefine a protected term.
-	ProtectedTermRedefinition
+	ProtectedTermRedefinition,
+
+	/// A term having the form of a keyword has been defined,
+	/// and such terms are rejected.
+	/// Note: this error is not defined in the JSON-LD API specification.
+	ReservedTerm,
+
+	/// The processing deadline has passed.
+	/// Note: this error is not defined in the JSON-LD API specification.
+	Timeout,
+
+	/// The expansion produced more objects than the configured maximum.
+	/// Note: this error is not defined in the JSON-LD API specification.
+	TooManyObjects,
+
+	/// A property has more values than the configured maximum.
+	/// Note: this error is not defined in the JSON-LD API specification.
+	TooManyValues
 }
 
 impl ErrorCode {
@@ -228,12 +319,16 @@ impl ErrorCode {
 		use ErrorCode::*;
 
 		match self {
+			Cancelled => "cancelled",
 			CollidingKeywords => "colliding keywords",
 			ConflictingIndexes => "conflicting indexes",
 			ContextOverflow => "context overflow",
 			CyclicIriMapping => "cyclic IRI mapping",
+			DocumentTooDeep => "document too deep",
+			InputTooLarge => "input too large",
 			InvalidIdValue => "invalid @id value",
 			InvalidImportValue => "invalid @import value",
+			InvalidAnnotation => "invalid annotation",
 			InvalidIncludedValue => "invalid @included value",
 			InvalidIndexValue => "invalid @index value",
 			InvalidNestValue => "invalid @nest value",
@@ -275,9 +370,14 @@ impl ErrorCode {
 			KeywordRedefinition => "keyword redefinition",
 			LoadingDocumentFailed => "loading document failed",
 			LoadingRemoteContextFailed => "loading remote context failed",
+			MalformedIri => "malformed IRI",
 			MultipleContextLinkHeaders => "multiple context link headers",
 			ProcessingModeConflict => "processing mode conflict",
-			ProtectedTermRedefinition => "protected term redefinition"
+			ProtectedTermRedefinition => "protected term redefinition",
+			ReservedTerm => "reserved term",
+			Timeout => "timeout",
+			TooManyObjects => "too many objects",
+			TooManyValues => "too many values"
 		}
 	}
 }
@@ -288,12 +388,16 @@ impl<'a> TryFrom<&'a str> for ErrorCode {
 	fn try_from(name: &'a str) -> Result<ErrorCode, ()> {
 		use ErrorCode::*;
 		match name {
+			"cancelled" => Ok(Cancelled),
 			"colliding keywords" => Ok(CollidingKeywords),
 			"conflicting indexes" => Ok(ConflictingIndexes),
 			"context overflow" => Ok(ContextOverflow),
 			"cyclic IRI mapping" => Ok(CyclicIriMapping),
+			"document too deep" => Ok(DocumentTooDeep),
+			"input too large" => Ok(InputTooLarge),
 			"invalid @id value" => Ok(InvalidIdValue),
 			"invalid @import value" => Ok(InvalidImportValue),
+			"invalid annotation" => Ok(InvalidAnnotation),
 			"invalid @included value" => Ok(InvalidIncludedValue),
 			"invalid @index value" => Ok(InvalidIndexValue),
 			"invalid @nest value" => Ok(InvalidNestValue),
@@ -335,9 +439,14 @@ impl<'a> TryFrom<&'a str> for ErrorCode {
 			"keyword redefinition" => Ok(KeywordRedefinition),
 			"loading document failed" => Ok(LoadingDocumentFailed),
 			"loading remote context failed" => Ok(LoadingRemoteContextFailed),
+			"malformed IRI" => Ok(MalformedIri),
 			"multiple context link headers" => Ok(MultipleContextLinkHeaders),
 			"processing mode conflict" => Ok(ProcessingModeConflict),
 			"protected term redefinition" => Ok(ProtectedTermRedefinition),
+			"reserved term" => Ok(ReservedTerm),
+			"timeout" => Ok(Timeout),
+			"too many objects" => Ok(TooManyObjects),
+			"too many values" => Ok(TooManyValues),
 			_ => Err(())
 		}
 	}
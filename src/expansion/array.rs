@@ -3,6 +3,7 @@ use json::JsonValue;
 use crate::{
 	Error,
 	Id,
+	Usage,
 	object::*,
 	ContextMut,
 	context::{
@@ -17,7 +18,7 @@ use super::{
 	expand_element
 };
 
-pub async fn expand_array<T: Send + Sync + Id, C: Send + Sync + ContextMut<T>, L: Send + Sync + Loader>(active_context: &C, active_property: Option<&str>, active_property_definition: Option<&TermDefinition<T, C>>, element: &[JsonValue], base_url: Option<Iri<'_>>, loader: &mut L, options: Options, from_map: bool) -> Result<Expanded<T>, Error> where C::LocalContext: Send + Sync + From<L::Output> + From<JsonValue>, L::Output: Into<JsonValue> {
+pub async fn expand_array<T: Send + Sync + Id, C: Send + Sync + ContextMut<T>, L: Send + Sync + Loader>(active_context: &C, active_property: Option<&str>, active_property_definition: Option<&TermDefinition<T, C>>, element: &[JsonValue], base_url: Option<Iri<'_>>, loader: &mut L, options: &Options, usage: &Usage, from_map: bool) -> Result<Expanded<T>, Error> where C::LocalContext: Send + Sync + From<L::Output> + From<JsonValue>, L::Output: Into<JsonValue> {
 	// Initialize an empty array, result.
 	let mut is_list = false;
 	let mut result = Vec::new();
@@ -34,11 +35,17 @@ pub async fn expand_array<T: Send + Sync + Id, C: Send + Sync + ContextMut<T>, L
 		// Initialize `expanded_item` to the result of using this algorithm
 		// recursively, passing `active_context`, `active_property`, `item` as element,
 		// `base_url`, the `frame_expansion`, `ordered`, and `from_map` flags.
-		result.extend(expand_element(active_context, active_property, item, base_url, loader, options, from_map).await?);
+		result.extend(expand_element(active_context, active_property, item, base_url, loader, options, usage, from_map).await?);
+
+		// Check the number of values while accumulating them,
+		// so that a single huge property cannot exhaust the memory.
+		if let Some(property) = active_property {
+			options.limits.check_values(property, result.len())?
+		}
 	}
 
 	if is_list {
-		return Ok(Expanded::Object(Object::List(result).into()))
+		return Ok(Expanded::Object(Object::List(result.into()).into()))
 	}
 
 	// Return result.
@@ -5,6 +5,9 @@ use json::JsonValue;
 use crate::{
 	Error,
 	ErrorCode,
+	Issue,
+	Usage,
+	Severity,
 	Id,
 	Indexed,
 	Lenient,
@@ -34,13 +37,19 @@ use super::{
 
 /// https://www.w3.org/TR/json-ld11-api/#expansion-algorithm
 /// The default specified value for `ordered` and `from_map` is `false`.
-pub fn expand_element<'a, T: Send + Sync + Id, C: Send + Sync + ContextMut<T>, L: Send + Sync + Loader>(active_context: &'a C, active_property: Option<&'a str>, element: &'a JsonValue, base_url: Option<Iri<'a>>, loader: &'a mut L, options: Options, from_map: bool) -> BoxFuture<'a, Result<Expanded<T>, Error>> where C::LocalContext: Send + Sync + From<L::Output> + From<JsonValue>, L::Output: Into<JsonValue> {
+pub fn expand_element<'a, T: Send + Sync + Id, C: Send + Sync + ContextMut<T>, L: Send + Sync + Loader>(active_context: &'a C, active_property: Option<&'a str>, element: &'a JsonValue, base_url: Option<Iri<'a>>, loader: &'a mut L, options: &'a Options, usage: &'a Usage, from_map: bool) -> BoxFuture<'a, Result<Expanded<T>, Error>> where C::LocalContext: Send + Sync + From<L::Output> + From<JsonValue>, L::Output: Into<JsonValue> {
 	async move {
 		// If `element` is null, return null.
 		if element.is_null() {
 			return Ok(Expanded::Null)
 		}
 
+		options.check_interrupt()?;
+
+		if !element.is_array() {
+			usage.count_object(&options.limits)?;
+		}
+
 		let active_property_definition = active_context.get_opt(active_property);
 
 		// // If `active_property` is `@default`, initialize the `frame_expansion` flag to `false`.
@@ -64,7 +73,7 @@ pub fn expand_element<'a, T: Send + Sync + Id, C: Send + Sync + ContextMut<T>, L
 		match element {
 			JsonValue::Null => unreachable!(),
 			JsonValue::Array(element) => {
-				expand_array(active_context, active_property, active_property_definition, element, base_url, loader, options, from_map).await
+				expand_array(active_context, active_property, active_property_definition, element, base_url, loader, options, usage, from_map).await
 			},
 
 			JsonValue::Object(element) => {
@@ -200,6 +209,8 @@ pub fn expand_element<'a, T: Send + Sync + Id, C: Send + Sync + ContextMut<T>, L
 				value_entry = None;
 				for Entry(key, value) in entries.iter() {
 					match expand_iri(active_context.as_ref(), key, false, true) {
+						// Annotations are ignored unless JSON-LD-star is enabled.
+						Lenient::Ok(Term::Keyword(Keyword::Annotation)) if !options.rdf_star => (),
 						Lenient::Ok(expanded_key) => {
 							match &expanded_key {
 								Term::Keyword(Keyword::Value) => {
@@ -217,10 +228,7 @@ pub fn expand_element<'a, T: Send + Sync + Id, C: Send + Sync + ContextMut<T>, L
 							expanded_entries.push(Entry((*key, expanded_key), value))
 						},
 						Lenient::Unknown(_) => {
-							if options.strict {
-								return Err(ErrorCode::KeyExpansionFailed.into());
-							}
-							warn!("failed to expand key `{}`", key);
+							options.report(Issue::new(Severity::Error, ErrorCode::KeyExpansionFailed, Some(key.to_string())).with_fragment((*value).clone()))?;
 						}
 					}
 				}
@@ -249,10 +257,10 @@ pub fn expand_element<'a, T: Send + Sync + Id, C: Send + Sync + ContextMut<T>, L
 					// result is an array..
 					let mut result = Vec::new();
 					for item in as_array(list_entry) {
-						result.extend(expand_element(active_context.as_ref(), active_property, item, base_url, loader, options, false).await?)
+						result.extend(expand_element(active_context.as_ref(), active_property, item, base_url, loader, options, usage, false).await?)
 					}
 
-					Ok(Expanded::Object(Indexed::new(Object::List(result), index)))
+					Ok(Expanded::Object(Indexed::new(Object::List(result.into()), index)))
 				} else if let Some(set_entry) = set_entry {
 					// Set objects.
 					// let mut index = None;
@@ -277,17 +285,17 @@ pub fn expand_element<'a, T: Send + Sync + Id, C: Send + Sync + ContextMut<T>, L
 					// set expanded value to the result of using this algorithm recursively,
 					// passing active context, active property, value for element, base URL, and
 					// the frameExpansion and ordered flags.
-					expand_element(active_context.as_ref(), active_property, set_entry, base_url, loader, options, false).await
+					expand_element(active_context.as_ref(), active_property, set_entry, base_url, loader, options, usage, false).await
 				} else if let Some(value_entry) = value_entry {
 					// Value objects.
-					if let Some(value) = expand_value(input_type, type_scoped_context, expanded_entries, value_entry)? {
+					if let Some(value) = expand_value(input_type, type_scoped_context, expanded_entries, value_entry, options)? {
 						Ok(Expanded::Object(value.into()))
 					} else {
 						Ok(Expanded::Null)
 					}
 				} else {
 					// Node objects.
-					if let Some(result) = expand_node(active_context.as_ref(), type_scoped_context, active_property, expanded_entries, base_url, loader, options).await? {
+					if let Some(result) = expand_node(active_context.as_ref(), type_scoped_context, active_property, expanded_entries, base_url, loader, options, usage).await? {
 						Ok(result.cast::<Object<T>>().into())
 					} else {
 						Ok(Expanded::Null)
@@ -329,7 +337,8 @@ pub fn expand_element<'a, T: Send + Sync + Id, C: Send + Sync + ContextMut<T>, L
 
 				// Return the result of the Value Expansion algorithm, passing the `active_context`,
 				// `active_property`, and `element` as value.
-				return Ok(Expanded::Object(expand_literal(active_context.as_ref(), active_property, element)?))
+				let value = expand_literal(active_context.as_ref(), active_property, element)?;
+				return Ok(Expanded::Object(options.normalize(value)))
 			}
 		}
 	}.boxed()
@@ -1,6 +1,12 @@
 use std::convert::TryFrom;
+use std::borrow::Cow;
 use iref::{Iri, IriRef};
+use super::Options;
 use crate::{
+	Error,
+	ErrorCode,
+	Issue,
+	Severity,
 	BlankId,
 	Id,
 	Lenient,
@@ -12,6 +18,31 @@ use crate::{
 	}
 };
 
+/// Concatenate an IRI mapping and a suffix, without intermediate reallocation.
+fn concat<'a>(mapping: &str, suffix: &'a str) -> Cow<'a, str> {
+	if mapping.is_empty() {
+		Cow::Borrowed(suffix)
+	} else {
+		let mut result = String::with_capacity(mapping.len() + suffix.len());
+		result.push_str(mapping);
+		result.push_str(suffix);
+		Cow::Owned(result)
+	}
+}
+
+/// Interpret the result of a concatenation as an IRI or blank node identifier.
+///
+/// The result is only copied if it is neither.
+fn concatenation_result<T: Id>(result: Cow<str>) -> Lenient<Term<T>> {
+	if let Ok(iri) = Iri::new(result.as_ref()) {
+		Term::from(T::from_iri(iri)).into()
+	} else if let Ok(blank) = BlankId::try_from(result.as_ref()) {
+		Term::from(blank).into()
+	} else {
+		Lenient::Unknown(result.into_owned())
+	}
+}
+
 // Default value for `document_relative` is `false` and for `vocab` is `true`.
 pub fn expand_iri<T: Id, C: Context<T>>(active_context: &C, value: &str, document_relative: bool, vocab: bool) -> Lenient<Term<T>> {
 	if let Ok(keyword) = Keyword::try_from(value) {
@@ -19,7 +50,8 @@ pub fn expand_iri<T: Id, C: Context<T>>(active_context: &C, value: &str, documen
 	} else {
 		// If value has the form of a keyword, a processor SHOULD generate a warning and return
 		// null.
-		if is_keyword_like(value) {
+		// Such terms can only be defined when explicitly allowed, see `ReservedTerms`.
+		if is_keyword_like(value) && active_context.get(value).is_none() {
 			return Term::Null.into()
 		}
 
@@ -71,18 +103,7 @@ pub fn expand_iri<T: Id, C: Context<T>>(active_context: &C, value: &str, documen
 				if let Some(term_definition) = active_context.get(prefix) {
 					if term_definition.prefix {
 						if let Some(mapping) = &term_definition.value {
-							let mut result = mapping.as_str().to_string();
-							result.push_str(suffix);
-
-							if let Ok(result) = Iri::new(&result) {
-								return Term::from(T::from_iri(result)).into()
-							} else {
-								if let Ok(blank) = BlankId::try_from(result.as_ref()) {
-									return Term::from(blank).into()
-								} else {
-									return Lenient::Unknown(result)
-								}
-							}
+							return concatenation_result(concat(mapping.as_str(), suffix))
 						}
 					}
 				}
@@ -99,18 +120,7 @@ pub fn expand_iri<T: Id, C: Context<T>>(active_context: &C, value: &str, documen
 		if vocab {
 			if let Some(vocabulary) = active_context.vocabulary() {
 				if let Term::Ref(mapping) = vocabulary {
-					let mut result = mapping.as_str().to_string();
-					result.push_str(value);
-
-					if let Ok(result) = Iri::new(&result) {
-						return Term::from(T::from_iri(result)).into()
-					} else {
-						if let Ok(blank) = BlankId::try_from(result.as_ref()) {
-							return Term::from(blank).into()
-						} else {
-							return Lenient::Unknown(result)
-						}
-					}
+					return concatenation_result(concat(mapping.as_str(), value))
 				} else {
 					return Lenient::Unknown(value.to_string())
 				}
@@ -140,3 +150,72 @@ pub fn expand_iri<T: Id, C: Context<T>>(active_context: &C, value: &str, documen
 		Lenient::Unknown(value.to_string())
 	}
 }
+
+/// Handling of malformed IRIs during expansion.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum IriValidation {
+	/// Malformed IRIs raise a `malformed IRI` error.
+	Strict,
+
+	/// Malformed IRIs are kept as [`Lenient::Unknown`] values.
+	Lenient,
+
+	/// Characters not allowed in IRIs (such as spaces) are percent-encoded,
+	/// with a warning.
+	/// IRIs that cannot be repaired this way are kept as [`Lenient::Unknown`] values.
+	Repair
+}
+
+impl Default for IriValidation {
+	fn default() -> IriValidation {
+		IriValidation::Lenient
+	}
+}
+
+/// Percent-encode the characters of the given value that are not allowed in IRIs.
+///
+/// Leading and trailing whitespaces are removed.
+/// Returns `None` if the value is left unchanged.
+pub fn repair_iri(value: &str) -> Option<String> {
+	let trimmed = value.trim();
+	let mut result = String::with_capacity(trimmed.len());
+	for c in trimmed.chars() {
+		match c {
+			' ' | '<' | '>' | '"' | '{' | '}' | '|' | '\\' | '^' | '`' => result.push_str(&format!("%{:02X}", c as u32)),
+			c if c.is_control() => {
+				let mut buffer = [0; 4];
+				for byte in c.encode_utf8(&mut buffer).bytes() {
+					result.push_str(&format!("%{:02X}", byte))
+				}
+			},
+			c => result.push(c)
+		}
+	}
+
+	if result == value {
+		None
+	} else {
+		Some(result)
+	}
+}
+
+/// IRI expansion of node identifiers and types, following the `iri_validation` option.
+pub(crate) fn expand_iri_with<T: Id, C: Context<T>>(active_context: &C, value: &str, document_relative: bool, vocab: bool, options: &Options) -> Result<Lenient<Term<T>>, Error> {
+	match expand_iri(active_context, value, document_relative, vocab) {
+		Lenient::Unknown(u) => match options.iri_validation {
+			IriValidation::Strict => Err(Error::from(ErrorCode::MalformedIri).with_subject(value)),
+			IriValidation::Lenient => Ok(Lenient::Unknown(u)),
+			IriValidation::Repair => match repair_iri(value) {
+				Some(repaired) => match expand_iri(active_context, &repaired, document_relative, vocab) {
+					Lenient::Ok(term) => {
+						options.report(Issue::new(Severity::Warning, ErrorCode::MalformedIri, Some(value.to_string())))?;
+						Ok(Lenient::Ok(term))
+					},
+					Lenient::Unknown(_) => Ok(Lenient::Unknown(u))
+				},
+				None => Ok(Lenient::Unknown(u))
+			}
+		},
+		term => Ok(term)
+	}
+}
@@ -10,18 +10,39 @@ mod element;
 
 use std::cmp::{Ord, Ordering};
 use std::collections::HashSet;
+use std::time::Instant;
 use futures::Future;
+use mown::Mown;
 use iref::{Iri, IriBuf};
+use langtag::LanguageTagBuf;
 use json::JsonValue;
 use crate::{
 	ProcessingMode,
 	Error,
+	ErrorCode,
+	Issue,
+	Severity,
+	Action,
+	SharedIssueHandler,
+	CancellationToken,
+	Limits,
+	Usage,
 	Id,
+	Lenient,
+	Reference,
+	Direction,
+	LanguageNormalization,
 	Indexed,
 	Object,
+	Node,
+	Value,
 	ContextMut,
+	OptionsError,
+	interrupt,
+	util,
 	context::{
 		ProcessingOptions,
+		ReservedTerms,
 		Loader
 	}
 };
@@ -34,23 +55,341 @@ pub use node::*;
 pub use array::*;
 pub use element::*;
 
-#[derive(Clone, Copy, Default)]
+#[derive(Clone, Default)]
 pub struct Options {
 	/// Sets the processing mode.
 	pub processing_mode: ProcessingMode,
 
+	/// Base IRI overriding the document base URL, if any.
+	pub base: Option<IriBuf>,
+
 	/// If true, an error is returned if a value fails to expand. If false, the value is dropped.
+	///
+	/// Ignored if an issue handler is set.
 	pub strict: bool,
 
 	/// If set to true, input document entries are processed lexicographically.
 	/// If false, order is not considered in processing.
-	pub ordered: bool
+	pub ordered: bool,
+
+	/// If set to true, `@annotation` entries of node objects are expanded (JSON-LD-star).
+	/// If false, they are ignored like any other keyword-like entry.
+	///
+	/// Annotations of value objects are not supported and raise an `invalid annotation` error.
+	pub rdf_star: bool,
+
+	/// Handler called for every recoverable issue, deciding whether to abort the expansion.
+	///
+	/// If not set, warnings are logged and errors abort the expansion only in `strict` mode.
+	pub issue_handler: Option<SharedIssueHandler>,
+
+	/// Instant after which the expansion is aborted with a `timeout` error, if any.
+	///
+	/// The deadline is checked before expanding each element.
+	pub deadline: Option<Instant>,
+
+	/// Token used to cancel the expansion, if any.
+	///
+	/// Once cancelled, the expansion is aborted with a `cancelled` error before expanding the next element.
+	pub cancellation: Option<CancellationToken>,
+
+	/// Resource limits enforced during the expansion.
+	pub limits: Limits,
+
+	/// Maximum nesting depth of the input document, if any.
+	///
+	/// Documents nested deeper are rejected with a `document too deep` error
+	/// before being expanded.
+	pub max_nesting_depth: Option<usize>,
+
+	/// If true, type-scoped contexts propagate to nested nodes like any other context,
+	/// as in JSON-LD 1.0.
+	///
+	/// See [`ProcessingOptions::ignore_propagation`](`crate::context::ProcessingOptions::ignore_propagation`).
+	pub ignore_propagation: bool,
+
+	/// Handling of context terms having the form of a keyword.
+	pub reserved_terms: ReservedTerms,
+
+	/// If true, node identifiers and types that are neither IRIs nor blank node identifiers
+	/// raise an `invalid @id value` or `invalid type value` error.
+	/// If false, they are kept as [`Lenient::Unknown`] values.
+	pub reject_unresolved: bool,
+
+	/// If true, string values typed with `xsd:boolean`, `xsd:integer` or `xsd:double`
+	/// (for instance through a type coercion) are converted into native JSON booleans and numbers.
+	///
+	/// This mirrors the `useNativeTypes` flag of the RDF deserialization algorithm.
+	/// See [`Value::into_native`](`crate::object::Value::into_native`).
+	pub use_native_types: bool,
+
+	/// Normalization of the language tags of expanded values.
+	pub language_normalization: LanguageNormalization,
+
+	/// Handling of malformed node identifiers and types.
+	pub iri_validation: IriValidation,
+
+	/// Default language set in the initial active context, if any.
+	///
+	/// This is equivalent to an `@language` entry in a context applied before the document.
+	/// The document contexts can override it.
+	pub default_language: Option<LanguageTagBuf>,
+
+	/// Default base direction set in the initial active context, if any.
+	///
+	/// This is equivalent to an `@direction` entry in a context applied before the document.
+	/// The document contexts can override it.
+	pub default_base_direction: Option<Direction>,
+
+	/// If true, the strings and keys of the input document are converted into
+	/// Unicode Normalization Form C (NFC) before expansion,
+	/// so that the expanded string literals and IRIs are NFC-normalized.
+	///
+	/// Remote contexts are not normalized.
+	/// This option requires the `unicode-normalization` feature.
+	#[cfg(feature="unicode-normalization")]
+	pub nfc: bool
+}
+
+impl Options {
+	/// Create a new options builder, starting from the default options.
+	pub fn builder() -> OptionsBuilder {
+		OptionsBuilder::new()
+	}
+
+	/// Checks the deadline and cancellation token.
+	pub(crate) fn check_interrupt(&self) -> Result<(), Error> {
+		interrupt::check(self.deadline, self.cancellation.as_ref())
+	}
+
+	/// Checks that the identifier and types of a node are resolved, if required.
+	pub(crate) fn check_resolved<T: Id>(&self, node: &Node<T>) -> Result<(), Error> {
+		self.check_resolved_id(node.id())?;
+
+		if self.reject_unresolved {
+			for ty in node.types() {
+				if let Lenient::Unknown(ty) = ty {
+					return Err(Error::from(ErrorCode::InvalidTypeValue).with_subject(ty.as_str()))
+				}
+			}
+		}
+
+		Ok(())
+	}
+
+	/// Checks that the given node or graph identifier is resolved,
+	/// if `reject_unresolved` is set.
+	pub(crate) fn check_resolved_id<T: Id>(&self, id: Option<&Lenient<Reference<T>>>) -> Result<(), Error> {
+		if self.reject_unresolved {
+			if let Some(Lenient::Unknown(id)) = id {
+				return Err(Error::from(ErrorCode::InvalidIdValue).with_subject(id.as_str()))
+			}
+		}
+
+		Ok(())
+	}
+
+	/// Applies the required normalizations to the given expanded value.
+	///
+	/// Typed literals are converted into native values if `use_native_types` is set,
+	/// and language tags are normalized following `language_normalization`.
+	pub(crate) fn normalize<T: Id>(&self, object: Indexed<Object<T>>) -> Indexed<Object<T>> {
+		let (object, index) = object.into_parts();
+		let object = match object {
+			Object::Value(Value::LangString(mut s)) => {
+				if let Some(language) = s.language() {
+					let language = self.language_normalization.apply_tag(language.cloned());
+					s.set_language(Some(language)).unwrap();
+				}
+
+				Object::Value(Value::LangString(s))
+			},
+			Object::Value(value) if self.use_native_types => Object::Value(value.into_native()),
+			object => object
+		};
+
+		Indexed::new(object, index)
+	}
+
+	/// Report a recoverable issue.
+	///
+	/// Returns the error corresponding to the issue if the expansion must be aborted.
+	pub(crate) fn report(&self, issue: Issue) -> Result<(), Error> {
+		let action = match &self.issue_handler {
+			Some(handler) => handler.handle(&issue),
+			None => match issue.severity {
+				Severity::Error if self.strict => Action::Abort,
+				_ => {
+					warn!("{}", issue);
+					Action::Continue
+				}
+			}
+		};
+
+		match action {
+			Action::Continue => Ok(()),
+			Action::Abort => Err(issue.into_error())
+		}
+	}
+}
+
+/// Builder of expansion [`Options`].
+pub struct OptionsBuilder {
+	options: Options
+}
+
+impl OptionsBuilder {
+	/// Create a new builder, starting from the default options.
+	pub fn new() -> OptionsBuilder {
+		OptionsBuilder {
+			options: Options::default()
+		}
+	}
+
+	/// Set the processing mode.
+	pub fn processing_mode(mut self, mode: ProcessingMode) -> Self {
+		self.options.processing_mode = mode;
+		self
+	}
+
+	/// Set the base IRI overriding the document base URL.
+	pub fn base(mut self, base: Option<IriBuf>) -> Self {
+		self.options.base = base;
+		self
+	}
+
+	/// Abort the expansion when a value fails to expand.
+	pub fn strict(mut self, strict: bool) -> Self {
+		self.options.strict = strict;
+		self
+	}
+
+	/// Process entries in lexicographical order.
+	pub fn ordered(mut self, ordered: bool) -> Self {
+		self.options.ordered = ordered;
+		self
+	}
+
+	/// Expand `@annotation` entries (JSON-LD-star).
+	pub fn rdf_star(mut self, rdf_star: bool) -> Self {
+		self.options.rdf_star = rdf_star;
+		self
+	}
+
+	/// Set the handler of recoverable issues.
+	pub fn issue_handler(mut self, handler: Option<SharedIssueHandler>) -> Self {
+		self.options.issue_handler = handler;
+		self
+	}
+
+	/// Set the instant after which the expansion is aborted.
+	pub fn deadline(mut self, deadline: Option<Instant>) -> Self {
+		self.options.deadline = deadline;
+		self
+	}
+
+	/// Set the token used to cancel the expansion.
+	pub fn cancellation(mut self, token: Option<CancellationToken>) -> Self {
+		self.options.cancellation = token;
+		self
+	}
+
+	/// Set the resource limits enforced during the expansion.
+	pub fn limits(mut self, limits: Limits) -> Self {
+		self.options.limits = limits;
+		self
+	}
+
+	/// Set the maximum nesting depth of the input document.
+	pub fn max_nesting_depth(mut self, max: Option<usize>) -> Self {
+		self.options.max_nesting_depth = max;
+		self
+	}
+
+	/// Let type-scoped contexts propagate to nested nodes.
+	pub fn ignore_propagation(mut self, value: bool) -> Self {
+		self.options.ignore_propagation = value;
+		self
+	}
+
+	/// Set the handling of context terms having the form of a keyword.
+	pub fn reserved_terms(mut self, policy: ReservedTerms) -> Self {
+		self.options.reserved_terms = policy;
+		self
+	}
+
+	/// Reject node identifiers and types that are neither IRIs nor blank node identifiers.
+	pub fn reject_unresolved(mut self, value: bool) -> Self {
+		self.options.reject_unresolved = value;
+		self
+	}
+
+	/// Convert typed string values into native JSON booleans and numbers.
+	pub fn use_native_types(mut self, value: bool) -> Self {
+		self.options.use_native_types = value;
+		self
+	}
+
+	/// Set the normalization of the language tags of expanded values.
+	pub fn language_normalization(mut self, normalization: LanguageNormalization) -> Self {
+		self.options.language_normalization = normalization;
+		self
+	}
+
+	/// Normalize the input document strings into Unicode Normalization Form C.
+	#[cfg(feature="unicode-normalization")]
+	pub fn nfc(mut self, value: bool) -> Self {
+		self.options.nfc = value;
+		self
+	}
+
+	/// Set the handling of malformed node identifiers and types.
+	pub fn iri_validation(mut self, validation: IriValidation) -> Self {
+		self.options.iri_validation = validation;
+		self
+	}
+
+	/// Set the default language of the initial active context.
+	pub fn default_language(mut self, language: Option<LanguageTagBuf>) -> Self {
+		self.options.default_language = language;
+		self
+	}
+
+	/// Set the default base direction of the initial active context.
+	pub fn default_base_direction(mut self, direction: Option<Direction>) -> Self {
+		self.options.default_base_direction = direction;
+		self
+	}
+
+	/// Validate and build the options.
+	///
+	/// JSON-LD-star requires the `json-ld-1.1` processing mode,
+	/// and the `strict` flag cannot be combined with an issue handler, which would ignore it.
+	pub fn build(self) -> Result<Options, OptionsError> {
+		if self.options.rdf_star && self.options.processing_mode == ProcessingMode::JsonLd1_0 {
+			return Err(OptionsError::RdfStarRequiresJsonLd1_1)
+		}
+
+		if self.options.strict && self.options.issue_handler.is_some() {
+			return Err(OptionsError::StrictWithIssueHandler)
+		}
+
+		Ok(self.options)
+	}
+}
+
+impl Default for OptionsBuilder {
+	fn default() -> OptionsBuilder {
+		OptionsBuilder::new()
+	}
 }
 
-impl From<Options> for ProcessingOptions {
-	fn from(options: Options) -> ProcessingOptions {
+impl<'a> From<&'a Options> for ProcessingOptions {
+	fn from(options: &'a Options) -> ProcessingOptions {
 		let mut copt = ProcessingOptions::default();
 		copt.processing_mode = options.processing_mode;
+		copt.ignore_propagation = options.ignore_propagation;
+		copt.reserved_terms = options.reserved_terms;
 		copt
 	}
 }
@@ -59,7 +398,17 @@ impl From<crate::compaction::Options> for Options {
 	fn from(options: crate::compaction::Options) -> Options {
 		Options {
 			processing_mode: options.processing_mode,
+			base: options.base,
 			ordered: options.ordered,
+			deadline: options.deadline,
+			cancellation: options.cancellation,
+			max_nesting_depth: options.max_nesting_depth,
+			limits: options.limits,
+			issue_handler: options.issue_handler,
+			strict: options.strict,
+			rdf_star: options.rdf_star,
+			ignore_propagation: options.ignore_propagation,
+			reserved_terms: options.reserved_terms,
 			..Options::default()
 		}
 	}
@@ -88,12 +437,50 @@ fn filter_top_level_item<T: Id>(item: &Indexed<Object<T>>) -> bool {
 	}
 }
 
-pub fn expand<'a, T: Send + Sync + Id, C: Send + Sync + ContextMut<T>, L: Send + Sync + Loader>(active_context: &'a C, element: &'a JsonValue, base_url: Option<Iri>, loader: &'a mut L, options: Options) -> impl 'a + Send + Future<Output=Result<HashSet<Indexed<Object<T>>>, Error>> where C::LocalContext: Send + Sync + From<L::Output> + From<JsonValue>, L::Output: Into<JsonValue> {
-	let base_url = base_url.map(|url| IriBuf::from(url));
+pub fn expand<'a, T: Send + Sync + Id, C: Send + Sync + ContextMut<T>, L: Send + Sync + Loader>(active_context: &'a C, element: &'a JsonValue, base_url: Option<Iri>, loader: &'a mut L, options: &'a Options) -> impl 'a + Send + Future<Output=Result<HashSet<Indexed<Object<T>>>, Error>> where C::LocalContext: Send + Sync + From<L::Output> + From<JsonValue>, L::Output: Into<JsonValue> {
+	// The `base` option, if set, takes precedence over the document base URL.
+	let base_url = match &options.base {
+		Some(base) => Some(base.clone()),
+		None => base_url.map(|url| IriBuf::from(url))
+	};
 
 	async move {
+		if let Some(max) = options.max_nesting_depth {
+			if util::json_depth_exceeds(element, max) {
+				return Err(ErrorCode::DocumentTooDeep.into())
+			}
+		}
+
+		#[cfg(feature="unicode-normalization")]
+		let normalized = if options.nfc {
+			Some(util::json_nfc(element))
+		} else {
+			None
+		};
+
+		#[cfg(feature="unicode-normalization")]
+		let element = normalized.as_ref().unwrap_or(element);
+
+		// The default language and base direction options contribute to the initial active context.
+		let active_context = if options.default_language.is_some() || options.default_base_direction.is_some() {
+			let mut context = active_context.clone();
+
+			if let Some(language) = &options.default_language {
+				context.set_default_language(Some(language.clone()))
+			}
+
+			if let Some(direction) = options.default_base_direction {
+				context.set_default_base_direction(Some(direction))
+			}
+
+			Mown::Owned(context)
+		} else {
+			Mown::Borrowed(active_context)
+		};
+
+		let usage = Usage::new();
 		let base_url = base_url.as_ref().map(|url| url.as_iri());
-		let expanded = expand_element(active_context, None, element, base_url, loader, options, false).await?;
+		let expanded = expand_element(active_context.as_ref(), None, element, base_url, loader, options, &usage, false).await?;
 		if expanded.len() == 1 {
 			match expanded.into_iter().next().unwrap().into_unnamed_graph() {
 				Ok(graph) => Ok(graph),
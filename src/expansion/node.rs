@@ -10,6 +10,7 @@ use crate::{
 	ProcessingMode,
 	LangString,
 	Id,
+	Usage,
 	Reference,
 	Lenient,
 	Indexed,
@@ -28,7 +29,7 @@ use crate::{
 	}
 };
 use crate::util::as_array;
-use super::{Expanded, Entry, Options, expand_element, expand_literal, expand_iri, filter_top_level_item};
+use super::{Expanded, Entry, Options, expand_element, expand_literal, expand_iri, expand_iri_with, filter_top_level_item};
 
 /// Convert a lenient term to a node id, if possible.
 /// Return `None` if the term is `null`.
@@ -41,12 +42,20 @@ pub fn node_id_of_term<T: Id>(term: Lenient<Term<T>>) -> Option<Lenient<Referenc
 	}
 }
 
-pub async fn expand_node<T: Send + Sync + Id, C: Send + Sync + ContextMut<T>, L: Send + Sync + Loader>(active_context: &C, type_scoped_context: &C, active_property: Option<&str>, expanded_entries: Vec<Entry<'_, (&str, Term<T>)>>, base_url: Option<Iri<'_>>, loader: &mut L, options: Options) -> Result<Option<Indexed<Node<T>>>, Error> where C::LocalContext: Send + Sync + From<L::Output> + From<JsonValue>, L::Output: Into<JsonValue> {
+pub async fn expand_node<T: Send + Sync + Id, C: Send + Sync + ContextMut<T>, L: Send + Sync + Loader>(active_context: &C, type_scoped_context: &C, active_property: Option<&str>, expanded_entries: Vec<Entry<'_, (&str, Term<T>)>>, base_url: Option<Iri<'_>>, loader: &mut L, options: &Options, usage: &Usage) -> Result<Option<Indexed<Node<T>>>, Error> where C::LocalContext: Send + Sync + From<L::Output> + From<JsonValue>, L::Output: Into<JsonValue> {
 	// Initialize two empty maps, `result` and `nests`.
 	let mut result = Indexed::new(Node::new(), None);
 	let mut has_value_object_entries = false;
 
-	expand_node_entries(&mut result, &mut has_value_object_entries, active_context, type_scoped_context, active_property, expanded_entries, base_url, loader, options).await?;
+	expand_node_entries(&mut result, &mut has_value_object_entries, active_context, type_scoped_context, active_property, expanded_entries, base_url, loader, options, usage).await?;
+
+	for (prop, values) in result.properties() {
+		options.limits.check_values(prop.as_str(), values.len())?
+	}
+
+	for (prop, nodes) in result.reverse_properties() {
+		options.limits.check_values(prop.as_str(), nodes.len())?
+	}
 
 	// If result contains the entry @value:
 	// The result must not contain any entries other than @direction, @index,
@@ -77,10 +86,12 @@ pub async fn expand_node<T: Send + Sync + Id, C: Send + Sync + ContextMut<T>, L:
 		}
 	}
 
+	options.check_resolved(&result)?;
+
 	Ok(Some(result))
 }
 
-fn expand_node_entries<'a, T: Send + Sync + Id, C: Send + Sync + ContextMut<T>, L: Send + Sync + Loader>(result: &'a mut Indexed<Node<T>>, has_value_object_entries: &'a mut bool, active_context: &'a C, type_scoped_context: &'a C, active_property: Option<&'a str>, expanded_entries: Vec<Entry<'a, (&'a str, Term<T>)>>, base_url: Option<Iri<'a>>, loader: &'a mut L, options: Options) -> BoxFuture<'a, Result<(), Error>> where C::LocalContext: Send + Sync + From<L::Output> + From<JsonValue>, L::Output: Into<JsonValue> {
+fn expand_node_entries<'a, T: Send + Sync + Id, C: Send + Sync + ContextMut<T>, L: Send + Sync + Loader>(result: &'a mut Indexed<Node<T>>, has_value_object_entries: &'a mut bool, active_context: &'a C, type_scoped_context: &'a C, active_property: Option<&'a str>, expanded_entries: Vec<Entry<'a, (&'a str, Term<T>)>>, base_url: Option<Iri<'a>>, loader: &'a mut L, options: &'a Options, usage: &'a Usage) -> BoxFuture<'a, Result<(), Error>> where C::LocalContext: Send + Sync + From<L::Output> + From<JsonValue>, L::Output: Into<JsonValue> {
 	async move {
 		// For each `key` and `value` in `element`, ordered lexicographically by key
 		// if `ordered` is `true`:
@@ -121,7 +132,7 @@ fn expand_node_entries<'a, T: Send + Sync + Id, C: Send + Sync + ContextMut<T>,
 								// Otherwise, set `expanded_value` to the result of IRI
 								// expanding value using true for document relative and
 								// false for vocab.
-								result.id = node_id_of_term(expand_iri(active_context, value, true, false))
+								result.id = node_id_of_term(expand_iri_with(active_context, value, true, false, options)?)
 							} else {
 								return Err(ErrorCode::InvalidIdValue.into())
 							}
@@ -136,14 +147,14 @@ fn expand_node_entries<'a, T: Send + Sync + Id, C: Send + Sync + ContextMut<T>,
 							// of its values using `type_scoped_context` for active
 							// context, and true for document relative.
 							for ty in value {
-								if let Some(ty) = ty.as_str() {
-									if let Ok(ty) = expand_iri(type_scoped_context, ty, true, true).try_cast() {
+								if let Some(ty_str) = ty.as_str() {
+									if let Ok(ty) = expand_iri_with(type_scoped_context, ty_str, true, true, options)?.try_cast() {
 										result.types.push(ty)
 									} else {
-										return Err(ErrorCode::InvalidTypeValue.into())
+										return Err(Error::from(ErrorCode::InvalidTypeValue).with_fragment(ty.clone()))
 									}
 								} else {
-									return Err(ErrorCode::InvalidTypeValue.into())
+									return Err(Error::from(ErrorCode::InvalidTypeValue).with_fragment(ty.clone()))
 								}
 							}
 						},
@@ -154,22 +165,22 @@ fn expand_node_entries<'a, T: Send + Sync + Id, C: Send + Sync + ContextMut<T>,
 							// property, `value` for element, `base_url`, and the
 							// `frame_expansion` and `ordered` flags, ensuring that
 							// `expanded_value` is an array of one or more maps.
-							let expanded_value = expand_element(active_context, Some("@graph"), value, base_url, loader, options, false).await?;
+							let expanded_value = expand_element(active_context, Some("@graph"), value, base_url, loader, options, usage, false).await?;
 							result.graph = Some(expanded_value.into_iter().filter(filter_top_level_item).collect());
 						},
 						// If expanded property is @included:
 						Keyword::Included => {
-							// If processing mode is json-ld-1.0, continue with the next
-							// key from element.
+							// `@included` is not available in json-ld-1.0:
+							// a processing mode conflict error has been detected.
 							if options.processing_mode == ProcessingMode::JsonLd1_0 {
-								continue
+								return Err(ErrorCode::ProcessingModeConflict.into())
 							}
 
 							// Set `expanded_value` to the result of using this algorithm
 							// recursively passing `active_context`, `active_property`,
 							// `value` for element, `base_url`, and the `frame_expansion`
 							// and `ordered` flags, ensuring that the result is an array.
-							let expanded_value = expand_element(active_context, Some("@included"), value, base_url, loader, options, false).await?;
+							let expanded_value = expand_element(active_context, Some("@included"), value, base_url, loader, options, usage, false).await?;
 							let mut expanded_nodes = Vec::new();
 							for obj in expanded_value.into_iter() {
 								match obj.try_cast::<Node<T>>() {
@@ -186,6 +197,38 @@ fn expand_node_entries<'a, T: Send + Sync + Id, C: Send + Sync + ContextMut<T>,
 								result.included = Some(expanded_nodes.into_iter().collect());
 							}
 						},
+						// If expanded property is @annotation (JSON-LD-star):
+						Keyword::Annotation => {
+							// Annotations are only allowed on property values.
+							match active_property {
+								None | Some("@graph") | Some("@included") | Some("@annotation") => {
+									return Err(ErrorCode::InvalidAnnotation.into())
+								},
+								_ => ()
+							}
+
+							// Set `expanded_value` to the result of using this algorithm
+							// recursively passing `active_context`, `@annotation` for active
+							// property, `value` for element, `base_url`, and the `frame_expansion`
+							// and `ordered` flags, ensuring that the result is an array
+							// of node objects without `@id`.
+							let expanded_value = expand_element(active_context, Some("@annotation"), value, base_url, loader, options, usage, false).await?;
+							let mut expanded_nodes = Vec::new();
+							for obj in expanded_value.into_iter() {
+								match obj.try_cast::<Node<T>>() {
+									Ok(node) if node.id.is_none() => expanded_nodes.push(node),
+									_ => {
+										return Err(ErrorCode::InvalidAnnotation.into())
+									}
+								}
+							}
+
+							if let Some(annotation) = &mut result.annotation {
+								annotation.extend(expanded_nodes.into_iter());
+							} else {
+								result.annotation = Some(expanded_nodes.into_iter().collect());
+							}
+						},
 						// If expanded property is @language:
 						Keyword::Language => {
 							*has_value_object_entries = true
@@ -224,7 +267,7 @@ fn expand_node_entries<'a, T: Send + Sync + Id, C: Send + Sync + ContextMut<T>,
 											return Err(ErrorCode::InvalidReversePropertyMap.into())
 										},
 										Lenient::Ok(Term::Ref(reverse_prop)) => {
-											let reverse_expanded_value = expand_element(active_context, Some(reverse_key), reverse_value, base_url, loader, options, false).await?;
+											let reverse_expanded_value = expand_element(active_context, Some(reverse_key), reverse_value, base_url, loader, options, usage, false).await?;
 
 											let is_double_reversed = if let Some(reverse_key_definition) = active_context.get(reverse_key) {
 												reverse_key_definition.reverse_property
@@ -276,7 +319,7 @@ fn expand_node_entries<'a, T: Send + Sync + Id, C: Send + Sync + ContextMut<T>,
 										}
 									});
 
-									expand_node_entries(result, has_value_object_entries, active_context, type_scoped_context, active_property, nested_expanded_entries.collect(), base_url, loader, options).await?
+									expand_node_entries(result, has_value_object_entries, active_context, type_scoped_context, active_property, nested_expanded_entries.collect(), base_url, loader, options, usage).await?
 								} else {
 									return Err(ErrorCode::InvalidNestValue.into())
 								}
@@ -315,7 +358,7 @@ fn expand_node_entries<'a, T: Send + Sync + Id, C: Send + Sync + ContextMut<T>,
 					}
 
 					let mut expanded_value = if is_json {
-						Expanded::Object(Object::Value(Value::Json(value.clone())).into())
+						Expanded::Object(Object::Value(Value::Json(value.clone().into())).into())
 					} else if value.is_object() && container_mapping.contains(ContainerType::Language) {
 						// Otherwise, if container mapping includes @language and value is a map then
 						// value is expanded from a language map as follows:
@@ -349,6 +392,15 @@ fn expand_node_entries<'a, T: Send + Sync + Id, C: Send + Sync + ContextMut<T>,
 							// an array containing only language value.
 							let language_value = as_array(language_value);
 
+							// If language is @none, or expands to
+							// @none, remove @language from v.
+							// This does not depend on the item, so it is done once per language.
+							let language = if expand_iri(active_context, language, false, true) == Term::Keyword(Keyword::None) {
+								Ok(None)
+							} else {
+								LanguageTagBuf::parse_copy(language).map(Some)
+							};
+
 							// For each item in language value:
 							for item in language_value {
 								match item {
@@ -358,30 +410,28 @@ fn expand_node_entries<'a, T: Send + Sync + Id, C: Send + Sync + ContextMut<T>,
 									JsonValue::Short(_) | JsonValue::String(_) => {
 										let item = item.as_str().unwrap();
 
-										// If language is @none, or expands to
-										// @none, remove @language from v.
-										let language = if expand_iri(active_context, language, false, true) == Term::Keyword(Keyword::None) {
-											None
-										} else {
-											match LanguageTagBuf::parse_copy(language) {
-												Ok(lang) => Some(lang),
-												Err(_) => return Err(ErrorCode::InvalidLanguageMapValue.into())
-											}
+										let language = match &language {
+											Ok(language) => language.clone(),
+											Err(_) => return Err(ErrorCode::InvalidLanguageMapValue.into())
 										};
 
 										// initialize a new map v consisting of two
 										// key-value pairs: (@value-item) and
 										// (@language-language).
-										if let Ok(v) = LangString::new(item.to_string(), language, direction) {
-											// If item is neither @none nor well-formed
-											// according to section 2.2.9 of [BCP47],
-											// processors SHOULD issue a warning.
-											// TODO warning
-
-											// Append v to expanded value.
-											expanded_value.push(Object::Value(Value::LangString(v)).into())
-										} else {
-											expanded_value.push(Object::Value(Value::Literal(Literal::String(item.to_string()), None)).into())
+										match LangString::new(item.to_string(), language, direction) {
+											Ok(v) => {
+												// If item is neither @none nor well-formed
+												// according to section 2.2.9 of [BCP47],
+												// processors SHOULD issue a warning.
+												// TODO warning
+
+												// Append v to expanded value.
+												expanded_value.push(options.normalize(Object::Value(Value::LangString(v)).into()))
+											},
+											Err(item) => {
+												// The string is given back: no need to copy it again.
+												expanded_value.push(Object::Value(Value::Literal(Literal::String(item), None)).into())
+											}
 										}
 									},
 									_ => {
@@ -473,7 +523,7 @@ fn expand_node_entries<'a, T: Send + Sync + Id, C: Send + Sync + ContextMut<T>,
 							// index value as element, base URL, and the
 							// frameExpansion and ordered flags.
 							// And `true` for `from_map`.
-							let index_value = expand_element(map_context.as_ref(), Some(key), index_value, base_url, loader, options, true).await?;
+							let index_value = expand_element(map_context.as_ref(), Some(key), index_value, base_url, loader, options, usage, true).await?;
 							// For each item in index value:
 							for mut item in index_value {
 								// If container mapping includes @graph,
@@ -482,11 +532,9 @@ fn expand_node_entries<'a, T: Send + Sync + Id, C: Send + Sync + ContextMut<T>,
 								// @graph-item, ensuring that the value is
 								// represented using an array.
 								if container_mapping.contains(ContainerType::Graph) && !item.is_graph() {
-									let mut node = Node::new();
 									let mut graph = HashSet::new();
 									graph.insert(item);
-									node.graph = Some(graph);
-									item = Object::Node(node).into();
+									item = Object::Graph(Graph::new(graph)).into();
 								}
 
 								if expanded_index.is_some() {
@@ -516,7 +564,7 @@ fn expand_node_entries<'a, T: Send + Sync + Id, C: Send + Sync + ContextMut<T>,
 
 										// Add the key-value pair (expanded index
 										// key-index property values) to item.
-										if let Object::Node(ref mut node) = *item {
+										if let Some(node) = item.node_mut() {
 											node.insert(expanded_index_key, re_expanded_index);
 										} else {
 											// If item is a value object, it MUST NOT
@@ -539,8 +587,16 @@ fn expand_node_entries<'a, T: Send + Sync + Id, C: Send + Sync + ContextMut<T>,
 										// item, where expanded index is set to the
 										// result of IRI expanding index using true for
 										// document relative and false for vocab.
-										if let Object::Node(ref mut node) = *item {
-											node.id = node_id_of_term(expand_iri(active_context, index, true, false));
+										match *item {
+											Object::Node(ref mut node) => {
+												node.id = node_id_of_term(expand_iri_with(active_context, index, true, false, options)?);
+												options.check_resolved(node)?;
+											},
+											Object::Graph(ref mut graph) => {
+												graph.id = node_id_of_term(expand_iri_with(active_context, index, true, false, options)?);
+												options.check_resolved_id(graph.id())?;
+											},
+											_ => ()
 										}
 									} else if container_mapping.contains(ContainerType::Type) {
 										// Otherwise, if container mapping includes
@@ -552,6 +608,7 @@ fn expand_node_entries<'a, T: Send + Sync + Id, C: Send + Sync + ContextMut<T>,
 										if let Ok(typ) = expanded_index.clone().unwrap().try_cast() {
 											if let Object::Node(ref mut node) = *item {
 												node.types.insert(0, typ);
+												options.check_resolved(node)?;
 											}
 										} else {
 											return Err(ErrorCode::InvalidTypeValue.into())
@@ -569,7 +626,7 @@ fn expand_node_entries<'a, T: Send + Sync + Id, C: Send + Sync + ContextMut<T>,
 						// Otherwise, initialize expanded value to the result of using this
 						// algorithm recursively, passing active context, key for active property,
 						// value for element, base URL, and the frameExpansion and ordered flags.
-						expand_element(active_context, Some(key), value, base_url, loader, options, false).await?
+						expand_element(active_context, Some(key), value, base_url, loader, options, usage, false).await?
 					};
 
 					// If container mapping includes @list and expanded value is
@@ -588,11 +645,9 @@ fn expand_node_entries<'a, T: Send + Sync + Id, C: Send + Sync + ContextMut<T>,
 					// into a graph object:
 					if container_mapping.contains(ContainerType::Graph) && !container_mapping.contains(ContainerType::Id) && !container_mapping.contains(ContainerType::Index) {
 						expanded_value = Expanded::Array(expanded_value.into_iter().map(|ev| {
-							let mut node = Node::new();
 							let mut graph = HashSet::new();
 							graph.insert(ev);
-							node.graph = Some(graph);
-							Object::Node(node).into()
+							Object::Graph(Graph::new(graph)).into()
 						}).collect());
 					}
 
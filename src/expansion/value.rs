@@ -2,8 +2,11 @@ use std::convert::TryFrom;
 use langtag::LanguageTagBuf;
 use json::JsonValue;
 use crate::{
+	ProcessingMode,
 	Error,
 	ErrorCode,
+	Issue,
+	Severity,
 	Direction,
 	LangString,
 	Id,
@@ -17,16 +20,16 @@ use crate::{
 		Term
 	}
 };
-use super::{Entry, expand_iri};
+use super::{Entry, Options, expand_iri};
 
-pub fn expand_value<'a, T: Id, C: ContextMut<T>>(input_type: Option<Lenient<Term<T>>>, type_scoped_context: &C, expanded_entries: Vec<Entry<(&str, Term<T>)>>, value_entry: &JsonValue) -> Result<Option<Indexed<Object<T>>>, Error> {
+pub fn expand_value<'a, T: Id, C: ContextMut<T>>(input_type: Option<Lenient<Term<T>>>, type_scoped_context: &C, expanded_entries: Vec<Entry<(&str, Term<T>)>>, value_entry: &JsonValue, options: &Options) -> Result<Option<Indexed<Object<T>>>, Error> {
 	let mut is_json = input_type == Some(Lenient::Ok(Term::Keyword(Keyword::Json)));
 	let mut ty = None;
 	let mut index = None;
 	let mut language = None;
 	let mut direction = None;
 
-	for Entry((_, expanded_key), value) in expanded_entries {
+	for Entry((key, expanded_key), value) in expanded_entries {
 		match expanded_key {
 			// If expanded property is @language:
 			Term::Keyword(Keyword::Language) => {
@@ -42,14 +45,16 @@ pub fn expand_value<'a, T: Id, C: ContextMut<T>>(input_type: Option<Lenient<Term
 						language = Some(value.to_string());
 					}
 				} else {
-					return Err(ErrorCode::InvalidLanguageTaggedString.into())
+					return Err(Error::from(ErrorCode::InvalidLanguageTaggedString).with_fragment(value.clone()))
 				}
 			},
 			// If expanded property is @direction:
 			Term::Keyword(Keyword::Direction) => {
-				// If processing mode is json-ld-1.0, continue with the next key
-				// from element.
-				// TODO processing mode.
+				// `@direction` is not available in json-ld-1.0:
+				// a processing mode conflict error has been detected.
+				if options.processing_mode == ProcessingMode::JsonLd1_0 {
+					return Err(ErrorCode::ProcessingModeConflict.into())
+				}
 
 				// If value is neither "ltr" nor "rtl", an invalid base direction
 				// error has been detected and processing is aborted.
@@ -57,10 +62,10 @@ pub fn expand_value<'a, T: Id, C: ContextMut<T>>(input_type: Option<Lenient<Term
 					if let Ok(value) = Direction::try_from(value) {
 						direction = Some(value);
 					} else {
-						return Err(ErrorCode::InvalidBaseDirection.into())
+						return Err(Error::from(ErrorCode::InvalidBaseDirection).with_fragment(value.clone()))
 					}
 				} else {
-					return Err(ErrorCode::InvalidBaseDirection.into())
+					return Err(Error::from(ErrorCode::InvalidBaseDirection).with_fragment(value.clone()))
 				}
 			},
 			// If expanded property is @index:
@@ -70,7 +75,7 @@ pub fn expand_value<'a, T: Id, C: ContextMut<T>>(input_type: Option<Lenient<Term
 				if let Some(value) = value.as_str() {
 					index = Some(value.to_string())
 				} else {
-					return Err(ErrorCode::InvalidIndexValue.into())
+					return Err(Error::from(ErrorCode::InvalidIndexValue).with_fragment(value.clone()))
 				}
 			},
 			// If expanded ...
@@ -87,16 +92,20 @@ pub fn expand_value<'a, T: Id, C: ContextMut<T>>(input_type: Option<Lenient<Term
 							ty = Some(expanded_ty)
 						},
 						_ => {
-							return Err(ErrorCode::InvalidTypedValue.into())
+							return Err(Error::from(ErrorCode::InvalidTypedValue).with_fragment(value.clone()))
 						}
 					}
 				} else {
-					return Err(ErrorCode::InvalidTypedValue.into())
+					return Err(Error::from(ErrorCode::InvalidTypedValue).with_fragment(value.clone()))
 				}
 			},
 			Term::Keyword(Keyword::Value) => (),
+			// Annotations of value objects cannot be represented yet.
+			Term::Keyword(Keyword::Annotation) => {
+				return Err(ErrorCode::InvalidAnnotation.into())
+			},
 			_ => {
-				return Err(ErrorCode::InvalidValueObject.into());
+				return Err(Error::from(ErrorCode::InvalidValueObject).with_subject(key).with_fragment(value.clone()));
 			}
 		}
 	}
@@ -106,10 +115,14 @@ pub fn expand_value<'a, T: Id, C: ContextMut<T>>(input_type: Option<Lenient<Term
 	// If processing mode is json-ld-1.0, an invalid value object value error has
 	// been detected and processing is aborted.
 	if is_json {
+		if options.processing_mode == ProcessingMode::JsonLd1_0 {
+			return Err(Error::from(ErrorCode::InvalidValueObjectValue).with_fragment(value_entry.clone()));
+		}
+
 		if language.is_some() || direction.is_some() {
-			return Err(ErrorCode::InvalidValueObject.into());
+			return Err(Error::from(ErrorCode::InvalidValueObject).with_fragment(value_entry.clone()));
 		}
-		return Ok(Some(Indexed::new(Object::Value(Value::Json(value_entry.clone())), index)))
+		return Ok(Some(Indexed::new(Object::Value(Value::Json(value_entry.clone().into())), index)))
 	}
 
 	// Otherwise, if value is not a scalar or null, an invalid value object value
@@ -128,7 +141,7 @@ pub fn expand_value<'a, T: Id, C: ContextMut<T>>(input_type: Option<Lenient<Term
 			Literal::Boolean(*b)
 		},
 		_ => {
-			return Err(ErrorCode::InvalidValueObjectValue.into());
+			return Err(Error::from(ErrorCode::InvalidValueObjectValue).with_fragment(value_entry.clone()));
 		}
 	};
 
@@ -154,14 +167,18 @@ pub fn expand_value<'a, T: Id, C: ContextMut<T>>(input_type: Option<Lenient<Term
 	// aborted.
 	if language.is_some() || direction.is_some() {
 		if ty.is_some() {
-			return Err(ErrorCode::InvalidValueObject.into())
+			return Err(Error::from(ErrorCode::InvalidValueObject).with_fragment(value_entry.clone()))
 		}
 
 		if let Literal::String(str) = result {
 			let lang = match language {
-				Some(language) => match LanguageTagBuf::new(language.into_bytes()) {
+				Some(language) => match LanguageTagBuf::parse_copy(language.as_str()) {
 					Ok(lang) => Some(lang),
-					Err(_) => return Ok(None)
+					Err(_) => {
+						// The value is dropped.
+						options.report(Issue::new(Severity::Error, ErrorCode::InvalidLanguageTaggedString, Some(language)).with_fragment(value_entry.clone()))?;
+						return Ok(None)
+					}
 				},
 				None => None
 			};
@@ -169,7 +186,7 @@ pub fn expand_value<'a, T: Id, C: ContextMut<T>>(input_type: Option<Lenient<Term
 			let result = LangString::new(str, lang, direction).unwrap();
 			return Ok(Some(Indexed::new(Object::Value(Value::LangString(result)), index)))
 		} else {
-			return Err(ErrorCode::InvalidLanguageTaggedValue.into())
+			return Err(Error::from(ErrorCode::InvalidLanguageTaggedValue).with_fragment(value_entry.clone()))
 		}
 	}
 
@@ -178,5 +195,5 @@ pub fn expand_value<'a, T: Id, C: ContextMut<T>>(input_type: Option<Lenient<Term
 	// @list, set result to null.
 	// TODO
 
-	return Ok(Some(Indexed::new(Object::Value(Value::Literal(result, ty)), index)));
+	return Ok(Some(options.normalize(Indexed::new(Object::Value(Value::Literal(result, ty)), index))));
 }
@@ -0,0 +1,26 @@
+//! Flattening algorithm.
+//!
+//! Flattening collects every node of an expanded document at the top-level,
+//! replacing nested node objects with node references,
+//! and moving the nodes of named graphs into the `@graph` entry of the node
+//! with the graph name.
+//! See <https://www.w3.org/TR/json-ld11-api/#flattening-algorithms>.
+use crate::{
+	Id,
+	Indexed,
+	Object,
+	Error,
+	BlankIdGenerator
+};
+
+mod node_map;
+
+pub use node_map::*;
+
+/// Flatten the given expanded objects.
+///
+/// Nodes without identifier are labeled, and existing blank node identifiers relabeled,
+/// using the given generator.
+pub fn flatten<T: Id, G: BlankIdGenerator, O: IntoIterator<Item=Indexed<Object<T>>>>(objects: O, generator: G) -> Result<Vec<Indexed<Object<T>>>, Error> {
+	Ok(NodeMap::generate(objects, generator)?.flatten())
+}
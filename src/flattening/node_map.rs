@@ -0,0 +1,399 @@
+use std::borrow::Borrow;
+use futures::future::{BoxFuture, FutureExt};
+use indexmap::IndexMap;
+use json::JsonValue;
+use crate::{
+	Id,
+	Context,
+	ContextMutProxy,
+	context::Loader,
+	compaction,
+	util::AsJson,
+	BlankIdGenerator,
+	Reference,
+	ToReference,
+	Lenient,
+	Indexed,
+	Object,
+	Node,
+	Error,
+	ErrorCode,
+	ExpandedDocument,
+	relabeling::Relabeling
+};
+
+/// Nodes of a single graph of a [`NodeMap`], by identifier.
+///
+/// Nodes are kept in insertion order.
+pub struct NodeMapGraph<T: Id> {
+	nodes: IndexMap<Reference<T>, Indexed<Node<T>>>
+}
+
+impl<T: Id> NodeMapGraph<T> {
+	/// Create a new empty graph.
+	pub fn new() -> NodeMapGraph<T> {
+		NodeMapGraph {
+			nodes: IndexMap::new()
+		}
+	}
+
+	/// Returns the number of nodes in the graph.
+	pub fn len(&self) -> usize {
+		self.nodes.len()
+	}
+
+	/// Checks if the graph has no nodes.
+	pub fn is_empty(&self) -> bool {
+		self.nodes.is_empty()
+	}
+
+	/// Checks if the graph contains a node with the given identifier.
+	pub fn contains<'a, Q: ToReference<T>>(&self, id: Q) -> bool where T: 'a {
+		self.nodes.contains_key(id.to_ref().borrow())
+	}
+
+	/// Get the node with the given identifier, if any.
+	pub fn get<'a, Q: ToReference<T>>(&self, id: Q) -> Option<&Indexed<Node<T>>> where T: 'a {
+		self.nodes.get(id.to_ref().borrow())
+	}
+
+	/// Get a mutable reference to the node with the given identifier, if any.
+	///
+	/// The identifier of the node should not be changed.
+	pub fn get_mut<'a, Q: ToReference<T>>(&mut self, id: Q) -> Option<&mut Indexed<Node<T>>> where T: 'a {
+		self.nodes.get_mut(id.to_ref().borrow())
+	}
+
+	/// Get the node with the given identifier, creating an empty node if there is none.
+	pub fn declare(&mut self, id: Reference<T>) -> &mut Indexed<Node<T>> {
+		let node_id = id.clone();
+		self.nodes.entry(id).or_insert_with(|| Indexed::new(Node::with_id(Lenient::Ok(node_id)), None))
+	}
+
+	/// Remove the node with the given identifier, and returns it.
+	pub fn remove<'a, Q: ToReference<T>>(&mut self, id: Q) -> Option<Indexed<Node<T>>> where T: 'a {
+		self.nodes.shift_remove(id.to_ref().borrow())
+	}
+
+	/// Returns an iterator over the nodes of the graph, with their identifier.
+	pub fn iter(&self) -> indexmap::map::Iter<Reference<T>, Indexed<Node<T>>> {
+		self.nodes.iter()
+	}
+
+	/// Returns an iterator over mutable references to the nodes of the graph, with their identifier.
+	pub fn iter_mut(&mut self) -> indexmap::map::IterMut<Reference<T>, Indexed<Node<T>>> {
+		self.nodes.iter_mut()
+	}
+
+	/// Returns the nodes of the graph as objects,
+	/// ignoring the nodes with no other entry than `@id`.
+	///
+	/// An `@index` entry counts as another entry.
+	fn into_objects<C: std::iter::FromIterator<Indexed<Object<T>>>>(self) -> C {
+		self.nodes.into_iter().filter(|(_, node)| !node.is_empty() || node.index().is_some()).map(|(_, node)| node.cast()).collect()
+	}
+}
+
+impl<T: Id> Default for NodeMapGraph<T> {
+	fn default() -> NodeMapGraph<T> {
+		NodeMapGraph::new()
+	}
+}
+
+impl<T: Id> IntoIterator for NodeMapGraph<T> {
+	type Item = (Reference<T>, Indexed<Node<T>>);
+	type IntoIter = indexmap::map::IntoIter<Reference<T>, Indexed<Node<T>>>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		self.nodes.into_iter()
+	}
+}
+
+impl<'a, T: Id> IntoIterator for &'a NodeMapGraph<T> {
+	type Item = (&'a Reference<T>, &'a Indexed<Node<T>>);
+	type IntoIter = indexmap::map::Iter<'a, Reference<T>, Indexed<Node<T>>>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		self.nodes.iter()
+	}
+}
+
+/// Node map.
+///
+/// Result of the node map generation algorithm:
+/// every node of a document, by graph and identifier.
+/// Nodes only hold references to other nodes,
+/// which makes it suitable to rewrite a document subject by subject.
+///
+/// It is built from an expanded document with [`ExpandedDocument::into_node_map`],
+/// and converted back into a (flattened) expanded document with [`NodeMap::into_document`].
+pub struct NodeMap<T: Id> {
+	/// Default graph.
+	default_graph: NodeMapGraph<T>,
+
+	/// Named graphs.
+	graphs: IndexMap<Reference<T>, NodeMapGraph<T>>
+}
+
+impl<T: Id> NodeMap<T> {
+	/// Create a new empty node map.
+	pub fn new() -> NodeMap<T> {
+		NodeMap {
+			default_graph: NodeMapGraph::new(),
+			graphs: IndexMap::new()
+		}
+	}
+
+	/// Generate the node map of the given expanded objects.
+	///
+	/// Nodes without identifier are labeled, and existing blank node identifiers relabeled,
+	/// using the given generator.
+	/// Fails with a `conflicting indexes` error if the same node is given different indexes.
+	pub fn generate<G: BlankIdGenerator, O: IntoIterator<Item=Indexed<Object<T>>>>(objects: O, generator: G) -> Result<NodeMap<T>, Error> {
+		let mut generation = Generation {
+			map: NodeMap::new(),
+			relabeling: Relabeling::new(generator)
+		};
+
+		for object in objects {
+			generation.object(None, object)?;
+		}
+
+		Ok(generation.map)
+	}
+
+	/// Returns the default graph.
+	pub fn default_graph(&self) -> &NodeMapGraph<T> {
+		&self.default_graph
+	}
+
+	/// Returns a mutable reference to the default graph.
+	pub fn default_graph_mut(&mut self) -> &mut NodeMapGraph<T> {
+		&mut self.default_graph
+	}
+
+	/// Get the graph with the given name, or the default graph if `None`.
+	pub fn graph(&self, graph: Option<&Reference<T>>) -> Option<&NodeMapGraph<T>> {
+		match graph {
+			Some(id) => self.graphs.get(id),
+			None => Some(&self.default_graph)
+		}
+	}
+
+	/// Get a mutable reference to the graph with the given name, or the default graph if `None`.
+	pub fn graph_mut(&mut self, graph: Option<&Reference<T>>) -> Option<&mut NodeMapGraph<T>> {
+		match graph {
+			Some(id) => self.graphs.get_mut(id),
+			None => Some(&mut self.default_graph)
+		}
+	}
+
+	/// Get the graph with the given name, or the default graph if `None`,
+	/// creating an empty graph if there is none.
+	pub fn declare_graph(&mut self, graph: Option<&Reference<T>>) -> &mut NodeMapGraph<T> {
+		match graph {
+			Some(id) => self.graphs.entry(id.clone()).or_insert_with(NodeMapGraph::new),
+			None => &mut self.default_graph
+		}
+	}
+
+	/// Returns an iterator over the named graphs, with their name.
+	pub fn graphs(&self) -> indexmap::map::Iter<Reference<T>, NodeMapGraph<T>> {
+		self.graphs.iter()
+	}
+
+	/// Returns an iterator over mutable references to the named graphs, with their name.
+	pub fn graphs_mut(&mut self) -> indexmap::map::IterMut<Reference<T>, NodeMapGraph<T>> {
+		self.graphs.iter_mut()
+	}
+
+	/// Get the node of the default graph with the given identifier, if any.
+	pub fn get<'a, Q: ToReference<T>>(&self, id: Q) -> Option<&Indexed<Node<T>>> where T: 'a {
+		self.default_graph.get(id)
+	}
+
+	/// Get a mutable reference to the node of the default graph with the given identifier, if any.
+	///
+	/// Use [`graph_mut`](NodeMap::graph_mut) to access the nodes of a named graph.
+	pub fn get_mut<'a, Q: ToReference<T>>(&mut self, id: Q) -> Option<&mut Indexed<Node<T>>> where T: 'a {
+		self.default_graph.get_mut(id)
+	}
+
+	/// Get the node of the default graph with the given identifier,
+	/// creating an empty node if there is none.
+	pub fn declare(&mut self, id: Reference<T>) -> &mut Indexed<Node<T>> {
+		self.default_graph.declare(id)
+	}
+
+	/// Flatten the node map.
+	///
+	/// Returns the nodes of the default graph.
+	/// Each named graph is added as the `@graph` entry of the node with the graph name.
+	/// Nodes with no other entry than `@id` (not even `@index`) are ignored.
+	pub fn flatten(self) -> Vec<Indexed<Object<T>>> {
+		let NodeMap { mut default_graph, graphs } = self;
+
+		for (graph_id, graph) in graphs {
+			default_graph.declare(graph_id).set_graph(Some(graph.into_objects()));
+		}
+
+		default_graph.into_objects()
+	}
+
+	/// Convert the node map back into an expanded document.
+	///
+	/// The document is in flattened form (see [`flatten`](NodeMap::flatten)).
+	pub fn into_document(self) -> ExpandedDocument<T> {
+		ExpandedDocument::from_vec(self.flatten())
+	}
+}
+
+impl<T: Sync + Send + Id> NodeMap<T> {
+	/// Compact the node map with the given context, document loader and compaction options.
+	///
+	/// The node map is first converted into a flattened document (see [`flatten`](NodeMap::flatten)),
+	/// which is compacted like [`ExpandedDocument::compact`] does,
+	/// except that the top-level nodes are always put in a `@graph` entry.
+	/// Each named graph is compacted into the `@graph` entry of the node with the graph name,
+	/// along with its `@id`.
+	pub fn compact<'a, C: ContextMutProxy<T> + Send + Sync + AsJson, L: Send + Sync + Loader>(self, context: &'a C, loader: &'a mut L, options: compaction::Options) -> BoxFuture<'a, Result<JsonValue, Error>> where
+		C::Target: Send + Sync,
+		<C::Target as Context<T>>::LocalContext: Send + Sync + From<L::Output>,
+		T: 'a
+	{
+		let doc = self.into_document();
+		async move {
+			let options = compaction::Options {
+				keep_graph_array: true,
+				..options
+			};
+
+			doc.compact(context, loader, options).await
+		}.boxed()
+	}
+}
+
+impl<T: Id> Default for NodeMap<T> {
+	fn default() -> NodeMap<T> {
+		NodeMap::new()
+	}
+}
+
+impl<T: Id> From<NodeMap<T>> for ExpandedDocument<T> {
+	fn from(map: NodeMap<T>) -> ExpandedDocument<T> {
+		map.into_document()
+	}
+}
+
+/// Node map generation state.
+struct Generation<T: Id, G> {
+	map: NodeMap<T>,
+	relabeling: Relabeling<G>
+}
+
+impl<T: Id, G: BlankIdGenerator> Generation<T, G> {
+	/// Add the given object to the node map, and returns its flattened version.
+	///
+	/// Node objects are replaced with node references.
+	fn object(&mut self, graph: Option<&Reference<T>>, object: Indexed<Object<T>>) -> Result<Indexed<Object<T>>, Error> {
+		let (object, index) = object.into_parts();
+		match object {
+			Object::Value(value) => Ok(Indexed::new(Object::Value(value), index)),
+			Object::List(items) => {
+				let mut flat_items = Vec::with_capacity(items.len());
+				for item in items {
+					flat_items.push(self.object(graph, item)?)
+				}
+
+				Ok(Indexed::new(Object::List(flat_items.into()), index))
+			},
+			Object::Node(mut node) => {
+				let annotation = node.take_annotation();
+				let id = self.node(graph, Indexed::new(node, index))?;
+				let mut reference = Node::with_id(Lenient::Ok(id));
+				reference.set_annotation(annotation);
+				Ok(Indexed::new(Object::Node(reference), None))
+			},
+			Object::Graph(graph_object) => {
+				let id = self.node(graph, Indexed::new(graph_object.into(), index))?;
+				Ok(Indexed::new(Object::Node(Node::with_id(Lenient::Ok(id))), None))
+			}
+		}
+	}
+
+	/// Add the given node to the node map, and returns its identifier.
+	fn node(&mut self, graph: Option<&Reference<T>>, node: Indexed<Node<T>>) -> Result<Reference<T>, Error> {
+		let (mut node, index) = node.into_parts();
+
+		let id = match node.id.take() {
+			Some(Lenient::Ok(id)) => self.relabeling.reference(id),
+			_ => Reference::Blank(self.relabeling.generator.next_blank_id())
+		};
+
+		let types: Vec<_> = std::mem::take(&mut node.types).into_iter().map(|ty| self.relabeling.lenient(ty)).collect();
+
+		let entry = self.map.declare_graph(graph).declare(id.clone());
+		if let Some(index) = index {
+			if entry.index().map(|other| other != index).unwrap_or(false) {
+				return Err(Error::from(ErrorCode::ConflictingIndexes).with_subject(id.as_str()))
+			}
+
+			entry.set_index(Some(index))
+		}
+
+		for ty in types {
+			if !entry.types.contains(&ty) {
+				entry.types.push(ty)
+			}
+		}
+
+		if let Some(annotation) = node.annotation.take() {
+			match &mut entry.annotation {
+				Some(entry_annotation) => entry_annotation.extend(annotation),
+				None => entry.annotation = Some(annotation)
+			}
+		}
+
+		for (prop, reverse_nodes) in std::mem::take(&mut node.reverse_properties) {
+			let prop = self.relabeling.reference(prop);
+			for reverse_node in reverse_nodes {
+				let subject = self.node(graph, reverse_node)?;
+				let reference = Indexed::new(Object::Node(Node::with_id(Lenient::Ok(id.clone()))), None);
+				let values = self.map.declare_graph(graph).declare(subject.clone()).entry(prop.clone()).or_default();
+				if !values.contains(&reference) {
+					values.push(reference)
+				}
+			}
+		}
+
+		if let Some(objects) = node.graph.take() {
+			self.map.declare_graph(Some(&id));
+			for object in objects {
+				self.object(Some(&id), object)?;
+			}
+		}
+
+		if let Some(included) = node.included.take() {
+			for included_node in included {
+				self.node(graph, included_node)?;
+			}
+		}
+
+		for (prop, values) in std::mem::take(&mut node.properties) {
+			let prop = self.relabeling.reference(prop);
+			let mut flat_values = Vec::with_capacity(values.len());
+			for value in values {
+				flat_values.push(self.object(graph, value)?)
+			}
+
+			let entry_values = self.map.declare_graph(graph).declare(id.clone()).entry(prop).or_default();
+			for value in flat_values {
+				if value.is_list() || !entry_values.contains(&value) {
+					entry_values.push(value)
+				}
+			}
+		}
+
+		Ok(id)
+	}
+}
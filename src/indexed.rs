@@ -47,11 +47,29 @@ impl<T> Indexed<T> {
 		}
 	}
 
+	/// Get a mutable reference to the index.
+	///
+	/// Note that the index is part of the hash of an indexed value:
+	/// it must not be modified while the value is stored in a hash set.
+	pub fn index_mut(&mut self) -> &mut Option<String> {
+		&mut self.index
+	}
+
 	/// Set the value index.
 	pub fn set_index(&mut self, index: Option<String>) {
 		self.index = index
 	}
 
+	/// Remove and return the index, if any.
+	pub fn take_index(&mut self) -> Option<String> {
+		self.index.take()
+	}
+
+	/// Get a mutable reference to the inner value.
+	pub fn inner_mut(&mut self) -> &mut T {
+		&mut self.value
+	}
+
 	/// Turn this indexed value into its components: inner value and index.
 	pub fn into_parts(self) -> (T, Option<String>) {
 		(self.value, self.index)
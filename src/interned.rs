@@ -0,0 +1,189 @@
+use std::fmt;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock, RwLockReadGuard};
+use once_cell::sync::OnceCell;
+use iref::{Iri, AsIri};
+use crate::Id;
+
+/// Table of interned IRIs.
+///
+/// Each IRI is stored once, and identified by its index in the table.
+/// IRIs are never removed from a vocabulary, but their storage is owned by the vocabulary
+/// and freed when it is dropped.
+///
+/// [`InternedIri`] always uses the shared vocabulary (see [`InternedIri::vocabulary`]),
+/// which lives until the end of the program.
+/// A vocabulary created with [`Vocabulary::new`] is independent from the shared one:
+/// it can be used to intern IRIs with a bounded lifetime, for instance one vocabulary per batch
+/// of documents mapped to an application-defined identifier type.
+///
+/// # Example
+/// ```
+/// use static_iref::*;
+/// use json_ld::Vocabulary;
+///
+/// let mut vocabulary = Vocabulary::new();
+/// let name = vocabulary.insert(iri!("http://xmlns.com/foaf/0.1/name"));
+/// assert_eq!(vocabulary.insert(iri!("http://xmlns.com/foaf/0.1/name")), name);
+/// assert_eq!(vocabulary.iri(name), Some(iri!("http://xmlns.com/foaf/0.1/name")));
+/// ```
+#[derive(Default)]
+pub struct Vocabulary {
+	map: HashMap<Arc<str>, u32>,
+	iris: Vec<Arc<str>>
+}
+
+impl Vocabulary {
+	/// Create a new empty vocabulary.
+	pub fn new() -> Vocabulary {
+		Vocabulary::default()
+	}
+
+	/// Number of interned IRIs.
+	pub fn len(&self) -> usize {
+		self.iris.len()
+	}
+
+	/// Checks if the vocabulary is empty.
+	pub fn is_empty(&self) -> bool {
+		self.iris.is_empty()
+	}
+
+	/// Get the index of the given IRI, if it is interned.
+	pub fn get(&self, iri: Iri) -> Option<u32> {
+		self.map.get(iri.as_str()).cloned()
+	}
+
+	/// Get the IRI with the given index, if any.
+	pub fn iri(&self, index: u32) -> Option<Iri> {
+		// interned IRIs are always valid.
+		self.iris.get(index as usize).map(|iri| Iri::new(iri.as_ref()).unwrap())
+	}
+
+	/// Intern the given IRI and returns its index.
+	///
+	/// If the IRI is already interned, its index is returned and nothing is allocated.
+	pub fn insert(&mut self, iri: Iri) -> u32 {
+		match self.map.get(iri.as_str()) {
+			Some(index) => *index,
+			None => {
+				let index = self.iris.len() as u32;
+				let iri: Arc<str> = iri.as_str().into();
+				self.map.insert(iri.clone(), index);
+				self.iris.push(iri);
+				index
+			}
+		}
+	}
+
+	/// Iterate through the interned IRIs, by index.
+	pub fn iter(&self) -> impl '_ + Iterator<Item = (u32, Iri)> {
+		self.iris.iter().enumerate().map(|(i, iri)| (i as u32, Iri::new(iri.as_ref()).unwrap()))
+	}
+}
+
+static VOCABULARY: OnceCell<RwLock<Vocabulary>> = OnceCell::new();
+
+fn shared_vocabulary() -> &'static RwLock<Vocabulary> {
+	VOCABULARY.get_or_init(|| RwLock::new(Vocabulary::new()))
+}
+
+/// Interned IRI.
+///
+/// An identifier type ([`Id`]) storing IRIs as indexes in a shared [`Vocabulary`],
+/// so that comparing, hashing and cloning identifiers is as cheap as for integers,
+/// and each distinct IRI is allocated only once, however many documents it appears in.
+///
+/// The shared vocabulary can be accessed with [`InternedIri::vocabulary`],
+/// and populated in advance with [`InternedIri::new`].
+///
+/// **The shared vocabulary is never emptied**: every distinct IRI ever interned stays in memory
+/// until the end of the program. This is fine for the usually small set of IRIs of a
+/// vocabulary, but processing untrusted documents with an unbounded number of distinct IRIs
+/// (e.g. node identifiers) makes the memory usage grow without limit.
+/// Use a caller-owned [`Vocabulary`] or another identifier type in that case.
+///
+/// It is used like any other identifier type, for instance by expanding documents with a
+/// `JsonContext<InternedIri>`.
+///
+/// # Example
+/// ```
+/// use static_iref::*;
+/// use json_ld::InternedIri;
+///
+/// let a = InternedIri::new(iri!("http://xmlns.com/foaf/0.1/name"));
+/// let b = InternedIri::new(iri!("http://xmlns.com/foaf/0.1/name"));
+/// assert_eq!(a, b);
+/// assert_eq!(a.iri(), iri!("http://xmlns.com/foaf/0.1/name"));
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct InternedIri(u32);
+
+impl InternedIri {
+	/// Intern the given IRI in the shared vocabulary.
+	pub fn new(iri: Iri) -> InternedIri {
+		if let Some(index) = InternedIri::vocabulary().get(iri) {
+			return InternedIri(index)
+		}
+
+		InternedIri(shared_vocabulary().write().unwrap().insert(iri))
+	}
+
+	/// Get the interned IRI with the given index in the shared vocabulary, if any.
+	pub fn from_index(index: u32) -> Option<InternedIri> {
+		if (index as usize) < InternedIri::vocabulary().len() {
+			Some(InternedIri(index))
+		} else {
+			None
+		}
+	}
+
+	/// Index of the IRI in the shared vocabulary.
+	pub fn index(&self) -> u32 {
+		self.0
+	}
+
+	/// Get a read access to the shared vocabulary.
+	pub fn vocabulary() -> RwLockReadGuard<'static, Vocabulary> {
+		shared_vocabulary().read().unwrap()
+	}
+
+	/// Get the IRI.
+	pub fn iri(&self) -> Iri<'static> {
+		let iri: *const str = InternedIri::vocabulary().iris[self.0 as usize].as_ref();
+		// The shared vocabulary is never dropped and interned IRIs are never removed from it.
+		// The storage of each IRI does not move when the vocabulary grows,
+		// so it can be borrowed for the whole lifetime of the program.
+		Iri::new(unsafe { &*iri }).unwrap()
+	}
+}
+
+impl AsIri for InternedIri {
+	fn as_iri(&self) -> Iri {
+		self.iri()
+	}
+}
+
+impl Id for InternedIri {
+	fn from_iri(iri: Iri) -> InternedIri {
+		InternedIri::new(iri)
+	}
+}
+
+impl<'a> From<Iri<'a>> for InternedIri {
+	fn from(iri: Iri<'a>) -> InternedIri {
+		InternedIri::new(iri)
+	}
+}
+
+impl fmt::Display for InternedIri {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		self.iri().fmt(f)
+	}
+}
+
+impl fmt::Debug for InternedIri {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "InternedIri({})", self.iri())
+	}
+}
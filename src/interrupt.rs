@@ -0,0 +1,53 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Instant;
+use crate::{
+	Error,
+	ErrorCode
+};
+
+/// Cooperative cancellation token.
+///
+/// Clones of a token share the same state:
+/// once [`cancel`](`CancellationToken::cancel`) is called on any of them,
+/// the processing using the token aborts with a `cancelled` error
+/// at the next element it reaches.
+#[derive(Clone, Default, Debug)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+	/// Create a new token.
+	pub fn new() -> CancellationToken {
+		CancellationToken::default()
+	}
+
+	/// Cancel the processing using this token.
+	pub fn cancel(&self) {
+		self.0.store(true, Ordering::Relaxed)
+	}
+
+	/// Checks if the token has been cancelled.
+	pub fn is_cancelled(&self) -> bool {
+		self.0.load(Ordering::Relaxed)
+	}
+}
+
+/// Checks if the processing must be interrupted.
+///
+/// Returns a `timeout` error if the deadline is passed,
+/// and a `cancelled` error if the token has been cancelled.
+pub(crate) fn check(deadline: Option<Instant>, cancellation: Option<&CancellationToken>) -> Result<(), Error> {
+	if let Some(token) = cancellation {
+		if token.is_cancelled() {
+			return Err(ErrorCode::Cancelled.into())
+		}
+	}
+
+	if let Some(deadline) = deadline {
+		if Instant::now() >= deadline {
+			return Err(ErrorCode::Timeout.into())
+		}
+	}
+
+	Ok(())
+}
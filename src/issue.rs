@@ -0,0 +1,126 @@
+use std::fmt;
+use std::sync::Arc;
+use json::JsonValue;
+use crate::{
+	Error,
+	ErrorCode
+};
+
+/// Severity of a recoverable issue.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Debug)]
+pub enum Severity {
+	/// The input is suspicious, but processing is not affected.
+	Warning,
+
+	/// The input is invalid, and the offending part is dropped
+	/// if processing continues.
+	Error
+}
+
+impl Severity {
+	/// Get the name of the severity level.
+	pub fn as_str(&self) -> &str {
+		match self {
+			Severity::Warning => "warning",
+			Severity::Error => "error"
+		}
+	}
+}
+
+impl fmt::Display for Severity {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{}", self.as_str())
+	}
+}
+
+/// Recoverable issue encountered during processing.
+#[derive(Clone, Debug)]
+pub struct Issue {
+	/// Severity of the issue.
+	pub severity: Severity,
+
+	/// Error code describing the issue.
+	///
+	/// This is the code of the error returned if processing is aborted.
+	pub code: ErrorCode,
+
+	/// The term, keyword or value this issue is about, if any.
+	pub subject: Option<String>,
+
+	/// The offending JSON fragment, if any.
+	///
+	/// For errors, this is the part of the input dropped if processing continues.
+	pub fragment: Option<JsonValue>
+}
+
+impl Issue {
+	/// Create a new issue.
+	pub fn new(severity: Severity, code: ErrorCode, subject: Option<String>) -> Issue {
+		Issue {
+			severity,
+			code,
+			subject,
+			fragment: None
+		}
+	}
+
+	/// Attach the offending JSON fragment to the issue.
+	pub fn with_fragment(mut self, fragment: JsonValue) -> Issue {
+		self.fragment = Some(fragment);
+		self
+	}
+
+	/// Turn the issue into the error returned when processing is aborted.
+	pub fn into_error(self) -> Error {
+		let mut error = Error::from(self.code);
+
+		if let Some(subject) = self.subject {
+			error = error.with_subject(subject)
+		}
+
+		if let Some(fragment) = self.fragment {
+			error = error.with_fragment(fragment)
+		}
+
+		error
+	}
+}
+
+impl fmt::Display for Issue {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match &self.subject {
+			Some(subject) => write!(f, "{}: {} `{}`", self.severity, self.code, subject),
+			None => write!(f, "{}: {}", self.severity, self.code)
+		}
+	}
+}
+
+/// Decision taken by an [`IssueHandler`] about an issue.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Action {
+	/// Recover from the issue and continue processing.
+	Continue,
+
+	/// Abort processing with the error corresponding to the issue.
+	Abort
+}
+
+/// Handler of recoverable issues.
+///
+/// The handler is called for every recoverable issue, and decides whether processing
+/// should continue or be aborted.
+/// It may also log or collect the issues.
+/// Any `Fn(&Issue) -> Action` closure is an issue handler.
+pub trait IssueHandler: Send + Sync {
+	/// Handle the given issue.
+	fn handle(&self, issue: &Issue) -> Action;
+}
+
+impl<F: Send + Sync + Fn(&Issue) -> Action> IssueHandler for F {
+	fn handle(&self, issue: &Issue) -> Action {
+		self(issue)
+	}
+}
+
+/// Shared issue handler, as stored in processing options.
+pub type SharedIssueHandler = Arc<dyn IssueHandler>;
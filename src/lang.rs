@@ -1,9 +1,80 @@
+use std::fmt;
 use crate::Direction;
 use langtag::{
 	LanguageTag,
 	LanguageTagBuf
 };
 
+/// Normalization of language tags.
+///
+/// Language tags are case-insensitive:
+/// normalizing them makes comparisons and language map keys predictable.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum LanguageNormalization {
+	/// Language tags are kept as is.
+	Preserve,
+
+	/// Language tags are lowercased, as recommended by the JSON-LD specification.
+	Lowercase,
+
+	/// Language tags follow the case conventions of [BCP47](https://tools.ietf.org/html/bcp47#section-2.1.1):
+	/// lowercase language, titlecase script and uppercase region subtags (for instance `en-Latn-US`).
+	Canonical
+}
+
+impl LanguageNormalization {
+	/// Normalize the given language tag.
+	pub fn apply(&self, tag: &str) -> String {
+		match self {
+			LanguageNormalization::Preserve => tag.to_string(),
+			LanguageNormalization::Lowercase => tag.to_lowercase(),
+			LanguageNormalization::Canonical => {
+				let mut result = String::with_capacity(tag.len());
+				let mut after_singleton = false;
+				for (i, subtag) in tag.split('-').enumerate() {
+					if i > 0 {
+						result.push('-')
+					}
+
+					let is_alpha = subtag.chars().all(|c| c.is_ascii_alphabetic());
+					if i > 0 && !after_singleton && subtag.len() == 4 && is_alpha {
+						// script subtag.
+						result.push_str(&subtag[..1].to_uppercase());
+						result.push_str(&subtag[1..].to_lowercase())
+					} else if i > 0 && !after_singleton && subtag.len() == 2 && is_alpha {
+						// region subtag.
+						result.push_str(&subtag.to_uppercase())
+					} else {
+						result.push_str(&subtag.to_lowercase())
+					}
+
+					if subtag.len() == 1 {
+						after_singleton = true
+					}
+				}
+
+				result
+			}
+		}
+	}
+
+	/// Normalize the given language tag.
+	///
+	/// The tag is kept as is if its normalized form is not a valid language tag.
+	pub fn apply_tag(&self, tag: LanguageTagBuf) -> LanguageTagBuf {
+		match self {
+			LanguageNormalization::Preserve => tag,
+			_ => LanguageTagBuf::parse_copy(self.apply(tag.as_str()).as_str()).unwrap_or(tag)
+		}
+	}
+}
+
+impl Default for LanguageNormalization {
+	fn default() -> LanguageNormalization {
+		LanguageNormalization::Preserve
+	}
+}
+
 /// Language string.
 /// 
 /// A language string is a string tagged with language and reading direction information.
@@ -21,6 +92,14 @@ pub struct LangString {
 #[derive(Clone, Copy, Debug)]
 pub struct InvalidLangString;
 
+impl fmt::Display for InvalidLangString {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "invalid language string")
+	}
+}
+
+impl std::error::Error for InvalidLangString {}
+
 impl LangString {
 	/// Create a new language string.
 	pub fn new(str: String, language: Option<LanguageTagBuf>, direction: Option<Direction>) -> Result<LangString, String> {
@@ -35,6 +114,23 @@ impl LangString {
 		}
 	}
 
+	/// Create a new language string from a language tag given as a string.
+	///
+	/// The language tag, if any, is validated against [BCP47](https://tools.ietf.org/html/bcp47).
+	/// Fails with an [`InvalidLangString`] error if the tag is ill-formed,
+	/// or if both `language` and `direction` are `None`.
+	pub fn parse(str: String, language: Option<&str>, direction: Option<Direction>) -> Result<LangString, InvalidLangString> {
+		let language = match language {
+			Some(language) => match LanguageTagBuf::new(language.as_bytes().to_vec()) {
+				Ok(language) => Some(language),
+				Err(_) => return Err(InvalidLangString)
+			},
+			None => None
+		};
+
+		LangString::new(str, language, direction).map_err(|_| InvalidLangString)
+	}
+
 	/// Reference to the undrlying string.
 	pub fn as_str(&self) -> &str {
 		self.data.as_str()
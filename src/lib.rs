@@ -7,9 +7,14 @@ extern crate iref;
 
 mod mode;
 mod error;
+mod issue;
+mod options;
+mod interrupt;
+mod limits;
 mod direction;
 mod lang;
 mod id;
+mod interned;
 mod blank;
 mod reference;
 mod lenient;
@@ -17,22 +22,63 @@ mod null;
 mod indexed;
 mod vocab;
 mod document;
+mod adjacency;
+mod equivalence;
+mod relabeling;
+mod traverse;
+mod quads;
 mod loader;
+mod ndjson;
+mod api;
 pub mod syntax;
 pub mod object;
 pub mod context;
 pub mod expansion;
 pub mod compaction;
+pub mod flattening;
 pub mod util;
+pub mod diagnostics;
 
 #[cfg(feature="reqwest-loader")]
 pub mod reqwest;
 
+#[cfg(feature="web-loader")]
+pub mod web;
+
+#[cfg(feature="oxrdf")]
+pub mod oxrdf;
+
+#[cfg(feature="sophia")]
+pub mod sophia;
+
+#[cfg(feature="rio")]
+pub mod rio;
+
+#[cfg(feature="serde")]
+pub mod mapping;
+
+#[cfg(feature="serde_json")]
+pub mod serde_json;
+
+#[cfg(feature="cbor-ld")]
+pub mod cbor_ld;
+
+#[cfg(feature="schema-org")]
+pub mod schema_org;
+
+#[cfg(feature="test-suite")]
+pub mod test_suite;
+
 pub use mode::*;
 pub use error::*;
+pub use issue::*;
+pub use options::*;
+pub use interrupt::CancellationToken;
+pub use limits::{Limits, Usage};
 pub use direction::*;
 pub use lang::*;
 pub use id::*;
+pub use interned::*;
 pub use blank::*;
 pub use reference::*;
 pub use lenient::*;
@@ -40,10 +86,20 @@ pub use null::*;
 pub use indexed::*;
 pub use vocab::*;
 pub use document::*;
+pub use adjacency::*;
+pub use traverse::*;
+pub use quads::*;
 pub use loader::*;
+pub use ndjson::expand_ndjson;
+pub use util::{
+	json_ld_eq,
+	json_ld_eq_with,
+	NumberComparison
+};
+pub use api::*;
 pub use compaction::Compact;
 
-pub use object::{Object, Node, Value};
+pub use object::{Object, Node, Value, List, Graph};
 pub use context::{
 	Context,
 	ContextMut,
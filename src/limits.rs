@@ -0,0 +1,121 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use crate::{
+	Error,
+	ErrorCode
+};
+
+/// Resource limits enforced during expansion.
+///
+/// Meant for services processing untrusted documents.
+/// No limit is set by default.
+///
+/// The limits only hold the maximum values:
+/// the resources used are counted separately by each expansion,
+/// so the same limits can be shared by concurrent expansions.
+#[derive(Clone, Debug)]
+pub struct Limits {
+	max_input_bytes: Option<usize>,
+	max_expanded_objects: Option<usize>,
+	max_values_per_property: Option<usize>
+}
+
+impl Limits {
+	/// Create new limits, with no limit set.
+	pub fn new() -> Limits {
+		Limits {
+			max_input_bytes: None,
+			max_expanded_objects: None,
+			max_values_per_property: None
+		}
+	}
+
+	/// Maximum size of a JSON text document, in bytes.
+	///
+	/// Only applies to documents expanded from their textual representation.
+	pub fn max_input_bytes(&self) -> Option<usize> {
+		self.max_input_bytes
+	}
+
+	/// Maximum number of elements expanded in a single document.
+	pub fn max_expanded_objects(&self) -> Option<usize> {
+		self.max_expanded_objects
+	}
+
+	/// Maximum number of values of a single node property.
+	///
+	/// The items of a single list are also bounded by this limit.
+	pub fn max_values_per_property(&self) -> Option<usize> {
+		self.max_values_per_property
+	}
+
+	/// Set the maximum size of a JSON text document, in bytes.
+	pub fn with_max_input_bytes(mut self, max: Option<usize>) -> Self {
+		self.max_input_bytes = max;
+		self
+	}
+
+	/// Set the maximum number of elements expanded in a single document.
+	pub fn with_max_expanded_objects(mut self, max: Option<usize>) -> Self {
+		self.max_expanded_objects = max;
+		self
+	}
+
+	/// Set the maximum number of values of a single node property.
+	pub fn with_max_values_per_property(mut self, max: Option<usize>) -> Self {
+		self.max_values_per_property = max;
+		self
+	}
+
+	/// Checks the size of a JSON text document.
+	pub(crate) fn check_input(&self, len: usize) -> Result<(), Error> {
+		match self.max_input_bytes {
+			Some(max) if len > max => Err(ErrorCode::InputTooLarge.into()),
+			_ => Ok(())
+		}
+	}
+
+	/// Checks the number of values of a property.
+	pub(crate) fn check_values(&self, property: &str, count: usize) -> Result<(), Error> {
+		match self.max_values_per_property {
+			Some(max) if count > max => Err(Error::from(ErrorCode::TooManyValues).with_subject(property)),
+			_ => Ok(())
+		}
+	}
+}
+
+impl Default for Limits {
+	fn default() -> Limits {
+		Limits::new()
+	}
+}
+
+/// Resources used by a single expansion, checked against the [`Limits`].
+///
+/// A new value must be created for each expansion.
+pub struct Usage {
+	expanded_objects: AtomicUsize
+}
+
+impl Usage {
+	/// Create a new usage, with no resource used yet.
+	pub fn new() -> Usage {
+		Usage {
+			expanded_objects: AtomicUsize::new(0)
+		}
+	}
+
+	/// Count a newly expanded object.
+	pub(crate) fn count_object(&self, limits: &Limits) -> Result<(), Error> {
+		let count = self.expanded_objects.fetch_add(1, Ordering::Relaxed) + 1;
+		match limits.max_expanded_objects {
+			Some(max) if count > max => Err(ErrorCode::TooManyObjects.into()),
+			_ => Ok(())
+		}
+	}
+}
+
+impl Default for Usage {
+	fn default() -> Usage {
+		Usage::new()
+	}
+}
@@ -37,14 +37,14 @@ impl<L: Send + Sync + Loader<Document = JsonValue>> context::Loader for L {
 						if let Some(context) = obj.get("@context") {
 							Ok(RemoteContext::from_parts(url, context.clone()))
 						} else {
-							Err(ErrorCode::InvalidRemoteContext.into())
+							Err(Error::from(ErrorCode::InvalidRemoteContext).with_subject(url.as_str()))
 						}
 					} else {
-						Err(ErrorCode::InvalidRemoteContext.into())
+						Err(Error::from(ErrorCode::InvalidRemoteContext).with_subject(url.as_str()))
 					}
 				},
-				Err(_) => {
-					Err(ErrorCode::LoadingRemoteContextFailed.into())
+				Err(e) => {
+					Err(Error::new(ErrorCode::LoadingRemoteContextFailed, e).with_subject(url.as_str()))
 				}
 			}
 		}.boxed()
@@ -89,6 +89,32 @@ impl FsLoader {
 	pub fn mount<P: AsRef<Path>>(&mut self, url: Iri, path: P) {
 		self.mount_points.insert(path.as_ref().into(), url.into());
 	}
+
+	/// Load the content of the file attached to the given URL, as text.
+	///
+	/// Unlike [`Loader::load`], the content is not parsed nor cached.
+	pub fn load_text(&self, url: Iri) -> Result<String, Error> {
+		for (path, target_url) in &self.mount_points {
+			let url_ref = url.as_iri_ref();
+			match url_ref.suffix(target_url.as_iri_ref()) {
+				Some((suffix, _, _)) => {
+					let mut filepath = path.clone();
+					for seg in suffix.as_path().segments() {
+						filepath.push(seg.as_str())
+					}
+
+					let file = File::open(filepath).map_err(|e| Error::new(ErrorCode::LoadingDocumentFailed, e).with_subject(url.as_str()))?;
+					let mut buf_reader = BufReader::new(file);
+					let mut contents = String::new();
+					buf_reader.read_to_string(&mut contents).map_err(|e| Error::new(ErrorCode::LoadingDocumentFailed, e).with_subject(url.as_str()))?;
+					return Ok(contents)
+				},
+				None => ()
+			}
+		}
+
+		Err(Error::from(ErrorCode::LoadingDocumentFailed).with_subject(url.as_str()))
+	}
 }
 
 impl Loader for FsLoader {
@@ -100,38 +126,11 @@ impl Loader for FsLoader {
 			match self.cache.get(&url) {
 				Some(doc) => Ok(doc.clone()),
 				None => {
-					for (path, target_url) in &self.mount_points {
-						let url_ref = url.as_iri_ref();
-						match url_ref.suffix(target_url.as_iri_ref()) {
-							Some((suffix, _, _)) => {
-								let mut filepath = path.clone();
-								for seg in suffix.as_path().segments() {
-									filepath.push(seg.as_str())
-								}
-
-								if let Ok(file) = File::open(filepath) {
-								    let mut buf_reader = BufReader::new(file);
-								    let mut contents = String::new();
-								    if buf_reader.read_to_string(&mut contents).is_ok() {
-										if let Ok(doc) = json::parse(contents.as_str()) {
-											let remote_doc = RemoteDocument::new(doc, url.as_iri());
-											self.cache.insert(url.clone(), remote_doc.clone());
-											return Ok(remote_doc)
-										} else {
-											return Err(ErrorCode::LoadingDocumentFailed.into())
-										}
-									} else {
-										return Err(ErrorCode::LoadingDocumentFailed.into())
-									}
-								} else {
-									return Err(ErrorCode::LoadingDocumentFailed.into())
-								}
-							},
-							None => ()
-						}
-					}
-
-					Err(ErrorCode::LoadingDocumentFailed.into())
+					let contents = self.load_text(url.as_iri())?;
+					let doc = json::parse(contents.as_str()).map_err(|e| Error::new(ErrorCode::LoadingDocumentFailed, e).with_subject(url.as_str()))?;
+					let remote_doc = RemoteDocument::new(doc, url.as_iri());
+					self.cache.insert(url.clone(), remote_doc.clone());
+					Ok(remote_doc)
 				}
 			}
 		}.boxed()
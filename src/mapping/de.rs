@@ -0,0 +1,392 @@
+use ::serde::de::{
+	self,
+	Deserialize,
+	Deserializer,
+	Visitor,
+	MapAccess,
+	SeqAccess,
+	DeserializeSeed,
+	IntoDeserializer,
+	value::BorrowedStrDeserializer
+};
+use crate::{
+	Id,
+	Lenient,
+	Reference,
+	Indexed,
+	Object,
+	Node,
+	Context,
+	util,
+	object::{
+		Value,
+		Literal
+	},
+	syntax::{
+		Keyword,
+		Term
+	}
+};
+use super::{
+	Error,
+	resolve
+};
+
+/// Deserialize a node into a Rust value, using the given context to map fields to properties.
+pub fn from_node<'a, D: Deserialize<'a>, T: Id, C: Context<T>>(node: &'a Node<T>, context: &'a C) -> Result<D, Error> {
+	D::deserialize(NodeDeserializer { node, context })
+}
+
+/// Deserialize an object into a Rust value, using the given context to map fields to properties.
+pub fn from_object<'a, D: Deserialize<'a>, T: Id, C: Context<T>>(object: &'a Object<T>, context: &'a C) -> Result<D, Error> {
+	D::deserialize(ObjectDeserializer { object, context })
+}
+
+fn reference_str<T: Id>(r: &Lenient<Reference<T>>) -> &str {
+	match r {
+		Lenient::Ok(r) => r.as_str(),
+		Lenient::Unknown(s) => s.as_str()
+	}
+}
+
+/// Value of a struct field.
+enum Field<'a, T: Id> {
+	Id(&'a str),
+	Types(&'a [Lenient<Reference<T>>]),
+	Objects(&'a [Indexed<Object<T>>])
+}
+
+struct NodeDeserializer<'a, T: Id, C> {
+	node: &'a Node<T>,
+	context: &'a C
+}
+
+impl<'a, T: Id, C: Context<T>> NodeDeserializer<'a, T, C> {
+	fn field(&self, name: &str) -> Option<Field<'a, T>> {
+		match resolve(self.context, name)? {
+			Term::Keyword(Keyword::Id) => self.node.id().map(|id| Field::Id(reference_str(id))),
+			Term::Keyword(Keyword::Type) if !self.node.types().is_empty() => Some(Field::Types(self.node.types())),
+			Term::Ref(prop) => match self.node.properties.get(&prop) {
+				Some(objects) if !objects.is_empty() => Some(Field::Objects(objects)),
+				_ => None
+			},
+			_ => None
+		}
+	}
+}
+
+impl<'a, T: Id, C: Context<T>> Deserializer<'a> for NodeDeserializer<'a, T, C> {
+	type Error = Error;
+
+	fn deserialize_any<V: Visitor<'a>>(self, visitor: V) -> Result<V::Value, Error> {
+		let mut entries = Vec::new();
+		if let Some(id) = self.node.id() {
+			entries.push((Keyword::Id.into_str(), Field::Id(reference_str(id))))
+		}
+
+		if !self.node.types().is_empty() {
+			entries.push((Keyword::Type.into_str(), Field::Types(self.node.types())))
+		}
+
+		for (prop, objects) in self.node.properties() {
+			if !objects.is_empty() {
+				entries.push((prop.as_str(), Field::Objects(objects)))
+			}
+		}
+
+		visitor.visit_map(FieldsAccess { entries: entries.into_iter(), value: None, context: self.context })
+	}
+
+	fn deserialize_str<V: Visitor<'a>>(self, visitor: V) -> Result<V::Value, Error> {
+		match self.node.id() {
+			Some(id) => visitor.visit_borrowed_str(reference_str(id)),
+			None => self.deserialize_any(visitor)
+		}
+	}
+
+	fn deserialize_string<V: Visitor<'a>>(self, visitor: V) -> Result<V::Value, Error> {
+		self.deserialize_str(visitor)
+	}
+
+	fn deserialize_struct<V: Visitor<'a>>(self, _name: &'static str, fields: &'static [&'static str], visitor: V) -> Result<V::Value, Error> {
+		let entries: Vec<_> = fields.iter().filter_map(|name| self.field(name).map(|field| (*name, field))).collect();
+		visitor.visit_map(FieldsAccess { entries: entries.into_iter(), value: None, context: self.context })
+	}
+
+	fn deserialize_option<V: Visitor<'a>>(self, visitor: V) -> Result<V::Value, Error> {
+		visitor.visit_some(self)
+	}
+
+	::serde::forward_to_deserialize_any! {
+		bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char
+		bytes byte_buf unit unit_struct newtype_struct seq tuple
+		tuple_struct map enum identifier ignored_any
+	}
+}
+
+struct FieldsAccess<'a, T: Id, C, I: Iterator<Item = (&'a str, Field<'a, T>)>> {
+	entries: I,
+	value: Option<Field<'a, T>>,
+	context: &'a C
+}
+
+impl<'a, T: Id, C: Context<T>, I: Iterator<Item = (&'a str, Field<'a, T>)>> MapAccess<'a> for FieldsAccess<'a, T, C, I> {
+	type Error = Error;
+
+	fn next_key_seed<K: DeserializeSeed<'a>>(&mut self, seed: K) -> Result<Option<K::Value>, Error> {
+		match self.entries.next() {
+			Some((key, value)) => {
+				self.value = Some(value);
+				seed.deserialize(BorrowedStrDeserializer::new(key)).map(Some)
+			},
+			None => Ok(None)
+		}
+	}
+
+	fn next_value_seed<V: DeserializeSeed<'a>>(&mut self, seed: V) -> Result<V::Value, Error> {
+		match self.value.take() {
+			Some(Field::Id(id)) => seed.deserialize(BorrowedStrDeserializer::new(id)),
+			Some(Field::Types(types)) => {
+				let types: Vec<&'a str> = types.iter().map(reference_str).collect();
+				seed.deserialize(de::value::SeqDeserializer::new(types.into_iter()))
+			},
+			Some(Field::Objects(objects)) => seed.deserialize(ObjectsDeserializer { objects, context: self.context }),
+			None => Err(de::Error::custom("value is missing"))
+		}
+	}
+}
+
+/// Deserializer for the values of a property.
+///
+/// Sequences are deserialized from every value (or the items of a single list),
+/// anything else from the first value.
+struct ObjectsDeserializer<'a, T: Id, C> {
+	objects: &'a [Indexed<Object<T>>],
+	context: &'a C
+}
+
+impl<'a, T: Id, C: Context<T>> ObjectsDeserializer<'a, T, C> {
+	fn first(&self) -> Result<ObjectDeserializer<'a, T, C>, Error> {
+		match self.objects.first() {
+			Some(object) => Ok(ObjectDeserializer {
+				object: object.inner(),
+				context: self.context
+			}),
+			None => Err(de::Error::custom("property has no value"))
+		}
+	}
+}
+
+macro_rules! forward_to_first {
+	($($method:ident)*) => {
+		$(
+			fn $method<V: Visitor<'a>>(self, visitor: V) -> Result<V::Value, Error> {
+				self.first()?.$method(visitor)
+			}
+		)*
+	};
+}
+
+impl<'a, T: Id, C: Context<T>> Deserializer<'a> for ObjectsDeserializer<'a, T, C> {
+	type Error = Error;
+
+	forward_to_first! {
+		deserialize_any deserialize_bool
+		deserialize_i8 deserialize_i16 deserialize_i32 deserialize_i64
+		deserialize_u8 deserialize_u16 deserialize_u32 deserialize_u64
+		deserialize_f32 deserialize_f64 deserialize_char deserialize_str deserialize_string
+		deserialize_bytes deserialize_byte_buf deserialize_unit deserialize_map
+		deserialize_identifier deserialize_ignored_any
+	}
+
+	fn deserialize_seq<V: Visitor<'a>>(self, visitor: V) -> Result<V::Value, Error> {
+		if self.objects.len() == 1 {
+			if let Object::List(items) = self.objects[0].inner() {
+				return visitor.visit_seq(ObjectsAccess { objects: items.iter(), context: self.context })
+			}
+		}
+
+		visitor.visit_seq(ObjectsAccess { objects: self.objects.iter(), context: self.context })
+	}
+
+	fn deserialize_option<V: Visitor<'a>>(self, visitor: V) -> Result<V::Value, Error> {
+		if self.objects.is_empty() {
+			visitor.visit_none()
+		} else {
+			visitor.visit_some(self)
+		}
+	}
+
+	fn deserialize_struct<V: Visitor<'a>>(self, name: &'static str, fields: &'static [&'static str], visitor: V) -> Result<V::Value, Error> {
+		self.first()?.deserialize_struct(name, fields, visitor)
+	}
+
+	fn deserialize_unit_struct<V: Visitor<'a>>(self, name: &'static str, visitor: V) -> Result<V::Value, Error> {
+		self.first()?.deserialize_unit_struct(name, visitor)
+	}
+
+	fn deserialize_newtype_struct<V: Visitor<'a>>(self, name: &'static str, visitor: V) -> Result<V::Value, Error> {
+		self.first()?.deserialize_newtype_struct(name, visitor)
+	}
+
+	fn deserialize_tuple<V: Visitor<'a>>(self, _len: usize, visitor: V) -> Result<V::Value, Error> {
+		self.deserialize_seq(visitor)
+	}
+
+	fn deserialize_tuple_struct<V: Visitor<'a>>(self, _name: &'static str, _len: usize, visitor: V) -> Result<V::Value, Error> {
+		self.deserialize_seq(visitor)
+	}
+
+	fn deserialize_enum<V: Visitor<'a>>(self, name: &'static str, variants: &'static [&'static str], visitor: V) -> Result<V::Value, Error> {
+		self.first()?.deserialize_enum(name, variants, visitor)
+	}
+}
+
+struct ObjectsAccess<'a, I, C> {
+	objects: I,
+	context: &'a C
+}
+
+impl<'a, T: 'a + Id, I: ExactSizeIterator<Item = &'a Indexed<Object<T>>>, C: Context<T>> SeqAccess<'a> for ObjectsAccess<'a, I, C> {
+	type Error = Error;
+
+	fn next_element_seed<S: DeserializeSeed<'a>>(&mut self, seed: S) -> Result<Option<S::Value>, Error> {
+		match self.objects.next() {
+			Some(object) => seed.deserialize(ObjectDeserializer { object: object.inner(), context: self.context }).map(Some),
+			None => Ok(None)
+		}
+	}
+
+	fn size_hint(&self) -> Option<usize> {
+		Some(self.objects.len())
+	}
+}
+
+struct ObjectDeserializer<'a, T: Id, C> {
+	object: &'a Object<T>,
+	context: &'a C
+}
+
+impl<'a, T: Id, C: Context<T>> ObjectDeserializer<'a, T, C> {
+	fn node(&self) -> Option<NodeDeserializer<'a, T, C>> {
+		match self.object {
+			Object::Node(node) => Some(NodeDeserializer { node, context: self.context }),
+			_ => None
+		}
+	}
+}
+
+impl<'a, T: Id, C: Context<T>> Deserializer<'a> for ObjectDeserializer<'a, T, C> {
+	type Error = Error;
+
+	fn deserialize_any<V: Visitor<'a>>(self, visitor: V) -> Result<V::Value, Error> {
+		match self.object {
+			Object::Value(Value::Literal(lit, _)) => match lit {
+				Literal::Null => visitor.visit_unit(),
+				Literal::Boolean(b) => visitor.visit_bool(*b),
+				Literal::Number(n) => match (util::json_number_as_i64(n), util::json_number_as_u64(n)) {
+					// Only integral numbers are visited as integers, `1.5` stays a float.
+					(Some(i), _) => visitor.visit_i64(i),
+					(None, Some(u)) => visitor.visit_u64(u),
+					(None, None) => visitor.visit_f64(lit.as_f64().unwrap())
+				},
+				Literal::String(s) => visitor.visit_borrowed_str(s.as_str())
+			},
+			Object::Value(Value::LangString(s)) => visitor.visit_borrowed_str(s.as_str()),
+			Object::Value(Value::Json(json)) => visitor.visit_string(json.dump()),
+			Object::Node(node) => NodeDeserializer { node, context: self.context }.deserialize_any(visitor),
+			Object::List(items) => visitor.visit_seq(ObjectsAccess { objects: items.iter(), context: self.context }),
+			Object::Graph(graph) => visitor.visit_seq(ObjectsAccess { objects: graph.iter(), context: self.context })
+		}
+	}
+
+	fn deserialize_i64<V: Visitor<'a>>(self, visitor: V) -> Result<V::Value, Error> {
+		match self.object.as_i64() {
+			Some(i) => visitor.visit_i64(i),
+			None => self.deserialize_any(visitor)
+		}
+	}
+
+	fn deserialize_i8<V: Visitor<'a>>(self, visitor: V) -> Result<V::Value, Error> {
+		self.deserialize_i64(visitor)
+	}
+
+	fn deserialize_i16<V: Visitor<'a>>(self, visitor: V) -> Result<V::Value, Error> {
+		self.deserialize_i64(visitor)
+	}
+
+	fn deserialize_i32<V: Visitor<'a>>(self, visitor: V) -> Result<V::Value, Error> {
+		self.deserialize_i64(visitor)
+	}
+
+	fn deserialize_u64<V: Visitor<'a>>(self, visitor: V) -> Result<V::Value, Error> {
+		match self.object.as_u64() {
+			Some(u) => visitor.visit_u64(u),
+			None => self.deserialize_any(visitor)
+		}
+	}
+
+	fn deserialize_u8<V: Visitor<'a>>(self, visitor: V) -> Result<V::Value, Error> {
+		self.deserialize_u64(visitor)
+	}
+
+	fn deserialize_u16<V: Visitor<'a>>(self, visitor: V) -> Result<V::Value, Error> {
+		self.deserialize_u64(visitor)
+	}
+
+	fn deserialize_u32<V: Visitor<'a>>(self, visitor: V) -> Result<V::Value, Error> {
+		self.deserialize_u64(visitor)
+	}
+
+	fn deserialize_f64<V: Visitor<'a>>(self, visitor: V) -> Result<V::Value, Error> {
+		match self.object.as_f64() {
+			Some(f) => visitor.visit_f64(f),
+			None => self.deserialize_any(visitor)
+		}
+	}
+
+	fn deserialize_f32<V: Visitor<'a>>(self, visitor: V) -> Result<V::Value, Error> {
+		self.deserialize_f64(visitor)
+	}
+
+	fn deserialize_str<V: Visitor<'a>>(self, visitor: V) -> Result<V::Value, Error> {
+		match self.node() {
+			Some(node) => node.deserialize_str(visitor),
+			None => self.deserialize_any(visitor)
+		}
+	}
+
+	fn deserialize_string<V: Visitor<'a>>(self, visitor: V) -> Result<V::Value, Error> {
+		self.deserialize_str(visitor)
+	}
+
+	fn deserialize_option<V: Visitor<'a>>(self, visitor: V) -> Result<V::Value, Error> {
+		match self.object {
+			Object::Value(Value::Literal(Literal::Null, _)) => visitor.visit_none(),
+			_ => visitor.visit_some(self)
+		}
+	}
+
+	fn deserialize_struct<V: Visitor<'a>>(self, name: &'static str, fields: &'static [&'static str], visitor: V) -> Result<V::Value, Error> {
+		match self.node() {
+			Some(node) => node.deserialize_struct(name, fields, visitor),
+			None => self.deserialize_any(visitor)
+		}
+	}
+
+	fn deserialize_newtype_struct<V: Visitor<'a>>(self, _name: &'static str, visitor: V) -> Result<V::Value, Error> {
+		visitor.visit_newtype_struct(self)
+	}
+
+	fn deserialize_enum<V: Visitor<'a>>(self, _name: &'static str, _variants: &'static [&'static str], visitor: V) -> Result<V::Value, Error> {
+		match self.object.as_str() {
+			Some(s) => visitor.visit_enum(s.into_deserializer()),
+			None => Err(de::Error::custom("expected a string for an enum"))
+		}
+	}
+
+	::serde::forward_to_deserialize_any! {
+		bool i128 u128 char bytes byte_buf unit unit_struct seq tuple
+		tuple_struct map identifier ignored_any
+	}
+}
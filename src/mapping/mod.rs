@@ -0,0 +1,115 @@
+//! Typed access to JSON-LD data through `serde`.
+//!
+//! This module is enabled by the `serde` feature.
+//! Struct fields are mapped to properties by interpreting field names as terms of a context:
+//! a field named `name` is mapped to the IRI the `name` term expands to in the given context
+//! (or to the vocabulary mapping + `name` if the term is undefined).
+//! Fields named (or renamed to) `@id` and `@type`, or terms aliasing those keywords,
+//! are mapped to the node identifier and types.
+//!
+//! ```ignore
+//! #[derive(Serialize, Deserialize)]
+//! struct Person {
+//! 	#[serde(rename = "@id")]
+//! 	id: String,
+//! 	name: String,
+//! 	knows: Vec<Person>
+//! }
+//!
+//! let person: Person = json_ld::mapping::from_node(&node, &context)?;
+//! ```
+//!
+//! The same mapping is used the other way around to serialize values into nodes with [`to_node`].
+//! String values of properties whose term definition has an `@id` or `@vocab` type mapping
+//! are serialized as node references, and the language, direction and container
+//! mappings of the term definition are honored.
+
+use std::fmt;
+use std::convert::TryFrom;
+use iref::Iri;
+use crate::{
+	Id,
+	Reference,
+	syntax::{
+		Keyword,
+		Term
+	},
+	Context
+};
+
+mod de;
+mod ser;
+
+pub use de::{
+	from_node,
+	from_object
+};
+pub use ser::{
+	to_node,
+	to_object
+};
+
+/// Mapping error.
+#[derive(Clone, Debug)]
+pub struct Error(String);
+
+impl Error {
+	/// Create a new error with the given message.
+	pub fn new<M: fmt::Display>(msg: M) -> Error {
+		Error(msg.to_string())
+	}
+}
+
+impl fmt::Display for Error {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		self.0.fmt(f)
+	}
+}
+
+impl std::error::Error for Error {}
+
+impl ::serde::de::Error for Error {
+	fn custom<M: fmt::Display>(msg: M) -> Error {
+		Error::new(msg)
+	}
+}
+
+impl ::serde::ser::Error for Error {
+	fn custom<M: fmt::Display>(msg: M) -> Error {
+		Error::new(msg)
+	}
+}
+
+/// Resolve a field name into the term it maps to in the given context.
+///
+/// Returns `None` if the field cannot be mapped, or if it maps to a reverse property.
+pub(crate) fn resolve<T: Id, C: Context<T>>(context: &C, field: &str) -> Option<Term<T>> {
+	if field.starts_with('@') {
+		return Keyword::try_from(field).ok().map(Term::Keyword)
+	}
+
+	if let Some(definition) = context.get(field) {
+		if definition.reverse_property {
+			return None
+		}
+
+		return definition.value.clone()
+	}
+
+	if field.contains(':') {
+		if let Ok(iri) = Iri::new(field) {
+			return Some(Term::Ref(Reference::Id(T::from_iri(iri))))
+		}
+	}
+
+	match context.vocabulary() {
+		Some(Term::Ref(Reference::Id(vocab))) => {
+			let iri = vocab.as_iri().as_str().to_string() + field;
+			match Iri::new(&iri) {
+				Ok(iri) => Some(Term::Ref(Reference::Id(T::from_iri(iri)))),
+				Err(_) => None
+			}
+		},
+		_ => None
+	}
+}
@@ -0,0 +1,412 @@
+use ::serde::ser::{
+	self,
+	Serialize,
+	Serializer
+};
+use iref::Iri;
+use crate::{
+	Id,
+	BlankId,
+	Lenient,
+	Reference,
+	Indexed,
+	Nullable,
+	LangString,
+	Object,
+	Node,
+	Context,
+	context::TermDefinition,
+	object::{
+		Value,
+		Literal
+	},
+	syntax::{
+		Keyword,
+		Term,
+		Type,
+		ContainerType
+	}
+};
+use super::{
+	Error,
+	resolve
+};
+
+/// Serialize a Rust value into a node, using the given context to map fields to properties.
+///
+/// The value must serialize as a struct or a map.
+pub fn to_node<S: Serialize + ?Sized, T: Id, C: Context<T>>(value: &S, context: &C) -> Result<Node<T>, Error> {
+	match to_object(value, context)? {
+		Some(Object::Node(node)) => Ok(node),
+		_ => Err(ser::Error::custom("expected a struct or map"))
+	}
+}
+
+/// Serialize a Rust value into an object, using the given context to map fields to properties.
+///
+/// Returns `None` if the value serializes as `None` or a unit value.
+pub fn to_object<S: Serialize + ?Sized, T: Id, C: Context<T>>(value: &S, context: &C) -> Result<Option<Object<T>>, Error> {
+	match value.serialize(ObjectSerializer { context, definition: None, plain: false })? {
+		Serialized::None => Ok(None),
+		Serialized::Object(object) => Ok(Some(object)),
+		Serialized::Objects(objects) => Ok(Some(Object::List(objects.into_iter().map(Indexed::from).collect())))
+	}
+}
+
+/// Parse a node reference, keeping invalid references as `Lenient::Unknown`.
+fn reference<T: Id>(s: &str) -> Lenient<Reference<T>> {
+	if let Some(name) = s.strip_prefix("_:") {
+		match BlankId::try_new(name) {
+			Ok(id) => Lenient::Ok(Reference::Blank(id)),
+			Err(_) => Lenient::Unknown(s.to_string())
+		}
+	} else {
+		match Iri::new(s) {
+			Ok(iri) => Lenient::Ok(Reference::Id(T::from_iri(iri))),
+			Err(_) => Lenient::Unknown(s.to_string())
+		}
+	}
+}
+
+/// Result of the serialization of a value.
+enum Serialized<T: Id> {
+	/// Nothing (`None` or unit).
+	None,
+
+	/// A single object.
+	Object(Object<T>),
+
+	/// Multiple objects (sequence).
+	Objects(Vec<Object<T>>)
+}
+
+impl<T: Id> Serialized<T> {
+	fn into_objects(self) -> Vec<Object<T>> {
+		match self {
+			Serialized::None => Vec::new(),
+			Serialized::Object(object) => vec![object],
+			Serialized::Objects(objects) => objects
+		}
+	}
+
+	fn into_string(self) -> Result<String, Error> {
+		match self {
+			Serialized::Object(Object::Value(Value::Literal(Literal::String(s), _))) => Ok(s),
+			_ => Err(ser::Error::custom("expected a string"))
+		}
+	}
+}
+
+/// Serializer of a value, possibly associated to the definition of the term it is the value of.
+struct ObjectSerializer<'a, T: Id, C: Context<T>> {
+	context: &'a C,
+	definition: Option<&'a TermDefinition<T, C>>,
+
+	/// If true, strings are serialized as plain string literals,
+	/// without the default language and direction of the context.
+	///
+	/// Used for map keys, `@id` and `@type` values.
+	plain: bool
+}
+
+impl<'a, T: Id, C: Context<T>> ObjectSerializer<'a, T, C> {
+	/// Serializer of map keys, `@id` and `@type` values.
+	fn plain(context: &'a C) -> Self {
+		ObjectSerializer {
+			context,
+			definition: None,
+			plain: true
+		}
+	}
+
+	fn type_mapping(&self) -> Option<&'a Type<T>> {
+		self.definition.and_then(|definition| definition.typ.as_ref())
+	}
+
+	fn literal(&self, lit: Literal) -> Result<Serialized<T>, Error> {
+		let ty = match self.type_mapping() {
+			Some(Type::Ref(ty)) => Some(ty.clone()),
+			_ => None
+		};
+
+		Ok(Serialized::Object(Object::Value(Value::Literal(lit, ty))))
+	}
+}
+
+macro_rules! serialize_number {
+	($($method:ident : $ty:ty),*) => {
+		$(
+			fn $method(self, v: $ty) -> Result<Serialized<T>, Error> {
+				self.literal(Literal::Number(v.into()))
+			}
+		)*
+	};
+}
+
+impl<'a, T: Id, C: Context<T>> Serializer for ObjectSerializer<'a, T, C> {
+	type Ok = Serialized<T>;
+	type Error = Error;
+	type SerializeSeq = SeqSerializer<'a, T, C>;
+	type SerializeTuple = SeqSerializer<'a, T, C>;
+	type SerializeTupleStruct = SeqSerializer<'a, T, C>;
+	type SerializeTupleVariant = ser::Impossible<Serialized<T>, Error>;
+	type SerializeMap = NodeSerializer<'a, T, C>;
+	type SerializeStruct = NodeSerializer<'a, T, C>;
+	type SerializeStructVariant = ser::Impossible<Serialized<T>, Error>;
+
+	fn serialize_bool(self, v: bool) -> Result<Serialized<T>, Error> {
+		self.literal(Literal::Boolean(v))
+	}
+
+	serialize_number! {
+		serialize_i8: i8, serialize_i16: i16, serialize_i32: i32, serialize_i64: i64,
+		serialize_u8: u8, serialize_u16: u16, serialize_u32: u32, serialize_u64: u64,
+		serialize_f32: f32, serialize_f64: f64
+	}
+
+	fn serialize_char(self, v: char) -> Result<Serialized<T>, Error> {
+		self.serialize_str(&v.to_string())
+	}
+
+	fn serialize_str(self, v: &str) -> Result<Serialized<T>, Error> {
+		match self.type_mapping() {
+			Some(Type::Id) | Some(Type::Vocab) => {
+				let mut node = Node::new();
+				node.set_id(Some(reference(v)));
+				return Ok(Serialized::Object(Object::Node(node)))
+			},
+			Some(Type::Ref(_)) => return self.literal(Literal::String(v.to_string())),
+			_ => ()
+		}
+
+		if self.plain {
+			return self.literal(Literal::String(v.to_string()))
+		}
+
+		let language = match self.definition.and_then(|definition| definition.language.as_ref()) {
+			Some(Nullable::Some(language)) => Some(language.clone()),
+			Some(Nullable::Null) => None,
+			None => self.context.default_language().map(|language| language.cloned())
+		};
+
+		let direction = match self.definition.and_then(|definition| definition.direction) {
+			Some(Nullable::Some(direction)) => Some(direction),
+			Some(Nullable::Null) => None,
+			None => self.context.default_base_direction()
+		};
+
+		match LangString::new(v.to_string(), language, direction) {
+			Ok(s) => Ok(Serialized::Object(Object::Value(Value::LangString(s)))),
+			Err(s) => self.literal(Literal::String(s))
+		}
+	}
+
+	fn serialize_bytes(self, _v: &[u8]) -> Result<Serialized<T>, Error> {
+		Err(ser::Error::custom("bytes are not supported"))
+	}
+
+	fn serialize_none(self) -> Result<Serialized<T>, Error> {
+		Ok(Serialized::None)
+	}
+
+	fn serialize_some<V: Serialize + ?Sized>(self, value: &V) -> Result<Serialized<T>, Error> {
+		value.serialize(self)
+	}
+
+	fn serialize_unit(self) -> Result<Serialized<T>, Error> {
+		Ok(Serialized::None)
+	}
+
+	fn serialize_unit_struct(self, _name: &'static str) -> Result<Serialized<T>, Error> {
+		Ok(Serialized::None)
+	}
+
+	fn serialize_unit_variant(self, _name: &'static str, _index: u32, variant: &'static str) -> Result<Serialized<T>, Error> {
+		self.serialize_str(variant)
+	}
+
+	fn serialize_newtype_struct<V: Serialize + ?Sized>(self, _name: &'static str, value: &V) -> Result<Serialized<T>, Error> {
+		value.serialize(self)
+	}
+
+	fn serialize_newtype_variant<V: Serialize + ?Sized>(self, _name: &'static str, _index: u32, _variant: &'static str, _value: &V) -> Result<Serialized<T>, Error> {
+		Err(ser::Error::custom("enum variants with data are not supported"))
+	}
+
+	fn serialize_seq(self, len: Option<usize>) -> Result<SeqSerializer<'a, T, C>, Error> {
+		Ok(SeqSerializer {
+			context: self.context,
+			definition: self.definition,
+			plain: self.plain,
+			objects: Vec::with_capacity(len.unwrap_or(0))
+		})
+	}
+
+	fn serialize_tuple(self, len: usize) -> Result<SeqSerializer<'a, T, C>, Error> {
+		self.serialize_seq(Some(len))
+	}
+
+	fn serialize_tuple_struct(self, _name: &'static str, len: usize) -> Result<SeqSerializer<'a, T, C>, Error> {
+		self.serialize_seq(Some(len))
+	}
+
+	fn serialize_tuple_variant(self, _name: &'static str, _index: u32, _variant: &'static str, _len: usize) -> Result<Self::SerializeTupleVariant, Error> {
+		Err(ser::Error::custom("enum variants with data are not supported"))
+	}
+
+	fn serialize_map(self, _len: Option<usize>) -> Result<NodeSerializer<'a, T, C>, Error> {
+		Ok(NodeSerializer {
+			context: self.context,
+			node: Node::new(),
+			key: None
+		})
+	}
+
+	fn serialize_struct(self, _name: &'static str, len: usize) -> Result<NodeSerializer<'a, T, C>, Error> {
+		self.serialize_map(Some(len))
+	}
+
+	fn serialize_struct_variant(self, _name: &'static str, _index: u32, _variant: &'static str, _len: usize) -> Result<Self::SerializeStructVariant, Error> {
+		Err(ser::Error::custom("enum variants with data are not supported"))
+	}
+}
+
+/// Sequence serializer.
+///
+/// Produces a list object if the term definition has a `@list` container,
+/// or multiple objects otherwise.
+struct SeqSerializer<'a, T: Id, C: Context<T>> {
+	context: &'a C,
+	definition: Option<&'a TermDefinition<T, C>>,
+	plain: bool,
+	objects: Vec<Object<T>>
+}
+
+impl<'a, T: Id, C: Context<T>> SeqSerializer<'a, T, C> {
+	fn push<V: Serialize + ?Sized>(&mut self, value: &V) -> Result<(), Error> {
+		let item = value.serialize(ObjectSerializer { context: self.context, definition: self.definition, plain: self.plain })?;
+		self.objects.extend(item.into_objects());
+		Ok(())
+	}
+
+	fn finish(self) -> Result<Serialized<T>, Error> {
+		if self.definition.map(|definition| definition.container.contains(ContainerType::List)).unwrap_or(false) {
+			Ok(Serialized::Object(Object::List(self.objects.into_iter().map(Indexed::from).collect())))
+		} else {
+			Ok(Serialized::Objects(self.objects))
+		}
+	}
+}
+
+impl<'a, T: Id, C: Context<T>> ser::SerializeSeq for SeqSerializer<'a, T, C> {
+	type Ok = Serialized<T>;
+	type Error = Error;
+
+	fn serialize_element<V: Serialize + ?Sized>(&mut self, value: &V) -> Result<(), Error> {
+		self.push(value)
+	}
+
+	fn end(self) -> Result<Serialized<T>, Error> {
+		self.finish()
+	}
+}
+
+impl<'a, T: Id, C: Context<T>> ser::SerializeTuple for SeqSerializer<'a, T, C> {
+	type Ok = Serialized<T>;
+	type Error = Error;
+
+	fn serialize_element<V: Serialize + ?Sized>(&mut self, value: &V) -> Result<(), Error> {
+		self.push(value)
+	}
+
+	fn end(self) -> Result<Serialized<T>, Error> {
+		self.finish()
+	}
+}
+
+impl<'a, T: Id, C: Context<T>> ser::SerializeTupleStruct for SeqSerializer<'a, T, C> {
+	type Ok = Serialized<T>;
+	type Error = Error;
+
+	fn serialize_field<V: Serialize + ?Sized>(&mut self, value: &V) -> Result<(), Error> {
+		self.push(value)
+	}
+
+	fn end(self) -> Result<Serialized<T>, Error> {
+		self.finish()
+	}
+}
+
+/// Node serializer, for structs and maps.
+struct NodeSerializer<'a, T: Id, C: Context<T>> {
+	context: &'a C,
+	node: Node<T>,
+	key: Option<String>
+}
+
+impl<'a, T: Id, C: Context<T>> NodeSerializer<'a, T, C> {
+	fn entry<V: Serialize + ?Sized>(&mut self, key: &str, value: &V) -> Result<(), Error> {
+		match resolve(self.context, key) {
+			Some(Term::Keyword(Keyword::Id)) => {
+				let id = value.serialize(ObjectSerializer::plain(self.context))?;
+				if let Serialized::Object(_) = id {
+					self.node.set_id(Some(reference(&id.into_string()?)))
+				}
+			},
+			Some(Term::Keyword(Keyword::Type)) => {
+				let types = value.serialize(ObjectSerializer::plain(self.context))?;
+				for ty in types.into_objects() {
+					let ty = Serialized::Object(ty).into_string()?;
+					let ty = match resolve(self.context, &ty) {
+						Some(Term::Ref(ty)) => Lenient::Ok(ty),
+						_ => reference(&ty)
+					};
+					self.node.types_mut().push(ty)
+				}
+			},
+			Some(Term::Ref(prop)) => {
+				let definition = self.context.get(key);
+				let values = value.serialize(ObjectSerializer { context: self.context, definition, plain: false })?;
+				self.node.insert_all(prop, values.into_objects().into_iter().map(Indexed::from))
+			},
+			_ => ()
+		}
+
+		Ok(())
+	}
+}
+
+impl<'a, T: Id, C: Context<T>> ser::SerializeStruct for NodeSerializer<'a, T, C> {
+	type Ok = Serialized<T>;
+	type Error = Error;
+
+	fn serialize_field<V: Serialize + ?Sized>(&mut self, key: &'static str, value: &V) -> Result<(), Error> {
+		self.entry(key, value)
+	}
+
+	fn end(self) -> Result<Serialized<T>, Error> {
+		Ok(Serialized::Object(Object::Node(self.node)))
+	}
+}
+
+impl<'a, T: Id, C: Context<T>> ser::SerializeMap for NodeSerializer<'a, T, C> {
+	type Ok = Serialized<T>;
+	type Error = Error;
+
+	fn serialize_key<K: Serialize + ?Sized>(&mut self, key: &K) -> Result<(), Error> {
+		let key = key.serialize(ObjectSerializer::plain(self.context))?;
+		self.key = Some(key.into_string()?);
+		Ok(())
+	}
+
+	fn serialize_value<V: Serialize + ?Sized>(&mut self, value: &V) -> Result<(), Error> {
+		match self.key.take() {
+			Some(key) => self.entry(&key, value),
+			None => Err(ser::Error::custom("value without key"))
+		}
+	}
+
+	fn end(self) -> Result<Serialized<T>, Error> {
+		Ok(Serialized::Object(Object::Node(self.node)))
+	}
+}
@@ -0,0 +1,108 @@
+use std::collections::HashMap;
+use futures::{
+	io::{AsyncBufRead, AsyncBufReadExt, Lines},
+	stream::{self, Stream, StreamExt}
+};
+use iref::{Iri, IriBuf};
+use json::JsonValue;
+use crate::{
+	Error,
+	ErrorCode,
+	Id,
+	Document,
+	ExpandedDocument,
+	Loader,
+	context::{
+		ContextMut,
+		Local
+	},
+	expansion
+};
+
+/// State of an NDJSON-LD expansion stream.
+struct Expander<'a, T: Id, C: ContextMut<T>, L: Loader, R> {
+	lines: Lines<R>,
+	base_url: Option<IriBuf>,
+	context: &'a C,
+	loader: &'a mut L,
+	options: expansion::Options,
+
+	/// Processed top-level contexts, indexed by their serialization.
+	cache: HashMap<String, C>,
+
+	t: std::marker::PhantomData<T>
+}
+
+impl<'a, T: 'a + Id + Send + Sync, C: Send + Sync + ContextMut<T>, L: Send + Sync + Loader, R: AsyncBufRead + Unpin> Expander<'a, T, C, L, R> where
+	C::LocalContext: Send + Sync + From<L::Output> + From<JsonValue>,
+	L::Output: Into<JsonValue>
+{
+	async fn expand_line(&mut self, line: &str) -> Result<ExpandedDocument<T>, Error> {
+		let mut doc = json::parse(line).map_err(|e| Error::new(ErrorCode::LoadingDocumentFailed, e))?;
+		let base_url = self.options.base.clone().or_else(|| self.base_url.clone());
+
+		let local_context = match &mut doc {
+			JsonValue::Object(obj) => obj.remove("@context"),
+			_ => None
+		};
+
+		match local_context {
+			Some(local_context) => {
+				let key = local_context.dump();
+				if !self.cache.contains_key(&key) {
+					let processed = local_context.process_with(self.context, self.loader, base_url.as_ref().map(|url| url.as_iri()), (&self.options).into()).await?;
+					self.cache.insert(key.clone(), processed.into_inner());
+				}
+
+				let active_context = &self.cache[&key];
+				doc.expand_with(base_url.as_ref().map(|url| url.as_iri()), active_context, self.loader, self.options.clone()).await
+			},
+			None => {
+				doc.expand_with(base_url.as_ref().map(|url| url.as_iri()), self.context, self.loader, self.options.clone()).await
+			}
+		}
+	}
+}
+
+/// Expand a stream of newline-delimited JSON-LD documents (NDJSON-LD).
+///
+/// Each non-empty line of the input is parsed as a JSON-LD document and expanded
+/// with the given initial context, document loader and options.
+/// The result of each line is yielded by the returned stream,
+/// so a malformed line does not stop the processing of the following lines.
+///
+/// Top-level contexts are processed once: lines sharing the same `@context` entry
+/// are expanded against the same cached processed context.
+/// This makes processing large datasets where every record refers to the same context
+/// significantly faster than expanding each line as an independent document.
+pub fn expand_ndjson<'a, T: 'a + Id + Send + Sync, C: Send + Sync + ContextMut<T>, L: Send + Sync + Loader, R: 'a + AsyncBufRead + Unpin>(reader: R, base_url: Option<Iri>, context: &'a C, loader: &'a mut L, options: expansion::Options) -> impl 'a + Stream<Item = Result<ExpandedDocument<T>, Error>> where
+	C::LocalContext: Send + Sync + From<L::Output> + From<JsonValue>,
+	L::Output: Into<JsonValue>
+{
+	let expander = Expander {
+		lines: reader.lines(),
+		base_url: base_url.map(IriBuf::from),
+		context,
+		loader,
+		options,
+		cache: HashMap::new(),
+		t: std::marker::PhantomData
+	};
+
+	stream::unfold(expander, |mut expander| async move {
+		loop {
+			match expander.lines.next().await {
+				None => return None,
+				Some(Err(e)) => return Some((Err(Error::new(ErrorCode::LoadingDocumentFailed, e)), expander)),
+				Some(Ok(line)) => {
+					if line.trim().is_empty() {
+						continue
+					}
+
+					let result = expander.expand_line(&line).await;
+					return Some((result, expander))
+				}
+			}
+		}
+	})
+}
@@ -0,0 +1,202 @@
+use std::collections::HashSet;
+use std::hash::{Hash, Hasher};
+use std::convert::TryFrom;
+use std::fmt;
+use iref::{Iri, IriBuf};
+use json::JsonValue;
+use crate::{
+	Id,
+	Reference,
+	Lenient,
+	object,
+	Object,
+	Node,
+	Indexed,
+	syntax::Keyword,
+	util::{
+		self,
+		AsJson
+	}
+};
+
+/// Graph object.
+///
+/// A graph object is a set of objects, optionally named by an identifier.
+/// It is a node object with no other entry than `@graph`, `@id` and `@index`.
+/// Node objects with other entries along a `@graph` entry are represented by [`Node`].
+#[derive(PartialEq, Eq)]
+pub struct Graph<T: Id = IriBuf> {
+	/// Graph name.
+	///
+	/// This is the `@id` field.
+	pub(crate) id: Option<Lenient<Reference<T>>>,
+
+	/// Objects of the graph.
+	///
+	/// This is the `@graph` field.
+	pub(crate) objects: HashSet<Indexed<Object<T>>>
+}
+
+impl<T: Id> Graph<T> {
+	/// Create a new unnamed graph (simple graph object) from its objects.
+	pub fn new(objects: HashSet<Indexed<Object<T>>>) -> Graph<T> {
+		Graph {
+			id: None,
+			objects
+		}
+	}
+
+	/// Create a new graph with the given name.
+	pub fn named(id: Lenient<Reference<T>>, objects: HashSet<Indexed<Object<T>>>) -> Graph<T> {
+		Graph {
+			id: Some(id),
+			objects
+		}
+	}
+
+	/// Name of the graph, if any.
+	pub fn id(&self) -> Option<&Lenient<Reference<T>>> {
+		self.id.as_ref()
+	}
+
+	/// Set the name of the graph.
+	pub fn set_id(&mut self, id: Option<Lenient<Reference<T>>>) {
+		self.id = id
+	}
+
+	/// Name of the graph as an IRI, if it is named by an IRI.
+	pub fn as_iri(&self) -> Option<Iri> {
+		match &self.id {
+			Some(Lenient::Ok(id)) => id.as_iri(),
+			_ => None
+		}
+	}
+
+	/// Tests if the graph is a simple graph object (without `@id` field).
+	pub fn is_simple(&self) -> bool {
+		self.id.is_none()
+	}
+
+	/// Returns the number of objects in the graph.
+	pub fn len(&self) -> usize {
+		self.objects.len()
+	}
+
+	/// Checks if the graph has no objects.
+	pub fn is_empty(&self) -> bool {
+		self.objects.is_empty()
+	}
+
+	/// Returns the objects of the graph.
+	pub fn objects(&self) -> &HashSet<Indexed<Object<T>>> {
+		&self.objects
+	}
+
+	/// Returns a mutable reference to the objects of the graph.
+	pub fn objects_mut(&mut self) -> &mut HashSet<Indexed<Object<T>>> {
+		&mut self.objects
+	}
+
+	/// Returns an iterator over the objects of the graph.
+	pub fn iter(&self) -> std::collections::hash_set::Iter<Indexed<Object<T>>> {
+		self.objects.iter()
+	}
+
+	/// Add an object to the graph.
+	///
+	/// Returns `false` if the graph already contained this object.
+	pub fn insert(&mut self, object: Indexed<Object<T>>) -> bool {
+		self.objects.insert(object)
+	}
+
+	/// Consume the graph and returns its objects.
+	pub fn into_objects(self) -> HashSet<Indexed<Object<T>>> {
+		self.objects
+	}
+
+	/// Consume the graph and returns its name and objects.
+	pub fn into_parts(self) -> (Option<Lenient<Reference<T>>>, HashSet<Indexed<Object<T>>>) {
+		(self.id, self.objects)
+	}
+}
+
+impl<T: Id> object::Any<T> for Graph<T> {
+	fn as_ref(&self) -> object::Ref<T> {
+		object::Ref::Graph(self)
+	}
+}
+
+/// Converts a node into a graph object, if it is one (see [`Node::is_graph`]).
+impl<T: Id> TryFrom<Node<T>> for Graph<T> {
+	type Error = Node<T>;
+
+	fn try_from(node: Node<T>) -> Result<Graph<T>, Node<T>> {
+		if node.is_graph() {
+			Ok(Graph {
+				id: node.id,
+				objects: node.graph.unwrap()
+			})
+		} else {
+			Err(node)
+		}
+	}
+}
+
+impl<T: Id> TryFrom<Object<T>> for Graph<T> {
+	type Error = Object<T>;
+
+	fn try_from(obj: Object<T>) -> Result<Graph<T>, Object<T>> {
+		match obj {
+			Object::Graph(graph) => Ok(graph),
+			obj => Err(obj)
+		}
+	}
+}
+
+/// Converts a graph object into a node with a `@graph` field.
+impl<T: Id> From<Graph<T>> for Node<T> {
+	fn from(graph: Graph<T>) -> Node<T> {
+		let mut node = Node::new();
+		node.id = graph.id;
+		node.graph = Some(graph.objects);
+		node
+	}
+}
+
+impl<'a, T: Id> IntoIterator for &'a Graph<T> {
+	type Item = &'a Indexed<Object<T>>;
+	type IntoIter = std::collections::hash_set::Iter<'a, Indexed<Object<T>>>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		self.objects.iter()
+	}
+}
+
+/// Canonical expanded JSON-LD representation of the graph.
+///
+/// Use the alternate flag (`{:#}`) for pretty-printing.
+impl<T: Id> fmt::Display for Graph<T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		util::fmt_canonical(&self.as_json(), f)
+	}
+}
+
+impl<T: Id> Hash for Graph<T> {
+	fn hash<H: Hasher>(&self, h: &mut H) {
+		self.id.hash(h);
+		util::hash_set(&self.objects, h)
+	}
+}
+
+impl<T: Id> AsJson for Graph<T> {
+	fn as_json(&self) -> JsonValue {
+		let mut obj = json::object::Object::new();
+
+		if let Some(id) = &self.id {
+			obj.insert(Keyword::Id.into(), id.as_json());
+		}
+
+		obj.insert(Keyword::Graph.into(), self.objects.as_json());
+		JsonValue::Object(obj)
+	}
+}
@@ -0,0 +1,189 @@
+use std::convert::TryFrom;
+use std::ops::{
+	Deref,
+	DerefMut
+};
+use std::fmt;
+use iref::IriBuf;
+use json::JsonValue;
+use crate::{
+	Id,
+	object,
+	Object,
+	Indexed,
+	syntax::Keyword,
+	util::{
+		self,
+		AsJson
+	}
+};
+
+/// List object.
+///
+/// A list is an ordered sequence of (indexed) objects.
+/// This is the `@list` entry of a list object.
+#[derive(PartialEq, Eq, Hash)]
+pub struct List<T: Id = IriBuf> {
+	/// Items of the list.
+	items: Vec<Indexed<Object<T>>>
+}
+
+impl<T: Id> List<T> {
+	/// Create a new empty list.
+	pub fn new() -> List<T> {
+		List {
+			items: Vec::new()
+		}
+	}
+
+	/// Create a new list from its items.
+	pub fn with_items(items: Vec<Indexed<Object<T>>>) -> List<T> {
+		List {
+			items
+		}
+	}
+
+	/// Returns the number of items in the list.
+	pub fn len(&self) -> usize {
+		self.items.len()
+	}
+
+	/// Checks if the list is empty.
+	pub fn is_empty(&self) -> bool {
+		self.items.is_empty()
+	}
+
+	/// Returns the items of the list.
+	pub fn as_slice(&self) -> &[Indexed<Object<T>>] {
+		&self.items
+	}
+
+	/// Returns a mutable reference to the items of the list.
+	pub fn as_mut_slice(&mut self) -> &mut [Indexed<Object<T>>] {
+		&mut self.items
+	}
+
+	/// Returns an iterator over the items of the list.
+	pub fn iter(&self) -> std::slice::Iter<Indexed<Object<T>>> {
+		self.items.iter()
+	}
+
+	/// Returns an iterator over mutable references to the items of the list.
+	pub fn iter_mut(&mut self) -> std::slice::IterMut<Indexed<Object<T>>> {
+		self.items.iter_mut()
+	}
+
+	/// Append an item at the end of the list.
+	pub fn push(&mut self, item: Indexed<Object<T>>) {
+		self.items.push(item)
+	}
+
+	/// Remove the last item of the list, and returns it.
+	pub fn pop(&mut self) -> Option<Indexed<Object<T>>> {
+		self.items.pop()
+	}
+
+	/// Consume the list and returns its items.
+	pub fn into_items(self) -> Vec<Indexed<Object<T>>> {
+		self.items
+	}
+}
+
+impl<T: Id> Default for List<T> {
+	fn default() -> List<T> {
+		List::new()
+	}
+}
+
+impl<T: Id> Deref for List<T> {
+	type Target = [Indexed<Object<T>>];
+
+	fn deref(&self) -> &[Indexed<Object<T>>] {
+		&self.items
+	}
+}
+
+impl<T: Id> DerefMut for List<T> {
+	fn deref_mut(&mut self) -> &mut [Indexed<Object<T>>] {
+		&mut self.items
+	}
+}
+
+impl<T: Id> From<Vec<Indexed<Object<T>>>> for List<T> {
+	fn from(items: Vec<Indexed<Object<T>>>) -> List<T> {
+		List::with_items(items)
+	}
+}
+
+impl<T: Id> std::iter::FromIterator<Indexed<Object<T>>> for List<T> {
+	fn from_iter<I: IntoIterator<Item=Indexed<Object<T>>>>(items: I) -> List<T> {
+		List::with_items(items.into_iter().collect())
+	}
+}
+
+impl<T: Id> Extend<Indexed<Object<T>>> for List<T> {
+	fn extend<I: IntoIterator<Item=Indexed<Object<T>>>>(&mut self, items: I) {
+		self.items.extend(items)
+	}
+}
+
+impl<T: Id> IntoIterator for List<T> {
+	type Item = Indexed<Object<T>>;
+	type IntoIter = std::vec::IntoIter<Indexed<Object<T>>>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		self.items.into_iter()
+	}
+}
+
+impl<'a, T: Id> IntoIterator for &'a List<T> {
+	type Item = &'a Indexed<Object<T>>;
+	type IntoIter = std::slice::Iter<'a, Indexed<Object<T>>>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		self.items.iter()
+	}
+}
+
+impl<'a, T: Id> IntoIterator for &'a mut List<T> {
+	type Item = &'a mut Indexed<Object<T>>;
+	type IntoIter = std::slice::IterMut<'a, Indexed<Object<T>>>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		self.items.iter_mut()
+	}
+}
+
+impl<T: Id> object::Any<T> for List<T> {
+	fn as_ref(&self) -> object::Ref<T> {
+		object::Ref::List(self)
+	}
+}
+
+impl<T: Id> TryFrom<Object<T>> for List<T> {
+	type Error = Object<T>;
+
+	fn try_from(obj: Object<T>) -> Result<List<T>, Object<T>> {
+		match obj {
+			Object::List(list) => Ok(list),
+			obj => Err(obj)
+		}
+	}
+}
+
+/// Canonical expanded JSON-LD representation of the list.
+///
+/// Use the alternate flag (`{:#}`) for pretty-printing.
+impl<T: Id> fmt::Display for List<T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		util::fmt_canonical(&self.as_json(), f)
+	}
+}
+
+impl<T: Id> AsJson for List<T> {
+	fn as_json(&self) -> JsonValue {
+		let mut obj = json::object::Object::new();
+		obj.insert(Keyword::List.into(), self.items.as_json());
+		JsonValue::Object(obj)
+	}
+}
@@ -2,8 +2,12 @@
 
 pub mod value;
 pub mod node;
+pub mod list;
+pub mod graph;
+mod parse;
 
 use std::collections::HashSet;
+use std::convert::TryFrom;
 use std::hash::Hash;
 use std::fmt;
 use iref::{Iri, IriBuf};
@@ -14,15 +18,21 @@ use crate::{
 	Lenient,
 	Reference,
 	Indexed,
-	syntax::Keyword,
-	util::AsJson
+	util::{
+		self,
+		AsJson
+	}
 };
 
 pub use value::{
 	Literal,
+	JsonLiteral,
 	Value
 };
-pub use node::Node;
+pub use node::{Node, NodeBuilder};
+pub use list::List;
+pub use graph::Graph;
+pub(crate) use parse::parse_document;
 
 pub trait Any<T: Id>: AsJson {
 	fn as_ref(&self) -> Ref<T>;
@@ -31,6 +41,7 @@ pub trait Any<T: Id>: AsJson {
 	fn id(&self) -> Option<&Lenient<Reference<T>>> {
 		match self.as_ref() {
 			Ref::Node(n) => n.id.as_ref(),
+			Ref::Graph(g) => g.id.as_ref(),
 			_ => None
 		}
 	}
@@ -62,6 +73,7 @@ pub trait Any<T: Id>: AsJson {
 	#[inline]
 	fn is_graph(&self) -> bool {
 		match self.as_ref() {
+			Ref::Graph(_) => true,
 			Ref::Node(n) => n.is_graph(),
 			_ => false
 		}
@@ -85,13 +97,16 @@ pub enum Ref<'a, T: Id> {
 	Node(&'a Node<T>),
 
 	/// List object.
-	List(&'a [Indexed<Object<T>>])
+	List(&'a List<T>),
+
+	/// Graph object.
+	Graph(&'a Graph<T>)
 }
 
 /// Object.
 ///
 /// JSON-LD connects together multiple kinds of data objects.
-/// Objects may be nodes, values or lists of objects.
+/// Objects may be nodes, values, lists of objects or graphs.
 #[derive(PartialEq, Eq, Hash)]
 pub enum Object<T: Id = IriBuf> {
 	/// Value object.
@@ -101,25 +116,33 @@ pub enum Object<T: Id = IriBuf> {
 	Node(Node<T>),
 
 	/// List object.
-	List(Vec<Indexed<Object<T>>>),
+	List(List<T>),
+
+	/// Graph object.
+	///
+	/// Node objects with no other entry than `@graph` and `@id`
+	/// are represented with this variant rather than [`Object::Node`].
+	Graph(Graph<T>)
 }
 
 impl<T: Id> Object<T> {
-	/// Identifier of the object, if it is a node object.
+	/// Identifier of the object, if it is a node object or a named graph.
 	pub fn id(&self) -> Option<&Lenient<Reference<T>>> {
 		match self {
 			Object::Node(n) => n.id.as_ref(),
+			Object::Graph(g) => g.id.as_ref(),
 			_ => None
 		}
 	}
 
 	/// Identifier of the object as an IRI.
 	///
-	/// If the object is a node identified by an IRI, returns this IRI.
+	/// If the object is a node or graph identified by an IRI, returns this IRI.
 	/// Returns `None` otherwise.
 	pub fn as_iri(&self) -> Option<Iri> {
 		match self {
 			Object::Node(node) => node.as_iri(),
+			Object::Graph(graph) => graph.as_iri(),
 			_ => None
 		}
 	}
@@ -140,14 +163,54 @@ impl<T: Id> Object<T> {
 		}
 	}
 
-	/// Tests if the object is a graph object (a node with a `@graph` field).
+	/// Tests if the object is a graph object.
+	///
+	/// This is either an [`Object::Graph`], or a node with only a `@graph` field
+	/// and optionally an `@id` field.
 	pub fn is_graph(&self) -> bool {
 		match self {
+			Object::Graph(_) => true,
 			Object::Node(n) => n.is_graph(),
 			_ => false
 		}
 	}
 
+	/// Returns a mutable reference to the node, if the object is a node object.
+	///
+	/// A graph object is first converted into a node object with a `@graph` field,
+	/// so that other fields can be added to it.
+	pub fn node_mut(&mut self) -> Option<&mut Node<T>> {
+		if let Object::Graph(_) = self {
+			if let Object::Graph(graph) = std::mem::replace(self, Object::Node(Node::new())) {
+				*self = Object::Node(graph.into())
+			}
+		}
+
+		match self {
+			Object::Node(node) => Some(node),
+			_ => None
+		}
+	}
+
+	/// Returns the object as a graph object, if it is an [`Object::Graph`].
+	pub fn as_graph(&self) -> Option<&Graph<T>> {
+		match self {
+			Object::Graph(graph) => Some(graph),
+			_ => None
+		}
+	}
+
+	/// Converts the object into a graph object, if it is a graph object.
+	///
+	/// Nodes that are graph objects (see [`Node::is_graph`]) are converted as well.
+	pub fn into_graph(self) -> Result<Graph<T>, Object<T>> {
+		match self {
+			Object::Graph(graph) => Ok(graph),
+			Object::Node(node) => Graph::try_from(node).map_err(Object::Node),
+			obj => Err(obj)
+		}
+	}
+
 	/// Tests if the object is a list.
 	pub fn is_list(&self) -> bool {
 		match self {
@@ -159,12 +222,13 @@ impl<T: Id> Object<T> {
 	/// Get the object as a string.
 	///
 	/// If the object is a value that is a string, returns this string.
-	/// If the object is a node that is identified, returns the identifier as a string.
+	/// If the object is a node or graph identified by an IRI, returns the IRI as a string.
 	/// Returns `None` otherwise.
 	pub fn as_str(&self) -> Option<&str> {
 		match self {
 			Object::Value(value) => value.as_str(),
 			Object::Node(node) => node.as_str(),
+			Object::Graph(graph) => graph.as_iri().map(|iri| iri.into_str()),
 			_ => None
 		}
 	}
@@ -185,10 +249,71 @@ impl<T: Id> Object<T> {
 		}
 	}
 
+	/// Get the value as a signed integer, if it is.
+	///
+	/// See [`Value::as_i64`].
+	pub fn as_i64(&self) -> Option<i64> {
+		match self {
+			Object::Value(value) => value.as_i64(),
+			_ => None
+		}
+	}
+
+	/// Get the value as an unsigned integer, if it is.
+	///
+	/// See [`Value::as_u64`].
+	pub fn as_u64(&self) -> Option<u64> {
+		match self {
+			Object::Value(value) => value.as_u64(),
+			_ => None
+		}
+	}
+
+	/// Get the value as a floating point number, if it is.
+	///
+	/// See [`Value::as_f64`].
+	pub fn as_f64(&self) -> Option<f64> {
+		match self {
+			Object::Value(value) => value.as_f64(),
+			_ => None
+		}
+	}
+
+	/// Get the value as a language tagged string with its language, if it is.
+	pub fn as_str_with_language(&self) -> Option<(&str, Option<LanguageTag>)> {
+		match self {
+			Object::Value(value) => value.as_str_with_language(),
+			_ => None
+		}
+	}
+
+	/// Get the value as a date and time, if it is.
+	///
+	/// See [`Value::as_datetime`].
+	#[cfg(feature="chrono")]
+	pub fn as_datetime(&self) -> Option<chrono::DateTime<chrono::FixedOffset>> {
+		match self {
+			Object::Value(value) => value.as_datetime(),
+			_ => None
+		}
+	}
+
+	/// Get the value as a date, if it is.
+	///
+	/// See [`Value::as_date`].
+	#[cfg(feature="chrono")]
+	pub fn as_date(&self) -> Option<chrono::NaiveDate> {
+		match self {
+			Object::Value(value) => value.as_date(),
+			_ => None
+		}
+	}
+
 	/// Try to convert this object into an unnamed graph.
 	pub fn into_unnamed_graph(self: Indexed<Self>) -> Result<HashSet<Indexed<Object<T>>>, Indexed<Self>> {
 		let (obj, index) = self.into_parts();
 		match obj {
+			Object::Graph(g) if g.is_simple() => Ok(g.objects),
 			Object::Node(n) => {
 				match n.into_unnamed_graph() {
 					Ok(g) => Ok(g),
@@ -214,14 +339,25 @@ impl<T: Id> Any<T> for Object<T> {
 		match self {
 			Object::Value(value) => Ref::Value(value),
 			Object::Node(node) => Ref::Node(node),
-			Object::List(list) => Ref::List(list.as_ref())
+			Object::List(list) => Ref::List(list),
+			Object::Graph(graph) => Ref::Graph(graph)
 		}
 	}
 }
 
+/// Canonical expanded JSON-LD representation of the object.
+///
+/// Entries and unordered arrays are sorted so that the output is deterministic.
+/// Use the alternate flag (`{:#}`) for pretty-printing.
+impl<T: Id> fmt::Display for Object<T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		util::fmt_canonical(&self.as_json(), f)
+	}
+}
+
 impl<T: Id> fmt::Debug for Object<T> {
 	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-		write!(f, "{}", self.as_json().pretty(2))
+		write!(f, "{}", util::json_ld_canonical(&self.as_json()).pretty(2))
 	}
 }
 
@@ -231,9 +367,25 @@ impl<T: Id> From<Value<T>> for Object<T> {
 	}
 }
 
+/// Graph objects (see [`Node::is_graph`]) are converted into [`Object::Graph`].
 impl<T: Id> From<Node<T>> for Object<T> {
 	fn from(node: Node<T>) -> Object<T> {
-		Object::Node(node)
+		match Graph::try_from(node) {
+			Ok(graph) => Object::Graph(graph),
+			Err(node) => Object::Node(node)
+		}
+	}
+}
+
+impl<T: Id> From<Graph<T>> for Object<T> {
+	fn from(graph: Graph<T>) -> Object<T> {
+		Object::Graph(graph)
+	}
+}
+
+impl<T: Id> From<List<T>> for Object<T> {
+	fn from(list: List<T>) -> Object<T> {
+		Object::List(list)
 	}
 }
 
@@ -242,11 +394,8 @@ impl<T: Id> AsJson for Object<T> {
 		match self {
 			Object::Value(v) => v.as_json(),
 			Object::Node(n) => n.as_json(),
-			Object::List(items) => {
-				let mut obj = json::object::Object::new();
-				obj.insert(Keyword::List.into(), items.as_json());
-				JsonValue::Object(obj)
-			}
+			Object::List(list) => list.as_json(),
+			Object::Graph(graph) => graph.as_json()
 		}
 	}
 }
@@ -1,7 +1,8 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::HashSet;
 use std::hash::{Hash, Hasher};
 use std::convert::TryFrom;
 use std::borrow::Borrow;
+use std::fmt;
 use iref::{Iri, IriBuf};
 use json::JsonValue;
 use crate::{
@@ -16,7 +17,11 @@ use crate::{
 		Keyword,
 		Term,
 	},
-	util
+	util::{
+		self,
+		SmallMap,
+		small_map
+	}
 };
 
 /// A node object.
@@ -24,6 +29,7 @@ use crate::{
 /// A node is defined by its identifier (`@id` field), types, properties and reverse properties.
 /// In addition, a node may represent a graph (`@graph field`) and includes nodes
 /// (`@included` field).
+/// When it is the value of a property, it may also be annotated (`@annotation` field).
 #[derive(PartialEq, Eq)]
 pub struct Node<T: Id = IriBuf> {
 	/// Identifier.
@@ -46,15 +52,21 @@ pub struct Node<T: Id = IriBuf> {
 	/// This is the `@included` field.
 	pub(crate) included: Option<HashSet<Indexed<Node<T>>>>,
 
+	/// Annotations.
+	///
+	/// This is the `@annotation` field, describing the statement
+	/// linking the node to the subject of the property it is the value of.
+	pub(crate) annotation: Option<HashSet<Indexed<Node<T>>>>,
+
 	/// Properties.
 	///
-	/// Any non-keyword field.
-	pub(crate) properties: HashMap<Reference<T>, Vec<Indexed<Object<T>>>>,
+	/// Any non-keyword field, in insertion order.
+	pub(crate) properties: SmallMap<Reference<T>, Vec<Indexed<Object<T>>>>,
 
 	/// Reverse properties.
 	///
-	/// This is the `@reverse` field.
-	pub(crate) reverse_properties: HashMap<Reference<T>, Vec<Indexed<Node<T>>>>
+	/// This is the `@reverse` field, in insertion order.
+	pub(crate) reverse_properties: SmallMap<Reference<T>, Vec<Indexed<Node<T>>>>
 }
 
 /// Iterator through indexed objects.
@@ -71,6 +83,97 @@ impl<'a, T: Id> Iterator for Objects<'a, T> {
 	}
 }
 
+/// Iterator through the properties of a node.
+///
+/// Created with [`Node::properties`].
+pub struct Properties<'a, T: Id>(small_map::Iter<'a, Reference<T>, Vec<Indexed<Object<T>>>>);
+
+impl<'a, T: Id> Iterator for Properties<'a, T> {
+	type Item = (&'a Reference<T>, &'a [Indexed<Object<T>>]);
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		self.0.size_hint()
+	}
+
+	fn next(&mut self) -> Option<Self::Item> {
+		self.0.next().map(|(prop, values)| (prop, values.as_ref()))
+	}
+}
+
+impl<'a, T: Id> ExactSizeIterator for Properties<'a, T> {}
+
+/// Iterator through the reverse properties of a node.
+///
+/// Created with [`Node::reverse_properties`].
+pub struct ReverseProperties<'a, T: Id>(small_map::Iter<'a, Reference<T>, Vec<Indexed<Node<T>>>>);
+
+impl<'a, T: Id> Iterator for ReverseProperties<'a, T> {
+	type Item = (&'a Reference<T>, &'a [Indexed<Node<T>>]);
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		self.0.size_hint()
+	}
+
+	fn next(&mut self) -> Option<Self::Item> {
+		self.0.next().map(|(prop, nodes)| (prop, nodes.as_ref()))
+	}
+}
+
+impl<'a, T: Id> ExactSizeIterator for ReverseProperties<'a, T> {}
+
+/// Consuming iterator through the properties of a node.
+///
+/// Created with the [`IntoIterator`] implementation of [`Node`].
+pub struct IntoProperties<T: Id>(small_map::IntoIter<Reference<T>, Vec<Indexed<Object<T>>>>);
+
+impl<T: Id> Iterator for IntoProperties<T> {
+	type Item = (Reference<T>, Vec<Indexed<Object<T>>>);
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		self.0.size_hint()
+	}
+
+	fn next(&mut self) -> Option<Self::Item> {
+		self.0.next()
+	}
+}
+
+impl<T: Id> ExactSizeIterator for IntoProperties<T> {}
+
+/// Property entry of a node.
+///
+/// Created with [`Node::entry`].
+pub struct Entry<'a, T: Id>(small_map::Entry<'a, Reference<T>, Vec<Indexed<Object<T>>>>);
+
+impl<'a, T: Id> Entry<'a, T> {
+	/// The property of this entry.
+	pub fn key(&self) -> &Reference<T> {
+		self.0.key()
+	}
+
+	/// Ensures the property is defined by inserting the result of `f` if it is not,
+	/// and returns a mutable reference to the associated objects.
+	pub fn or_insert_with<F: FnOnce() -> Vec<Indexed<Object<T>>>>(self, f: F) -> &'a mut Vec<Indexed<Object<T>>> {
+		self.0.or_insert_with(f)
+	}
+
+	/// Ensures the property is defined, with no associated objects if it is not,
+	/// and returns a mutable reference to the associated objects.
+	pub fn or_default(self) -> &'a mut Vec<Indexed<Object<T>>> {
+		self.0.or_insert_with(Vec::new)
+	}
+
+	/// Modify the associated objects if the property is defined.
+	pub fn and_modify<F: FnOnce(&mut Vec<Indexed<Object<T>>>)>(self, f: F) -> Self {
+		Entry(self.0.and_modify(f))
+	}
+
+	/// Associate the given object to the node through this entry's property.
+	pub fn append(self, value: Indexed<Object<T>>) {
+		self.or_default().push(value)
+	}
+}
+
 impl<T: Id> Node<T> {
 	/// Create a new empty node.
 	pub fn new() -> Node<T> {
@@ -79,8 +182,9 @@ impl<T: Id> Node<T> {
 			types: Vec::new(),
 			graph: None,
 			included: None,
-			properties: HashMap::new(),
-			reverse_properties: HashMap::new()
+			annotation: None,
+			properties: SmallMap::new(),
+			reverse_properties: SmallMap::new()
 		}
 	}
 
@@ -91,11 +195,31 @@ impl<T: Id> Node<T> {
 			types: Vec::new(),
 			graph: None,
 			included: None,
-			properties: HashMap::new(),
-			reverse_properties: HashMap::new()
+			annotation: None,
+			properties: SmallMap::new(),
+			reverse_properties: SmallMap::new()
 		}
 	}
 
+	/// Create a new node builder.
+	///
+	/// # Example
+	/// ```
+	/// use static_iref::*;
+	/// use iref::IriBuf;
+	/// use json_ld::{Node, Value};
+	///
+	/// let name: Value = "Timothée Haudebourg".into();
+	/// let node: Node = Node::builder()
+	/// 	.id(IriBuf::from(iri!("http://timothee.haudebourg.net/")))
+	/// 	.type_(IriBuf::from(iri!("http://xmlns.com/foaf/0.1/Person")))
+	/// 	.property(IriBuf::from(iri!("http://xmlns.com/foaf/0.1/name")), name)
+	/// 	.build();
+	/// ```
+	pub fn builder() -> NodeBuilder<T> {
+		NodeBuilder::new()
+	}
+
 	/// Checks if the node object has the given term as key.
 	///
 	/// # Example
@@ -114,6 +238,7 @@ impl<T: Id> Node<T> {
 			Term::Keyword(Keyword::Type) => !self.types.is_empty(),
 			Term::Keyword(Keyword::Graph) => self.graph.is_some(),
 			Term::Keyword(Keyword::Included) => self.included.is_some(),
+			Term::Keyword(Keyword::Annotation) => self.annotation.is_some(),
 			Term::Keyword(Keyword::Reverse) => !self.reverse_properties.is_empty(),
 			Term::Ref(prop) => self.properties.get(prop).is_some(),
 			_ => false
@@ -127,6 +252,11 @@ impl<T: Id> Node<T> {
 		self.id.as_ref()
 	}
 
+	/// Set the identifier of the node.
+	pub fn set_id(&mut self, id: Option<Lenient<Reference<T>>>) {
+		self.id = id
+	}
+
 	/// Get the node's as an IRI if possible.
 	///
 	/// Returns the node's IRI id if any. Returns `None` otherwise.
@@ -156,6 +286,11 @@ impl<T: Id> Node<T> {
 		self.types.as_ref()
 	}
 
+	/// Get the mutable list of the node's types.
+	pub fn types_mut(&mut self) -> &mut Vec<Lenient<Reference<T>>> {
+		&mut self.types
+	}
+
 	/// Checks if the node has the given type.
 	pub fn has_type<U>(&self, ty: &U) -> bool where Lenient<Reference<T>>: PartialEq<U> {
 		for self_ty in &self.types {
@@ -174,6 +309,7 @@ impl<T: Id> Node<T> {
 		self.types.is_empty()
 		&& self.graph.is_none()
 		&& self.included.is_none()
+		&& self.annotation.is_none()
 		&& self.properties.is_empty()
 		&& self.reverse_properties.is_empty()
 	}
@@ -185,6 +321,7 @@ impl<T: Id> Node<T> {
 		self.graph.is_some()
 		&& self.types.is_empty()
 		&& self.included.is_none()
+		&& self.annotation.is_none()
 		&& self.properties.is_empty()
 		&& self.reverse_properties.is_empty()
 	}
@@ -209,6 +346,11 @@ impl<T: Id> Node<T> {
 		self.graph = graph
 	}
 
+	/// Remove and return the graph associated to the node, if any.
+	pub fn take_graph(&mut self) -> Option<HashSet<Indexed<Object<T>>>> {
+		self.graph.take()
+	}
+
 	/// Get the set of nodes included by this node.
 	///
 	/// This correspond to the `@included` field in the JSON representation.
@@ -228,6 +370,36 @@ impl<T: Id> Node<T> {
 		self.included = included
 	}
 
+	/// Remove and return the set of nodes included by this node, if any.
+	pub fn take_included(&mut self) -> Option<HashSet<Indexed<Node<T>>>> {
+		self.included.take()
+	}
+
+	/// Get the annotations of the node.
+	///
+	/// This correspond to the `@annotation` field in the JSON representation.
+	/// Annotations are only expanded when the `rdf_star` expansion option is set.
+	pub fn annotation(&self) -> Option<&HashSet<Indexed<Node<T>>>> {
+		self.annotation.as_ref()
+	}
+
+	/// Get the mutable annotations of the node.
+	///
+	/// This correspond to the `@annotation` field in the JSON representation.
+	pub fn annotation_mut(&mut self) -> Option<&mut HashSet<Indexed<Node<T>>>> {
+		self.annotation.as_mut()
+	}
+
+	/// Set the annotations of the node.
+	pub fn set_annotation(&mut self, annotation: Option<HashSet<Indexed<Node<T>>>>) {
+		self.annotation = annotation
+	}
+
+	/// Remove and return the annotations of the node, if any.
+	pub fn take_annotation(&mut self) -> Option<HashSet<Indexed<Node<T>>>> {
+		self.annotation.take()
+	}
+
 	/// Get all the objects associated to the node with the given property.
 	pub fn get<'a, Q: ToReference<T>>(&self, prop: Q) -> Objects<T> where T: 'a {
 		match self.properties.get(prop.to_ref().borrow()) {
@@ -247,24 +419,92 @@ impl<T: Id> Node<T> {
 		}
 	}
 
+	/// Get the entry of the given property in the node, for in-place manipulation.
+	pub fn entry(&mut self, prop: Reference<T>) -> Entry<T> {
+		Entry(self.properties.entry(prop))
+	}
+
 	/// Associate the given object to the node through the given property.
 	pub fn insert(&mut self, prop: Reference<T>, value: Indexed<Object<T>>) {
-		if let Some(node_values) = self.properties.get_mut(&prop) {
-			node_values.push(value);
-		} else {
-			let mut node_values = Vec::new();
-			node_values.push(value);
-			self.properties.insert(prop, node_values);
-		}
+		self.entry(prop).append(value)
 	}
 
 	/// Associate all the given objects to the node through the given property.
 	pub fn insert_all<Objects: Iterator<Item=Indexed<Object<T>>>>(&mut self, prop: Reference<T>, values: Objects) {
-		if let Some(node_values) = self.properties.get_mut(&prop) {
-			node_values.extend(values);
-		} else {
-			self.properties.insert(prop, values.collect());
-		}
+		self.entry(prop).or_default().extend(values)
+	}
+
+	/// Replace all the objects associated to the node through the given property.
+	///
+	/// Returns the previously associated objects, if any.
+	pub fn set(&mut self, prop: Reference<T>, values: Vec<Indexed<Object<T>>>) -> Option<Vec<Indexed<Object<T>>>> {
+		self.properties.insert(prop, values)
+	}
+
+	/// Remove the given property from the node.
+	///
+	/// Returns the objects that were associated to the node through this property, if any.
+	/// The order of the remaining properties is preserved.
+	pub fn remove<'a, Q: ToReference<T>>(&mut self, prop: Q) -> Option<Vec<Indexed<Object<T>>>> where T: 'a {
+		self.properties.shift_remove(prop.to_ref().borrow())
+	}
+
+	/// Remove the given property from the node and returns the associated objects.
+	///
+	/// Returns an empty list if the property was not defined.
+	pub fn take<'a, Q: ToReference<T>>(&mut self, prop: Q) -> Vec<Indexed<Object<T>>> where T: 'a {
+		self.remove(prop).unwrap_or_default()
+	}
+
+	/// Iterate through the properties of the node and their associated objects.
+	///
+	/// Properties are visited in insertion order.
+	pub fn properties(&self) -> Properties<T> {
+		Properties(self.properties.iter())
+	}
+
+	/// Iterate through the reverse properties of the node and their associated nodes.
+	///
+	/// Reverse properties are visited in insertion order.
+	pub fn reverse_properties(&self) -> ReverseProperties<T> {
+		ReverseProperties(self.reverse_properties.iter())
+	}
+
+	/// Get the mutable map of the node's properties.
+	pub fn properties_mut(&mut self) -> &mut SmallMap<Reference<T>, Vec<Indexed<Object<T>>>> {
+		&mut self.properties
+	}
+
+	/// Get the mutable map of the node's reverse properties.
+	pub fn reverse_properties_mut(&mut self) -> &mut SmallMap<Reference<T>, Vec<Indexed<Node<T>>>> {
+		&mut self.reverse_properties
+	}
+
+	/// Consume the node and return its properties.
+	pub fn into_properties(self) -> SmallMap<Reference<T>, Vec<Indexed<Object<T>>>> {
+		self.properties
+	}
+
+	/// Consume the node and return its reverse properties.
+	pub fn into_reverse_properties(self) -> SmallMap<Reference<T>, Vec<Indexed<Node<T>>>> {
+		self.reverse_properties
+	}
+
+	/// Consume the node and return its types.
+	pub fn into_types(self) -> Vec<Lenient<Reference<T>>> {
+		self.types
+	}
+
+	/// Remove the given reverse property from the node.
+	///
+	/// Returns the nodes that were associated to the node through this reverse property, if any.
+	pub fn remove_reverse<'a, Q: ToReference<T>>(&mut self, reverse_prop: Q) -> Option<Vec<Indexed<Node<T>>>> where T: 'a {
+		self.reverse_properties.shift_remove(reverse_prop.to_ref().borrow())
+	}
+
+	/// Remove all the reverse properties of the node.
+	pub fn clear_reverse_properties(&mut self) {
+		self.reverse_properties.clear()
 	}
 
 	pub fn insert_reverse(&mut self, reverse_prop: Reference<T>, reverse_value: Indexed<Node<T>>) {
@@ -294,6 +534,7 @@ impl<T: Id> Node<T> {
 		&& self.id.is_none()
 		&& self.types.is_empty()
 		&& self.included.is_none()
+		&& self.annotation.is_none()
 		&& self.properties.is_empty()
 		&& self.reverse_properties.is_empty()
 	}
@@ -309,6 +550,137 @@ impl<T: Id> Node<T> {
 			Err(self)
 		}
 	}
+
+	/// Merge the given node into this node.
+	///
+	/// Types, properties, reverse properties, graph, included nodes and annotations of `other` are added
+	/// to the ones of this node, ignoring duplicates.
+	/// The identifier of `other` is used only if this node has no identifier.
+	pub fn merge_with(&mut self, other: Node<T>) {
+		if self.id.is_none() {
+			self.id = other.id
+		}
+
+		for ty in other.types {
+			if !self.types.contains(&ty) {
+				self.types.push(ty)
+			}
+		}
+
+		if let Some(graph) = other.graph {
+			match &mut self.graph {
+				Some(self_graph) => self_graph.extend(graph),
+				None => self.graph = Some(graph)
+			}
+		}
+
+		if let Some(included) = other.included {
+			match &mut self.included {
+				Some(self_included) => self_included.extend(included),
+				None => self.included = Some(included)
+			}
+		}
+
+		if let Some(annotation) = other.annotation {
+			match &mut self.annotation {
+				Some(self_annotation) => self_annotation.extend(annotation),
+				None => self.annotation = Some(annotation)
+			}
+		}
+
+		for (prop, values) in other.properties {
+			let self_values = self.entry(prop).or_default();
+			for value in values {
+				if !self_values.contains(&value) {
+					self_values.push(value)
+				}
+			}
+		}
+
+		for (prop, nodes) in other.reverse_properties {
+			let self_nodes = self.reverse_properties.entry(prop).or_insert_with(Vec::new);
+			for node in nodes {
+				if !self_nodes.contains(&node) {
+					self_nodes.push(node)
+				}
+			}
+		}
+	}
+}
+
+/// Node builder.
+///
+/// Created with [`Node::builder`].
+pub struct NodeBuilder<T: Id> {
+	node: Node<T>
+}
+
+impl<T: Id> NodeBuilder<T> {
+	/// Create a new builder for an empty node.
+	pub fn new() -> NodeBuilder<T> {
+		NodeBuilder {
+			node: Node::new()
+		}
+	}
+
+	/// Set the identifier of the node.
+	pub fn id<R: Into<Reference<T>>>(mut self, id: R) -> Self {
+		self.node.id = Some(Lenient::Ok(id.into()));
+		self
+	}
+
+	/// Add a type to the node.
+	pub fn type_<R: Into<Reference<T>>>(mut self, ty: R) -> Self {
+		self.node.types.push(Lenient::Ok(ty.into()));
+		self
+	}
+
+	/// Associate the given object to the node through the given property.
+	pub fn property<P: Into<Reference<T>>, O: Into<Object<T>>>(mut self, prop: P, value: O) -> Self {
+		self.node.insert(prop.into(), Indexed::new(value.into(), None));
+		self
+	}
+
+	/// Associate the given indexed object to the node through the given property.
+	pub fn indexed_property<P: Into<Reference<T>>>(mut self, prop: P, value: Indexed<Object<T>>) -> Self {
+		self.node.insert(prop.into(), value);
+		self
+	}
+
+	/// Associate the given node to this node through the given reverse property.
+	pub fn reverse_property<P: Into<Reference<T>>>(mut self, prop: P, node: Node<T>) -> Self {
+		self.node.insert_reverse(prop.into(), Indexed::new(node, None));
+		self
+	}
+
+	/// Add the given object to the graph of the node.
+	pub fn graph_object<O: Into<Object<T>>>(mut self, object: O) -> Self {
+		self.node.graph.get_or_insert_with(HashSet::new).insert(Indexed::new(object.into(), None));
+		self
+	}
+
+	/// Include the given node.
+	pub fn included(mut self, node: Node<T>) -> Self {
+		self.node.included.get_or_insert_with(HashSet::new).insert(Indexed::new(node, None));
+		self
+	}
+
+	/// Annotate the node with the given annotation node.
+	pub fn annotation(mut self, node: Node<T>) -> Self {
+		self.node.annotation.get_or_insert_with(HashSet::new).insert(Indexed::new(node, None));
+		self
+	}
+
+	/// Build the node.
+	pub fn build(self) -> Node<T> {
+		self.node
+	}
+}
+
+impl<T: Id> Default for NodeBuilder<T> {
+	fn default() -> NodeBuilder<T> {
+		NodeBuilder::new()
+	}
 }
 
 impl<T: Id> object::Any<T> for Node<T> {
@@ -323,17 +695,49 @@ impl<T: Id> TryFrom<Object<T>> for Node<T> {
 	fn try_from(obj: Object<T>) -> Result<Node<T>, Object<T>> {
 		match obj {
 			Object::Node(node) => Ok(node),
+			Object::Graph(graph) => Ok(graph.into()),
 			obj => Err(obj)
 		}
 	}
 }
 
+/// Iterate through the properties of the node.
+///
+/// The identifier, types, graph, included nodes, annotations and reverse properties are dropped.
+impl<T: Id> IntoIterator for Node<T> {
+	type Item = (Reference<T>, Vec<Indexed<Object<T>>>);
+	type IntoIter = IntoProperties<T>;
+
+	fn into_iter(self) -> IntoProperties<T> {
+		IntoProperties(self.properties.into_iter())
+	}
+}
+
+impl<'a, T: Id> IntoIterator for &'a Node<T> {
+	type Item = (&'a Reference<T>, &'a [Indexed<Object<T>>]);
+	type IntoIter = Properties<'a, T>;
+
+	fn into_iter(self) -> Properties<'a, T> {
+		self.properties()
+	}
+}
+
+/// Canonical expanded JSON-LD representation of the node.
+///
+/// Use the alternate flag (`{:#}`) for pretty-printing.
+impl<T: Id> fmt::Display for Node<T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		util::fmt_canonical(&util::AsJson::as_json(self), f)
+	}
+}
+
 impl<T: Id> Hash for Node<T> {
 	fn hash<H: Hasher>(&self, h: &mut H) {
 		self.id.hash(h);
 		self.types.hash(h);
 		util::hash_set_opt(&self.graph, h);
 		util::hash_set_opt(&self.included, h);
+		util::hash_set_opt(&self.annotation, h);
 		util::hash_map(&self.properties, h);
 		util::hash_map(&self.reverse_properties, h);
 	}
@@ -359,6 +763,10 @@ impl<T: Id> util::AsJson for Node<T> {
 			obj.insert(Keyword::Included.into(), included.as_json())
 		}
 
+		if let Some(annotation) = &self.annotation {
+			obj.insert(Keyword::Annotation.into(), annotation.as_json())
+		}
+
 		if !self.reverse_properties.is_empty() {
 			let mut reverse = json::object::Object::new();
 			for (key, value) in &self.reverse_properties {
@@ -0,0 +1,258 @@
+//! Parsing of already expanded JSON-LD documents into the object model.
+
+use std::collections::HashSet;
+use std::convert::TryFrom;
+use iref::Iri;
+use langtag::LanguageTagBuf;
+use json::JsonValue;
+use crate::{
+	Id,
+	Error,
+	ErrorCode,
+	BlankId,
+	Lenient,
+	Reference,
+	Indexed,
+	Direction,
+	LangString,
+	syntax::Keyword
+};
+use super::{
+	Object,
+	Node,
+	Value,
+	Literal
+};
+
+/// Parse a node reference (IRI or blank node identifier).
+fn parse_reference<T: Id>(str: &str) -> Option<Reference<T>> {
+	if str.starts_with("_:") {
+		Some(Reference::Blank(BlankId::new(&str[2..])))
+	} else {
+		match Iri::new(str) {
+			Ok(iri) => Some(Reference::Id(T::from_iri(iri))),
+			Err(_) => None
+		}
+	}
+}
+
+/// Parse a node reference, keeping invalid references as `Lenient::Unknown`,
+/// as the expansion algorithm does.
+fn parse_lenient_reference<T: Id>(json: &JsonValue, code: ErrorCode) -> Result<Lenient<Reference<T>>, Error> {
+	match json.as_str() {
+		Some(str) => match parse_reference(str) {
+			Some(r) => Ok(Lenient::Ok(r)),
+			None => Ok(Lenient::Unknown(str.to_string()))
+		},
+		None => Err(code.into())
+	}
+}
+
+/// Iterate over the items of an expanded array.
+///
+/// In expanded form, every property value is an array,
+/// but a single item is also accepted here for convenience.
+fn items(json: &JsonValue) -> impl Iterator<Item = &JsonValue> {
+	let items: &[JsonValue] = match json {
+		JsonValue::Array(items) => items.as_ref(),
+		item => std::slice::from_ref(item)
+	};
+
+	items.iter()
+}
+
+fn parse_objects<T: Id>(json: &JsonValue) -> Result<Vec<Indexed<Object<T>>>, Error> {
+	items(json).map(Indexed::<Object<T>>::try_from).collect()
+}
+
+fn parse_nodes<T: Id>(json: &JsonValue, code: ErrorCode) -> Result<Vec<Indexed<Node<T>>>, Error> {
+	let mut nodes = Vec::new();
+	for item in items(json) {
+		match Indexed::<Object<T>>::try_from(item)?.try_cast::<Node<T>>() {
+			Ok(node) => nodes.push(node),
+			Err(_) => return Err(code.into())
+		}
+	}
+
+	Ok(nodes)
+}
+
+fn parse_value<T: Id>(obj: &json::object::Object) -> Result<Value<T>, Error> {
+	let mut value = None;
+	let mut ty = None;
+	let mut language = None;
+	let mut direction = None;
+
+	for (key, entry) in obj.iter() {
+		match Keyword::try_from(key) {
+			Ok(Keyword::Value) => value = Some(entry),
+			Ok(Keyword::Type) => ty = Some(entry),
+			Ok(Keyword::Language) => language = Some(entry),
+			Ok(Keyword::Direction) => direction = Some(entry),
+			Ok(Keyword::Index) => (),
+			_ => return Err(ErrorCode::InvalidValueObject.into())
+		}
+	}
+
+	let value = value.unwrap();
+
+	if let Some(ty) = ty {
+		if language.is_some() || direction.is_some() {
+			return Err(ErrorCode::InvalidValueObject.into())
+		}
+
+		let ty = ty.as_str().ok_or(ErrorCode::InvalidTypedValue)?;
+		if ty == Keyword::Json.into_str() {
+			return Ok(Value::Json(value.clone().into()))
+		}
+
+		let ty = match Iri::new(ty) {
+			Ok(iri) => T::from_iri(iri),
+			Err(_) => return Err(ErrorCode::InvalidTypedValue.into())
+		};
+
+		return Ok(Value::Literal(parse_literal(value)?, Some(ty)))
+	}
+
+	if language.is_some() || direction.is_some() {
+		let str = value.as_str().ok_or(ErrorCode::InvalidLanguageTaggedValue)?;
+
+		let language = match language {
+			Some(language) => {
+				let language = language.as_str().ok_or(ErrorCode::InvalidLanguageTaggedString)?;
+				match LanguageTagBuf::new(language.as_bytes().to_vec()) {
+					Ok(language) => Some(language),
+					Err(_) => return Err(ErrorCode::InvalidLanguageTaggedString.into())
+				}
+			},
+			None => None
+		};
+
+		let direction = match direction {
+			Some(direction) => match direction.as_str().map(Direction::try_from) {
+				Some(Ok(direction)) => Some(direction),
+				_ => return Err(ErrorCode::InvalidBaseDirection.into())
+			},
+			None => None
+		};
+
+		return Ok(Value::LangString(LangString::new(str.to_string(), language, direction).unwrap()))
+	}
+
+	Ok(Value::Literal(parse_literal(value)?, None))
+}
+
+fn parse_literal(json: &JsonValue) -> Result<Literal, Error> {
+	match json {
+		JsonValue::Boolean(b) => Ok(Literal::Boolean(*b)),
+		JsonValue::Number(n) => Ok(Literal::Number(*n)),
+		JsonValue::Short(_) | JsonValue::String(_) => Ok(Literal::String(json.as_str().unwrap().to_string())),
+		_ => Err(ErrorCode::InvalidValueObjectValue.into())
+	}
+}
+
+fn parse_node<T: Id>(obj: &json::object::Object) -> Result<Node<T>, Error> {
+	let mut node = Node::new();
+
+	for (key, entry) in obj.iter() {
+		match Keyword::try_from(key) {
+			Ok(Keyword::Id) => node.id = Some(parse_lenient_reference(entry, ErrorCode::InvalidIdValue)?),
+			Ok(Keyword::Type) => {
+				for ty in items(entry) {
+					node.types.push(parse_lenient_reference(ty, ErrorCode::InvalidTypeValue)?)
+				}
+			},
+			Ok(Keyword::Graph) => {
+				node.graph = Some(parse_objects(entry)?.into_iter().collect())
+			},
+			Ok(Keyword::Included) => {
+				node.included = Some(parse_nodes(entry, ErrorCode::InvalidIncludedValue)?.into_iter().collect())
+			},
+			Ok(Keyword::Annotation) => {
+				node.annotation = Some(parse_nodes(entry, ErrorCode::InvalidAnnotation)?.into_iter().collect())
+			},
+			Ok(Keyword::Reverse) => {
+				let reverse = match entry {
+					JsonValue::Object(reverse) => reverse,
+					_ => return Err(ErrorCode::InvalidReverseValue.into())
+				};
+
+				for (prop, values) in reverse.iter() {
+					let prop = parse_reference(prop).ok_or(ErrorCode::InvalidReversePropertyMap)?;
+					let nodes = parse_nodes(values, ErrorCode::InvalidReversePropertyValue)?;
+					node.insert_all_reverse(prop, nodes.into_iter())
+				}
+			},
+			Ok(Keyword::Index) => (),
+			Ok(_) => return Err(ErrorCode::InvalidValueObject.into()),
+			Err(_) => {
+				let prop = parse_reference(key).ok_or(ErrorCode::KeyExpansionFailed)?;
+				node.insert_all(prop, parse_objects(entry)?.into_iter())
+			}
+		}
+	}
+
+	Ok(node)
+}
+
+/// Parse an expanded object, with its index.
+impl<'a, T: Id> TryFrom<&'a JsonValue> for Indexed<Object<T>> {
+	type Error = Error;
+
+	fn try_from(json: &'a JsonValue) -> Result<Indexed<Object<T>>, Error> {
+		let obj = match json {
+			JsonValue::Object(obj) => obj,
+			_ => return Err(ErrorCode::InvalidValueObject.into())
+		};
+
+		let index = match obj.get(Keyword::Index.into_str()) {
+			Some(index) => Some(index.as_str().ok_or(ErrorCode::InvalidIndexValue)?.to_string()),
+			None => None
+		};
+
+		let object = if obj.get(Keyword::Value.into_str()).is_some() {
+			Object::Value(parse_value(obj)?)
+		} else if let Some(list) = obj.get(Keyword::List.into_str()) {
+			for (key, _) in obj.iter() {
+				match Keyword::try_from(key) {
+					Ok(Keyword::List) | Ok(Keyword::Index) => (),
+					_ => return Err(ErrorCode::InvalidSetOrListObject.into())
+				}
+			}
+
+			Object::List(parse_objects(list)?.into())
+		} else {
+			parse_node(obj)?.into()
+		};
+
+		Ok(Indexed::new(object, index))
+	}
+}
+
+/// Parse an expanded object.
+///
+/// The `@index` entry of the object, if any, is ignored.
+/// Use `Indexed<Object<T>>::try_from` to preserve it.
+impl<'a, T: Id> TryFrom<&'a JsonValue> for Object<T> {
+	type Error = Error;
+
+	fn try_from(json: &'a JsonValue) -> Result<Object<T>, Error> {
+		Ok(Indexed::<Object<T>>::try_from(json)?.into_inner())
+	}
+}
+
+/// Parse an expanded node object.
+///
+/// The `@index` entry of the node, if any, is ignored.
+impl<'a, T: Id> TryFrom<&'a JsonValue> for Node<T> {
+	type Error = Error;
+
+	fn try_from(json: &'a JsonValue) -> Result<Node<T>, Error> {
+		Node::try_from(Object::try_from(json)?).map_err(|_| ErrorCode::InvalidValueObject.into())
+	}
+}
+
+/// Parse the objects of an expanded document.
+pub(crate) fn parse_document<T: Id>(json: &JsonValue) -> Result<HashSet<Indexed<Object<T>>>, Error> {
+	Ok(parse_objects(json)?.into_iter().collect())
+}
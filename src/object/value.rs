@@ -1,4 +1,7 @@
 use std::hash::{Hash, Hasher};
+use std::ops::Deref;
+use std::sync::Arc;
+use once_cell::sync::OnceCell;
 use iref::IriBuf;
 use langtag::LanguageTag;
 use json::JsonValue;
@@ -79,6 +82,126 @@ impl Literal {
 			_ => None
 		}
 	}
+
+	/// Returns the literal as a signed integer, if it is an integral number in the `i64` range.
+	///
+	/// Numbers with a fractional part (such as `1.5`) are not truncated.
+	pub fn as_i64(&self) -> Option<i64> {
+		match self {
+			Literal::Number(n) => util::json_number_as_i64(n),
+			_ => None
+		}
+	}
+
+	/// Returns the literal as an unsigned integer, if it is a non-negative integral number
+	/// in the `u64` range.
+	///
+	/// Numbers with a fractional part (such as `1.5`) are not truncated.
+	pub fn as_u64(&self) -> Option<u64> {
+		match self {
+			Literal::Number(n) => util::json_number_as_u64(n),
+			_ => None
+		}
+	}
+
+	/// Returns the literal as a floating point number, if it is a number.
+	pub fn as_f64(&self) -> Option<f64> {
+		match self {
+			Literal::Number(n) => Some((*n).into()),
+			_ => None
+		}
+	}
+}
+
+/// JSON literal value.
+///
+/// The JSON value is shared, so cloning a JSON literal does not copy the underlying JSON tree.
+/// Its hash is computed once and cached.
+#[derive(Clone)]
+pub struct JsonLiteral {
+	value: Arc<JsonValue>,
+	hash: Arc<OnceCell<u64>>
+}
+
+impl JsonLiteral {
+	/// Creates a new JSON literal.
+	pub fn new(value: JsonValue) -> JsonLiteral {
+		JsonLiteral {
+			value: Arc::new(value),
+			hash: Arc::new(OnceCell::new())
+		}
+	}
+
+	/// Returns the JSON value.
+	pub fn as_json_value(&self) -> &JsonValue {
+		&self.value
+	}
+
+	/// Consumes the literal and returns the JSON value.
+	///
+	/// The JSON value is copied only if the literal is shared.
+	pub fn into_json_value(self) -> JsonValue {
+		Arc::try_unwrap(self.value).unwrap_or_else(|value| (*value).clone())
+	}
+
+	fn hash_value(&self) -> u64 {
+		*self.hash.get_or_init(|| {
+			let mut h = std::collections::hash_map::DefaultHasher::new();
+			util::hash_json(&self.value, &mut h);
+			h.finish()
+		})
+	}
+}
+
+impl From<JsonValue> for JsonLiteral {
+	fn from(value: JsonValue) -> JsonLiteral {
+		JsonLiteral::new(value)
+	}
+}
+
+impl Deref for JsonLiteral {
+	type Target = JsonValue;
+
+	fn deref(&self) -> &JsonValue {
+		&self.value
+	}
+}
+
+impl PartialEq for JsonLiteral {
+	fn eq(&self, other: &JsonLiteral) -> bool {
+		Arc::ptr_eq(&self.value, &other.value) || (self.hash_value() == other.hash_value() && self.value == other.value)
+	}
+}
+
+impl Eq for JsonLiteral {}
+
+impl Hash for JsonLiteral {
+	fn hash<H: Hasher>(&self, h: &mut H) {
+		h.write_u64(self.hash_value())
+	}
+}
+
+/// XSD namespace.
+const XSD: &str = "http://www.w3.org/2001/XMLSchema#";
+
+/// XSD integer types, whose lexical form can be parsed as an integer.
+const XSD_INTEGERS: &[&str] = &[
+	"integer", "long", "int", "short", "byte",
+	"nonNegativeInteger", "positiveInteger", "nonPositiveInteger", "negativeInteger",
+	"unsignedLong", "unsignedInt", "unsignedShort", "unsignedByte"
+];
+
+/// XSD floating point and decimal types, whose lexical form can be parsed as a `f64`.
+const XSD_FLOATS: &[&str] = &[
+	"double", "float", "decimal"
+];
+
+/// Returns the local name of the given XSD type, if it is in the XSD namespace.
+fn xsd_type<T: Id>(ty: &Option<T>) -> Option<&str> {
+	match ty {
+		Some(ty) => ty.as_iri().into_str().strip_prefix(XSD),
+		None => None
+	}
 }
 
 /// Value object.
@@ -93,10 +216,15 @@ pub enum Value<T: Id = IriBuf> {
 	LangString(LangString),
 
 	/// A JSON literal value.
-	Json(JsonValue)
+	Json(JsonLiteral)
 }
 
 impl<T: Id> Value<T> {
+	/// Creates a new literal value with the given type.
+	pub fn typed(lit: Literal, ty: T) -> Value<T> {
+		Value::Literal(lit, Some(ty))
+	}
+
 	pub fn as_str(&self) -> Option<&str> {
 		match self {
 			Value::Literal(lit, _) => lit.as_str(),
@@ -119,6 +247,112 @@ impl<T: Id> Value<T> {
 		}
 	}
 
+	/// Returns the value as a signed integer.
+	///
+	/// This is either an integral number, or a string literal typed
+	/// with a XSD integer type (such as `xsd:integer` or `xsd:long`) with a valid lexical form.
+	pub fn as_i64(&self) -> Option<i64> {
+		match self {
+			Value::Literal(Literal::String(s), ty) if xsd_type(ty).map_or(false, |ty| XSD_INTEGERS.contains(&ty)) => s.trim().parse().ok(),
+			Value::Literal(lit, _) => lit.as_i64(),
+			_ => None
+		}
+	}
+
+	/// Returns the value as an unsigned integer.
+	///
+	/// This is either a non-negative integral number, or a string literal typed
+	/// with a XSD integer type with a valid non-negative lexical form.
+	pub fn as_u64(&self) -> Option<u64> {
+		match self {
+			Value::Literal(Literal::String(s), ty) if xsd_type(ty).map_or(false, |ty| XSD_INTEGERS.contains(&ty)) => s.trim().trim_start_matches('+').parse().ok(),
+			Value::Literal(lit, _) => lit.as_u64(),
+			_ => None
+		}
+	}
+
+	/// Returns the value as a floating point number.
+	///
+	/// This is either a number, or a string literal typed
+	/// with `xsd:double`, `xsd:float`, `xsd:decimal` or a XSD integer type with a valid lexical form.
+	pub fn as_f64(&self) -> Option<f64> {
+		match self {
+			Value::Literal(Literal::String(s), ty) => match xsd_type(ty) {
+				Some(ty) if XSD_FLOATS.contains(&ty) || XSD_INTEGERS.contains(&ty) => match s.trim() {
+					"INF" | "+INF" => Some(f64::INFINITY),
+					"-INF" => Some(f64::NEG_INFINITY),
+					"NaN" => Some(f64::NAN),
+					s => s.parse().ok()
+				},
+				_ => None
+			},
+			Value::Literal(lit, _) => lit.as_f64(),
+			_ => None
+		}
+	}
+
+	/// Converts a string literal typed with `xsd:boolean`, `xsd:integer` or `xsd:double`
+	/// into a native boolean or number, if its lexical form is valid.
+	///
+	/// This mirrors the `useNativeTypes` flag of the RDF deserialization algorithm.
+	/// Other values, and doubles that are not finite, are returned unchanged.
+	pub fn into_native(self) -> Value<T> {
+		match self {
+			Value::Literal(Literal::String(s), ty) => {
+				let native = match xsd_type(&ty) {
+					Some("boolean") => match s.as_str() {
+						"true" => Some(Literal::Boolean(true)),
+						"false" => Some(Literal::Boolean(false)),
+						_ => None
+					},
+					Some("integer") => s.parse::<i64>().ok().map(|i| Literal::Number(i.into())),
+					Some("double") => s.parse::<f64>().ok().filter(|d| d.is_finite()).map(|d| Literal::Number(d.into())),
+					_ => None
+				};
+
+				match native {
+					Some(lit) => Value::Literal(lit, None),
+					None => Value::Literal(Literal::String(s), ty)
+				}
+			},
+			value => value
+		}
+	}
+
+	/// If the value is a language tagged string, returns the string with its associated language, if any.
+	pub fn as_str_with_language(&self) -> Option<(&str, Option<LanguageTag>)> {
+		match self {
+			Value::LangString(str) => Some((str.as_str(), str.language())),
+			_ => None
+		}
+	}
+
+	/// Returns the value as a date and time, if it is a string literal typed with
+	/// `xsd:dateTime` or `xsd:dateTimeStamp` with a valid lexical form including a timezone.
+	#[cfg(feature="chrono")]
+	pub fn as_datetime(&self) -> Option<chrono::DateTime<chrono::FixedOffset>> {
+		match self {
+			Value::Literal(Literal::String(s), ty) => match xsd_type(ty) {
+				Some("dateTime") | Some("dateTimeStamp") => chrono::DateTime::parse_from_rfc3339(s.trim()).ok(),
+				_ => None
+			},
+			_ => None
+		}
+	}
+
+	/// Returns the value as a date, if it is a string literal typed with
+	/// `xsd:date` with a valid lexical form (without timezone).
+	#[cfg(feature="chrono")]
+	pub fn as_date(&self) -> Option<chrono::NaiveDate> {
+		match self {
+			Value::Literal(Literal::String(s), ty) => match xsd_type(ty) {
+				Some("date") => chrono::NaiveDate::parse_from_str(s.trim(), "%Y-%m-%d").ok(),
+				_ => None
+			},
+			_ => None
+		}
+	}
+
 	/// Return the type of the value if any.
 	///
 	/// This will return `Some(Type::Json)` for JSON literal values.
@@ -151,6 +385,48 @@ impl<T: Id> Value<T> {
 	}
 }
 
+impl<T: Id> From<bool> for Value<T> {
+	fn from(b: bool) -> Value<T> {
+		Value::Literal(Literal::Boolean(b), None)
+	}
+}
+
+impl<T: Id> From<i64> for Value<T> {
+	fn from(n: i64) -> Value<T> {
+		Value::Literal(Literal::Number(n.into()), None)
+	}
+}
+
+impl<T: Id> From<u64> for Value<T> {
+	fn from(n: u64) -> Value<T> {
+		Value::Literal(Literal::Number(n.into()), None)
+	}
+}
+
+impl<T: Id> From<f64> for Value<T> {
+	fn from(n: f64) -> Value<T> {
+		Value::Literal(Literal::Number(n.into()), None)
+	}
+}
+
+impl<'a, T: Id> From<&'a str> for Value<T> {
+	fn from(s: &'a str) -> Value<T> {
+		Value::Literal(Literal::String(s.to_string()), None)
+	}
+}
+
+impl<T: Id> From<String> for Value<T> {
+	fn from(s: String) -> Value<T> {
+		Value::Literal(Literal::String(s), None)
+	}
+}
+
+impl<T: Id> From<LangString> for Value<T> {
+	fn from(s: LangString) -> Value<T> {
+		Value::LangString(s)
+	}
+}
+
 impl<T: Id> object::Any<T> for Value<T> {
 	fn as_ref(&self) -> object::Ref<T> {
 		object::Ref::Value(self)
@@ -165,7 +441,7 @@ impl<T: Id> Hash for Value<T> {
 				ty.hash(h);
 			},
 			Value::LangString(str) => str.hash(h),
-			Value::Json(json) => util::hash_json(json, h)
+			Value::Json(json) => json.hash(h)
 		}
 	}
 }
@@ -207,7 +483,7 @@ impl<T: Id> util::AsJson for Value<T> {
 				}
 			},
 			Value::Json(json) => {
-				obj.insert(Keyword::Value.into(), json.clone());
+				obj.insert(Keyword::Value.into(), json.as_json_value().clone());
 				obj.insert(Keyword::Type.into(), Keyword::Json.as_json())
 			}
 		}
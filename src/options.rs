@@ -0,0 +1,325 @@
+use std::fmt;
+use std::time::Instant;
+use iref::IriBuf;
+use langtag::LanguageTagBuf;
+use crate::{
+	ProcessingMode,
+	SharedIssueHandler,
+	CancellationToken,
+	Limits,
+	Direction,
+	LanguageNormalization,
+	context::{
+		ProcessingOptions,
+		ReservedTerms
+	},
+	expansion,
+	compaction
+};
+
+/// Invalid combination of options.
+///
+/// Returned by the `build` method of the option builders.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum OptionsError {
+	/// JSON-LD-star (`rdf_star`) requires the `json-ld-1.1` processing mode.
+	RdfStarRequiresJsonLd1_1,
+
+	/// Disabling the context propagation requires the `json-ld-1.1` processing mode.
+	PropagationRequiresJsonLd1_1,
+
+	/// The `strict` flag is ignored when an issue handler is set.
+	StrictWithIssueHandler,
+
+	/// The compaction concurrency must be at least 1.
+	ZeroConcurrency
+}
+
+impl OptionsError {
+	/// Get the error message corresponding to the error.
+	pub fn as_str(&self) -> &str {
+		match self {
+			OptionsError::RdfStarRequiresJsonLd1_1 => "JSON-LD-star requires the json-ld-1.1 processing mode",
+			OptionsError::PropagationRequiresJsonLd1_1 => "disabling the context propagation requires the json-ld-1.1 processing mode",
+			OptionsError::StrictWithIssueHandler => "the strict flag cannot be used along with an issue handler",
+			OptionsError::ZeroConcurrency => "compaction concurrency must be at least 1"
+		}
+	}
+}
+
+impl fmt::Display for OptionsError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{}", self.as_str())
+	}
+}
+
+impl std::error::Error for OptionsError {}
+
+/// Options of all the JSON-LD algorithms.
+///
+/// Gathers the expansion, compaction and context processing options in a single type,
+/// from which the options of each algorithm are derived.
+/// Use [`Options::builder`] to build a validated set of options.
+#[derive(Clone)]
+pub struct Options {
+	expansion: expansion::Options,
+	compaction: compaction::Options
+}
+
+impl Options {
+	/// Create a new options builder, starting from the default options.
+	pub fn builder() -> OptionsBuilder {
+		OptionsBuilder::new()
+	}
+
+	/// Expansion options.
+	pub fn expansion(&self) -> &expansion::Options {
+		&self.expansion
+	}
+
+	/// Compaction options.
+	pub fn compaction(&self) -> &compaction::Options {
+		&self.compaction
+	}
+
+	/// Context processing options.
+	pub fn processing(&self) -> ProcessingOptions {
+		(&self.expansion).into()
+	}
+}
+
+impl Default for Options {
+	fn default() -> Options {
+		Options {
+			expansion: expansion::Options::default(),
+			compaction: compaction::Options::default()
+		}
+	}
+}
+
+impl From<Options> for expansion::Options {
+	fn from(options: Options) -> expansion::Options {
+		options.expansion
+	}
+}
+
+impl From<Options> for compaction::Options {
+	fn from(options: Options) -> compaction::Options {
+		options.compaction
+	}
+}
+
+impl From<Options> for ProcessingOptions {
+	fn from(options: Options) -> ProcessingOptions {
+		options.processing()
+	}
+}
+
+/// Builder of [`Options`].
+///
+/// Options shared by several algorithms (processing mode, base IRI, ordering)
+/// are set once for all of them.
+pub struct OptionsBuilder {
+	expansion: expansion::OptionsBuilder,
+	compaction: compaction::OptionsBuilder
+}
+
+impl OptionsBuilder {
+	/// Create a new builder, starting from the default options.
+	pub fn new() -> OptionsBuilder {
+		OptionsBuilder {
+			expansion: expansion::OptionsBuilder::new(),
+			compaction: compaction::OptionsBuilder::new()
+		}
+	}
+
+	/// Set the processing mode.
+	pub fn processing_mode(mut self, mode: ProcessingMode) -> Self {
+		self.expansion = self.expansion.processing_mode(mode);
+		self.compaction = self.compaction.processing_mode(mode);
+		self
+	}
+
+	/// Set the base IRI overriding the document base URL.
+	pub fn base(mut self, base: Option<IriBuf>) -> Self {
+		self.expansion = self.expansion.base(base.clone());
+		self.compaction = self.compaction.base(base);
+		self
+	}
+
+	/// Process entries in lexicographical order.
+	pub fn ordered(mut self, ordered: bool) -> Self {
+		self.expansion = self.expansion.ordered(ordered);
+		self.compaction = self.compaction.ordered(ordered);
+		self
+	}
+
+	/// Abort the expansion when a value fails to expand.
+	pub fn strict(mut self, strict: bool) -> Self {
+		self.expansion = self.expansion.strict(strict);
+		self.compaction = self.compaction.strict(strict);
+		self
+	}
+
+	/// Expand `@annotation` entries (JSON-LD-star).
+	pub fn rdf_star(mut self, rdf_star: bool) -> Self {
+		self.expansion = self.expansion.rdf_star(rdf_star);
+		self.compaction = self.compaction.rdf_star(rdf_star);
+		self
+	}
+
+	/// Set the handler of recoverable issues.
+	pub fn issue_handler(mut self, handler: Option<SharedIssueHandler>) -> Self {
+		self.expansion = self.expansion.issue_handler(handler.clone());
+		self.compaction = self.compaction.issue_handler(handler);
+		self
+	}
+
+	/// Compact IRIs relative to the base IRI.
+	pub fn compact_to_relative(mut self, value: bool) -> Self {
+		self.compaction = self.compaction.compact_to_relative(value);
+		self
+	}
+
+	/// Compact single-item arrays into their item.
+	pub fn compact_arrays(mut self, value: bool) -> Self {
+		self.compaction = self.compaction.compact_arrays(value);
+		self
+	}
+
+	/// Allow explicit `@reverse` entries in compacted documents.
+	pub fn allow_reverse_block(mut self, value: bool) -> Self {
+		self.compaction = self.compaction.allow_reverse_block(value);
+		self
+	}
+
+	/// Always compact `@type` values into arrays.
+	pub fn type_as_array(mut self, value: bool) -> Self {
+		self.compaction = self.compaction.type_as_array(value);
+		self
+	}
+
+	/// Normalize the keys of language maps to lowercase.
+	pub fn lowercase_language_keys(mut self, value: bool) -> Self {
+		self.compaction = self.compaction.lowercase_language_keys(value);
+		self
+	}
+
+	/// Compact the items of each collection concurrently.
+	pub fn concurrency(mut self, concurrency: Option<usize>) -> Self {
+		self.compaction = self.compaction.concurrency(concurrency);
+		self
+	}
+
+	/// Set the instant after which the processing is aborted.
+	pub fn deadline(mut self, deadline: Option<Instant>) -> Self {
+		self.expansion = self.expansion.deadline(deadline);
+		self.compaction = self.compaction.deadline(deadline);
+		self
+	}
+
+	/// Set the token used to cancel the processing.
+	pub fn cancellation(mut self, token: Option<CancellationToken>) -> Self {
+		self.expansion = self.expansion.cancellation(token.clone());
+		self.compaction = self.compaction.cancellation(token);
+		self
+	}
+
+	/// Set the resource limits enforced during the expansion.
+	pub fn limits(mut self, limits: Limits) -> Self {
+		self.expansion = self.expansion.limits(limits.clone());
+		self.compaction = self.compaction.limits(limits);
+		self
+	}
+
+	/// Set the maximum nesting depth of the input document.
+	pub fn max_nesting_depth(mut self, max: Option<usize>) -> Self {
+		self.expansion = self.expansion.max_nesting_depth(max);
+		self.compaction = self.compaction.max_nesting_depth(max);
+		self
+	}
+
+	/// Let type-scoped contexts propagate to nested nodes, as in JSON-LD 1.0.
+	pub fn ignore_propagation(mut self, value: bool) -> Self {
+		self.expansion = self.expansion.ignore_propagation(value);
+		self.compaction = self.compaction.ignore_propagation(value);
+		self
+	}
+
+	/// Set the handling of context terms having the form of a keyword.
+	pub fn reserved_terms(mut self, policy: ReservedTerms) -> Self {
+		self.expansion = self.expansion.reserved_terms(policy);
+		self.compaction = self.compaction.reserved_terms(policy);
+		self
+	}
+
+	/// Reject node identifiers and types that are neither IRIs nor blank node identifiers.
+	pub fn reject_unresolved(mut self, value: bool) -> Self {
+		self.expansion = self.expansion.reject_unresolved(value);
+		self
+	}
+
+	/// Convert typed string values into native JSON booleans and numbers during expansion.
+	pub fn use_native_types(mut self, value: bool) -> Self {
+		self.expansion = self.expansion.use_native_types(value);
+		self
+	}
+
+	/// Normalize the input document strings into Unicode Normalization Form C before expansion.
+	#[cfg(feature="unicode-normalization")]
+	pub fn nfc(mut self, value: bool) -> Self {
+		self.expansion = self.expansion.nfc(value);
+		self
+	}
+
+	/// Set the handling of malformed node identifiers and types during expansion.
+	pub fn iri_validation(mut self, validation: expansion::IriValidation) -> Self {
+		self.expansion = self.expansion.iri_validation(validation);
+		self
+	}
+
+	/// Always put the top-level objects of compacted documents in a `@graph` array.
+	pub fn keep_graph_array(mut self, value: bool) -> Self {
+		self.compaction = self.compaction.keep_graph_array(value);
+		self
+	}
+
+	/// Always compact the values of the given property into an array.
+	pub fn keep_array<P: Into<String>>(mut self, property: P) -> Self {
+		self.compaction = self.compaction.keep_array(property);
+		self
+	}
+
+	/// Set the default language of the initial active context during expansion.
+	pub fn default_language(mut self, language: Option<LanguageTagBuf>) -> Self {
+		self.expansion = self.expansion.default_language(language);
+		self
+	}
+
+	/// Set the default base direction of the initial active context during expansion.
+	pub fn default_base_direction(mut self, direction: Option<Direction>) -> Self {
+		self.expansion = self.expansion.default_base_direction(direction);
+		self
+	}
+
+	/// Set the normalization of language tags, in expanded values and compacted documents.
+	pub fn language_normalization(mut self, normalization: LanguageNormalization) -> Self {
+		self.expansion = self.expansion.language_normalization(normalization);
+		self.compaction = self.compaction.language_normalization(normalization);
+		self
+	}
+
+	/// Validate and build the options.
+	pub fn build(self) -> Result<Options, OptionsError> {
+		Ok(Options {
+			expansion: self.expansion.build()?,
+			compaction: self.compaction.build()?
+		})
+	}
+}
+
+impl Default for OptionsBuilder {
+	fn default() -> OptionsBuilder {
+		OptionsBuilder::new()
+	}
+}
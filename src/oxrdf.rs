@@ -0,0 +1,453 @@
+//! Conversions from and to [`oxrdf`](https://crates.io/crates/oxrdf) terms.
+//!
+//! This module is enabled by the `oxrdf` feature.
+//! Node references are converted into named or blank nodes,
+//! and value objects into literals following the
+//! [Object to RDF Conversion](https://www.w3.org/TR/json-ld11-api/#object-to-rdf-conversion)
+//! algorithm (without the `rdfDirection` option).
+//!
+//! The lexical representation of literals of custom datatypes
+//! can be controlled by registering [`DatatypeHandler`]s in a [`Datatypes`] registry.
+//!
+//! Whole flattened documents are converted into RDF quads with [`to_rdf`].
+
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::sync::Arc;
+use iref::{Iri, IriBuf};
+use crate::{
+	Id,
+	BlankId,
+	BlankIdGenerator,
+	Reference,
+	Lenient,
+	LangString,
+	ExpandedDocument,
+	Object,
+	Predicate,
+	QuadObject,
+	object::{
+		Value,
+		Literal
+	}
+};
+
+const XSD_STRING: &str = "http://www.w3.org/2001/XMLSchema#string";
+const XSD_BOOLEAN: &str = "http://www.w3.org/2001/XMLSchema#boolean";
+const XSD_INTEGER: &str = "http://www.w3.org/2001/XMLSchema#integer";
+const XSD_DOUBLE: &str = "http://www.w3.org/2001/XMLSchema#double";
+const XSD_DECIMAL: &str = "http://www.w3.org/2001/XMLSchema#decimal";
+const RDF_JSON: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#JSON";
+const RDF_TYPE: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#type";
+const RDF_FIRST: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#first";
+const RDF_REST: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#rest";
+const RDF_NIL: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#nil";
+
+impl<T: Id> From<Reference<T>> for ::oxrdf::NamedOrBlankNode {
+	fn from(r: Reference<T>) -> ::oxrdf::NamedOrBlankNode {
+		match r {
+			Reference::Id(id) => ::oxrdf::NamedNode::new_unchecked(id.as_iri().as_str()).into(),
+			Reference::Blank(id) => ::oxrdf::BlankNode::new_unchecked(id.name()).into()
+		}
+	}
+}
+
+/// Converts a node reference into a named node.
+///
+/// Fails and returns the reference if it is a blank node identifier.
+impl<T: Id> TryFrom<Reference<T>> for ::oxrdf::NamedNode {
+	type Error = Reference<T>;
+
+	fn try_from(r: Reference<T>) -> Result<::oxrdf::NamedNode, Reference<T>> {
+		match r {
+			Reference::Id(id) => Ok(::oxrdf::NamedNode::new_unchecked(id.as_iri().as_str())),
+			r => Err(r)
+		}
+	}
+}
+
+impl<T: Id> From<::oxrdf::NamedNode> for Reference<T> {
+	fn from(node: ::oxrdf::NamedNode) -> Reference<T> {
+		// named nodes always contain a valid IRI.
+		Reference::Id(T::from_iri(Iri::new(node.as_str()).unwrap()))
+	}
+}
+
+impl<T: Id> From<::oxrdf::BlankNode> for Reference<T> {
+	fn from(node: ::oxrdf::BlankNode) -> Reference<T> {
+		Reference::Blank(BlankId::new(node.as_str()))
+	}
+}
+
+impl<T: Id> From<::oxrdf::NamedOrBlankNode> for Reference<T> {
+	fn from(node: ::oxrdf::NamedOrBlankNode) -> Reference<T> {
+		match node {
+			::oxrdf::NamedOrBlankNode::NamedNode(node) => node.into(),
+			::oxrdf::NamedOrBlankNode::BlankNode(node) => node.into()
+		}
+	}
+}
+
+/// Canonical lexical form of a `xsd:double`.
+fn canonical_double(d: f64) -> String {
+	let s = format!("{:E}", d);
+	match s.find('E') {
+		Some(i) if !s[..i].contains('.') => format!("{}.0{}", &s[..i], &s[i..]),
+		_ => s
+	}
+}
+
+/// Mapping of JSON numbers to RDF literals.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum NumericPolicy {
+	/// Numbers with a fractional part, or whose magnitude is at least 10^21,
+	/// are converted into `xsd:double` literals, other numbers into `xsd:integer` literals.
+	///
+	/// This is the conversion defined by the specification.
+	Spec,
+
+	/// Every number is converted into an `xsd:decimal` literal.
+	Decimal,
+
+	/// Integral numbers that can be represented as 64-bit signed integers without loss,
+	/// whatever their magnitude, are converted into `xsd:integer` literals,
+	/// other numbers (with a fractional part or out of the `i64` range)
+	/// into `xsd:double` literals.
+	IntegerWhenLossless
+}
+
+impl Default for NumericPolicy {
+	fn default() -> NumericPolicy {
+		NumericPolicy::Spec
+	}
+}
+
+/// Canonical lexical form of a `xsd:decimal`.
+fn canonical_decimal(d: f64) -> String {
+	let s = format!("{}", d);
+	if s.contains('.') {
+		s
+	} else {
+		format!("{}.0", s)
+	}
+}
+
+/// Canonical lexical form of a `xsd:integer`, given a number `n` whose value `f` is integral.
+///
+/// The lexical form is computed from the decimal representation of `n` when it is integral,
+/// so that integers beyond the precision of `f64` are not rounded.
+fn canonical_integer(n: &json::number::Number, f: f64) -> String {
+	let (positive, mut mantissa, mut exponent) = n.as_parts();
+	while exponent < 0 && mantissa % 10 == 0 && mantissa != 0 {
+		mantissa /= 10;
+		exponent += 1
+	}
+
+	if mantissa == 0 {
+		"0".to_string()
+	} else if exponent >= 0 {
+		let sign = if positive { "" } else { "-" };
+		format!("{}{}{}", sign, mantissa, "0".repeat(exponent as usize))
+	} else {
+		format!("{:.0}", f)
+	}
+}
+
+/// Converts a number into a literal following the given policy.
+///
+/// An explicit type `ty` overrides the type chosen by the policy,
+/// but not the lexical form, unless it is `xsd:double`.
+fn number_literal<T: Id>(n: json::number::Number, ty: Option<T>, policy: NumericPolicy) -> ::oxrdf::Literal {
+	let ty = ty.map(|ty| ty.as_iri().as_str().to_string());
+	let is_double = ty.as_ref().map(|ty| ty == XSD_DOUBLE).unwrap_or(false);
+	let f: f64 = n.into();
+	let (lexical, default_ty) = if is_double {
+		(canonical_double(f), XSD_DOUBLE)
+	} else {
+		match (policy, crate::util::json_number_as_i64(&n)) {
+			(NumericPolicy::Spec, _) if f.fract() == 0.0 && f.abs() < 1e21 => (canonical_integer(&n, f), XSD_INTEGER),
+			(NumericPolicy::IntegerWhenLossless, Some(i)) => (i.to_string(), XSD_INTEGER),
+			(NumericPolicy::Decimal, _) if f.is_finite() => (canonical_decimal(f), XSD_DECIMAL),
+			_ => (canonical_double(f), XSD_DOUBLE)
+		}
+	};
+
+	let ty = ty.unwrap_or_else(|| default_ty.to_string());
+	::oxrdf::Literal::new_typed_literal(lexical, ::oxrdf::NamedNode::new_unchecked(ty))
+}
+
+/// Converts a value object into a literal.
+///
+/// Numbers are converted following [`NumericPolicy::Spec`].
+/// Fails and returns the value if it is `null`.
+impl<T: Id> TryFrom<Value<T>> for ::oxrdf::Literal {
+	type Error = Value<T>;
+
+	fn try_from(value: Value<T>) -> Result<::oxrdf::Literal, Value<T>> {
+		match value {
+			Value::Literal(Literal::Null, ty) => Err(Value::Literal(Literal::Null, ty)),
+			Value::Literal(Literal::Boolean(b), ty) => {
+				let ty = ty.map(|ty| ty.as_iri().as_str().to_string()).unwrap_or_else(|| XSD_BOOLEAN.to_string());
+				Ok(::oxrdf::Literal::new_typed_literal(if b { "true" } else { "false" }, ::oxrdf::NamedNode::new_unchecked(ty)))
+			},
+			Value::Literal(Literal::Number(n), ty) => Ok(number_literal(n, ty, NumericPolicy::Spec)),
+			Value::Literal(Literal::String(s), Some(ty)) => {
+				Ok(::oxrdf::Literal::new_typed_literal(s, ::oxrdf::NamedNode::new_unchecked(ty.as_iri().as_str())))
+			},
+			Value::Literal(Literal::String(s), None) => {
+				Ok(::oxrdf::Literal::new_simple_literal(s))
+			},
+			Value::LangString(s) => match s.language() {
+				Some(language) => {
+					let language = language.as_str().to_lowercase();
+					Ok(::oxrdf::Literal::new_language_tagged_literal_unchecked(s.as_str(), language))
+				},
+				None => Ok(::oxrdf::Literal::new_simple_literal(s.as_str()))
+			},
+			Value::Json(json) => {
+				let lexical = crate::util::json_ld_canonical(&json).dump();
+				Ok(::oxrdf::Literal::new_typed_literal(lexical, ::oxrdf::NamedNode::new_unchecked(RDF_JSON)))
+			}
+		}
+	}
+}
+
+/// Converts a literal into a value object.
+///
+/// Literals are never converted into native JSON types (`useNativeTypes` is `false`).
+impl<T: Id> From<::oxrdf::Literal> for Value<T> {
+	fn from(lit: ::oxrdf::Literal) -> Value<T> {
+		if let Some(language) = lit.language() {
+			if let Ok(s) = LangString::parse(lit.value().to_string(), Some(language), None) {
+				return Value::LangString(s)
+			}
+		}
+
+		let datatype = lit.datatype().as_str();
+		if datatype == XSD_STRING {
+			Value::Literal(Literal::String(lit.value().to_string()), None)
+		} else if datatype == RDF_JSON {
+			match json::parse(lit.value()) {
+				Ok(json) => Value::Json(json.into()),
+				Err(_) => Value::Literal(Literal::String(lit.value().to_string()), Some(T::from_iri(Iri::new(datatype).unwrap())))
+			}
+		} else {
+			// literal datatypes are always valid IRIs.
+			Value::Literal(Literal::String(lit.value().to_string()), Some(T::from_iri(Iri::new(datatype).unwrap())))
+		}
+	}
+}
+
+/// Handler of a custom literal datatype.
+///
+/// Controls the lexical representation of the literals of a datatype
+/// when converting value objects into RDF literals, and back.
+pub trait DatatypeHandler<T: Id>: Send + Sync {
+	/// Returns the lexical form of the given literal value with type `ty`.
+	///
+	/// Returns `None` to use the default conversion.
+	fn to_lexical(&self, literal: &Literal, ty: &T) -> Option<String>;
+
+	/// Returns the literal value of the given lexical form with type `ty`.
+	///
+	/// Returns `None` to keep the lexical form as a string literal.
+	fn from_lexical(&self, lexical: &str, ty: &T) -> Option<Literal>;
+}
+
+/// Registry of custom datatype handlers, indexed by datatype IRI.
+///
+/// Values whose datatype has no registered handler
+/// are converted using the default conversions,
+/// numbers following the registry [`NumericPolicy`].
+pub struct Datatypes<T: Id> {
+	handlers: HashMap<IriBuf, Arc<dyn DatatypeHandler<T>>>,
+	numeric_policy: NumericPolicy
+}
+
+impl<T: Id> Datatypes<T> {
+	/// Create a new empty registry.
+	pub fn new() -> Datatypes<T> {
+		Datatypes {
+			handlers: HashMap::new(),
+			numeric_policy: NumericPolicy::Spec
+		}
+	}
+
+	/// Mapping of JSON numbers to RDF literals.
+	pub fn numeric_policy(&self) -> NumericPolicy {
+		self.numeric_policy
+	}
+
+	/// Set the mapping of JSON numbers to RDF literals.
+	pub fn with_numeric_policy(mut self, policy: NumericPolicy) -> Self {
+		self.numeric_policy = policy;
+		self
+	}
+
+	/// Register the handler of the given datatype, replacing any previous one.
+	pub fn insert<H: 'static + DatatypeHandler<T>>(&mut self, datatype: IriBuf, handler: H) {
+		self.handlers.insert(datatype, Arc::new(handler));
+	}
+
+	/// Register the handler of the given datatype, replacing any previous one.
+	pub fn with<H: 'static + DatatypeHandler<T>>(mut self, datatype: IriBuf, handler: H) -> Self {
+		self.insert(datatype, handler);
+		self
+	}
+
+	/// Get the handler of the given datatype, if any.
+	pub fn get(&self, datatype: Iri) -> Option<&dyn DatatypeHandler<T>> {
+		self.handlers.get(&IriBuf::from(datatype)).map(|handler| handler.as_ref())
+	}
+
+	/// Converts a value object into a literal, using the registered handlers.
+	///
+	/// Fails and returns the value if it is `null`.
+	pub fn to_literal(&self, value: Value<T>) -> Result<::oxrdf::Literal, Value<T>> {
+		if let Value::Literal(lit, Some(ty)) = &value {
+			if let Some(handler) = self.get(ty.as_iri()) {
+				if let Some(lexical) = handler.to_lexical(lit, ty) {
+					return Ok(::oxrdf::Literal::new_typed_literal(lexical, ::oxrdf::NamedNode::new_unchecked(ty.as_iri().as_str())))
+				}
+			}
+		}
+
+		match value {
+			Value::Literal(Literal::Number(n), ty) => Ok(number_literal(n, ty, self.numeric_policy)),
+			value => ::oxrdf::Literal::try_from(value)
+		}
+	}
+
+	/// Converts a literal into a value object, using the registered handlers.
+	pub fn from_literal(&self, lit: ::oxrdf::Literal) -> Value<T> {
+		if lit.language().is_none() {
+			// literal datatypes are always valid IRIs.
+			let datatype = Iri::new(lit.datatype().as_str()).unwrap();
+			if let Some(handler) = self.get(datatype) {
+				let ty = T::from_iri(datatype);
+				if let Some(value) = handler.from_lexical(lit.value(), &ty) {
+					return Value::Literal(value, Some(ty))
+				}
+			}
+		}
+
+		lit.into()
+	}
+}
+
+impl<T: Id> Default for Datatypes<T> {
+	fn default() -> Datatypes<T> {
+		Datatypes::new()
+	}
+}
+
+impl<T: Id> Clone for Datatypes<T> {
+	fn clone(&self) -> Datatypes<T> {
+		Datatypes {
+			handlers: self.handlers.clone(),
+			numeric_policy: self.numeric_policy
+		}
+	}
+}
+
+fn lenient_node<T: Id>(r: Option<&Lenient<Reference<T>>>) -> Option<::oxrdf::NamedOrBlankNode> {
+	match r {
+		Some(Lenient::Ok(r)) => Some(r.clone().into()),
+		_ => None
+	}
+}
+
+/// Converts an object into an RDF term, adding the quads describing it (for lists) to `quads`.
+///
+/// Returns `None` if the object cannot be represented in RDF.
+fn object_to_rdf<T: Id, G: BlankIdGenerator>(object: &Object<T>, graph: &::oxrdf::GraphName, datatypes: &Datatypes<T>, generator: &mut G, quads: &mut Vec<::oxrdf::Quad>) -> Option<::oxrdf::Term> {
+	match object {
+		Object::Value(value) => datatypes.to_literal(value.clone()).ok().map(Into::into),
+		Object::Node(node) => lenient_node(node.id()).map(Into::into),
+		Object::List(list) => {
+			let nodes: Vec<::oxrdf::BlankNode> = list.iter().map(|_| ::oxrdf::BlankNode::new_unchecked(generator.next_blank_id().name())).collect();
+
+			for (i, item) in list.iter().enumerate() {
+				if let Some(first) = object_to_rdf(item.inner(), graph, datatypes, generator, quads) {
+					quads.push(::oxrdf::Quad::new(nodes[i].clone(), ::oxrdf::NamedNode::new_unchecked(RDF_FIRST), first, graph.clone()));
+				}
+
+				let rest: ::oxrdf::Term = match nodes.get(i + 1) {
+					Some(next) => next.clone().into(),
+					None => ::oxrdf::NamedNode::new_unchecked(RDF_NIL).into()
+				};
+
+				quads.push(::oxrdf::Quad::new(nodes[i].clone(), ::oxrdf::NamedNode::new_unchecked(RDF_REST), rest, graph.clone()));
+			}
+
+			match nodes.into_iter().next() {
+				Some(head) => Some(head.into()),
+				None => Some(::oxrdf::NamedNode::new_unchecked(RDF_NIL).into())
+			}
+		},
+		Object::Graph(_) => None
+	}
+}
+
+/// Converts a flattened document into RDF quads.
+///
+/// This follows the [Deserialize JSON-LD to RDF](https://www.w3.org/TR/json-ld11-api/#deserialize-json-ld-to-rdf-algorithm)
+/// algorithm, without the `rdfDirection` and `produceGeneralizedRdf` options.
+/// Values are converted into literals using the given datatype handlers,
+/// and list nodes are labeled using the given generator,
+/// which must not produce identifiers already used in the document.
+///
+/// The document is expected to be flattened (see [`ExpandedDocument::flatten`]),
+/// so that every node has an identifier.
+/// Quads that cannot be represented in RDF are dropped:
+/// quads about nodes without (valid) identifier, with a blank node predicate,
+/// or with a `null` value.
+/// Annotations are not converted, since RDF-star quoted triples are not supported
+/// (see [`Quads`](`crate::Quads`)).
+pub fn to_rdf<T: Id, G: BlankIdGenerator>(doc: &ExpandedDocument<T>, datatypes: &Datatypes<T>, mut generator: G) -> Vec<::oxrdf::Quad> {
+	let mut quads = Vec::new();
+
+	for quad in doc.quads() {
+		let graph: ::oxrdf::GraphName = match quad.graph() {
+			Some(graph) => match lenient_node(graph.id()) {
+				Some(graph) => graph.into(),
+				None => continue
+			},
+			None => ::oxrdf::GraphName::DefaultGraph
+		};
+
+		let subject = match lenient_node(quad.subject().id()) {
+			Some(subject) => subject,
+			None => continue
+		};
+
+		let predicate = match quad.predicate() {
+			Predicate::Type => ::oxrdf::NamedNode::new_unchecked(RDF_TYPE),
+			Predicate::Property(prop) => match ::oxrdf::NamedNode::try_from(prop.clone()) {
+				Ok(prop) => prop,
+				Err(_) => continue
+			}
+		};
+
+		match quad.object() {
+			QuadObject::Type(ty) => {
+				if let Some(ty) = lenient_node(Some(ty)) {
+					quads.push(::oxrdf::Quad::new(subject, predicate, ty, graph))
+				}
+			},
+			QuadObject::Object(object) => {
+				if let Some(object) = object_to_rdf(object.inner(), &graph, datatypes, &mut generator, &mut quads) {
+					quads.push(::oxrdf::Quad::new(subject, predicate, object, graph))
+				}
+			},
+			QuadObject::Node(node) => {
+				// `node` references the subject through the reverse property.
+				if let Some(node) = lenient_node(node.id()) {
+					quads.push(::oxrdf::Quad::new(node, predicate, subject, graph))
+				}
+			}
+		}
+	}
+
+	quads
+}
@@ -0,0 +1,235 @@
+use std::collections::hash_set;
+use crate::{
+	Id,
+	Reference,
+	Lenient,
+	Indexed,
+	Object,
+	Node,
+	Graph,
+	ExpandedDocument
+};
+
+/// Graph of a [`Quad`].
+pub enum GraphName<'a, T: Id> {
+	/// Node object with a `@graph` entry.
+	Node(&'a Node<T>),
+
+	/// Graph object.
+	Graph(&'a Graph<T>)
+}
+
+impl<'a, T: Id> GraphName<'a, T> {
+	/// Identifier of the graph, if any.
+	pub fn id(&self) -> Option<&'a Lenient<Reference<T>>> {
+		match self {
+			GraphName::Node(node) => node.id(),
+			GraphName::Graph(graph) => graph.id()
+		}
+	}
+}
+
+/// Predicate of a [`Quad`].
+pub enum Predicate<'a, T: Id> {
+	/// The `@type` keyword, corresponding to `rdf:type`.
+	Type,
+
+	/// A property.
+	Property(&'a Reference<T>)
+}
+
+/// Object of a [`Quad`].
+pub enum QuadObject<'a, T: Id> {
+	/// Type of the subject (with the [`Predicate::Type`] predicate).
+	Type(&'a Lenient<Reference<T>>),
+
+	/// Property value: a value, a node object or a list.
+	Object(&'a Indexed<Object<T>>),
+
+	/// Node referencing the subject through a reverse property.
+	Node(&'a Node<T>)
+}
+
+/// Borrowed (graph, subject, predicate, object) quad of an expanded document.
+///
+/// The graph is `None` for the default graph, or the object defining a named graph.
+/// Graphs and subjects are given as objects, since they may have no identifier.
+/// Lists are not decomposed: a list is the object of a single quad.
+pub struct Quad<'a, T: Id>(pub Option<GraphName<'a, T>>, pub &'a Node<T>, pub Predicate<'a, T>, pub QuadObject<'a, T>);
+
+impl<'a, T: Id> Clone for GraphName<'a, T> {
+	fn clone(&self) -> Self {
+		*self
+	}
+}
+
+impl<'a, T: Id> Copy for GraphName<'a, T> {}
+
+impl<'a, T: Id> Clone for Predicate<'a, T> {
+	fn clone(&self) -> Self {
+		*self
+	}
+}
+
+impl<'a, T: Id> Copy for Predicate<'a, T> {}
+
+impl<'a, T: Id> Clone for QuadObject<'a, T> {
+	fn clone(&self) -> Self {
+		*self
+	}
+}
+
+impl<'a, T: Id> Copy for QuadObject<'a, T> {}
+
+impl<'a, T: Id> Clone for Quad<'a, T> {
+	fn clone(&self) -> Self {
+		*self
+	}
+}
+
+impl<'a, T: Id> Copy for Quad<'a, T> {}
+
+impl<'a, T: Id> Quad<'a, T> {
+	/// Graph of the quad, or `None` for the default graph.
+	pub fn graph(&self) -> Option<GraphName<'a, T>> {
+		self.0
+	}
+
+	/// Subject of the quad.
+	pub fn subject(&self) -> &'a Node<T> {
+		self.1
+	}
+
+	/// Predicate of the quad.
+	pub fn predicate(&self) -> Predicate<'a, T> {
+		self.2
+	}
+
+	/// Object of the quad.
+	pub fn object(&self) -> QuadObject<'a, T> {
+		self.3
+	}
+}
+
+/// Pending part of the document, with the graph it belongs to.
+enum Frame<'a, T: Id> {
+	Objects(Option<GraphName<'a, T>>, std::slice::Iter<'a, Indexed<Object<T>>>),
+	Graph(GraphName<'a, T>, hash_set::Iter<'a, Indexed<Object<T>>>),
+	Included(Option<GraphName<'a, T>>, hash_set::Iter<'a, Indexed<Node<T>>>),
+	Types(Option<GraphName<'a, T>>, &'a Node<T>, std::slice::Iter<'a, Lenient<Reference<T>>>),
+	Properties(Option<GraphName<'a, T>>, &'a Node<T>, crate::util::small_map::Iter<'a, Reference<T>, Vec<Indexed<Object<T>>>>),
+	Property(Option<GraphName<'a, T>>, &'a Node<T>, &'a Reference<T>, std::slice::Iter<'a, Indexed<Object<T>>>),
+	ReverseProperties(Option<GraphName<'a, T>>, &'a Node<T>, crate::util::small_map::Iter<'a, Reference<T>, Vec<Indexed<Node<T>>>>),
+	ReverseProperty(Option<GraphName<'a, T>>, &'a Node<T>, &'a Reference<T>, std::slice::Iter<'a, Indexed<Node<T>>>)
+}
+
+/// Iterator over the quads of an expanded document.
+///
+/// Created with [`ExpandedDocument::quads`].
+/// Nodes are visited depth-first: nested nodes, graph members and included nodes
+/// are visited after the quad referencing them.
+///
+/// Annotations (`@annotation` entries) are not visited:
+/// an annotation describes the quad it is attached to,
+/// which, as an RDF-star quoted triple, cannot be the subject of a [`Quad`].
+/// Use [`Node::annotation`] on the quad object to access them.
+pub struct Quads<'a, T: Id> {
+	stack: Vec<Frame<'a, T>>
+}
+
+impl<'a, T: Id> Quads<'a, T> {
+	pub(crate) fn new(doc: &'a ExpandedDocument<T>) -> Quads<'a, T> {
+		Quads {
+			stack: vec![Frame::Objects(None, doc.objects().iter())]
+		}
+	}
+
+	fn push_object(&mut self, graph: Option<GraphName<'a, T>>, object: &'a Object<T>) {
+		match object {
+			Object::Value(_) => (),
+			Object::List(items) => self.stack.push(Frame::Objects(graph, items.iter())),
+			Object::Node(node) => self.push_node(graph, node),
+			Object::Graph(graph_object) => self.stack.push(Frame::Graph(GraphName::Graph(graph_object), graph_object.iter()))
+		}
+	}
+
+	fn push_node(&mut self, graph: Option<GraphName<'a, T>>, node: &'a Node<T>) {
+		if let Some(included) = &node.included {
+			self.stack.push(Frame::Included(graph, included.iter()))
+		}
+
+		if let Some(objects) = &node.graph {
+			self.stack.push(Frame::Graph(GraphName::Node(node), objects.iter()))
+		}
+
+		self.stack.push(Frame::ReverseProperties(graph, node, node.reverse_properties.iter()));
+		self.stack.push(Frame::Properties(graph, node, node.properties.iter()));
+		self.stack.push(Frame::Types(graph, node, node.types.iter()))
+	}
+}
+
+impl<'a, T: Id> Iterator for Quads<'a, T> {
+	type Item = Quad<'a, T>;
+
+	fn next(&mut self) -> Option<Quad<'a, T>> {
+		loop {
+			match self.stack.last_mut()? {
+				Frame::Objects(graph, objects) => match objects.next() {
+					Some(object) => {
+						let graph = *graph;
+						self.push_object(graph, object)
+					},
+					None => { self.stack.pop(); }
+				},
+				Frame::Graph(graph, objects) => match objects.next() {
+					Some(object) => {
+						let graph = Some(*graph);
+						self.push_object(graph, object)
+					},
+					None => { self.stack.pop(); }
+				},
+				Frame::Included(graph, nodes) => match nodes.next() {
+					Some(node) => {
+						let graph = *graph;
+						self.push_node(graph, node)
+					},
+					None => { self.stack.pop(); }
+				},
+				Frame::Types(graph, subject, types) => match types.next() {
+					Some(ty) => return Some(Quad(*graph, *subject, Predicate::Type, QuadObject::Type(ty))),
+					None => { self.stack.pop(); }
+				},
+				Frame::Properties(graph, subject, properties) => match properties.next() {
+					Some((prop, values)) => {
+						let frame = Frame::Property(*graph, *subject, prop, values.iter());
+						self.stack.push(frame)
+					},
+					None => { self.stack.pop(); }
+				},
+				Frame::Property(graph, subject, prop, values) => match values.next() {
+					Some(value) => {
+						let quad = Quad(*graph, *subject, Predicate::Property(*prop), QuadObject::Object(value));
+						self.push_object(quad.0, value);
+						return Some(quad)
+					},
+					None => { self.stack.pop(); }
+				},
+				Frame::ReverseProperties(graph, object, properties) => match properties.next() {
+					Some((prop, nodes)) => {
+						let frame = Frame::ReverseProperty(*graph, *object, prop, nodes.iter());
+						self.stack.push(frame)
+					},
+					None => { self.stack.pop(); }
+				},
+				Frame::ReverseProperty(graph, object, prop, nodes) => match nodes.next() {
+					Some(subject) => {
+						let quad = Quad(*graph, subject.inner(), Predicate::Property(*prop), QuadObject::Node(*object));
+						self.push_node(quad.0, subject.inner());
+						return Some(quad)
+					},
+					None => { self.stack.pop(); }
+				}
+			}
+		}
+	}
+}
@@ -130,6 +130,46 @@ impl<T: AsIri> TryFrom<Term<T>> for Reference<T> {
 	}
 }
 
+/// Invalid node reference.
+///
+/// Returned when a string is neither a valid IRI nor a valid blank node identifier.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct InvalidReference(pub String);
+
+impl fmt::Display for InvalidReference {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "invalid node reference `{}`", self.0)
+	}
+}
+
+impl std::error::Error for InvalidReference {}
+
+impl<'a> TryFrom<&'a str> for Reference<IriBuf> {
+	type Error = InvalidReference;
+
+	/// Strings starting with `_:` are parsed as blank node identifiers,
+	/// other strings as IRIs.
+	fn try_from(str: &'a str) -> Result<Reference<IriBuf>, InvalidReference> {
+		if str.starts_with("_:") {
+			match BlankId::try_from(str) {
+				Ok(blank) => Ok(Reference::Blank(blank)),
+				Err(_) => Err(InvalidReference(str.to_string()))
+			}
+		} else {
+			match IriBuf::new(str) {
+				Ok(iri) => Ok(Reference::Id(iri)),
+				Err(_) => Err(InvalidReference(str.to_string()))
+			}
+		}
+	}
+}
+
+impl<'a> From<Iri<'a>> for Reference<IriBuf> {
+	fn from(iri: Iri<'a>) -> Reference<IriBuf> {
+		Reference::Id(iri.into())
+	}
+}
+
 impl<T: AsIri> From<BlankId> for Reference<T> {
 	fn from(blank: BlankId) -> Reference<T> {
 		Reference::Blank(blank)
@@ -195,3 +235,45 @@ impl<'a, T: Id> ToReference<T> for &'a Reference<T> {
 		self
 	}
 }
+
+impl<T: Id> ToReference<T> for Reference<T> {
+	type Reference = Reference<T>;
+
+	fn to_ref(&self) -> Self::Reference {
+		self.clone()
+	}
+}
+
+impl<T: Id> ToReference<T> for T {
+	type Reference = Reference<T>;
+
+	fn to_ref(&self) -> Self::Reference {
+		Reference::Id(self.clone())
+	}
+}
+
+impl<'a> ToReference<IriBuf> for Iri<'a> {
+	type Reference = Reference<IriBuf>;
+
+	fn to_ref(&self) -> Self::Reference {
+		Reference::Id((*self).into())
+	}
+}
+
+/// Strings starting with `_:` are converted into blank node identifiers,
+/// other strings into IRIs.
+///
+/// # Panics
+///
+/// Panics if the string is neither a valid IRI nor a valid blank node identifier.
+/// Use [`Reference::try_from`] to handle invalid strings.
+impl<'a> ToReference<IriBuf> for &'a str {
+	type Reference = Reference<IriBuf>;
+
+	fn to_ref(&self) -> Self::Reference {
+		match Reference::try_from(*self) {
+			Ok(r) => r,
+			Err(e) => panic!("{}", e)
+		}
+	}
+}
@@ -0,0 +1,108 @@
+//! Blank node relabeling.
+
+use std::collections::{
+	HashMap,
+	HashSet
+};
+use crate::{
+	Id,
+	BlankId,
+	BlankIdGenerator,
+	Reference,
+	Lenient,
+	Indexed,
+	Object,
+	Node
+};
+
+/// Blank node identifiers relabeling, using a generator.
+pub(crate) struct Relabeling<G> {
+	pub generator: G,
+
+	/// Blank node identifiers already relabeled.
+	pub labels: HashMap<BlankId, BlankId>
+}
+
+impl<G: BlankIdGenerator> Relabeling<G> {
+	pub fn new(generator: G) -> Relabeling<G> {
+		Relabeling {
+			generator,
+			labels: HashMap::new()
+		}
+	}
+
+	/// Returns the new label of the given blank node identifier,
+	/// generating it if necessary.
+	pub fn blank(&mut self, id: BlankId) -> BlankId {
+		let generator = &mut self.generator;
+		self.labels.entry(id).or_insert_with(|| generator.next_blank_id()).clone()
+	}
+
+	pub fn reference<T: Id>(&mut self, id: Reference<T>) -> Reference<T> {
+		match id {
+			Reference::Blank(id) => Reference::Blank(self.blank(id)),
+			id => id
+		}
+	}
+
+	pub fn lenient<T: Id>(&mut self, id: Lenient<Reference<T>>) -> Lenient<Reference<T>> {
+		match id {
+			Lenient::Ok(id) => Lenient::Ok(self.reference(id)),
+			id => id
+		}
+	}
+
+	pub fn object<T: Id>(&mut self, object: &mut Object<T>) {
+		match object {
+			Object::Value(_) => (),
+			Object::List(items) => for item in items {
+				self.object(item.inner_mut())
+			},
+			Object::Node(node) => self.node(node),
+			Object::Graph(graph) => {
+				graph.id = graph.id.take().map(|id| self.lenient(id));
+				graph.objects = self.objects(std::mem::take(&mut graph.objects))
+			}
+		}
+	}
+
+	pub fn objects<T: Id>(&mut self, objects: HashSet<Indexed<Object<T>>>) -> HashSet<Indexed<Object<T>>> {
+		objects.into_iter().map(|mut object| {
+			self.object(object.inner_mut());
+			object
+		}).collect()
+	}
+
+	pub fn nodes<T: Id>(&mut self, nodes: HashSet<Indexed<Node<T>>>) -> HashSet<Indexed<Node<T>>> {
+		nodes.into_iter().map(|mut node| {
+			self.node(node.inner_mut());
+			node
+		}).collect()
+	}
+
+	pub fn node<T: Id>(&mut self, node: &mut Node<T>) {
+		node.id = node.id.take().map(|id| self.lenient(id));
+		node.types = std::mem::take(&mut node.types).into_iter().map(|ty| self.lenient(ty)).collect();
+		node.graph = node.graph.take().map(|graph| self.objects(graph));
+		node.included = node.included.take().map(|included| self.nodes(included));
+		node.annotation = node.annotation.take().map(|annotation| self.nodes(annotation));
+
+		node.properties = std::mem::take(&mut node.properties).into_iter().map(|(prop, mut values)| {
+			let prop = self.reference(prop);
+			for value in &mut values {
+				self.object(value.inner_mut())
+			}
+
+			(prop, values)
+		}).collect();
+
+		node.reverse_properties = std::mem::take(&mut node.reverse_properties).into_iter().map(|(prop, mut nodes)| {
+			let prop = self.reference(prop);
+			for reverse_node in &mut nodes {
+				self.node(reverse_node.inner_mut())
+			}
+
+			(prop, nodes)
+		}).collect();
+	}
+}
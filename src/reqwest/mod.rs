@@ -7,16 +7,10 @@ use json::JsonValue;
 use crate::{
 	Error,
 	ErrorCode,
-	RemoteDocument,
-	context::{
-		self,
-		RemoteContext
-	}
+	RemoteDocument
 };
 
-pub fn is_json_media_type(ty: &str) -> bool {
-	ty == "application/json" || ty == "application/ld+json"
-}
+pub use crate::is_json_media_type;
 
 pub async fn load_remote_json_ld_document(url: Iri<'_>) -> Result<RemoteDocument, Error> {
 	info!("loading remote document `{}'", url);
@@ -26,21 +20,30 @@ pub async fn load_remote_json_ld_document(url: Iri<'_>) -> Result<RemoteDocument
 	let request = client.get(url.as_str()).header(ACCEPT, "application/ld+json, application/json");
 	let response = request.send().await?;
 
-	if response.headers().get_all(CONTENT_TYPE).iter().find(|&value| {
-		if let Ok(value) = value.to_str() {
-			is_json_media_type(value)
-		} else {
-			false
-		}
-	}).is_some() {
+	// The final URL, after redirections, is the base URL of the document.
+	let final_url = IriBuf::new(response.url().as_str()).unwrap_or_else(|_| url.into());
+
+	let content_type = response.headers().get_all(CONTENT_TYPE).iter().filter_map(|value| value.to_str().ok()).find(|value| {
+		is_json_media_type(value)
+	}).map(|value| value.to_string());
+
+	let links: Vec<String> = response.headers().get_all(LINK).iter().filter_map(|value| value.to_str().ok()).map(|value| value.to_string()).collect();
+
+	if let Some(content_type) = content_type {
 		let body = response.text().await?;
 
 		match json::parse(body.as_str()) {
-			Ok(doc) => Ok(RemoteDocument::new(doc, url.into())),
-			Err(e) => panic!("invalid json: {:?}: {}", e, body.as_str())
+			Ok(doc) => {
+				let mut remote_doc = RemoteDocument::new(doc, final_url.as_iri()).with_content_type_header(&content_type);
+				for link in &links {
+					remote_doc = remote_doc.with_link_header(link)
+				}
+				Ok(remote_doc)
+			},
+			Err(e) => Err(Error::new(ErrorCode::LoadingDocumentFailed, e))
 		}
 	} else {
-		panic!("not a json document")
+		Err(ErrorCode::LoadingDocumentFailed.into())
 	}
 }
 
@@ -70,29 +73,14 @@ impl Loader {
 	}
 }
 
-impl context::Loader for Loader {
-	type Output = JsonValue;
+/// The context loader is derived from this implementation.
+impl crate::Loader for Loader {
+	type Document = JsonValue;
 
-	fn load_context<'a>(&'a mut self, url: Iri) -> BoxFuture<'a, Result<RemoteContext<JsonValue>, Error>> {
+	fn load<'a>(&'a mut self, url: Iri<'_>) -> BoxFuture<'a, Result<RemoteDocument, Error>> {
 		let url = IriBuf::from(url);
 		async move {
-			match self.load(url.as_iri()).await {
-				Ok(remote_doc) => {
-					let (doc, url) = remote_doc.into_parts();
-					if let JsonValue::Object(obj) = doc {
-						if let Some(context) = obj.get("@context") {
-							Ok(RemoteContext::from_parts(url, context.clone()))
-						} else {
-							Err(ErrorCode::InvalidRemoteContext.into())
-						}
-					} else {
-						Err(ErrorCode::InvalidRemoteContext.into())
-					}
-				},
-				Err(_) => {
-					Err(ErrorCode::LoadingRemoteContextFailed.into())
-				}
-			}
+			Loader::load(self, url.as_iri()).await
 		}.boxed()
 	}
 }
@@ -0,0 +1,103 @@
+//! Integration with the [`rio`](https://crates.io/crates/rio_api) RDF parsers and formatters.
+//!
+//! This module is enabled by the `rio` feature, which also enables the `oxrdf` feature.
+//! Flattened documents are converted into RDF quads with [`oxrdf::to_rdf`](`crate::oxrdf::to_rdf`),
+//! and pushed one by one into a rio [`QuadsFormatter`],
+//! for instance to write them as N-Quads or TriG.
+//!
+//! Only the serialization into RDF is covered:
+//! deserialization from RDF (and thus from rio parsers) is not implemented yet.
+
+use rio_api::{
+	formatter::QuadsFormatter,
+	model
+};
+use crate::{
+	Id,
+	BlankIdGenerator,
+	ExpandedDocument,
+	oxrdf::{
+		self,
+		Datatypes
+	}
+};
+
+/// Converts a flattened document into RDF quads, and push them into the given formatter.
+///
+/// See [`oxrdf::to_rdf`](`crate::oxrdf::to_rdf`) for the meaning of the other parameters.
+/// Stops at the first error returned by the formatter.
+pub fn format<T: Id, G: BlankIdGenerator, F: QuadsFormatter>(doc: &ExpandedDocument<T>, datatypes: &Datatypes<T>, generator: G, formatter: &mut F) -> Result<(), F::Error> {
+	for quad in oxrdf::to_rdf(doc, datatypes, generator) {
+		formatter.format(&quad_ref(&quad))?
+	}
+
+	Ok(())
+}
+
+/// Borrows a quad produced by [`oxrdf::to_rdf`](`crate::oxrdf::to_rdf`).
+///
+/// Such quads never contain quoted triples.
+fn quad_ref(quad: &::oxrdf::Quad) -> model::Quad {
+	let subject = match &quad.subject {
+		::oxrdf::Subject::NamedNode(iri) => model::Subject::NamedNode(named_node_ref(iri)),
+		::oxrdf::Subject::BlankNode(id) => model::Subject::BlankNode(blank_node_ref(id)),
+		#[allow(unreachable_patterns)]
+		_ => unreachable!()
+	};
+
+	let object = match &quad.object {
+		::oxrdf::Term::NamedNode(iri) => model::Term::NamedNode(named_node_ref(iri)),
+		::oxrdf::Term::BlankNode(id) => model::Term::BlankNode(blank_node_ref(id)),
+		::oxrdf::Term::Literal(lit) => model::Term::Literal(literal_ref(lit)),
+		#[allow(unreachable_patterns)]
+		_ => unreachable!()
+	};
+
+	let graph_name = match &quad.graph_name {
+		::oxrdf::GraphName::NamedNode(iri) => Some(model::GraphName::NamedNode(named_node_ref(iri))),
+		::oxrdf::GraphName::BlankNode(id) => Some(model::GraphName::BlankNode(blank_node_ref(id))),
+		::oxrdf::GraphName::DefaultGraph => None
+	};
+
+	model::Quad {
+		subject,
+		predicate: named_node_ref(&quad.predicate),
+		object,
+		graph_name
+	}
+}
+
+fn named_node_ref(iri: &::oxrdf::NamedNode) -> model::NamedNode {
+	model::NamedNode {
+		iri: iri.as_str()
+	}
+}
+
+fn blank_node_ref(id: &::oxrdf::BlankNode) -> model::BlankNode {
+	model::BlankNode {
+		id: id.as_str()
+	}
+}
+
+fn literal_ref(lit: &::oxrdf::Literal) -> model::Literal {
+	match lit.language() {
+		Some(language) => model::Literal::LanguageTaggedString {
+			value: lit.value(),
+			language
+		},
+		None => {
+			if lit.is_plain() {
+				model::Literal::Simple {
+					value: lit.value()
+				}
+			} else {
+				model::Literal::Typed {
+					value: lit.value(),
+					datatype: model::NamedNode {
+						iri: lit.datatype().as_str()
+					}
+				}
+			}
+		}
+	}
+}
@@ -0,0 +1,168 @@
+//! [schema.org](https://schema.org/) identifiers.
+//!
+//! This module is enabled by the `schema-org` feature.
+//! It provides the [`SchemaOrg`] identifier type, covering the most common schema.org
+//! types and properties without allocation, and falling back to an [`IriBuf`] for any other IRI.
+//!
+//! ```ignore
+//! use json_ld::{Document, JsonContext, NoLoader, schema_org::SchemaOrg};
+//!
+//! let expanded = doc.expand::<JsonContext<SchemaOrg>, _>(&mut NoLoader).await?;
+//! for node in expanded.nodes_with_type(&SchemaOrg::Person) {
+//! 	for name in node.get(SchemaOrg::Name) {
+//! 		println!("{}", name.as_str().unwrap())
+//! 	}
+//! }
+//! ```
+
+use std::fmt;
+use iref::{Iri, IriBuf, AsIri};
+use crate::{
+	Id,
+	Reference,
+	ToReference
+};
+
+macro_rules! schema_org {
+	($($variant:ident : $iri:literal),*) => {
+		/// schema.org identifier.
+		///
+		/// Only the `http://schema.org/` namespace, used by the official schema.org context,
+		/// is recognized: `https://schema.org/` IRIs are stored as [`SchemaOrg::Other`],
+		/// so that the conversion from and to IRIs remains a bijection.
+		#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+		pub enum SchemaOrg {
+			$(
+				#[doc = $iri]
+				$variant,
+			)*
+
+			/// Any other IRI.
+			Other(IriBuf)
+		}
+
+		impl SchemaOrg {
+			/// Get the identifier IRI as a string.
+			pub fn as_str(&self) -> &str {
+				match self {
+					$(
+						SchemaOrg::$variant => $iri,
+					)*
+					SchemaOrg::Other(iri) => iri.as_str()
+				}
+			}
+		}
+
+		impl Id for SchemaOrg {
+			fn from_iri(iri: Iri) -> SchemaOrg {
+				match iri.as_str() {
+					$(
+						$iri => SchemaOrg::$variant,
+					)*
+					_ => SchemaOrg::Other(iri.into())
+				}
+			}
+		}
+	};
+}
+
+schema_org! {
+	// Types.
+	Thing: "http://schema.org/Thing",
+	CreativeWork: "http://schema.org/CreativeWork",
+	Article: "http://schema.org/Article",
+	NewsArticle: "http://schema.org/NewsArticle",
+	BlogPosting: "http://schema.org/BlogPosting",
+	WebPage: "http://schema.org/WebPage",
+	WebSite: "http://schema.org/WebSite",
+	ImageObject: "http://schema.org/ImageObject",
+	VideoObject: "http://schema.org/VideoObject",
+	Book: "http://schema.org/Book",
+	Recipe: "http://schema.org/Recipe",
+	Person: "http://schema.org/Person",
+	Organization: "http://schema.org/Organization",
+	LocalBusiness: "http://schema.org/LocalBusiness",
+	Place: "http://schema.org/Place",
+	PostalAddress: "http://schema.org/PostalAddress",
+	GeoCoordinates: "http://schema.org/GeoCoordinates",
+	Event: "http://schema.org/Event",
+	Product: "http://schema.org/Product",
+	Offer: "http://schema.org/Offer",
+	Review: "http://schema.org/Review",
+	Rating: "http://schema.org/Rating",
+	AggregateRating: "http://schema.org/AggregateRating",
+	BreadcrumbList: "http://schema.org/BreadcrumbList",
+	ListItem: "http://schema.org/ListItem",
+	FAQPage: "http://schema.org/FAQPage",
+	Question: "http://schema.org/Question",
+	Answer: "http://schema.org/Answer",
+
+	// Properties.
+	Name: "http://schema.org/name",
+	Description: "http://schema.org/description",
+	Url: "http://schema.org/url",
+	Image: "http://schema.org/image",
+	Identifier: "http://schema.org/identifier",
+	SameAs: "http://schema.org/sameAs",
+	Author: "http://schema.org/author",
+	Publisher: "http://schema.org/publisher",
+	Headline: "http://schema.org/headline",
+	DatePublished: "http://schema.org/datePublished",
+	DateModified: "http://schema.org/dateModified",
+	StartDate: "http://schema.org/startDate",
+	EndDate: "http://schema.org/endDate",
+	Location: "http://schema.org/location",
+	Address: "http://schema.org/address",
+	StreetAddress: "http://schema.org/streetAddress",
+	AddressLocality: "http://schema.org/addressLocality",
+	AddressCountry: "http://schema.org/addressCountry",
+	PostalCode: "http://schema.org/postalCode",
+	Latitude: "http://schema.org/latitude",
+	Longitude: "http://schema.org/longitude",
+	Email: "http://schema.org/email",
+	Telephone: "http://schema.org/telephone",
+	GivenName: "http://schema.org/givenName",
+	FamilyName: "http://schema.org/familyName",
+	JobTitle: "http://schema.org/jobTitle",
+	WorksFor: "http://schema.org/worksFor",
+	Logo: "http://schema.org/logo",
+	Brand: "http://schema.org/brand",
+	Sku: "http://schema.org/sku",
+	Offers: "http://schema.org/offers",
+	Price: "http://schema.org/price",
+	PriceCurrency: "http://schema.org/priceCurrency",
+	Availability: "http://schema.org/availability",
+	ReviewRating: "http://schema.org/reviewRating",
+	RatingValue: "http://schema.org/ratingValue",
+	ReviewCount: "http://schema.org/reviewCount",
+	ItemListElement: "http://schema.org/itemListElement",
+	Position: "http://schema.org/position",
+	Item: "http://schema.org/item",
+	MainEntity: "http://schema.org/mainEntity",
+	AcceptedAnswer: "http://schema.org/acceptedAnswer",
+	Text: "http://schema.org/text"
+}
+
+impl AsIri for SchemaOrg {
+	fn as_iri(&self) -> Iri {
+		match self {
+			SchemaOrg::Other(iri) => iri.as_iri(),
+			// known variants always hold a valid IRI.
+			id => Iri::new(id.as_str()).unwrap()
+		}
+	}
+}
+
+impl fmt::Display for SchemaOrg {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		self.as_str().fmt(f)
+	}
+}
+
+impl ToReference<SchemaOrg> for SchemaOrg {
+	type Reference = Reference<SchemaOrg>;
+
+	fn to_ref(&self) -> Reference<SchemaOrg> {
+		Reference::Id(self.clone())
+	}
+}
@@ -0,0 +1,104 @@
+//! Support for [`serde_json`](https://crates.io/crates/serde_json) values.
+//!
+//! This module is enabled by the `serde_json` feature.
+//! `serde_json::Value` implements [`Document`], with no base URL
+//! (use [`RemoteDocument`](`crate::RemoteDocument`) to attach one).
+//! The value is converted into a [`JsonValue`] before expansion.
+//! Use [`from_json`] to convert compacted documents back into `serde_json` values.
+
+use futures::future::{BoxFuture, FutureExt};
+use iref::{Iri, IriBuf};
+use json::JsonValue;
+use crate::{
+	Error,
+	Id,
+	Document,
+	ExpandedDocument,
+	ContextMut,
+	context::Loader,
+	expansion,
+	util
+};
+
+/// Convert a `serde_json` value into a [`JsonValue`].
+pub fn to_json(value: &::serde_json::Value) -> JsonValue {
+	use ::serde_json::Value;
+	match value {
+		Value::Null => JsonValue::Null,
+		Value::Bool(b) => (*b).into(),
+		Value::Number(n) => {
+			if let Some(i) = n.as_i64() {
+				i.into()
+			} else if let Some(u) = n.as_u64() {
+				u.into()
+			} else {
+				n.as_f64().unwrap().into()
+			}
+		},
+		Value::String(s) => s.as_str().into(),
+		Value::Array(items) => JsonValue::Array(items.iter().map(to_json).collect()),
+		Value::Object(map) => {
+			let mut obj = json::object::Object::with_capacity(map.len());
+			for (key, value) in map {
+				obj.insert(key, to_json(value))
+			}
+			JsonValue::Object(obj)
+		}
+	}
+}
+
+/// Convert a [`JsonValue`] into a `serde_json` value.
+///
+/// Integral numbers are converted into integers when they fit in an `i64` or `u64`,
+/// and other numbers into floating point numbers, without truncating their fractional part.
+/// Numbers that cannot be represented in `serde_json` (NaN and infinities) are converted into `null`.
+pub fn from_json(value: &JsonValue) -> ::serde_json::Value {
+	use ::serde_json::Value;
+	match value {
+		JsonValue::Null => Value::Null,
+		JsonValue::Boolean(b) => Value::Bool(*b),
+		JsonValue::Number(n) => {
+			if let Some(i) = util::json_number_as_i64(n) {
+				i.into()
+			} else if let Some(u) = util::json_number_as_u64(n) {
+				u.into()
+			} else {
+				match ::serde_json::Number::from_f64(value.as_f64().unwrap()) {
+					Some(n) => Value::Number(n),
+					None => Value::Null
+				}
+			}
+		},
+		JsonValue::Short(_) | JsonValue::String(_) => Value::String(value.as_str().unwrap().to_string()),
+		JsonValue::Array(items) => Value::Array(items.iter().map(from_json).collect()),
+		JsonValue::Object(obj) => {
+			let mut map = ::serde_json::Map::with_capacity(obj.len());
+			for (key, value) in obj.iter() {
+				map.insert(key.to_string(), from_json(value));
+			}
+			Value::Object(map)
+		}
+	}
+}
+
+impl<T: Id> Document<T> for ::serde_json::Value {
+	type LocalContext = JsonValue;
+
+	/// Returns `None`.
+	fn base_url(&self) -> Option<Iri> {
+		None
+	}
+
+	fn expand_with<'a, C: Send + Sync + ContextMut<T>, L: Send + Sync + Loader>(&'a self, base_url: Option<Iri>, context: &'a C, loader: &'a mut L, options: expansion::Options) -> BoxFuture<'a, Result<ExpandedDocument<T>, Error>> where
+		C::LocalContext: Send + Sync + From<L::Output> + From<JsonValue>,
+		L::Output: Into<JsonValue>,
+		T: 'a + Send + Sync
+	{
+		let base_url = base_url.map(|url| IriBuf::from(url));
+		async move {
+			let doc = to_json(self);
+			let objects = expansion::expand(context, &doc, base_url.as_ref().map(|url| url.as_iri()), loader, &options).await?;
+			Ok(ExpandedDocument::new(objects))
+		}.boxed()
+	}
+}
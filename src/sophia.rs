@@ -0,0 +1,214 @@
+//! Integration with the [`sophia`](https://crates.io/crates/sophia_api) RDF toolkit.
+//!
+//! This module is enabled by the `sophia` feature, which also enables the `oxrdf` feature.
+//! Flattened documents are converted into RDF quads with [`oxrdf::to_rdf`](`crate::oxrdf::to_rdf`),
+//! and exposed as a sophia [`Dataset`](`sophia_api::dataset::Dataset`),
+//! so that they can be consumed by any sophia tool (serializers, inference, etc.).
+//!
+//! Only the serialization into RDF is covered:
+//! deserialization from RDF (and thus from sophia quad sources) is not implemented yet.
+
+use std::convert::Infallible;
+use sophia_api::{
+	dataset::{
+		self,
+		DQuadSource
+	},
+	quad::{
+		self,
+		streaming_mode::{
+			ByValue,
+			StreamedQuad
+		}
+	},
+	term::{
+		TTerm,
+		TermKind,
+		RawValue,
+		SimpleIri
+	}
+};
+use crate::{
+	Id,
+	BlankIdGenerator,
+	ExpandedDocument,
+	oxrdf::{
+		self,
+		Datatypes
+	}
+};
+
+/// RDF term of a [`Dataset`].
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub enum Term {
+	Iri(::oxrdf::NamedNode),
+	Blank(::oxrdf::BlankNode),
+	Literal(::oxrdf::Literal)
+}
+
+impl TTerm for Term {
+	fn kind(&self) -> TermKind {
+		match self {
+			Term::Iri(_) => TermKind::Iri,
+			Term::Blank(_) => TermKind::BlankNode,
+			Term::Literal(_) => TermKind::Literal
+		}
+	}
+
+	fn value_raw(&self) -> RawValue {
+		match self {
+			Term::Iri(iri) => iri.as_str().into(),
+			Term::Blank(id) => id.as_str().into(),
+			Term::Literal(lit) => lit.value().into()
+		}
+	}
+
+	fn datatype(&self) -> Option<SimpleIri> {
+		match self {
+			Term::Literal(lit) => Some(SimpleIri::new_unchecked(lit.datatype().as_str(), None)),
+			_ => None
+		}
+	}
+
+	fn language(&self) -> Option<&str> {
+		match self {
+			Term::Literal(lit) => lit.language(),
+			_ => None
+		}
+	}
+
+	fn as_dyn(&self) -> &dyn TTerm {
+		self
+	}
+}
+
+/// RDF quad of a [`Dataset`].
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Quad {
+	subject: Term,
+	predicate: Term,
+	object: Term,
+	graph: Option<Term>
+}
+
+impl Quad {
+	/// Converts a quad produced by [`oxrdf::to_rdf`](`crate::oxrdf::to_rdf`).
+	///
+	/// Such quads never contain quoted triples.
+	fn from_oxrdf(quad: ::oxrdf::Quad) -> Quad {
+		let subject = match quad.subject {
+			::oxrdf::Subject::NamedNode(iri) => Term::Iri(iri),
+			::oxrdf::Subject::BlankNode(id) => Term::Blank(id),
+			#[allow(unreachable_patterns)]
+			_ => unreachable!()
+		};
+
+		let object = match quad.object {
+			::oxrdf::Term::NamedNode(iri) => Term::Iri(iri),
+			::oxrdf::Term::BlankNode(id) => Term::Blank(id),
+			::oxrdf::Term::Literal(lit) => Term::Literal(lit),
+			#[allow(unreachable_patterns)]
+			_ => unreachable!()
+		};
+
+		let graph = match quad.graph_name {
+			::oxrdf::GraphName::NamedNode(iri) => Some(Term::Iri(iri)),
+			::oxrdf::GraphName::BlankNode(id) => Some(Term::Blank(id)),
+			::oxrdf::GraphName::DefaultGraph => None
+		};
+
+		Quad {
+			subject,
+			predicate: Term::Iri(quad.predicate),
+			object,
+			graph
+		}
+	}
+}
+
+impl quad::Quad for Quad {
+	type Term = Term;
+
+	fn s(&self) -> &Term {
+		&self.subject
+	}
+
+	fn p(&self) -> &Term {
+		&self.predicate
+	}
+
+	fn o(&self) -> &Term {
+		&self.object
+	}
+
+	fn g(&self) -> Option<&Term> {
+		self.graph.as_ref()
+	}
+}
+
+/// RDF dataset obtained from a JSON-LD document.
+///
+/// It implements the sophia [`Dataset`](`sophia_api::dataset::Dataset`) trait,
+/// and its quads can also be iterated directly with [`Dataset::iter`].
+#[derive(Clone, Default, Debug)]
+pub struct Dataset {
+	quads: Vec<Quad>
+}
+
+impl Dataset {
+	/// Converts a flattened document into a dataset.
+	///
+	/// See [`oxrdf::to_rdf`](`crate::oxrdf::to_rdf`) for the meaning of the parameters.
+	pub fn from_document<T: Id, G: BlankIdGenerator>(doc: &ExpandedDocument<T>, datatypes: &Datatypes<T>, generator: G) -> Dataset {
+		Dataset {
+			quads: oxrdf::to_rdf(doc, datatypes, generator).into_iter().map(Quad::from_oxrdf).collect()
+		}
+	}
+
+	/// Number of quads in the dataset.
+	pub fn len(&self) -> usize {
+		self.quads.len()
+	}
+
+	/// Checks if the dataset is empty.
+	pub fn is_empty(&self) -> bool {
+		self.quads.is_empty()
+	}
+
+	/// Iterate through the quads of the dataset.
+	pub fn iter(&self) -> std::slice::Iter<Quad> {
+		self.quads.iter()
+	}
+
+	/// Consume the dataset and return its quads.
+	pub fn into_quads(self) -> Vec<Quad> {
+		self.quads
+	}
+}
+
+impl dataset::Dataset for Dataset {
+	type Quad = ByValue<Quad>;
+	type Error = Infallible;
+
+	fn quads(&self) -> DQuadSource<Self> {
+		Box::new(self.quads.iter().cloned().map(|quad| Ok(StreamedQuad::by_value(quad))))
+	}
+}
+
+impl IntoIterator for Dataset {
+	type Item = Quad;
+	type IntoIter = std::vec::IntoIter<Quad>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		self.quads.into_iter()
+	}
+}
+
+impl<'a> IntoIterator for &'a Dataset {
+	type Item = &'a Quad;
+	type IntoIter = std::slice::Iter<'a, Quad>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		self.quads.iter()
+	}
+}
@@ -1,4 +1,9 @@
 use std::convert::TryFrom;
+use json::JsonValue;
+use crate::util::{
+	self,
+	AsJson
+};
 use super::Keyword;
 
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
@@ -227,3 +232,14 @@ impl Container {
 		}
 	}
 }
+
+impl util::AsJson for Container {
+	fn as_json(&self) -> JsonValue {
+		let types = self.as_slice();
+		if types.len() == 1 {
+			Keyword::from(types[0]).as_json()
+		} else {
+			JsonValue::Array(types.iter().map(|c| Keyword::from(*c).as_json()).collect())
+		}
+	}
+}
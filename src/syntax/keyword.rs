@@ -6,6 +6,11 @@ use crate::util;
 /// JSON-LD keywords.
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
 pub enum Keyword {
+	/// `@annotation`.
+	/// Used in JSON-LD-star to annotate the statement linking a node or value object
+	/// to the subject of the property it is the value of.
+	Annotation,
+
 	/// `@base`.
 	/// Used to set the base IRI against which to resolve those relative IRI references
 	/// which are otherwise interpreted relative to the document.
@@ -120,6 +125,7 @@ impl Keyword {
 	pub fn into_str(self) -> &'static str {
 		use Keyword::*;
 		match self {
+			Annotation => "@annotation",
 			Base => "@base",
 			Container => "@container",
 			Context => "@context",
@@ -153,6 +159,7 @@ impl<'a> TryFrom<&'a str> for Keyword {
 	fn try_from(str: &'a str) -> Result<Keyword, &'a str> {
 		use Keyword::*;
 		match str {
+			"@annotation" => Ok(Annotation),
 			"@base" => Ok(Base),
 			"@container" => Ok(Container),
 			"@context" => Ok(Context),
@@ -0,0 +1,721 @@
+//! W3C test-suite runner.
+//!
+//! This module is enabled by the `test-suite` feature.
+//! It loads the manifests of the official [JSON-LD test-suite](https://w3c.github.io/json-ld-api/tests/)
+//! through a document loader, runs each test with the options it specifies,
+//! and reports the outcome of each test.
+//! Use a [`FsLoader`](`crate::FsLoader`) mounted on a local copy of the test-suite to run it offline.
+//!
+//! ```ignore
+//! let mut loader = FsLoader::new();
+//! loader.mount(iri!("https://w3c.github.io/json-ld-api"), "json-ld-api");
+//!
+//! let summary = task::block_on(test_suite::run_manifest(iri!("https://w3c.github.io/json-ld-api/tests/expand-manifest.jsonld"), &mut loader))?;
+//! println!("{} passed, {} failed, {} skipped", summary.passed(), summary.failed(), summary.skipped());
+//! ```
+//!
+//! Non-normative tests and tests restricted to the `json-ld-1.0` specification are skipped,
+//! as well as tests using the `rdfDirection`, `produceGeneralizedRdf` or `useNativeTypes` options
+//! that are not supported.
+//!
+//! The tests of the toRdf manifest are run with the `oxrdf` feature, and skipped otherwise.
+//! Their output is converted with [`oxrdf::to_rdf`](`crate::oxrdf::to_rdf`) and compared
+//! with the expected N-Quads document, loaded through the [`TextLoader`] trait,
+//! up to blank node renaming.
+
+use std::convert::TryFrom;
+use std::fmt;
+use futures::future::{BoxFuture, FutureExt};
+use iref::{Iri, IriBuf, IriRef};
+use json::JsonValue;
+use crate::{
+	Error,
+	ErrorCode,
+	ProcessingMode,
+	Document,
+	ExpandedDocument,
+	Loader,
+	FsLoader,
+	JsonContext,
+	Context,
+	context::{
+		Local,
+		Loader as ContextLoader,
+		Processed,
+		ProcessingOptions
+	},
+	expansion,
+	compaction,
+	util::{
+		self,
+		AsJson
+	}
+};
+
+/// Algorithm tested by a test.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Kind {
+	Expand,
+	Compact,
+	Flatten,
+	ToRdf
+}
+
+impl Kind {
+	fn from_type(ty: &str) -> Option<Kind> {
+		if ty.ends_with("ExpandTest") {
+			Some(Kind::Expand)
+		} else if ty.ends_with("CompactTest") {
+			Some(Kind::Compact)
+		} else if ty.ends_with("FlattenTest") {
+			Some(Kind::Flatten)
+		} else if ty.ends_with("ToRDFTest") {
+			Some(Kind::ToRdf)
+		} else {
+			None
+		}
+	}
+}
+
+/// Expected result of a test.
+#[derive(Clone, Debug)]
+pub enum Expect {
+	/// The test must succeed, with the output located at the given URL.
+	Output(IriBuf),
+
+	/// The test must succeed (syntax tests).
+	Success,
+
+	/// The test must fail with the given error code.
+	Error(String)
+}
+
+/// Test-suite entry.
+#[derive(Clone, Debug)]
+pub struct Test {
+	/// Test identifier.
+	pub id: String,
+
+	/// Test name.
+	pub name: String,
+
+	/// Tested algorithm, if known.
+	pub kind: Option<Kind>,
+
+	/// URL of the input document.
+	pub input: IriBuf,
+
+	/// URL of the context used for compaction, if any.
+	pub context: Option<IriBuf>,
+
+	/// Expected result.
+	pub expect: Expect,
+
+	/// Processing mode.
+	pub processing_mode: ProcessingMode,
+
+	/// Specification version the test is restricted to, if any.
+	pub spec_version: Option<String>,
+
+	/// Whether the test is normative.
+	pub normative: bool,
+
+	/// Base IRI overriding the input document URL, if any.
+	pub base: Option<IriBuf>,
+
+	/// URL of the context to process before expanding the input, if any.
+	pub expand_context: Option<IriBuf>,
+
+	/// Value of the `compactArrays` option.
+	pub compact_arrays: bool,
+
+	/// Value of the `compactToRelative` option.
+	pub compact_to_relative: bool,
+
+	/// Value of the `useNativeTypes` option.
+	pub use_native_types: bool,
+
+	/// Value of the `rdfDirection` option, if any.
+	pub rdf_direction: Option<String>,
+
+	/// Value of the `produceGeneralizedRdf` option.
+	pub produce_generalized_rdf: bool
+}
+
+impl Test {
+	/// Parse a manifest entry, resolving URLs against the manifest URL.
+	fn from_entry(manifest_url: Iri, entry: &JsonValue) -> Option<Test> {
+		let resolve = |value: &JsonValue| -> Option<IriBuf> {
+			IriRef::new(value.as_str()?).ok().map(|iri_ref| iri_ref.resolved(manifest_url))
+		};
+
+		let types: Vec<&str> = util::as_array(&entry["@type"]).iter().filter_map(JsonValue::as_str).collect();
+		let kind = types.iter().filter_map(|ty| Kind::from_type(ty)).next();
+
+		let expect = if types.iter().any(|ty| ty.ends_with("NegativeEvaluationTest") || ty.ends_with("NegativeSyntaxTest")) {
+			Expect::Error(entry["expectErrorCode"].as_str().unwrap_or("").to_string())
+		} else {
+			match resolve(&entry["expect"]) {
+				Some(url) => Expect::Output(url),
+				None => Expect::Success
+			}
+		};
+
+		let option = &entry["option"];
+		let processing_mode = option["processingMode"].as_str().and_then(|mode| ProcessingMode::try_from(mode).ok()).unwrap_or_default();
+
+		Some(Test {
+			id: entry["@id"].as_str().unwrap_or("").to_string(),
+			name: entry["name"].as_str().unwrap_or("").to_string(),
+			kind,
+			input: resolve(&entry["input"])?,
+			context: resolve(&entry["context"]),
+			expect,
+			processing_mode,
+			spec_version: option["specVersion"].as_str().map(|v| v.to_string()),
+			normative: option["normative"].as_bool().unwrap_or(true),
+			base: resolve(&option["base"]),
+			expand_context: resolve(&option["expandContext"]),
+			compact_arrays: option["compactArrays"].as_bool().unwrap_or(true),
+			compact_to_relative: option["compactToRelative"].as_bool().unwrap_or(true),
+			use_native_types: option["useNativeTypes"].as_bool().unwrap_or(false),
+			rdf_direction: option["rdfDirection"].as_str().map(|d| d.to_string()),
+			produce_generalized_rdf: option["produceGeneralizedRdf"].as_bool().unwrap_or(false)
+		})
+	}
+
+	/// Base URL used to process the input document.
+	fn base_url(&self) -> Iri {
+		match &self.base {
+			Some(base) => base.as_iri(),
+			None => self.input.as_iri()
+		}
+	}
+
+	fn processing_options(&self) -> ProcessingOptions {
+		ProcessingOptions {
+			processing_mode: self.processing_mode,
+			..ProcessingOptions::default()
+		}
+	}
+
+	fn expansion_options(&self) -> expansion::Options {
+		expansion::Options {
+			processing_mode: self.processing_mode,
+			ordered: false,
+			..expansion::Options::default()
+		}
+	}
+
+	fn compaction_options(&self) -> compaction::Options {
+		compaction::Options {
+			processing_mode: self.processing_mode,
+			compact_arrays: self.compact_arrays,
+			compact_to_relative: self.compact_to_relative,
+			ordered: false,
+			..compaction::Options::default()
+		}
+	}
+
+	/// Reason to skip the test, if any.
+	fn skip_reason(&self) -> Option<&'static str> {
+		if !self.normative {
+			return Some("non normative")
+		}
+
+		if let Some(version) = &self.spec_version {
+			if version != "json-ld-1.1" {
+				return Some("unsupported specification version")
+			}
+		}
+
+		if self.rdf_direction.is_some() {
+			return Some("unsupported option `rdfDirection`")
+		}
+
+		if self.produce_generalized_rdf {
+			return Some("unsupported option `produceGeneralizedRdf`")
+		}
+
+		if self.use_native_types {
+			return Some("unsupported option `useNativeTypes`")
+		}
+
+		match self.kind {
+			None => Some("unknown test type"),
+			#[cfg(not(feature="oxrdf"))]
+			Some(Kind::ToRdf) => Some("RDF serialization requires the `oxrdf` feature"),
+			_ => None
+		}
+	}
+}
+
+/// Outcome of a test.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Outcome {
+	Passed,
+
+	/// The test failed, for the given reason.
+	Failed(String),
+
+	/// The test was not run, for the given reason.
+	Skipped(String)
+}
+
+/// Result of a test.
+#[derive(Clone, Debug)]
+pub struct TestResult {
+	/// Test identifier.
+	pub id: String,
+
+	/// Test name.
+	pub name: String,
+
+	/// Outcome of the test.
+	pub outcome: Outcome
+}
+
+impl fmt::Display for TestResult {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match &self.outcome {
+			Outcome::Passed => write!(f, "{} passed", self.id),
+			Outcome::Failed(reason) => write!(f, "{} failed: {}", self.id, reason),
+			Outcome::Skipped(reason) => write!(f, "{} skipped: {}", self.id, reason)
+		}
+	}
+}
+
+/// Results of all the tests of a manifest.
+#[derive(Clone, Debug)]
+pub struct Summary {
+	/// Results of the tests, in the manifest order.
+	pub results: Vec<TestResult>
+}
+
+impl Summary {
+	/// Number of passed tests.
+	pub fn passed(&self) -> usize {
+		self.results.iter().filter(|r| r.outcome == Outcome::Passed).count()
+	}
+
+	/// Number of failed tests.
+	pub fn failed(&self) -> usize {
+		self.results.iter().filter(|r| matches!(r.outcome, Outcome::Failed(_))).count()
+	}
+
+	/// Number of skipped tests.
+	pub fn skipped(&self) -> usize {
+		self.results.iter().filter(|r| matches!(r.outcome, Outcome::Skipped(_))).count()
+	}
+
+	/// Checks that no test failed.
+	pub fn is_success(&self) -> bool {
+		self.failed() == 0
+	}
+}
+
+/// Loader of the test-suite documents that are not JSON documents.
+///
+/// The expected outputs of the toRdf tests are N-Quads documents,
+/// that cannot be loaded by a JSON document [`Loader`].
+pub trait TextLoader {
+	/// Load the content of the document behind the given URL, as text.
+	fn load_text<'a>(&'a mut self, url: Iri<'_>) -> BoxFuture<'a, Result<String, Error>>;
+}
+
+impl TextLoader for FsLoader {
+	fn load_text<'a>(&'a mut self, url: Iri<'_>) -> BoxFuture<'a, Result<String, Error>> {
+		let result = FsLoader::load_text(self, url);
+		async move {
+			result
+		}.boxed()
+	}
+}
+
+/// Load the tests of the manifest located at the given URL.
+pub fn load_manifest<'a, L: Send + Sync + Loader<Document = JsonValue>>(url: Iri<'a>, loader: &'a mut L) -> BoxFuture<'a, Result<Vec<Test>, Error>> {
+	async move {
+		let manifest = loader.load(url).await?.into_document();
+
+		if !manifest.is_object() {
+			return Err(Error::from(ErrorCode::LoadingDocumentFailed).with_subject(url.as_str()))
+		}
+
+		Ok(util::as_array(&manifest["sequence"]).iter().filter_map(|entry| Test::from_entry(url, entry)).collect())
+	}.boxed()
+}
+
+/// Run the given test.
+pub fn run_test<'a, L: Send + Sync + Loader<Document = JsonValue> + TextLoader>(test: &'a Test, loader: &'a mut L) -> BoxFuture<'a, TestResult> {
+	async move {
+		let outcome = match test.skip_reason() {
+			Some(reason) => Outcome::Skipped(reason.to_string()),
+			#[cfg(feature="oxrdf")]
+			None if test.kind == Some(Kind::ToRdf) => {
+				let result = run_to_rdf(test, loader).await;
+				check_to_rdf(test, result, loader).await
+			},
+			None => {
+				let result = match test.kind {
+					Some(Kind::Compact) => run_compact(test, loader).await,
+					Some(Kind::Flatten) => run_flatten(test, loader).await,
+					_ => run_expand(test, loader).await
+				};
+
+				check(test, result, loader).await
+			}
+		};
+
+		TestResult {
+			id: test.id.clone(),
+			name: test.name.clone(),
+			outcome
+		}
+	}.boxed()
+}
+
+/// Load and run all the tests of the manifest located at the given URL.
+pub fn run_manifest<'a, L: Send + Sync + Loader<Document = JsonValue> + TextLoader>(url: Iri<'a>, loader: &'a mut L) -> BoxFuture<'a, Result<Summary, Error>> {
+	async move {
+		let tests = load_manifest(url, loader).await?;
+		let mut results = Vec::with_capacity(tests.len());
+
+		for test in &tests {
+			let result = run_test(test, loader).await;
+			debug!("{}", result);
+			results.push(result)
+		}
+
+		Ok(Summary { results })
+	}.boxed()
+}
+
+/// Compare the result of a test with the expected result.
+async fn check<L: Send + Sync + Loader<Document = JsonValue>>(test: &Test, result: Result<JsonValue, Error>, loader: &mut L) -> Outcome {
+	match (&test.expect, result) {
+		(Expect::Output(url), Ok(output)) => match loader.load(url.as_iri()).await {
+			Ok(expected) => {
+				if util::json_ld_eq(&output, &expected.into_document()) {
+					Outcome::Passed
+				} else {
+					Outcome::Failed(format!("unexpected output:\n{}", output.pretty(2)))
+				}
+			},
+			Err(e) => Outcome::Failed(format!("unable to load the expected output: {}", e))
+		},
+		(_, result) => check_status(test, result.map(|_| ()))
+	}
+}
+
+/// Compare the success or failure of a test with the expected result, ignoring the output.
+fn check_status(test: &Test, result: Result<(), Error>) -> Outcome {
+	match (&test.expect, result) {
+		(Expect::Error(code), Ok(())) => Outcome::Failed(format!("succeeded where it should have failed with code: {}", code)),
+		(Expect::Error(code), Err(e)) => {
+			if e.code().as_str() == code {
+				Outcome::Passed
+			} else {
+				Outcome::Failed(format!("failed with `{}` instead of `{}`", e.code(), code))
+			}
+		},
+		(_, Ok(())) => Outcome::Passed,
+		(_, Err(e)) => Outcome::Failed(format!("unexpected error: {}", e))
+	}
+}
+
+/// Compare the RDF output of a toRdf test with the expected N-Quads document.
+#[cfg(feature="oxrdf")]
+async fn check_to_rdf<L: TextLoader>(test: &Test, result: Result<Vec<::oxrdf::Quad>, Error>, loader: &mut L) -> Outcome {
+	match (&test.expect, result) {
+		(Expect::Output(url), Ok(output)) => match loader.load_text(url.as_iri()).await {
+			Ok(expected) => match nquads::parse(&expected) {
+				Ok(expected) => {
+					if nquads::isomorphic(&output, &expected) {
+						Outcome::Passed
+					} else {
+						Outcome::Failed(format!("unexpected output:\n{}", nquads::to_string(&output)))
+					}
+				},
+				Err(line) => Outcome::Failed(format!("invalid expected output at line {}", line))
+			},
+			Err(e) => Outcome::Failed(format!("unable to load the expected output: {}", e))
+		},
+		(_, result) => check_status(test, result.map(|_| ()))
+	}
+}
+
+async fn expand<L: Send + Sync + Loader<Document = JsonValue>>(test: &Test, loader: &mut L) -> Result<ExpandedDocument<IriBuf>, Error> {
+	let input = loader.load(test.input.as_iri()).await?;
+	let base_url = test.base_url();
+	let mut active_context: JsonContext<IriBuf> = JsonContext::new(Some(base_url));
+
+	if let Some(context_url) = &test.expand_context {
+		let local_context = ContextLoader::load_context(loader, context_url.as_iri()).await?.into_context();
+		active_context = local_context.process_with(&active_context, loader, Some(base_url), test.processing_options()).await?.into_inner();
+	}
+
+	Document::<IriBuf>::expand_with(&input, Some(base_url), &active_context, loader, test.expansion_options()).await
+}
+
+async fn run_expand<L: Send + Sync + Loader<Document = JsonValue>>(test: &Test, loader: &mut L) -> Result<JsonValue, Error> {
+	Ok(expand(test, loader).await?.as_json())
+}
+
+#[cfg(feature="oxrdf")]
+async fn run_to_rdf<L: Send + Sync + Loader<Document = JsonValue>>(test: &Test, loader: &mut L) -> Result<Vec<::oxrdf::Quad>, Error> {
+	let flattened = expand(test, loader).await?.flatten(crate::BlankIdCounter::new())?;
+	Ok(crate::oxrdf::to_rdf(&flattened, &crate::oxrdf::Datatypes::default(), crate::BlankIdCounter::with_prefix("l")))
+}
+
+async fn run_compact<L: Send + Sync + Loader<Document = JsonValue>>(test: &Test, loader: &mut L) -> Result<JsonValue, Error> {
+	let input = loader.load(test.input.as_iri()).await?;
+	let base_url = test.base_url();
+	let base_json_context = json::object! {
+		"@base": JsonValue::from(base_url.as_str())
+	};
+	let mut context: Processed<JsonValue, JsonContext<IriBuf>> = Processed::new(base_json_context, JsonContext::new(Some(base_url)));
+
+	if let Some(context_url) = &test.context {
+		let local_context = ContextLoader::load_context(loader, context_url.as_iri()).await?.into_context();
+		context = local_context.process_with(context.as_ref(), loader, Some(base_url), test.processing_options()).await?.owned();
+	}
+
+	Document::<IriBuf>::compact_with(&input, Some(base_url), &context, loader, test.compaction_options()).await
+}
+
+async fn run_flatten<L: Send + Sync + Loader<Document = JsonValue>>(test: &Test, loader: &mut L) -> Result<JsonValue, Error> {
+	let input = loader.load(test.input.as_iri()).await?;
+	let base_url = test.base_url();
+	let options = compaction::Options {
+		base: Some(IriBuf::from(base_url)),
+		..test.compaction_options()
+	};
+
+	match &test.context {
+		Some(context_url) => {
+			let base_json_context = json::object! {
+				"@base": JsonValue::from(base_url.as_str())
+			};
+			let context: Processed<JsonValue, JsonContext<IriBuf>> = Processed::new(base_json_context, JsonContext::new(Some(base_url)));
+			let local_context = ContextLoader::load_context(loader, context_url.as_iri()).await?.into_context();
+			let context = local_context.process_with(context.as_ref(), loader, Some(base_url), test.processing_options()).await?.owned();
+			Document::<IriBuf>::flatten_with(&input, Some(&context), loader, options).await
+		},
+		None => Document::<IriBuf>::flatten_with(&input, None::<&Processed<JsonValue, JsonContext<IriBuf>>>, loader, options).await
+	}
+}
+
+/// Minimal N-Quads support, to compare the output of the toRdf tests with the expected documents.
+#[cfg(feature="oxrdf")]
+mod nquads {
+	use std::iter::Peekable;
+	use std::str::Chars;
+	use ::oxrdf::{
+		Quad,
+		Subject,
+		Term,
+		GraphName,
+		NamedNode,
+		BlankNode,
+		Literal,
+		Dataset
+	};
+
+	/// Parse an N-Quads document.
+	///
+	/// Returns the number of the first invalid line in case of error.
+	pub fn parse(content: &str) -> Result<Vec<Quad>, usize> {
+		let mut quads = Vec::new();
+
+		for (i, line) in content.lines().enumerate() {
+			let mut parser = Parser {
+				chars: line.chars().peekable()
+			};
+
+			if !parser.is_end() {
+				quads.push(parser.quad().ok_or(i + 1)?)
+			}
+		}
+
+		Ok(quads)
+	}
+
+	/// Checks that the two given sets of quads are equal, up to blank node renaming.
+	pub fn isomorphic(a: &[Quad], b: &[Quad]) -> bool {
+		let mut a: Dataset = a.iter().collect();
+		let mut b: Dataset = b.iter().collect();
+		a.canonicalize();
+		b.canonicalize();
+		a == b
+	}
+
+	/// Format the given quads as an N-Quads document.
+	pub fn to_string(quads: &[Quad]) -> String {
+		let mut result = String::new();
+		for quad in quads {
+			result.push_str(&format!("{} .\n", quad))
+		}
+
+		result
+	}
+
+	enum Token {
+		Iri(String),
+		Blank(String),
+		Literal(Literal)
+	}
+
+	struct Parser<'a> {
+		chars: Peekable<Chars<'a>>
+	}
+
+	impl<'a> Parser<'a> {
+		fn skip_whitespaces(&mut self) {
+			while let Some(c) = self.chars.peek() {
+				if c.is_whitespace() {
+					self.chars.next();
+				} else {
+					break
+				}
+			}
+		}
+
+		/// Checks that the rest of the line is empty or a comment.
+		fn is_end(&mut self) -> bool {
+			self.skip_whitespaces();
+			matches!(self.chars.peek(), None | Some('#'))
+		}
+
+		fn quad(&mut self) -> Option<Quad> {
+			let subject: Subject = match self.token()? {
+				Token::Iri(iri) => NamedNode::new_unchecked(iri).into(),
+				Token::Blank(id) => BlankNode::new_unchecked(id).into(),
+				Token::Literal(_) => return None
+			};
+
+			let predicate = match self.token()? {
+				Token::Iri(iri) => NamedNode::new_unchecked(iri),
+				_ => return None
+			};
+
+			let object: Term = match self.token()? {
+				Token::Iri(iri) => NamedNode::new_unchecked(iri).into(),
+				Token::Blank(id) => BlankNode::new_unchecked(id).into(),
+				Token::Literal(lit) => lit.into()
+			};
+
+			self.skip_whitespaces();
+			let graph: GraphName = if self.chars.peek() == Some(&'.') {
+				GraphName::DefaultGraph
+			} else {
+				match self.token()? {
+					Token::Iri(iri) => NamedNode::new_unchecked(iri).into(),
+					Token::Blank(id) => BlankNode::new_unchecked(id).into(),
+					Token::Literal(_) => return None
+				}
+			};
+
+			self.skip_whitespaces();
+			if self.chars.next() != Some('.') || !self.is_end() {
+				return None
+			}
+
+			Some(Quad::new(subject, predicate, object, graph))
+		}
+
+		fn token(&mut self) -> Option<Token> {
+			self.skip_whitespaces();
+			match self.chars.next()? {
+				'<' => Some(Token::Iri(self.until('>')?)),
+				'_' => {
+					if self.chars.next()? != ':' {
+						return None
+					}
+
+					let mut id = String::new();
+					while let Some(&c) = self.chars.peek() {
+						if c.is_alphanumeric() || c == '_' || c == '-' {
+							id.push(c);
+							self.chars.next();
+						} else {
+							break
+						}
+					}
+
+					if id.is_empty() {
+						None
+					} else {
+						Some(Token::Blank(id))
+					}
+				},
+				'"' => {
+					let value = self.until('"')?;
+					match self.chars.peek() {
+						Some('@') => {
+							self.chars.next();
+							let mut lang = String::new();
+							while let Some(&c) = self.chars.peek() {
+								if c.is_ascii_alphanumeric() || c == '-' {
+									lang.push(c);
+									self.chars.next();
+								} else {
+									break
+								}
+							}
+
+							Some(Token::Literal(Literal::new_language_tagged_literal_unchecked(value, lang)))
+						},
+						Some('^') => {
+							self.chars.next();
+							if self.chars.next()? != '^' || self.chars.next()? != '<' {
+								return None
+							}
+
+							let ty = self.until('>')?;
+							Some(Token::Literal(Literal::new_typed_literal(value, NamedNode::new_unchecked(ty))))
+						},
+						_ => Some(Token::Literal(Literal::new_simple_literal(value)))
+					}
+				},
+				_ => None
+			}
+		}
+
+		/// Read the characters up to the given delimiter, decoding escape sequences.
+		fn until(&mut self, end: char) -> Option<String> {
+			let mut result = String::new();
+			loop {
+				match self.chars.next()? {
+					'\\' => result.push(self.escape()?),
+					c if c == end => return Some(result),
+					c => result.push(c)
+				}
+			}
+		}
+
+		fn escape(&mut self) -> Option<char> {
+			match self.chars.next()? {
+				't' => Some('\t'),
+				'b' => Some('\u{8}'),
+				'n' => Some('\n'),
+				'r' => Some('\r'),
+				'f' => Some('\u{c}'),
+				'"' => Some('"'),
+				'\'' => Some('\''),
+				'\\' => Some('\\'),
+				'u' => self.hex(4),
+				'U' => self.hex(8),
+				_ => None
+			}
+		}
+
+		fn hex(&mut self, len: usize) -> Option<char> {
+			let mut code = 0u32;
+			for _ in 0..len {
+				code = code * 16 + self.chars.next()?.to_digit(16)?;
+			}
+
+			std::char::from_u32(code)
+		}
+	}
+}
@@ -0,0 +1,191 @@
+use crate::{
+	Id,
+	Reference,
+	Indexed,
+	Object,
+	Node,
+	ExpandedDocument
+};
+
+/// Step of a [`Path`] from the root of a document to one of its nested objects.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub enum Segment<'a, T: Id> {
+	/// Top-level object at the given position.
+	Root(usize),
+
+	/// Object at the given position in the values of a property.
+	Property(&'a Reference<T>, usize),
+
+	/// Node at the given position in the values of a reverse property.
+	ReverseProperty(&'a Reference<T>, usize),
+
+	/// Item at the given position in a list.
+	List(usize),
+
+	/// Object at the given position in a graph.
+	///
+	/// Since graphs are unordered, the position is only meaningful during the traversal.
+	Graph(usize),
+
+	/// Node at the given position in the included nodes.
+	///
+	/// Since included nodes are unordered, the position is only meaningful during the traversal.
+	Included(usize),
+
+	/// Node at the given position in the annotations.
+	///
+	/// Since annotations are unordered, the position is only meaningful during the traversal.
+	Annotation(usize)
+}
+
+/// Path from the root of a document to one of its nested objects.
+pub type Path<'a, T> = Vec<Segment<'a, T>>;
+
+/// Item visited during a traversal.
+pub enum Visited<'a, T: Id> {
+	/// An object: top-level object, property value, list item or graph member.
+	Object(&'a Indexed<Object<T>>),
+
+	/// A node: included node or reverse property value.
+	Node(&'a Indexed<Node<T>>)
+}
+
+impl<'a, T: Id> Visited<'a, T> {
+	/// Returns the visited node, if any.
+	///
+	/// This is either a node object or an included/reverse node.
+	pub fn as_node(&self) -> Option<&'a Node<T>> {
+		match self {
+			Visited::Object(object) => match object.inner() {
+				Object::Node(node) => Some(node),
+				_ => None
+			},
+			Visited::Node(node) => Some(node.inner())
+		}
+	}
+
+	/// Returns the index of the visited item, if any.
+	pub fn index(&self) -> Option<&'a str> {
+		match self {
+			Visited::Object(object) => object.index(),
+			Visited::Node(node) => node.index()
+		}
+	}
+}
+
+/// Node identifier or type that is neither an IRI nor a blank node identifier.
+///
+/// Such values are kept as [`Lenient::Unknown`](`crate::Lenient::Unknown`) by the expansion.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Unresolved<'a> {
+	/// Unresolved node identifier.
+	Id(&'a str),
+
+	/// Unresolved node type.
+	Type(&'a str)
+}
+
+impl<'a> Unresolved<'a> {
+	/// Returns the unresolved value.
+	pub fn as_str(&self) -> &'a str {
+		match self {
+			Unresolved::Id(value) => value,
+			Unresolved::Type(value) => value
+		}
+	}
+}
+
+/// Depth-first traversal of every object nested in a document.
+///
+/// Created with [`ExpandedDocument::traverse`].
+/// Each item is visited before its children.
+pub struct Traverse<'a, T: Id> {
+	stack: Vec<(Path<'a, T>, Visited<'a, T>)>
+}
+
+impl<'a, T: Id> Traverse<'a, T> {
+	pub(crate) fn new(doc: &'a ExpandedDocument<T>) -> Traverse<'a, T> {
+		let mut stack: Vec<_> = doc.objects().iter().enumerate().map(|(i, object)| {
+			(vec![Segment::Root(i)], Visited::Object(object))
+		}).collect();
+		stack.reverse();
+
+		Traverse {
+			stack
+		}
+	}
+
+	fn push_children(&mut self, path: &Path<'a, T>, item: &Visited<'a, T>) {
+		let mut children = Vec::new();
+
+		let child = |segment| {
+			let mut child_path = path.clone();
+			child_path.push(segment);
+			child_path
+		};
+
+		if let Visited::Object(object) = item {
+			match object.inner() {
+				Object::List(items) => {
+					for (i, item) in items.iter().enumerate() {
+						children.push((child(Segment::List(i)), Visited::Object(item)))
+					}
+				},
+				Object::Graph(graph) => {
+					for (i, object) in graph.iter().enumerate() {
+						children.push((child(Segment::Graph(i)), Visited::Object(object)))
+					}
+				},
+				_ => ()
+			}
+		}
+
+		if let Some(node) = item.as_node() {
+			if let Some(graph) = &node.graph {
+				for (i, object) in graph.iter().enumerate() {
+					children.push((child(Segment::Graph(i)), Visited::Object(object)))
+				}
+			}
+
+			if let Some(included) = &node.included {
+				for (i, node) in included.iter().enumerate() {
+					children.push((child(Segment::Included(i)), Visited::Node(node)))
+				}
+			}
+
+			if let Some(annotation) = &node.annotation {
+				for (i, node) in annotation.iter().enumerate() {
+					children.push((child(Segment::Annotation(i)), Visited::Node(node)))
+				}
+			}
+
+			for (prop, values) in &node.properties {
+				for (i, value) in values.iter().enumerate() {
+					children.push((child(Segment::Property(prop, i)), Visited::Object(value)))
+				}
+			}
+
+			for (prop, nodes) in &node.reverse_properties {
+				for (i, node) in nodes.iter().enumerate() {
+					children.push((child(Segment::ReverseProperty(prop, i)), Visited::Node(node)))
+				}
+			}
+		}
+
+		self.stack.extend(children.into_iter().rev())
+	}
+}
+
+impl<'a, T: Id> Iterator for Traverse<'a, T> {
+	type Item = (Path<'a, T>, Visited<'a, T>);
+
+	fn next(&mut self) -> Option<Self::Item> {
+		match self.stack.pop() {
+			Some((path, item)) => {
+				self.push_children(&path, &item);
+				Some((path, item))
+			},
+			None => None
+		}
+	}
+}
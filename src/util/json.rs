@@ -1,5 +1,6 @@
+use std::fmt;
 use std::collections::HashSet;
-use json::JsonValue;
+use json::{JsonValue, number::Number};
 use langtag::{
 	LanguageTag,
 	LanguageTagBuf
@@ -73,7 +74,93 @@ impl<T: AsJson> AsJson for HashSet<T> {
 	}
 }
 
+/// Returns a copy of the given JSON value where every string and object key
+/// is in Unicode Normalization Form C (NFC).
+#[cfg(feature="unicode-normalization")]
+pub fn json_nfc(value: &JsonValue) -> JsonValue {
+	use unicode_normalization::UnicodeNormalization;
+	match value {
+		JsonValue::Short(_) | JsonValue::String(_) => value.as_str().unwrap().nfc().collect::<String>().into(),
+		JsonValue::Array(items) => JsonValue::Array(items.iter().map(json_nfc).collect()),
+		JsonValue::Object(obj) => {
+			let mut result = json::object::Object::with_capacity(obj.len());
+			for (key, value) in obj.iter() {
+				result.insert(&key.nfc().collect::<String>(), json_nfc(value))
+			}
+			JsonValue::Object(result)
+		},
+		value => value.clone()
+	}
+}
+
+/// Checks if the nesting depth of the given JSON value exceeds `max`.
+///
+/// Scalar values have a depth of 0, and each array or object adds one level.
+/// The value is traversed iteratively, so this function does not itself overflow the stack
+/// on deeply nested values.
+pub fn json_depth_exceeds(value: &JsonValue, max: usize) -> bool {
+	let mut stack = vec![(value, 0)];
+	while let Some((value, depth)) = stack.pop() {
+		match value {
+			JsonValue::Array(items) => {
+				if depth + 1 > max {
+					return true
+				}
+
+				stack.extend(items.iter().map(|item| (item, depth + 1)))
+			},
+			JsonValue::Object(obj) => {
+				if depth + 1 > max {
+					return true
+				}
+
+				stack.extend(obj.iter().map(|(_, item)| (item, depth + 1)))
+			},
+			_ => ()
+		}
+	}
+
+	false
+}
+
+/// Number comparison mode of [`json_ld_eq_with`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum NumberComparison {
+	/// Numbers are compared literally, using the `PartialEq` implementation of the JSON backend.
+	Literal,
+
+	/// Numbers are compared by value, following the value rules of the RDF serialization algorithm.
+	///
+	/// Numbers with no fractional part whose absolute value is lower than 10^21
+	/// are `xsd:integer` values, compared exactly (`1` equals `1.0` and `10` equals `1e1`).
+	/// Other numbers are `xsd:double` values, compared as `f64`.
+	Value
+}
+
+impl Default for NumberComparison {
+	fn default() -> NumberComparison {
+		NumberComparison::Literal
+	}
+}
+
+/// Compares two JSON-LD documents semantically.
+///
+/// Arrays are compared as unordered sets, except for the values of `@list` entries
+/// whose order is significant.
+/// Objects are compared entry by entry, regardless of the order of their keys.
+/// Other values are compared literally.
+///
+/// This is meant to compare expanded or compacted documents.
+/// To compare documents that may use different contexts, see [`Document::compare`](`crate::Document::compare`).
+/// To compare numbers by value, see [`json_ld_eq_with`].
 pub fn json_ld_eq(a: &JsonValue, b: &JsonValue) -> bool {
+	json_ld_eq_with(a, b, NumberComparison::Literal)
+}
+
+/// Compares two JSON-LD documents semantically, using the given number comparison mode.
+///
+/// Same as [`json_ld_eq`], except that numbers are compared according to `numbers`.
+pub fn json_ld_eq_with(a: &JsonValue, b: &JsonValue, numbers: NumberComparison) -> bool {
 	match (a, b) {
 		(JsonValue::Array(a), JsonValue::Array(b)) if a.len() == b.len() => {
 			let mut selected = Vec::with_capacity(a.len());
@@ -81,7 +168,7 @@ pub fn json_ld_eq(a: &JsonValue, b: &JsonValue) -> bool {
 
 			'a_items: for item in a {
 				for i in 0..b.len() {
-					if !selected[i] && json_ld_eq(&b[i], item) {
+					if !selected[i] && json_ld_eq_with(&b[i], item, numbers) {
 						selected[i] = true;
 						continue 'a_items
 					}
@@ -97,19 +184,19 @@ pub fn json_ld_eq(a: &JsonValue, b: &JsonValue) -> bool {
 						match (value_a, value_b) {
 							(JsonValue::Array(item_a), JsonValue::Array(item_b)) if item_a.len() == item_b.len() => {
 								for i in 0..item_a.len() {
-									if !json_ld_eq(&item_a[i], &item_b[i]) {
+									if !json_ld_eq_with(&item_a[i], &item_b[i], numbers) {
 										return false
 									}
 								}
 							},
 							_ => {
-								if !json_ld_eq(value_a, value_b) {
+								if !json_ld_eq_with(value_a, value_b, numbers) {
 									return false
 								}
 							}
 						}
 					} else {
-						if !json_ld_eq(value_a, value_b) {
+						if !json_ld_eq_with(value_a, value_b, numbers) {
 							return false
 						}
 					}
@@ -118,8 +205,97 @@ pub fn json_ld_eq(a: &JsonValue, b: &JsonValue) -> bool {
 				}
 			}
 		},
+		(JsonValue::Number(a), JsonValue::Number(b)) if numbers == NumberComparison::Value => {
+			return number_value_eq(a, b)
+		},
 		_ => return a == b
 	}
 
 	true
 }
+
+/// Compares two numbers by value, following the RDF serialization value rules.
+fn number_value_eq(a: &Number, b: &Number) -> bool {
+	match (rdf_integer(a), rdf_integer(b)) {
+		(Some(a), Some(b)) => a == b,
+		(None, None) => f64::from(*a) == f64::from(*b),
+		_ => false
+	}
+}
+
+/// Returns the normalized `(positive, mantissa, exponent)` parts of the given number,
+/// if it is an `xsd:integer` value.
+///
+/// Trailing zeros of the mantissa are moved to the exponent, so that equal values
+/// have equal parts.
+fn rdf_integer(number: &Number) -> Option<(bool, u64, i16)> {
+	let (positive, mut mantissa, mut exponent) = number.as_parts();
+	if mantissa == 0 {
+		return Some((true, 0, 0))
+	}
+
+	while mantissa % 10 == 0 {
+		mantissa /= 10;
+		exponent = exponent.saturating_add(1)
+	}
+
+	if exponent < 0 {
+		return None
+	}
+
+	// The value is lower than 10^21 iff its number of digits is at most 21.
+	let digits = mantissa.to_string().len() as i32;
+	if digits + exponent as i32 > 21 {
+		return None
+	}
+
+	Some((positive, mantissa, exponent))
+}
+
+/// Returns a canonical version of the given JSON-LD document.
+///
+/// Object entries are sorted by key, and the items of arrays are sorted by their serialization,
+/// except for `@list` arrays whose order is significant.
+/// `@value` entries are left untouched, since they may contain JSON literals.
+pub fn json_ld_canonical(json: &JsonValue) -> JsonValue {
+	match json {
+		JsonValue::Array(ary) => {
+			let mut items: Vec<_> = ary.iter().map(|item| {
+				let item = json_ld_canonical(item);
+				(item.dump(), item)
+			}).collect();
+			items.sort_by(|(a, _), (b, _)| a.cmp(b));
+			JsonValue::Array(items.into_iter().map(|(_, item)| item).collect())
+		},
+		JsonValue::Object(obj) => {
+			let mut entries: Vec<_> = obj.iter().collect();
+			entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+			let mut result = json::object::Object::with_capacity(entries.len());
+			for (key, value) in entries {
+				let value = match (key, value) {
+					("@value", value) => value.clone(),
+					("@list", JsonValue::Array(items)) => JsonValue::Array(items.iter().map(json_ld_canonical).collect()),
+					(_, value) => json_ld_canonical(value)
+				};
+
+				result.insert(key, value)
+			}
+
+			JsonValue::Object(result)
+		},
+		json => json.clone()
+	}
+}
+
+/// Format the canonical version of the given JSON-LD document.
+///
+/// The output is pretty-printed when the alternate flag (`{:#}`) is set.
+pub fn fmt_canonical(json: &JsonValue, f: &mut fmt::Formatter) -> fmt::Result {
+	let json = json_ld_canonical(json);
+	if f.alternate() {
+		write!(f, "{}", json.pretty(2))
+	} else {
+		write!(f, "{}", json.dump())
+	}
+}
@@ -1,11 +1,14 @@
 //! Utility functions.
 
 use std::hash::{Hash, Hasher};
-use std::collections::{HashSet, HashMap, hash_map::DefaultHasher};
+use std::convert::TryFrom;
+use std::collections::{HashSet, hash_map::DefaultHasher};
 use ::json::{JsonValue, number::Number};
 
 mod json;
+pub mod small_map;
 pub use self::json::*;
+pub use self::small_map::SmallMap;
 
 pub fn as_array(json: &JsonValue) -> &[JsonValue] {
 	match json {
@@ -21,6 +24,60 @@ pub fn hash_json_number<H: Hasher>(number: &Number, hasher: &mut H) {
 	exponent.hash(hasher);
 }
 
+/// Returns the given number as a signed integer, if it is integral and fits in an `i64`.
+///
+/// Unlike `Number::as_fixed_point_i64`, this never truncates the fractional part
+/// nor wraps around on overflow.
+pub fn json_number_as_i64(number: &Number) -> Option<i64> {
+	let (positive, mantissa, exponent) = number.as_parts();
+	let magnitude = integral_magnitude(mantissa, exponent)?;
+	if positive {
+		i64::try_from(magnitude).ok()
+	} else if magnitude <= i64::MAX as u64 + 1 {
+		Some((magnitude as i64).wrapping_neg())
+	} else {
+		None
+	}
+}
+
+/// Returns the given number as an unsigned integer, if it is integral, non-negative
+/// and fits in an `u64`.
+///
+/// Unlike `Number::as_fixed_point_u64`, this never truncates the fractional part
+/// nor wraps around on overflow.
+pub fn json_number_as_u64(number: &Number) -> Option<u64> {
+	let (positive, mantissa, exponent) = number.as_parts();
+	let magnitude = integral_magnitude(mantissa, exponent)?;
+	if positive || magnitude == 0 {
+		Some(magnitude)
+	} else {
+		None
+	}
+}
+
+/// Computes `mantissa * 10^exponent`, if it is an integer that fits in an `u64`.
+fn integral_magnitude(mut mantissa: u64, exponent: i16) -> Option<u64> {
+	if mantissa == 0 {
+		return Some(0)
+	}
+
+	if exponent < 0 {
+		for _ in exponent..0 {
+			if mantissa % 10 != 0 {
+				return None
+			}
+
+			mantissa /= 10
+		}
+	} else {
+		for _ in 0..exponent {
+			mantissa = mantissa.checked_mul(10)?
+		}
+	}
+
+	Some(mantissa)
+}
+
 pub fn hash_json<H: Hasher>(value: &JsonValue, hasher: &mut H) {
 	match value {
 		JsonValue::Null => (),
@@ -64,7 +121,7 @@ pub fn hash_set_opt<T: Hash, H: Hasher>(set_opt: &Option<HashSet<T>>, hasher: &m
 	}
 }
 
-pub fn hash_map<K: Hash, V: Hash, H: Hasher>(map: &HashMap<K, V>, hasher: &mut H) {
+pub fn hash_map<'a, K: 'a + Hash, V: 'a + Hash, M, H: Hasher>(map: &'a M, hasher: &mut H) where &'a M: IntoIterator<Item = (&'a K, &'a V)> {
 	// Elements must be combined with a associative and commutative operation •.
 	// (u64, •, 0) must form a commutative monoid.
 	// This is satisfied by • = u64::wrapping_add.
@@ -0,0 +1,393 @@
+//! Insertion-ordered map optimized for small sizes.
+
+use std::borrow::Borrow;
+use std::hash::Hash;
+use std::fmt;
+use indexmap::IndexMap;
+
+/// Number of entries above which a [`SmallMap`] switches to a hash table.
+const THRESHOLD: usize = 8;
+
+/// Insertion-ordered map optimized for small sizes.
+///
+/// Up to 8 entries are stored in a single vector and looked up linearly,
+/// which avoids the memory overhead of a hash table for the typical small map.
+/// Larger maps switch to an [`IndexMap`].
+pub struct SmallMap<K, V>(Repr<K, V>);
+
+enum Repr<K, V> {
+	Small(Vec<(K, V)>),
+	Large(IndexMap<K, V>)
+}
+
+fn is_key<K: Borrow<Q>, Q: ?Sized + Eq>(k: &K, key: &Q) -> bool {
+	<K as Borrow<Q>>::borrow(k) == key
+}
+
+impl<K, V> SmallMap<K, V> {
+	/// Create a new empty map.
+	pub fn new() -> SmallMap<K, V> {
+		SmallMap(Repr::Small(Vec::new()))
+	}
+
+	/// Number of entries in the map.
+	pub fn len(&self) -> usize {
+		match &self.0 {
+			Repr::Small(entries) => entries.len(),
+			Repr::Large(map) => map.len()
+		}
+	}
+
+	/// Checks if the map is empty.
+	pub fn is_empty(&self) -> bool {
+		self.len() == 0
+	}
+
+	/// Remove all the entries of the map.
+	pub fn clear(&mut self) {
+		self.0 = Repr::Small(Vec::new())
+	}
+
+	/// Iterate through the entries of the map, in insertion order.
+	pub fn iter(&self) -> Iter<K, V> {
+		match &self.0 {
+			Repr::Small(entries) => Iter(IterRepr::Small(entries.iter())),
+			Repr::Large(map) => Iter(IterRepr::Large(map.iter()))
+		}
+	}
+
+	/// Iterate through the values of the map, in insertion order.
+	pub fn values_mut(&mut self) -> ValuesMut<K, V> {
+		match &mut self.0 {
+			Repr::Small(entries) => ValuesMut(ValuesMutRepr::Small(entries.iter_mut())),
+			Repr::Large(map) => ValuesMut(ValuesMutRepr::Large(map.values_mut()))
+		}
+	}
+}
+
+impl<K: Hash + Eq, V> SmallMap<K, V> {
+	/// Get the value associated to the given key, if any.
+	pub fn get<Q: ?Sized + Hash + Eq>(&self, key: &Q) -> Option<&V> where K: Borrow<Q> {
+		match &self.0 {
+			Repr::Small(entries) => entries.iter().find(|(k, _)| is_key(k, key)).map(|(_, v)| v),
+			Repr::Large(map) => map.get(key)
+		}
+	}
+
+	/// Get a mutable reference to the value associated to the given key, if any.
+	pub fn get_mut<Q: ?Sized + Hash + Eq>(&mut self, key: &Q) -> Option<&mut V> where K: Borrow<Q> {
+		match &mut self.0 {
+			Repr::Small(entries) => entries.iter_mut().find(|(k, _)| is_key(k, key)).map(|(_, v)| v),
+			Repr::Large(map) => map.get_mut(key)
+		}
+	}
+
+	/// Checks if the given key is in the map.
+	pub fn contains_key<Q: ?Sized + Hash + Eq>(&self, key: &Q) -> bool where K: Borrow<Q> {
+		self.get(key).is_some()
+	}
+
+	/// Associate the given value to the given key.
+	///
+	/// Returns the previous value, if any.
+	/// The key keeps its position if it was already in the map.
+	pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+		match self.entry(key) {
+			Entry::Occupied(entry) => Some(std::mem::replace(entry.into_mut(), value)),
+			Entry::Vacant(entry) => {
+				entry.insert(value);
+				None
+			}
+		}
+	}
+
+	/// Remove the given key from the map, preserving the order of the remaining entries.
+	///
+	/// Returns the value that was associated to the key, if any.
+	pub fn shift_remove<Q: ?Sized + Hash + Eq>(&mut self, key: &Q) -> Option<V> where K: Borrow<Q> {
+		match &mut self.0 {
+			Repr::Small(entries) => {
+				let i = entries.iter().position(|(k, _)| is_key(k, key))?;
+				Some(entries.remove(i).1)
+			},
+			Repr::Large(map) => map.shift_remove(key)
+		}
+	}
+
+	/// Get the entry of the given key, for in-place manipulation.
+	pub fn entry(&mut self, key: K) -> Entry<K, V> {
+		if let Repr::Small(entries) = &mut self.0 {
+			if entries.len() >= THRESHOLD && !entries.iter().any(|(k, _)| *k == key) {
+				let map = std::mem::take(entries).into_iter().collect();
+				self.0 = Repr::Large(map)
+			}
+		}
+
+		match &mut self.0 {
+			Repr::Small(entries) => match entries.iter().position(|(k, _)| *k == key) {
+				Some(index) => Entry::Occupied(OccupiedEntry(OccupiedRepr::Small(entries, index))),
+				None => Entry::Vacant(VacantEntry(VacantRepr::Small(entries, key)))
+			},
+			Repr::Large(map) => match map.entry(key) {
+				indexmap::map::Entry::Occupied(entry) => Entry::Occupied(OccupiedEntry(OccupiedRepr::Large(entry))),
+				indexmap::map::Entry::Vacant(entry) => Entry::Vacant(VacantEntry(VacantRepr::Large(entry)))
+			}
+		}
+	}
+}
+
+impl<K, V> Default for SmallMap<K, V> {
+	fn default() -> SmallMap<K, V> {
+		SmallMap::new()
+	}
+}
+
+impl<K: Clone, V: Clone> Clone for SmallMap<K, V> {
+	fn clone(&self) -> SmallMap<K, V> {
+		match &self.0 {
+			Repr::Small(entries) => SmallMap(Repr::Small(entries.clone())),
+			Repr::Large(map) => SmallMap(Repr::Large(map.clone()))
+		}
+	}
+}
+
+/// Two maps are equal if they have the same entries, in any order.
+impl<K: Hash + Eq, V: PartialEq> PartialEq for SmallMap<K, V> {
+	fn eq(&self, other: &SmallMap<K, V>) -> bool {
+		self.len() == other.len() && self.iter().all(|(key, value)| other.get(key).map(|v| v == value).unwrap_or(false))
+	}
+}
+
+impl<K: Hash + Eq, V: Eq> Eq for SmallMap<K, V> {}
+
+impl<K: fmt::Debug, V: fmt::Debug> fmt::Debug for SmallMap<K, V> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.debug_map().entries(self.iter()).finish()
+	}
+}
+
+impl<K: Hash + Eq, V> std::iter::FromIterator<(K, V)> for SmallMap<K, V> {
+	fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> SmallMap<K, V> {
+		let mut map = SmallMap::new();
+		map.extend(iter);
+		map
+	}
+}
+
+impl<K: Hash + Eq, V> Extend<(K, V)> for SmallMap<K, V> {
+	fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+		for (key, value) in iter {
+			self.insert(key, value);
+		}
+	}
+}
+
+impl<K, V> IntoIterator for SmallMap<K, V> {
+	type Item = (K, V);
+	type IntoIter = IntoIter<K, V>;
+
+	fn into_iter(self) -> IntoIter<K, V> {
+		match self.0 {
+			Repr::Small(entries) => IntoIter(IntoIterRepr::Small(entries.into_iter())),
+			Repr::Large(map) => IntoIter(IntoIterRepr::Large(map.into_iter()))
+		}
+	}
+}
+
+impl<'a, K, V> IntoIterator for &'a SmallMap<K, V> {
+	type Item = (&'a K, &'a V);
+	type IntoIter = Iter<'a, K, V>;
+
+	fn into_iter(self) -> Iter<'a, K, V> {
+		self.iter()
+	}
+}
+
+/// Iterator through the entries of a [`SmallMap`].
+pub struct Iter<'a, K, V>(IterRepr<'a, K, V>);
+
+enum IterRepr<'a, K, V> {
+	Small(std::slice::Iter<'a, (K, V)>),
+	Large(indexmap::map::Iter<'a, K, V>)
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V> {
+	type Item = (&'a K, &'a V);
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		match &self.0 {
+			IterRepr::Small(it) => it.size_hint(),
+			IterRepr::Large(it) => it.size_hint()
+		}
+	}
+
+	fn next(&mut self) -> Option<Self::Item> {
+		match &mut self.0 {
+			IterRepr::Small(it) => it.next().map(|(k, v)| (k, v)),
+			IterRepr::Large(it) => it.next()
+		}
+	}
+}
+
+impl<'a, K, V> ExactSizeIterator for Iter<'a, K, V> {}
+
+/// Iterator through the values of a [`SmallMap`], with mutable access.
+pub struct ValuesMut<'a, K, V>(ValuesMutRepr<'a, K, V>);
+
+enum ValuesMutRepr<'a, K, V> {
+	Small(std::slice::IterMut<'a, (K, V)>),
+	Large(indexmap::map::ValuesMut<'a, K, V>)
+}
+
+impl<'a, K, V> Iterator for ValuesMut<'a, K, V> {
+	type Item = &'a mut V;
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		match &self.0 {
+			ValuesMutRepr::Small(it) => it.size_hint(),
+			ValuesMutRepr::Large(it) => it.size_hint()
+		}
+	}
+
+	fn next(&mut self) -> Option<&'a mut V> {
+		match &mut self.0 {
+			ValuesMutRepr::Small(it) => it.next().map(|(_, v)| v),
+			ValuesMutRepr::Large(it) => it.next()
+		}
+	}
+}
+
+impl<'a, K, V> ExactSizeIterator for ValuesMut<'a, K, V> {}
+
+/// Consuming iterator through the entries of a [`SmallMap`].
+pub struct IntoIter<K, V>(IntoIterRepr<K, V>);
+
+enum IntoIterRepr<K, V> {
+	Small(std::vec::IntoIter<(K, V)>),
+	Large(indexmap::map::IntoIter<K, V>)
+}
+
+impl<K, V> Iterator for IntoIter<K, V> {
+	type Item = (K, V);
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		match &self.0 {
+			IntoIterRepr::Small(it) => it.size_hint(),
+			IntoIterRepr::Large(it) => it.size_hint()
+		}
+	}
+
+	fn next(&mut self) -> Option<(K, V)> {
+		match &mut self.0 {
+			IntoIterRepr::Small(it) => it.next(),
+			IntoIterRepr::Large(it) => it.next()
+		}
+	}
+}
+
+impl<K, V> ExactSizeIterator for IntoIter<K, V> {}
+
+/// Entry of a [`SmallMap`].
+///
+/// Created with [`SmallMap::entry`].
+pub enum Entry<'a, K, V> {
+	Occupied(OccupiedEntry<'a, K, V>),
+	Vacant(VacantEntry<'a, K, V>)
+}
+
+impl<'a, K, V> Entry<'a, K, V> {
+	/// The key of this entry.
+	pub fn key(&self) -> &K {
+		match self {
+			Entry::Occupied(entry) => entry.key(),
+			Entry::Vacant(entry) => entry.key()
+		}
+	}
+
+	/// Ensures a value is in the entry by inserting the result of `f` if it is vacant,
+	/// and returns a mutable reference to the value.
+	pub fn or_insert_with<F: FnOnce() -> V>(self, f: F) -> &'a mut V {
+		match self {
+			Entry::Occupied(entry) => entry.into_mut(),
+			Entry::Vacant(entry) => entry.insert(f())
+		}
+	}
+
+	/// Ensures a value is in the entry by inserting the default value if it is vacant,
+	/// and returns a mutable reference to the value.
+	pub fn or_default(self) -> &'a mut V where V: Default {
+		self.or_insert_with(V::default)
+	}
+
+	/// Modify the value if the entry is occupied.
+	pub fn and_modify<F: FnOnce(&mut V)>(mut self, f: F) -> Self {
+		if let Entry::Occupied(entry) = &mut self {
+			f(entry.get_mut())
+		}
+
+		self
+	}
+}
+
+/// Occupied entry of a [`SmallMap`].
+pub struct OccupiedEntry<'a, K, V>(OccupiedRepr<'a, K, V>);
+
+enum OccupiedRepr<'a, K, V> {
+	Small(&'a mut Vec<(K, V)>, usize),
+	Large(indexmap::map::OccupiedEntry<'a, K, V>)
+}
+
+impl<'a, K, V> OccupiedEntry<'a, K, V> {
+	/// The key of this entry.
+	pub fn key(&self) -> &K {
+		match &self.0 {
+			OccupiedRepr::Small(entries, index) => &entries[*index].0,
+			OccupiedRepr::Large(entry) => entry.key()
+		}
+	}
+
+	/// Get a mutable reference to the value of this entry.
+	pub fn get_mut(&mut self) -> &mut V {
+		match &mut self.0 {
+			OccupiedRepr::Small(entries, index) => &mut entries[*index].1,
+			OccupiedRepr::Large(entry) => entry.get_mut()
+		}
+	}
+
+	/// Convert the entry into a mutable reference to its value.
+	pub fn into_mut(self) -> &'a mut V {
+		match self.0 {
+			OccupiedRepr::Small(entries, index) => &mut entries[index].1,
+			OccupiedRepr::Large(entry) => entry.into_mut()
+		}
+	}
+}
+
+/// Vacant entry of a [`SmallMap`].
+pub struct VacantEntry<'a, K, V>(VacantRepr<'a, K, V>);
+
+enum VacantRepr<'a, K, V> {
+	Small(&'a mut Vec<(K, V)>, K),
+	Large(indexmap::map::VacantEntry<'a, K, V>)
+}
+
+impl<'a, K, V> VacantEntry<'a, K, V> {
+	/// The key of this entry.
+	pub fn key(&self) -> &K {
+		match &self.0 {
+			VacantRepr::Small(_, key) => key,
+			VacantRepr::Large(entry) => entry.key()
+		}
+	}
+
+	/// Insert the given value in the entry, and returns a mutable reference to it.
+	pub fn insert(self, value: V) -> &'a mut V {
+		match self.0 {
+			VacantRepr::Small(entries, key) => {
+				entries.push((key, value));
+				&mut entries.last_mut().unwrap().1
+			},
+			VacantRepr::Large(entry) => entry.insert(value)
+		}
+	}
+}
@@ -0,0 +1,133 @@
+//! Document loader for web browsers and workers, based on the
+//! [`fetch`](https://developer.mozilla.org/docs/Web/API/Fetch_API) API.
+//!
+//! This module is enabled by the `web-loader` feature, and is meant to be used on the
+//! `wasm32-unknown-unknown` target, with a single-threaded executor such as
+//! [`wasm_bindgen_futures::spawn_local`](https://docs.rs/wasm-bindgen-futures).
+//!
+//! JavaScript futures are not [`Send`], while the loader interface requires it.
+//! Since WebAssembly is single-threaded, the fetch futures are wrapped in a
+//! [`SendWrapper`](https://docs.rs/send_wrapper), which panics if they are ever polled from
+//! another thread than the one that created them.
+
+use std::collections::HashMap;
+use futures::future::{FutureExt, BoxFuture};
+use iref::{Iri, IriBuf};
+use json::JsonValue;
+use send_wrapper::SendWrapper;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::JsFuture;
+use crate::{
+	Error,
+	ErrorCode,
+	RemoteDocument,
+	is_json_media_type
+};
+
+fn loading_failed<E>(_: E) -> Error {
+	ErrorCode::LoadingDocumentFailed.into()
+}
+
+/// Fetch the JSON-LD document behind the given URL.
+///
+/// Works in both window and worker scopes.
+/// The returned future holds JavaScript values and is hence not [`Send`]:
+/// it must be polled by a local executor.
+/// [`WebLoader`] wraps it to implement the [`Loader`](`crate::Loader`) interface.
+pub async fn fetch_json_ld_document(url: Iri<'_>) -> Result<RemoteDocument, Error> {
+	info!("fetching remote document `{}'", url);
+
+	let init = web_sys::RequestInit::new();
+	init.set_method("GET");
+
+	let request = web_sys::Request::new_with_str_and_init(url.as_str(), &init).map_err(loading_failed)?;
+	request.headers().set("Accept", "application/ld+json, application/json").map_err(loading_failed)?;
+
+	let global = js_sys::global();
+	let promise = if let Some(window) = global.dyn_ref::<web_sys::Window>() {
+		window.fetch_with_request(&request)
+	} else if let Some(worker) = global.dyn_ref::<web_sys::WorkerGlobalScope>() {
+		worker.fetch_with_request(&request)
+	} else {
+		return Err(ErrorCode::LoadingDocumentFailed.into())
+	};
+
+	let response: web_sys::Response = JsFuture::from(promise).await.map_err(loading_failed)?.dyn_into().map_err(loading_failed)?;
+
+	if !response.ok() {
+		return Err(ErrorCode::LoadingDocumentFailed.into())
+	}
+
+	let content_type = match response.headers().get("Content-Type").map_err(loading_failed)? {
+		Some(ty) if is_json_media_type(&ty) => ty,
+		_ => return Err(ErrorCode::LoadingDocumentFailed.into())
+	};
+
+	let link = response.headers().get("Link").map_err(loading_failed)?;
+
+	// Redirections are followed by `fetch`: the final URL is the document base URL.
+	let final_url = IriBuf::new(&response.url()).unwrap_or_else(|_| url.into());
+
+	let body = JsFuture::from(response.text().map_err(loading_failed)?).await.map_err(loading_failed)?;
+	let body = body.as_string().ok_or(ErrorCode::LoadingDocumentFailed)?;
+
+	match json::parse(&body) {
+		Ok(doc) => {
+			let remote_doc = RemoteDocument::new(doc, final_url.as_iri()).with_content_type_header(&content_type);
+			match link {
+				Some(link) => Ok(remote_doc.with_link_header(&link)),
+				None => Ok(remote_doc)
+			}
+		},
+		Err(e) => Err(Error::new(ErrorCode::LoadingDocumentFailed, e))
+	}
+}
+
+/// Document loader using the `fetch` API.
+///
+/// Loaded documents are cached.
+/// The loader itself only holds Rust values and is [`Send`] and [`Sync`].
+/// Its [`load`](`crate::Loader::load`) futures are [`Send`] through a
+/// [`SendWrapper`](https://docs.rs/send_wrapper), and must be polled and dropped
+/// on the thread that created them.
+pub struct WebLoader {
+	cache: HashMap<IriBuf, RemoteDocument>
+}
+
+impl WebLoader {
+	/// Create a new loader with an empty cache.
+	pub fn new() -> WebLoader {
+		WebLoader {
+			cache: HashMap::new()
+		}
+	}
+}
+
+impl Default for WebLoader {
+	fn default() -> WebLoader {
+		WebLoader::new()
+	}
+}
+
+impl crate::Loader for WebLoader {
+	type Document = JsonValue;
+
+	fn load<'a>(&'a mut self, url: Iri<'_>) -> BoxFuture<'a, Result<RemoteDocument, Error>> {
+		let url = IriBuf::from(url);
+		async move {
+			if let Some(doc) = self.cache.get(&url) {
+				return Ok(doc.clone())
+			}
+
+			let fetch = SendWrapper::new(async move {
+				let doc = fetch_json_ld_document(url.as_iri()).await;
+				(url, doc)
+			});
+
+			let (url, doc) = fetch.await;
+			let doc = doc?;
+			self.cache.insert(url, doc.clone());
+			Ok(doc)
+		}.boxed()
+	}
+}
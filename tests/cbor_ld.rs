@@ -0,0 +1,60 @@
+#![cfg(feature = "cbor-ld")]
+
+extern crate iref;
+extern crate json;
+extern crate json_ld;
+
+use iref::IriBuf;
+use json_ld::{
+	JsonContext,
+	NumberComparison,
+	json_ld_eq_with,
+	cbor_ld::{
+		to_cborld,
+		from_cborld
+	}
+};
+
+#[test]
+fn round_trip() {
+	let context: JsonContext<IriBuf> = JsonContext::new(None);
+	let doc = json::parse(r#"{
+		"@id": "http://example.org/a",
+		"http://example.org/p": [ 0, 42, -42, 1.5, -0.25, 1e30, 18446744073709551615 ],
+		"http://example.org/q": { "@value": "foo", "@language": "en" },
+		"http://example.org/r": [ true, false, null ]
+	}"#).unwrap();
+
+	let bytes = to_cborld(&doc, &context);
+	let decoded = from_cborld(&bytes, &context).unwrap();
+	assert!(json_ld_eq_with(&doc, &decoded, NumberComparison::Value))
+}
+
+#[test]
+fn truncated_input() {
+	let context: JsonContext<IriBuf> = JsonContext::new(None);
+	let doc = json::parse(r#"{
+		"@id": "http://example.org/a",
+		"http://example.org/p": [ 42, 1.5, "foo" ]
+	}"#).unwrap();
+
+	let bytes = to_cborld(&doc, &context);
+	for len in 0..bytes.len() {
+		assert!(from_cborld(&bytes[..len], &context).is_err())
+	}
+}
+
+#[test]
+fn huge_lengths() {
+	let context: JsonContext<IriBuf> = JsonContext::new(None);
+	let mut header = to_cborld(&json::JsonValue::Null, &context);
+	header.pop();
+
+	// Array, map and text string heads with a 64-bit length argument of `u64::MAX`.
+	for major in &[0x9bu8, 0xbb, 0x7b] {
+		let mut bytes = header.clone();
+		bytes.push(*major);
+		bytes.extend_from_slice(&[0xff; 8]);
+		assert!(from_cborld(&bytes, &context).is_err())
+	}
+}
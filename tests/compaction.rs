@@ -0,0 +1,434 @@
+extern crate async_std;
+extern crate iref;
+extern crate json;
+extern crate json_ld;
+
+use async_std::task;
+use iref::IriBuf;
+use json::JsonValue;
+use json_ld::{
+	Document,
+	JsonContext,
+	NoLoader,
+	BlankIdCounter,
+	compaction,
+	context::Local,
+	json_ld_eq
+};
+
+/// Compact the given input with the given context, and compare it with the expected output,
+/// like the W3C compaction tests do.
+fn compaction_test(input: &str, context: &str, expected: &str, options: compaction::Options) {
+	let input = json::parse(input).unwrap();
+	let context = json::parse(context).unwrap();
+	let expected = json::parse(expected).unwrap();
+
+	let active_context: JsonContext<IriBuf> = JsonContext::new(None);
+	let processed = task::block_on(context.process_with(&active_context, &mut NoLoader, None, (&options).into())).unwrap();
+	let output: JsonValue = task::block_on(input.compact_with(None, &processed, &mut NoLoader, options)).unwrap();
+
+	assert_json_ld_eq(&output, &expected)
+}
+
+fn assert_json_ld_eq(output: &JsonValue, expected: &JsonValue) {
+	let success = json_ld_eq(output, expected);
+	if !success {
+		println!("output=\n{}", output.pretty(2));
+		println!("\nexpected=\n{}", expected.pretty(2));
+	}
+
+	assert!(success)
+}
+
+#[test]
+fn language_map_none() {
+	compaction_test(
+		r#"[{
+			"http://example.org/label": [
+				{ "@value": "hello", "@language": "en" },
+				{ "@value": "hi" }
+			]
+		}]"#,
+		r#"{ "label": { "@id": "http://example.org/label", "@container": "@language" } }"#,
+		r#"{
+			"@context": { "label": { "@id": "http://example.org/label", "@container": "@language" } },
+			"label": { "en": "hello", "@none": "hi" }
+		}"#,
+		compaction::Options::default()
+	)
+}
+
+#[test]
+fn language_map_lowercase_keys() {
+	compaction_test(
+		r#"[{
+			"http://example.org/label": [
+				{ "@value": "color", "@language": "en-US" }
+			]
+		}]"#,
+		r#"{ "label": { "@id": "http://example.org/label", "@container": "@language" } }"#,
+		r#"{
+			"@context": { "label": { "@id": "http://example.org/label", "@container": "@language" } },
+			"label": { "en-us": "color" }
+		}"#,
+		compaction::Options {
+			lowercase_language_keys: true,
+			..compaction::Options::default()
+		}
+	)
+}
+
+#[test]
+fn language_map_direction_mismatch() {
+	compaction_test(
+		r#"[{
+			"http://example.org/label": [
+				{ "@value": "hello", "@language": "en" },
+				{ "@value": "مرحبا", "@language": "ar", "@direction": "rtl" }
+			]
+		}]"#,
+		r#"{
+			"ex": "http://example.org/",
+			"label": { "@id": "ex:label", "@container": "@language" }
+		}"#,
+		r#"{
+			"@context": {
+				"ex": "http://example.org/",
+				"label": { "@id": "ex:label", "@container": "@language" }
+			},
+			"label": { "en": "hello" },
+			"ex:label": { "@value": "مرحبا", "@language": "ar", "@direction": "rtl" }
+		}"#,
+		compaction::Options::default()
+	)
+}
+
+#[test]
+fn language_map_direction_mismatch_term() {
+	compaction_test(
+		r#"[{
+			"http://example.org/label": [
+				{ "@value": "hello", "@language": "en" },
+				{ "@value": "مرحبا", "@language": "ar", "@direction": "rtl" }
+			]
+		}]"#,
+		r#"{
+			"label": { "@id": "http://example.org/label", "@container": "@language" },
+			"rawLabel": { "@id": "http://example.org/label" }
+		}"#,
+		r#"{
+			"@context": {
+				"label": { "@id": "http://example.org/label", "@container": "@language" },
+				"rawLabel": { "@id": "http://example.org/label" }
+			},
+			"label": { "en": "hello" },
+			"rawLabel": { "@value": "مرحبا", "@language": "ar", "@direction": "rtl" }
+		}"#,
+		compaction::Options::default()
+	)
+}
+
+/// Run a graph container test with every combination of the `ordered` option,
+/// which must not change the result.
+fn graph_container_test(input: &str, context: &str, expected: &str, compact_arrays: bool) {
+	for ordered in &[false, true] {
+		compaction_test(input, context, expected, compaction::Options {
+			compact_arrays,
+			ordered: *ordered,
+			..compaction::Options::default()
+		})
+	}
+}
+
+const SIMPLE_GRAPH: &str = r#"[{
+	"http://example.org/input": [{
+		"@graph": [{ "http://example.org/value": [{ "@value": "x" }] }]
+	}]
+}]"#;
+
+const NAMED_GRAPH: &str = r#"[{
+	"http://example.org/input": [{
+		"@id": "http://example.org/g",
+		"@graph": [{ "http://example.org/value": [{ "@value": "x" }] }]
+	}]
+}]"#;
+
+const INDEXED_GRAPHS: &str = r#"[{
+	"http://example.org/input": [
+		{ "@index": "a", "@graph": [{ "http://example.org/value": [{ "@value": "x" }] }] },
+		{ "@graph": [{ "http://example.org/value": [{ "@value": "y" }] }] }
+	]
+}]"#;
+
+#[test]
+fn graph_container_simple_graph() {
+	graph_container_test(
+		SIMPLE_GRAPH,
+		r#"{ "@vocab": "http://example.org/", "input": { "@container": "@graph" } }"#,
+		r#"{
+			"@context": { "@vocab": "http://example.org/", "input": { "@container": "@graph" } },
+			"input": { "value": "x" }
+		}"#,
+		true
+	)
+}
+
+#[test]
+fn graph_container_included() {
+	graph_container_test(
+		r#"[{
+			"http://example.org/input": [{
+				"@graph": [
+					{ "http://example.org/a": [{ "@value": "x" }] },
+					{ "http://example.org/b": [{ "@value": "y" }] }
+				]
+			}]
+		}]"#,
+		r#"{ "@vocab": "http://example.org/", "input": { "@container": "@graph" } }"#,
+		r#"{
+			"@context": { "@vocab": "http://example.org/", "input": { "@container": "@graph" } },
+			"input": { "@included": [ { "a": "x" }, { "b": "y" } ] }
+		}"#,
+		true
+	)
+}
+
+#[test]
+fn graph_container_named_graph() {
+	graph_container_test(
+		NAMED_GRAPH,
+		r#"{ "@vocab": "http://example.org/", "input": { "@container": "@graph" } }"#,
+		r#"{
+			"@context": { "@vocab": "http://example.org/", "input": { "@container": "@graph" } },
+			"input": { "@id": "http://example.org/g", "@graph": { "value": "x" } }
+		}"#,
+		true
+	)
+}
+
+#[test]
+fn graph_set_container() {
+	graph_container_test(
+		SIMPLE_GRAPH,
+		r#"{ "@vocab": "http://example.org/", "input": { "@container": [ "@graph", "@set" ] } }"#,
+		r#"{
+			"@context": { "@vocab": "http://example.org/", "input": { "@container": [ "@graph", "@set" ] } },
+			"input": [ { "value": "x" } ]
+		}"#,
+		true
+	)
+}
+
+#[test]
+fn graph_id_container() {
+	graph_container_test(
+		r#"[{
+			"http://example.org/input": [
+				{ "@id": "http://example.org/g", "@graph": [{ "http://example.org/value": [{ "@value": "x" }] }] },
+				{ "@graph": [{ "http://example.org/value": [{ "@value": "y" }] }] }
+			]
+		}]"#,
+		r#"{ "@vocab": "http://example.org/", "input": { "@container": [ "@graph", "@id" ] } }"#,
+		r#"{
+			"@context": { "@vocab": "http://example.org/", "input": { "@container": [ "@graph", "@id" ] } },
+			"input": {
+				"http://example.org/g": { "value": "x" },
+				"@none": { "value": "y" }
+			}
+		}"#,
+		true
+	)
+}
+
+#[test]
+fn graph_id_container_no_compact_arrays() {
+	graph_container_test(
+		NAMED_GRAPH,
+		r#"{ "@vocab": "http://example.org/", "input": { "@container": [ "@graph", "@id" ] } }"#,
+		r#"{
+			"@context": { "@vocab": "http://example.org/", "input": { "@container": [ "@graph", "@id" ] } },
+			"@graph": [{
+				"input": {
+					"http://example.org/g": [ { "value": [ "x" ] } ]
+				}
+			}]
+		}"#,
+		false
+	)
+}
+
+#[test]
+fn graph_index_container() {
+	graph_container_test(
+		INDEXED_GRAPHS,
+		r#"{ "@vocab": "http://example.org/", "input": { "@container": [ "@graph", "@index" ] } }"#,
+		r#"{
+			"@context": { "@vocab": "http://example.org/", "input": { "@container": [ "@graph", "@index" ] } },
+			"input": {
+				"a": { "value": "x" },
+				"@none": { "value": "y" }
+			}
+		}"#,
+		true
+	)
+}
+
+#[test]
+fn graph_index_container_none_alias() {
+	graph_container_test(
+		INDEXED_GRAPHS,
+		r#"{ "@vocab": "http://example.org/", "none": "@none", "input": { "@container": [ "@graph", "@index" ] } }"#,
+		r#"{
+			"@context": { "@vocab": "http://example.org/", "none": "@none", "input": { "@container": [ "@graph", "@index" ] } },
+			"input": {
+				"a": { "value": "x" },
+				"none": { "value": "y" }
+			}
+		}"#,
+		true
+	)
+}
+
+#[test]
+fn graph_without_graph_container() {
+	graph_container_test(
+		r#"[{
+			"http://example.org/input": [{
+				"@id": "http://example.org/g",
+				"@index": "a",
+				"@graph": [{ "http://example.org/value": [{ "@value": "x" }] }]
+			}]
+		}]"#,
+		r#"{ "@vocab": "http://example.org/" }"#,
+		r#"{
+			"@context": { "@vocab": "http://example.org/" },
+			"input": { "@id": "http://example.org/g", "@index": "a", "@graph": { "value": "x" } }
+		}"#,
+		true
+	)
+}
+
+const DATASET: &str = r#"[
+	{
+		"@id": "http://example.org/g",
+		"@graph": {
+			"@id": "http://example.org/a",
+			"http://example.org/knows": { "@id": "http://example.org/b", "http://example.org/p": "b" }
+		}
+	},
+	{
+		"@id": "http://example.org/c",
+		"http://example.org/p": "c"
+	}
+]"#;
+
+const DATASET_COMPACTED: &str = r#"{
+	"@context": { "@vocab": "http://example.org/", "knows": { "@type": "@id" } },
+	"@graph": [
+		{
+			"@id": "http://example.org/g",
+			"@graph": [
+				{ "@id": "http://example.org/a", "knows": "http://example.org/b" },
+				{ "@id": "http://example.org/b", "p": "b" }
+			]
+		},
+		{ "@id": "http://example.org/c", "p": "c" }
+	]
+}"#;
+
+const DATASET_CONTEXT: &str = r#"{ "@vocab": "http://example.org/", "knows": { "@type": "@id" } }"#;
+
+#[test]
+fn compact_node_map_with_named_graphs() {
+	let input = json::parse(DATASET).unwrap();
+	let context = json::parse(DATASET_CONTEXT).unwrap();
+	let options = json_ld::Options::default();
+
+	let expanded = task::block_on(json_ld::expand(&input, options.clone())).unwrap();
+	let node_map = expanded.into_node_map(BlankIdCounter::new()).unwrap();
+
+	let active_context: JsonContext<IriBuf> = JsonContext::new(None);
+	let processed = task::block_on(context.process_with(&active_context, &mut NoLoader, None, options.processing())).unwrap();
+	let output = task::block_on(node_map.compact(&processed, &mut NoLoader, options.compaction().clone())).unwrap();
+
+	assert_json_ld_eq(&output, &json::parse(DATASET_COMPACTED).unwrap())
+}
+
+#[test]
+fn flatten_with_named_graphs() {
+	let input = json::parse(DATASET).unwrap();
+	let context = json::parse(DATASET_CONTEXT).unwrap();
+	let output = task::block_on(json_ld::flatten(&input, Some(&context), json_ld::Options::default())).unwrap();
+
+	assert_json_ld_eq(&output, &json::parse(DATASET_COMPACTED).unwrap())
+}
+
+/// Compact a node with the given `@id` against the given base IRI,
+/// using the same processed context each time, and return the compacted `@id`.
+fn compact_id(processed: &json_ld::context::Processed<JsonValue, JsonContext<IriBuf>>, id: &str, base: &str) -> JsonValue {
+	let input = json::array![json::object! { "@id": id, "http://example.org/p": "x" }];
+	let options = compaction::Options {
+		base: Some(IriBuf::new(base).unwrap()),
+		..compaction::Options::default()
+	};
+
+	let mut output: JsonValue = task::block_on(input.compact_with(None, processed, &mut NoLoader, options)).unwrap();
+	output.remove("@id")
+}
+
+fn processed_vocab_context() -> json_ld::context::Processed<JsonValue, JsonContext<IriBuf>> {
+	let context = json::parse(r#"{ "@vocab": "http://example.org/" }"#).unwrap();
+	let active_context: JsonContext<IriBuf> = JsonContext::new(None);
+	task::block_on(context.process_with(&active_context, &mut NoLoader, None, Default::default())).unwrap().owned()
+}
+
+#[test]
+fn compact_iri_depends_on_base() {
+	let processed = processed_vocab_context();
+	assert_eq!(compact_id(&processed, "http://example.org/a/b", "http://example.org/a/c"), "b");
+	assert_eq!(compact_id(&processed, "http://example.org/a/b", "http://example.org/c"), "a/b");
+	assert_eq!(compact_id(&processed, "http://example.org/a/b", "http://example.com/"), "http://example.org/a/b")
+}
+
+#[test]
+fn compact_iri_relative_paths() {
+	let processed = processed_vocab_context();
+	assert_eq!(compact_id(&processed, "http://example.org/a/d", "http://example.org/a/b/c"), "../d");
+	assert_eq!(compact_id(&processed, "http://example.org/", "http://example.org/a/b/c"), "../../");
+	assert_eq!(compact_id(&processed, "http://example.org/a/b/", "http://example.org/a/b/c"), "./");
+	assert_eq!(compact_id(&processed, "http://example.org/a", "http://example.org"), "a");
+	assert_eq!(compact_id(&processed, "http://example.org", "http://example.org/a/b/c"), "http://example.org");
+	assert_eq!(compact_id(&processed, "http://example.org?q", "http://example.org"), "?q")
+}
+
+#[test]
+fn concurrent_compaction() {
+	for concurrency in &[None, Some(1), Some(4)] {
+		compaction_test(
+			r#"[
+				{ "@id": "http://example.org/a", "http://example.org/p": [{ "@value": "x" }, { "@value": "y" }, { "@value": "z" }] },
+				{ "@id": "http://example.org/b", "http://example.org/q": [{ "@list": [{ "@value": 1 }, { "@value": 2 }] }] },
+				{ "@id": "http://example.org/c", "http://example.org/p": [{ "@id": "http://example.org/a" }] }
+			]"#,
+			r#"{ "@vocab": "http://example.org/", "q": { "@container": "@list" } }"#,
+			r#"{
+				"@context": { "@vocab": "http://example.org/", "q": { "@container": "@list" } },
+				"@graph": [
+					{ "@id": "http://example.org/a", "p": [ "x", "y", "z" ] },
+					{ "@id": "http://example.org/b", "q": [ 1, 2 ] },
+					{ "@id": "http://example.org/c", "p": { "@id": "http://example.org/a" } }
+				]
+			}"#,
+			compaction::Options {
+				concurrency: *concurrency,
+				ordered: true,
+				..compaction::Options::default()
+			}
+		)
+	}
+}
+
+#[test]
+fn zero_concurrency_is_rejected() {
+	assert_eq!(compaction::Options::builder().concurrency(Some(0)).build().err(), Some(json_ld::OptionsError::ZeroConcurrency))
+}
@@ -0,0 +1,49 @@
+extern crate json;
+extern crate json_ld;
+
+use json_ld::{
+	NumberComparison,
+	json_ld_eq,
+	json_ld_eq_with
+};
+
+fn eq_by_value(a: &str, b: &str) -> bool {
+	json_ld_eq_with(&json::parse(a).unwrap(), &json::parse(b).unwrap(), NumberComparison::Value)
+}
+
+#[test]
+fn integers_by_value() {
+	assert!(eq_by_value("1", "1.0"));
+	assert!(eq_by_value("10", "1e1"));
+	assert!(eq_by_value("-0", "0"));
+	assert!(!eq_by_value("1", "2"));
+	assert!(!eq_by_value("9007199254740993", "9007199254740992"))
+}
+
+#[test]
+fn doubles_by_value() {
+	assert!(eq_by_value("1.5", "1.50"));
+	assert!(eq_by_value("1e30", "1.0e30"));
+	assert!(!eq_by_value("1.5", "1.25"));
+	assert!(!eq_by_value("1", "1.5"))
+}
+
+#[test]
+fn nested_numbers_by_value() {
+	assert!(eq_by_value(
+		r#"[{ "http://example.org/p": [{ "@value": 1 }, { "@value": 2.5 }] }]"#,
+		r#"[{ "http://example.org/p": [{ "@value": 2.50 }, { "@value": 1.0 }] }]"#
+	));
+	assert!(!eq_by_value(
+		r#"[{ "http://example.org/p": { "@list": [ 1, 2 ] } }]"#,
+		r#"[{ "http://example.org/p": { "@list": [ 2, 1 ] } }]"#
+	))
+}
+
+#[test]
+fn literal_comparison_is_the_default() {
+	let a = json::parse(r#"{ "http://example.org/p": 1 }"#).unwrap();
+	let b = json::parse(r#"{ "http://example.org/p": 1 }"#).unwrap();
+	assert!(json_ld_eq(&a, &b));
+	assert_eq!(json_ld_eq(&a, &b), json_ld_eq_with(&a, &b, NumberComparison::default()))
+}
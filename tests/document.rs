@@ -0,0 +1,326 @@
+extern crate async_std;
+extern crate iref;
+extern crate json;
+extern crate json_ld;
+
+use async_std::task;
+use iref::IriBuf;
+use json_ld::{
+	Options,
+	ExpandedDocument,
+	Object,
+	Node,
+	BlankId,
+	BlankIdCounter,
+	Quad,
+	Predicate,
+	QuadObject,
+	json_ld_eq,
+	util::AsJson
+};
+
+fn expand(input: &str) -> ExpandedDocument<IriBuf> {
+	let input = json::parse(input).unwrap();
+	task::block_on(json_ld::expand(&input, Options::default())).unwrap()
+}
+
+fn assert_json_ld_eq(doc: &ExpandedDocument<IriBuf>, expected: &str) {
+	let output = doc.as_json();
+	let expected = json::parse(expected).unwrap();
+
+	let success = json_ld_eq(&output, &expected);
+	if !success {
+		println!("output=\n{}", output.pretty(2));
+		println!("\nexpected=\n{}", expected.pretty(2));
+	}
+
+	assert!(success)
+}
+
+#[test]
+fn relabel_blank_nodes() {
+	let mut doc = expand(r#"[
+		{
+			"@id": "_:g",
+			"@graph": {
+				"@id": "_:x",
+				"http://example.org/knows": { "@id": "_:y" }
+			}
+		},
+		{
+			"@id": "_:y",
+			"@reverse": { "http://example.org/knows": { "@id": "_:x" } }
+		}
+	]"#);
+
+	let labels = doc.relabel_blank_nodes(BlankIdCounter::with_prefix("n"));
+	assert_eq!(labels.len(), 3);
+
+	let g = labels[&BlankId::new("g")].as_str();
+	let x = labels[&BlankId::new("x")].as_str();
+	let y = labels[&BlankId::new("y")].as_str();
+	assert!(g.starts_with("_:n") && x.starts_with("_:n") && y.starts_with("_:n"));
+
+	assert_json_ld_eq(&doc, &format!(r#"[
+		{{
+			"@id": "{g}",
+			"@graph": [{{
+				"@id": "{x}",
+				"http://example.org/knows": [{{ "@id": "{y}" }}]
+			}}]
+		}},
+		{{
+			"@id": "{y}",
+			"@reverse": {{ "http://example.org/knows": [{{ "@id": "{x}" }}] }}
+		}}
+	]"#, g = g, x = x, y = y))
+}
+
+fn node_id(node: &Node<IriBuf>) -> String {
+	node.id().map(|id| id.as_str().to_string()).unwrap_or_default()
+}
+
+/// String representation of a quad, to compare quads easily.
+fn quad_strings(Quad(graph, subject, predicate, object): Quad<IriBuf>) -> (String, String, String, String) {
+	let graph = graph.and_then(|g| g.id()).map(|id| id.as_str().to_string()).unwrap_or_default();
+	let predicate = match predicate {
+		Predicate::Type => "@type".to_string(),
+		Predicate::Property(prop) => prop.as_str().to_string()
+	};
+	let object = match object {
+		QuadObject::Type(ty) => ty.as_str().to_string(),
+		QuadObject::Node(node) => node_id(node),
+		QuadObject::Object(object) => match object.inner() {
+			Object::Node(node) => node_id(node),
+			Object::List(_) => "@list".to_string(),
+			Object::Value(value) => value.as_str().map(str::to_string).unwrap_or_else(|| "@value".to_string()),
+			Object::Graph(_) => "@graph".to_string()
+		}
+	};
+
+	(graph, node_id(subject), predicate, object)
+}
+
+#[test]
+fn quads() {
+	let doc = expand(r#"{
+		"@id": "http://example.org/g",
+		"@graph": {
+			"@id": "http://example.org/a",
+			"@type": "http://example.org/T",
+			"http://example.org/p": [ "v", { "@list": [ 1, 2 ] } ],
+			"http://example.org/q": { "@id": "http://example.org/b", "http://example.org/p": "w" },
+			"@reverse": { "http://example.org/r": { "@id": "http://example.org/c" } }
+		}
+	}"#);
+
+	let mut quads: Vec<_> = doc.quads().map(quad_strings).collect();
+	quads.sort();
+
+	let g = "http://example.org/g";
+	let mut expected: Vec<_> = vec![
+		(g, "http://example.org/a", "@type", "http://example.org/T"),
+		(g, "http://example.org/a", "http://example.org/p", "v"),
+		(g, "http://example.org/a", "http://example.org/p", "@list"),
+		(g, "http://example.org/a", "http://example.org/q", "http://example.org/b"),
+		(g, "http://example.org/b", "http://example.org/p", "w"),
+		(g, "http://example.org/c", "http://example.org/r", "http://example.org/a")
+	].into_iter().map(|(g, s, p, o)| (g.to_string(), s.to_string(), p.to_string(), o.to_string())).collect();
+	expected.sort();
+
+	assert_eq!(quads, expected)
+}
+
+#[test]
+fn quads_default_graph() {
+	let doc = expand(r#"{
+		"@id": "http://example.org/a",
+		"http://example.org/p": "v"
+	}"#);
+
+	let quads: Vec<_> = doc.quads().collect();
+	assert_eq!(quads.len(), 1);
+	assert!(quads[0].graph().is_none())
+}
+
+#[test]
+fn lift_included() {
+	let mut doc = expand(r#"{
+		"@id": "http://example.org/a",
+		"@included": [{
+			"@id": "http://example.org/b",
+			"http://example.org/p": "b",
+			"@included": {
+				"@id": "http://example.org/c",
+				"http://example.org/p": "c"
+			}
+		}]
+	}"#);
+
+	doc.lift_included();
+
+	assert_json_ld_eq(&doc, r#"[
+		{
+			"@id": "http://example.org/a",
+			"@included": [{ "@id": "http://example.org/b" }]
+		},
+		{
+			"@id": "http://example.org/b",
+			"http://example.org/p": [{ "@value": "b" }],
+			"@included": [{ "@id": "http://example.org/c" }]
+		},
+		{
+			"@id": "http://example.org/c",
+			"http://example.org/p": [{ "@value": "c" }]
+		}
+	]"#)
+}
+
+#[test]
+fn lift_included_in_named_graph() {
+	let mut doc = expand(r#"{
+		"@id": "http://example.org/g",
+		"@graph": {
+			"@id": "http://example.org/a",
+			"@included": { "@id": "http://example.org/b", "http://example.org/p": "b" }
+		}
+	}"#);
+
+	doc.lift_included();
+
+	assert_json_ld_eq(&doc, r#"[{
+		"@id": "http://example.org/g",
+		"@graph": [
+			{
+				"@id": "http://example.org/a",
+				"@included": [{ "@id": "http://example.org/b" }]
+			},
+			{
+				"@id": "http://example.org/b",
+				"http://example.org/p": [{ "@value": "b" }]
+			}
+		]
+	}]"#)
+}
+
+#[test]
+fn apply_reverse_properties() {
+	let mut doc = expand(r#"[
+		{
+			"@id": "http://example.org/a",
+			"@reverse": {
+				"http://example.org/knows": {
+					"@id": "http://example.org/b",
+					"http://example.org/name": "b"
+				}
+			}
+		},
+		{
+			"@id": "http://example.org/b",
+			"http://example.org/age": "1"
+		}
+	]"#);
+
+	doc.apply_reverse_properties(BlankIdCounter::new());
+
+	assert_json_ld_eq(&doc, r#"[
+		{ "@id": "http://example.org/a" },
+		{
+			"@id": "http://example.org/b",
+			"http://example.org/age": [{ "@value": "1" }],
+			"http://example.org/name": [{ "@value": "b" }],
+			"http://example.org/knows": [{ "@id": "http://example.org/a" }]
+		}
+	]"#)
+}
+
+#[test]
+fn apply_reverse_properties_labels_blank_nodes() {
+	let mut doc = expand(r#"{
+		"@reverse": {
+			"http://example.org/knows": { "@id": "http://example.org/b" }
+		}
+	}"#);
+
+	doc.apply_reverse_properties(BlankIdCounter::new());
+
+	assert_json_ld_eq(&doc, r#"[
+		{ "@id": "_:b0" },
+		{
+			"@id": "http://example.org/b",
+			"http://example.org/knows": [{ "@id": "_:b0" }]
+		}
+	]"#)
+}
+
+/// Lifting the anonymous included nodes of `x` and `y` may produce duplicate top-level nodes.
+#[test]
+fn equality_counts_duplicates() {
+	let mut a = expand(r#"[
+		{ "@id": "http://example.org/x", "@included": { "http://example.org/p": "a" } },
+		{ "@id": "http://example.org/y", "@included": { "http://example.org/p": "a" } }
+	]"#);
+	let mut b = expand(r#"[
+		{ "@id": "http://example.org/x", "@included": { "http://example.org/p": "a" } },
+		{ "@id": "http://example.org/y", "@included": { "http://example.org/p": "b" } }
+	]"#);
+
+	a.lift_included();
+	b.lift_included();
+	assert_eq!(a.len(), b.len());
+	assert!(a != b);
+	assert!(b != a);
+
+	let mut c = expand(r#"[
+		{ "@id": "http://example.org/x", "@included": { "http://example.org/p": "a" } },
+		{ "@id": "http://example.org/y", "@included": { "http://example.org/p": "a" } }
+	]"#);
+	c.lift_included();
+	assert!(a == c)
+}
+
+#[test]
+fn equivalent_up_to_blank_node_renaming() {
+	let a = expand(r#"[
+		{ "@id": "_:a", "http://example.org/knows": { "@id": "_:b" }, "http://example.org/name": "a" },
+		{ "@id": "_:b", "http://example.org/knows": { "@id": "_:a" }, "http://example.org/name": "b" }
+	]"#);
+	let b = expand(r#"[
+		{ "@id": "_:y", "http://example.org/knows": { "@id": "_:x" }, "http://example.org/name": "b" },
+		{ "@id": "_:x", "http://example.org/knows": { "@id": "_:y" }, "http://example.org/name": "a" }
+	]"#);
+	let c = expand(r#"[
+		{ "@id": "_:x", "http://example.org/knows": { "@id": "_:x" }, "http://example.org/name": "a" },
+		{ "@id": "_:y", "http://example.org/knows": { "@id": "_:x" }, "http://example.org/name": "b" }
+	]"#);
+
+	assert!(a.equivalent(&b));
+	assert!(b.equivalent(&a));
+	assert!(!a.equivalent(&c))
+}
+
+#[test]
+fn equivalent_many_blank_nodes() {
+	let chain = |prefix: &str, reversed: bool| {
+		let mut nodes: Vec<String> = (0..50).map(|i| {
+			format!(r#"{{ "@id": "_:{}{}", "http://example.org/next": {{ "@id": "_:{}{}" }}, "http://example.org/p": [ 1, 2 ] }}"#, prefix, i, prefix, i + 1)
+		}).collect();
+
+		if reversed {
+			nodes.reverse()
+		}
+
+		expand(&format!("[{}]", nodes.join(",")))
+	};
+
+	assert!(chain("a", false).equivalent(&chain("b", true)))
+}
+
+#[test]
+fn json_media_types() {
+	assert!(json_ld::is_json_media_type("application/json"));
+	assert!(json_ld::is_json_media_type("application/ld+json; profile=\"http://www.w3.org/ns/json-ld#expanded\""));
+	assert!(json_ld::is_json_media_type("application/activity+json"));
+	assert!(!json_ld::is_json_media_type("text/html"));
+	assert!(!json_ld::is_json_media_type("application/n-quads"))
+}
@@ -0,0 +1,135 @@
+extern crate async_std;
+extern crate json;
+extern crate json_ld;
+
+use async_std::task;
+use json::JsonValue;
+use json_ld::{
+	Options,
+	json_ld_eq,
+	util::AsJson
+};
+
+/// Expand the given input, and compare it with the expected output,
+/// like the W3C expansion tests do.
+fn expansion_test(input: &str, expected: &str, options: Options) {
+	let input = json::parse(input).unwrap();
+	let expected = json::parse(expected).unwrap();
+	let output: JsonValue = task::block_on(json_ld::expand(&input, options)).unwrap().as_json();
+
+	let success = json_ld_eq(&output, &expected);
+	if !success {
+		println!("output=\n{}", output.pretty(2));
+		println!("\nexpected=\n{}", expected.pretty(2));
+	}
+
+	assert!(success)
+}
+
+fn ignore_propagation() -> Options {
+	Options::builder().ignore_propagation(true).build().unwrap()
+}
+
+const TYPE_SCOPED: &str = r#"{
+	"@context": {
+		"@vocab": "http://example.org/",
+		"Person": { "@context": { "name": "http://schema.org/name" } }
+	},
+	"@type": "Person",
+	"name": "a",
+	"knows": { "name": "b" }
+}"#;
+
+const NOT_PROPAGATED: &str = r#"{
+	"@context": { "@vocab": "http://example.org/" },
+	"knows": {
+		"@context": { "@propagate": false, "name": "http://schema.org/name" },
+		"name": "b",
+		"knows": { "name": "c" }
+	}
+}"#;
+
+#[test]
+fn type_scoped_context_not_propagated() {
+	expansion_test(
+		TYPE_SCOPED,
+		r#"[{
+			"@type": [ "http://example.org/Person" ],
+			"http://schema.org/name": [{ "@value": "a" }],
+			"http://example.org/knows": [{
+				"http://example.org/name": [{ "@value": "b" }]
+			}]
+		}]"#,
+		Options::default()
+	)
+}
+
+#[test]
+fn type_scoped_context_propagated() {
+	expansion_test(
+		r#"{
+			"@context": {
+				"@vocab": "http://example.org/",
+				"Person": { "@context": { "@propagate": true, "name": "http://schema.org/name" } }
+			},
+			"@type": "Person",
+			"name": "a",
+			"knows": { "name": "b" }
+		}"#,
+		r#"[{
+			"@type": [ "http://example.org/Person" ],
+			"http://schema.org/name": [{ "@value": "a" }],
+			"http://example.org/knows": [{
+				"http://schema.org/name": [{ "@value": "b" }]
+			}]
+		}]"#,
+		Options::default()
+	)
+}
+
+#[test]
+fn type_scoped_context_ignore_propagation() {
+	expansion_test(
+		TYPE_SCOPED,
+		r#"[{
+			"@type": [ "http://example.org/Person" ],
+			"http://schema.org/name": [{ "@value": "a" }],
+			"http://example.org/knows": [{
+				"http://schema.org/name": [{ "@value": "b" }]
+			}]
+		}]"#,
+		ignore_propagation()
+	)
+}
+
+#[test]
+fn embedded_context_not_propagated() {
+	expansion_test(
+		NOT_PROPAGATED,
+		r#"[{
+			"http://example.org/knows": [{
+				"http://schema.org/name": [{ "@value": "b" }],
+				"http://example.org/knows": [{
+					"http://example.org/name": [{ "@value": "c" }]
+				}]
+			}]
+		}]"#,
+		Options::default()
+	)
+}
+
+#[test]
+fn embedded_context_ignore_propagation() {
+	expansion_test(
+		NOT_PROPAGATED,
+		r#"[{
+			"http://example.org/knows": [{
+				"http://schema.org/name": [{ "@value": "b" }],
+				"http://example.org/knows": [{
+					"http://schema.org/name": [{ "@value": "c" }]
+				}]
+			}]
+		}]"#,
+		ignore_propagation()
+	)
+}
@@ -0,0 +1,161 @@
+extern crate async_std;
+extern crate iref;
+extern crate json;
+extern crate json_ld;
+
+use async_std::task;
+use iref::IriBuf;
+use json::JsonValue;
+use json_ld::{
+	Options,
+	ExpandedDocument,
+	BlankIdCounter,
+	Reference,
+	json_ld_eq,
+	util::AsJson
+};
+
+fn expand(input: &str) -> ExpandedDocument<IriBuf> {
+	let input = json::parse(input).unwrap();
+	task::block_on(json_ld::expand(&input, Options::default())).unwrap()
+}
+
+/// Flatten the given input, and compare it with the expected output,
+/// like the W3C flattening tests do.
+fn flattening_test(input: &str, expected: &str) {
+	let expected = json::parse(expected).unwrap();
+	let output: JsonValue = expand(input).flatten(BlankIdCounter::new()).unwrap().as_json();
+
+	let success = json_ld_eq(&output, &expected);
+	if !success {
+		println!("output=\n{}", output.pretty(2));
+		println!("\nexpected=\n{}", expected.pretty(2));
+	}
+
+	assert!(success)
+}
+
+#[test]
+fn nested_nodes() {
+	flattening_test(
+		r#"{
+			"@id": "http://example.org/a",
+			"http://example.org/knows": {
+				"@id": "http://example.org/b",
+				"http://example.org/name": "b"
+			}
+		}"#,
+		r#"[
+			{
+				"@id": "http://example.org/a",
+				"http://example.org/knows": [{ "@id": "http://example.org/b" }]
+			},
+			{
+				"@id": "http://example.org/b",
+				"http://example.org/name": [{ "@value": "b" }]
+			}
+		]"#
+	)
+}
+
+#[test]
+fn blank_nodes_are_labeled() {
+	flattening_test(
+		r#"{
+			"http://example.org/knows": { "http://example.org/name": "b" }
+		}"#,
+		r#"[
+			{
+				"@id": "_:b0",
+				"http://example.org/knows": [{ "@id": "_:b1" }]
+			},
+			{
+				"@id": "_:b1",
+				"http://example.org/name": [{ "@value": "b" }]
+			}
+		]"#
+	)
+}
+
+#[test]
+fn named_graphs() {
+	flattening_test(
+		r#"{
+			"@id": "http://example.org/g",
+			"@graph": {
+				"@id": "http://example.org/a",
+				"http://example.org/knows": { "@id": "http://example.org/b", "http://example.org/name": "b" }
+			}
+		}"#,
+		r#"[{
+			"@id": "http://example.org/g",
+			"@graph": [
+				{
+					"@id": "http://example.org/a",
+					"http://example.org/knows": [{ "@id": "http://example.org/b" }]
+				},
+				{
+					"@id": "http://example.org/b",
+					"http://example.org/name": [{ "@value": "b" }]
+				}
+			]
+		}]"#
+	)
+}
+
+#[test]
+fn id_only_nodes_are_dropped() {
+	flattening_test(
+		r#"[
+			{ "@id": "http://example.org/a" },
+			{ "@id": "http://example.org/b", "http://example.org/name": "b" }
+		]"#,
+		r#"[{
+			"@id": "http://example.org/b",
+			"http://example.org/name": [{ "@value": "b" }]
+		}]"#
+	)
+}
+
+#[test]
+fn indexed_nodes_are_kept() {
+	flattening_test(
+		r#"{ "@id": "http://example.org/a", "@index": "i" }"#,
+		r#"[{ "@id": "http://example.org/a", "@index": "i" }]"#
+	)
+}
+
+#[test]
+fn node_map() {
+	let doc = expand(r#"{
+		"@id": "http://example.org/g",
+		"http://example.org/name": "g",
+		"@graph": { "@id": "http://example.org/a", "http://example.org/name": "a" }
+	}"#);
+
+	let map = doc.into_node_map(BlankIdCounter::new()).unwrap();
+	assert_eq!(map.default_graph().len(), 1);
+	assert!(map.get("http://example.org/g").is_some());
+	assert!(map.get("http://example.org/a").is_none());
+
+	let g: Reference<IriBuf> = Reference::Id(IriBuf::new("http://example.org/g").unwrap());
+	let graph = map.graph(Some(&g)).unwrap();
+	assert_eq!(graph.len(), 1);
+	assert!(graph.contains("http://example.org/a"));
+}
+
+#[test]
+fn node_map_round_trip() {
+	let doc = expand(r#"{
+		"@id": "http://example.org/a",
+		"http://example.org/knows": { "@id": "http://example.org/b", "http://example.org/name": "b" }
+	}"#);
+
+	let flattened = expand(r#"{
+		"@id": "http://example.org/a",
+		"http://example.org/knows": { "@id": "http://example.org/b", "http://example.org/name": "b" }
+	}"#).flatten(BlankIdCounter::new()).unwrap();
+
+	let map = doc.into_node_map(BlankIdCounter::new()).unwrap();
+	assert!(json_ld_eq(&map.into_document().as_json(), &flattened.as_json()))
+}
@@ -0,0 +1,170 @@
+#![cfg(feature = "serde")]
+
+extern crate async_std;
+extern crate iref;
+extern crate json;
+extern crate json_ld;
+
+use std::collections::BTreeMap;
+use async_std::task;
+use iref::IriBuf;
+use json_ld::{
+	JsonContext,
+	NoLoader,
+	Node,
+	Reference,
+	json_ld_eq,
+	context::Local,
+	util::AsJson,
+	mapping::{
+		to_node,
+		from_node
+	}
+};
+
+const P: &str = "http://example.org/p";
+const Q: &str = "http://example.org/q";
+
+fn prop(iri: &str) -> Reference<IriBuf> {
+	Reference::Id(IriBuf::new(iri).unwrap())
+}
+
+#[test]
+fn round_trip() {
+	let context: JsonContext<IriBuf> = JsonContext::new(None);
+	let mut map = BTreeMap::new();
+	map.insert(P.to_string(), vec!["a".to_string(), "b".to_string()]);
+
+	let node: Node<IriBuf> = to_node(&map, &context).unwrap();
+	let decoded: BTreeMap<String, Vec<String>> = from_node(&node, &context).unwrap();
+	assert_eq!(decoded, map)
+}
+
+#[test]
+fn round_trip_numbers() {
+	let context: JsonContext<IriBuf> = JsonContext::new(None);
+	let mut map = BTreeMap::new();
+	map.insert(P.to_string(), -42i64);
+	let node: Node<IriBuf> = to_node(&map, &context).unwrap();
+	assert_eq!(from_node::<BTreeMap<String, i64>, _, _>(&node, &context).unwrap(), map);
+
+	let mut map = BTreeMap::new();
+	map.insert(P.to_string(), 1.5f64);
+	let node: Node<IriBuf> = to_node(&map, &context).unwrap();
+	assert_eq!(from_node::<BTreeMap<String, f64>, _, _>(&node, &context).unwrap(), map)
+}
+
+#[test]
+fn empty_properties_are_skipped() {
+	let context: JsonContext<IriBuf> = JsonContext::new(None);
+	let mut map = BTreeMap::new();
+	map.insert(P.to_string(), 1i64);
+
+	let mut node: Node<IriBuf> = to_node(&map, &context).unwrap();
+	node.entry(prop(Q)).or_default();
+
+	let decoded: BTreeMap<String, i64> = from_node(&node, &context).unwrap();
+	assert_eq!(decoded, map)
+}
+
+const CONTEXT: &str = r#"{
+	"@vocab": "http://example.org/",
+	"knows": { "@type": "@id" },
+	"label": { "@language": "en" },
+	"items": { "@container": "@list" }
+}"#;
+
+fn context() -> JsonContext<IriBuf> {
+	let context = json::parse(CONTEXT).unwrap();
+	let active_context: JsonContext<IriBuf> = JsonContext::new(None);
+	task::block_on(context.process_with(&active_context, &mut NoLoader, None, Default::default())).unwrap().into_inner()
+}
+
+fn assert_node_eq(node: &Node<IriBuf>, expected: &str) {
+	let output = node.as_json();
+	let expected = json::parse(expected).unwrap();
+	if !json_ld_eq(&output, &expected) {
+		panic!("output=\n{}\n\nexpected=\n{}", output.pretty(2), expected.pretty(2))
+	}
+}
+
+#[test]
+fn serialize_id() {
+	let context = context();
+	let mut map = BTreeMap::new();
+	map.insert("@id", "http://example.org/a");
+	map.insert("label", "a");
+
+	let node: Node<IriBuf> = to_node(&map, &context).unwrap();
+	assert_node_eq(&node, r#"{
+		"@id": "http://example.org/a",
+		"http://example.org/label": [{ "@value": "a", "@language": "en" }]
+	}"#)
+}
+
+#[test]
+fn serialize_node_references() {
+	let context = context();
+	let mut map = BTreeMap::new();
+	map.insert("knows", vec!["http://example.org/b", "_:c"]);
+
+	let node: Node<IriBuf> = to_node(&map, &context).unwrap();
+	assert_node_eq(&node, r#"{
+		"http://example.org/knows": [{ "@id": "http://example.org/b" }, { "@id": "_:c" }]
+	}"#)
+}
+
+#[test]
+fn serialize_list() {
+	let context = context();
+	let mut map = BTreeMap::new();
+	map.insert("items", vec![1, 2]);
+
+	let node: Node<IriBuf> = to_node(&map, &context).unwrap();
+	assert_node_eq(&node, r#"{
+		"http://example.org/items": [{ "@list": [{ "@value": 1 }, { "@value": 2 }] }]
+	}"#);
+
+	let decoded: BTreeMap<String, Vec<i64>> = from_node(&node, &context).unwrap();
+	assert_eq!(decoded["http://example.org/items"], vec![1, 2])
+}
+
+#[test]
+fn serialize_non_struct_fails() {
+	let context = context();
+	assert!(to_node::<_, IriBuf, _>(&42, &context).is_err())
+}
+
+const LANGUAGE_CONTEXT: &str = r#"{
+	"@vocab": "http://example.org/",
+	"@language": "en"
+}"#;
+
+#[test]
+fn serialize_with_default_language() {
+	let context = json::parse(LANGUAGE_CONTEXT).unwrap();
+	let active_context: JsonContext<IriBuf> = JsonContext::new(None);
+	let context = task::block_on(context.process_with(&active_context, &mut NoLoader, None, Default::default())).unwrap().into_inner();
+
+	let mut map = BTreeMap::new();
+	map.insert("@id", vec!["http://example.org/a"]);
+	map.insert("@type", vec!["Thing"]);
+	map.insert("label", vec!["a"]);
+
+	let node: Node<IriBuf> = to_node(&map, &context).unwrap();
+	assert_node_eq(&node, r#"{
+		"@id": "http://example.org/a",
+		"@type": [ "http://example.org/Thing" ],
+		"http://example.org/label": [{ "@value": "a", "@language": "en" }]
+	}"#)
+}
+
+#[test]
+fn serialize_invalid_blank_id() {
+	let context = context();
+	let mut map = BTreeMap::new();
+	map.insert("@id", "_:not valid");
+
+	let node: Node<IriBuf> = to_node(&map, &context).unwrap();
+	assert!(node.id() == Some(&json_ld::Lenient::Unknown("_:not valid".to_string())))
+}
@@ -0,0 +1,83 @@
+extern crate async_std;
+extern crate futures;
+extern crate json;
+extern crate json_ld;
+
+use async_std::task;
+use json_ld::{
+	Options,
+	Limits,
+	ErrorCode
+};
+
+const DOC: &str = r#"{ "http://xmlns.com/foaf/0.1/name": "Timothée Haudebourg" }"#;
+
+fn limited() -> Options {
+	Options::builder()
+		.limits(Limits::new().with_max_input_bytes(Some(8)))
+		.build()
+		.unwrap()
+}
+
+#[test]
+fn compact_enforces_limits() {
+	let doc = DOC.to_string();
+	let context = json::parse(r#"{ "name": "http://xmlns.com/foaf/0.1/name" }"#).unwrap();
+	let result = task::block_on(json_ld::compact(&doc, &context, limited()));
+	assert_eq!(result.unwrap_err().code(), ErrorCode::InputTooLarge)
+}
+
+#[test]
+fn flatten_enforces_limits() {
+	let doc = DOC.to_string();
+	let result = task::block_on(json_ld::flatten(&doc, None, limited()));
+	assert_eq!(result.unwrap_err().code(), ErrorCode::InputTooLarge)
+}
+
+#[test]
+fn compaction_options_keep_expansion_settings() {
+	let options = Options::builder()
+		.limits(Limits::new().with_max_input_bytes(Some(8)))
+		.rdf_star(true)
+		.build()
+		.unwrap();
+
+	let expansion: json_ld::expansion::Options = options.compaction().clone().into();
+	assert_eq!(expansion.limits.max_input_bytes(), Some(8));
+	assert!(expansion.rdf_star)
+}
+
+#[test]
+fn expanded_objects_are_counted_per_expansion() {
+	// The document expands into a node object and a value object.
+	let doc = json::parse(DOC).unwrap();
+	let options = Options::builder()
+		.limits(Limits::new().with_max_expanded_objects(Some(2)))
+		.build()
+		.unwrap();
+
+	let (a, b) = task::block_on(futures::future::join(
+		json_ld::expand(&doc, options.clone()),
+		json_ld::expand(&doc, options.clone())
+	));
+	assert!(a.is_ok() && b.is_ok());
+
+	let options = Options::builder()
+		.limits(Limits::new().with_max_expanded_objects(Some(1)))
+		.build()
+		.unwrap();
+	let result = task::block_on(json_ld::expand(&doc, options));
+	assert_eq!(result.err().unwrap().code(), ErrorCode::TooManyObjects)
+}
+
+#[test]
+fn values_per_property_are_bounded() {
+	let doc = json::parse(r#"{ "http://xmlns.com/foaf/0.1/nick": [ "a", "b", "c", "d" ] }"#).unwrap();
+	let options = Options::builder()
+		.limits(Limits::new().with_max_values_per_property(Some(3)))
+		.build()
+		.unwrap();
+
+	let result = task::block_on(json_ld::expand(&doc, options));
+	assert_eq!(result.err().unwrap().code(), ErrorCode::TooManyValues)
+}
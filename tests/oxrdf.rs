@@ -0,0 +1,107 @@
+#![cfg(feature = "oxrdf")]
+
+extern crate async_std;
+extern crate json;
+extern crate json_ld;
+extern crate oxrdf;
+
+use std::collections::HashSet;
+use std::convert::TryFrom;
+use async_std::task;
+use json_ld::{
+	object::{
+		Literal,
+		Value
+	},
+	oxrdf::{
+		Datatypes,
+		NumericPolicy
+	}
+};
+
+const XSD_INTEGER: &str = "http://www.w3.org/2001/XMLSchema#integer";
+const XSD_DOUBLE: &str = "http://www.w3.org/2001/XMLSchema#double";
+const RDF: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#";
+const EX: &str = "http://example.org/";
+
+fn number(s: &str) -> Value {
+	Value::Literal(Literal::Number(json::parse(s).unwrap().as_number().unwrap()), None)
+}
+
+fn to_literal(s: &str, policy: NumericPolicy) -> (String, String) {
+	let lit = Datatypes::new().with_numeric_policy(policy).to_literal(number(s)).unwrap();
+	(lit.value().to_string(), lit.datatype().as_str().to_string())
+}
+
+#[test]
+fn spec_integers() {
+	let lit = oxrdf::Literal::try_from(number("42")).unwrap();
+	assert_eq!(lit.value(), "42");
+	assert_eq!(lit.datatype().as_str(), XSD_INTEGER);
+
+	assert_eq!(to_literal("1.0", NumericPolicy::Spec), ("1".to_string(), XSD_INTEGER.to_string()));
+	assert_eq!(to_literal("-1e20", NumericPolicy::Spec), ("-100000000000000000000".to_string(), XSD_INTEGER.to_string()));
+}
+
+#[test]
+fn spec_doubles() {
+	assert_eq!(to_literal("1.5", NumericPolicy::Spec), ("1.5E0".to_string(), XSD_DOUBLE.to_string()));
+	assert_eq!(to_literal("1e21", NumericPolicy::Spec), ("1.0E21".to_string(), XSD_DOUBLE.to_string()));
+}
+
+#[test]
+fn integer_when_lossless() {
+	assert_eq!(to_literal("1e20", NumericPolicy::IntegerWhenLossless), ("1.0E20".to_string(), XSD_DOUBLE.to_string()));
+	assert_eq!(to_literal("9223372036854775807", NumericPolicy::IntegerWhenLossless), ("9223372036854775807".to_string(), XSD_INTEGER.to_string()));
+	assert_eq!(to_literal("9223372036854775808", NumericPolicy::IntegerWhenLossless).1, XSD_DOUBLE);
+	assert_eq!(to_literal("1.5", NumericPolicy::IntegerWhenLossless), ("1.5E0".to_string(), XSD_DOUBLE.to_string()));
+	assert_eq!(to_literal("2.0", NumericPolicy::IntegerWhenLossless), ("2".to_string(), XSD_INTEGER.to_string()));
+}
+
+fn iri(prefix: &str, name: &str) -> oxrdf::NamedNode {
+	oxrdf::NamedNode::new_unchecked(format!("{}{}", prefix, name))
+}
+
+fn blank(name: &str) -> oxrdf::BlankNode {
+	oxrdf::BlankNode::new_unchecked(name)
+}
+
+#[test]
+fn to_rdf() {
+	let doc = json::parse(r#"[
+		{
+			"@id": "http://example.org/a",
+			"@type": "http://example.org/Thing",
+			"http://example.org/items": { "@list": [ 1, "x" ] },
+			"@reverse": { "http://example.org/knows": { "@id": "http://example.org/b" } }
+		},
+		{
+			"@id": "http://example.org/g",
+			"@graph": { "@id": "http://example.org/c", "http://example.org/p": "y" }
+		}
+	]"#).unwrap();
+
+	let quads: HashSet<oxrdf::Quad> = task::block_on(json_ld::to_rdf(&doc, json_ld::Options::default())).unwrap().into_iter().collect();
+	let default_graph = oxrdf::GraphName::DefaultGraph;
+	let expected: HashSet<oxrdf::Quad> = vec![
+		oxrdf::Quad::new(iri(EX, "a"), iri(RDF, "type"), iri(EX, "Thing"), default_graph.clone()),
+		oxrdf::Quad::new(iri(EX, "a"), iri(EX, "items"), blank("l0"), default_graph.clone()),
+		oxrdf::Quad::new(blank("l0"), iri(RDF, "first"), oxrdf::Literal::new_typed_literal("1", oxrdf::NamedNode::new_unchecked(XSD_INTEGER)), default_graph.clone()),
+		oxrdf::Quad::new(blank("l0"), iri(RDF, "rest"), blank("l1"), default_graph.clone()),
+		oxrdf::Quad::new(blank("l1"), iri(RDF, "first"), oxrdf::Literal::new_simple_literal("x"), default_graph.clone()),
+		oxrdf::Quad::new(blank("l1"), iri(RDF, "rest"), iri(RDF, "nil"), default_graph.clone()),
+		oxrdf::Quad::new(iri(EX, "b"), iri(EX, "knows"), iri(EX, "a"), default_graph),
+		oxrdf::Quad::new(iri(EX, "c"), iri(EX, "p"), oxrdf::Literal::new_simple_literal("y"), iri(EX, "g"))
+	].into_iter().collect();
+
+	assert_eq!(quads, expected)
+}
+
+#[test]
+fn to_rdf_empty_list() {
+	let doc = json::parse(r#"{ "@id": "http://example.org/a", "http://example.org/items": { "@list": [] } }"#).unwrap();
+	let quads = task::block_on(json_ld::to_rdf(&doc, json_ld::Options::default())).unwrap();
+	assert_eq!(quads, vec![
+		oxrdf::Quad::new(iri(EX, "a"), iri(EX, "items"), iri(RDF, "nil"), oxrdf::GraphName::DefaultGraph)
+	])
+}
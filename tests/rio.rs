@@ -0,0 +1,39 @@
+#![cfg(feature = "rio")]
+
+extern crate async_std;
+extern crate json;
+extern crate json_ld;
+extern crate rio_api;
+extern crate rio_turtle;
+
+use async_std::task;
+use rio_turtle::NQuadsFormatter;
+use json_ld::{
+	BlankIdCounter,
+	oxrdf::Datatypes
+};
+
+#[test]
+fn format_nquads() {
+	let doc = json::parse(r#"{
+		"@id": "http://example.org/g",
+		"@graph": {
+			"@id": "http://example.org/a",
+			"http://example.org/name": { "@value": "a", "@language": "en" },
+			"http://example.org/age": 42
+		}
+	}"#).unwrap();
+
+	let expanded = task::block_on(json_ld::expand(&doc, json_ld::Options::default())).unwrap();
+	let flattened = expanded.flatten(BlankIdCounter::new()).unwrap();
+	let mut formatter = NQuadsFormatter::new(Vec::new());
+	json_ld::rio::format(&flattened, &Datatypes::default(), BlankIdCounter::with_prefix("l"), &mut formatter).unwrap();
+
+	let output = String::from_utf8(formatter.finish().unwrap()).unwrap();
+	let mut lines: Vec<&str> = output.lines().collect();
+	lines.sort_unstable();
+	assert_eq!(lines, vec![
+		"<http://example.org/a> <http://example.org/age> \"42\"^^<http://www.w3.org/2001/XMLSchema#integer> <http://example.org/g> .",
+		"<http://example.org/a> <http://example.org/name> \"a\"@en <http://example.org/g> ."
+	])
+}
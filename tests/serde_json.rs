@@ -0,0 +1,13 @@
+#![cfg(feature = "serde_json")]
+
+extern crate json;
+extern crate json_ld;
+extern crate serde_json;
+
+use json_ld::serde_json::from_json;
+
+#[test]
+fn from_json_numbers() {
+	let value = json::parse("[ 1.5, -0.25, 2.0, -3, 18446744073709551615 ]").unwrap();
+	assert_eq!(from_json(&value), serde_json::json!([ 1.5, -0.25, 2, -3, 18446744073709551615u64 ]))
+}
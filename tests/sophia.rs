@@ -0,0 +1,47 @@
+#![cfg(feature = "sophia")]
+
+extern crate async_std;
+extern crate json;
+extern crate json_ld;
+extern crate sophia_api;
+
+use async_std::task;
+use sophia_api::{
+	dataset::Dataset as _,
+	quad::Quad as _,
+	term::{
+		TTerm,
+		TermKind
+	}
+};
+use json_ld::{
+	BlankIdCounter,
+	oxrdf::Datatypes,
+	sophia::Dataset
+};
+
+#[test]
+fn dataset() {
+	let doc = json::parse(r#"{
+		"@id": "http://example.org/a",
+		"http://example.org/name": { "@value": "a", "@language": "en" },
+		"http://example.org/knows": { "http://example.org/name": "b" }
+	}"#).unwrap();
+
+	let expanded = task::block_on(json_ld::expand(&doc, json_ld::Options::default())).unwrap();
+	let flattened = expanded.flatten(BlankIdCounter::new()).unwrap();
+	let dataset = Dataset::from_document(&flattened, &Datatypes::default(), BlankIdCounter::with_prefix("l"));
+	assert_eq!(dataset.len(), 3);
+
+	let quads: Vec<_> = dataset.quads().collect::<Result<_, _>>().unwrap();
+	assert_eq!(quads.len(), 3);
+
+	let name = dataset.iter().find(|quad| &*quad.s().value() == "http://example.org/a" && &*quad.p().value() == "http://example.org/name").unwrap();
+	assert_eq!(name.o().kind(), TermKind::Literal);
+	assert_eq!(&*name.o().value(), "a");
+	assert_eq!(name.o().language(), Some("en"));
+	assert!(name.g().is_none());
+
+	let knows = dataset.iter().find(|quad| &*quad.p().value() == "http://example.org/knows").unwrap();
+	assert_eq!(knows.o().kind(), TermKind::BlankNode)
+}
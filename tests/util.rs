@@ -0,0 +1,63 @@
+extern crate json;
+extern crate json_ld;
+
+use json::number::Number;
+use json_ld::util::{
+	SmallMap,
+	json_number_as_i64,
+	json_number_as_u64
+};
+
+#[test]
+fn small_map_keeps_insertion_order() {
+	// Past 8 entries, the map switches to a hash table.
+	for len in &[3usize, 20] {
+		let mut map = SmallMap::new();
+		for i in (0..*len).rev() {
+			assert_eq!(map.insert(i, i * 10), None)
+		}
+
+		assert_eq!(map.len(), *len);
+		assert_eq!(map.insert(0, 1), Some(0));
+		assert_eq!(map.get(&0), Some(&1));
+		assert_eq!(map.shift_remove(&1), Some(10));
+		assert_eq!(map.shift_remove(&1), None);
+
+		let keys: Vec<usize> = map.iter().map(|(k, _)| *k).collect();
+		let expected: Vec<usize> = (0..*len).rev().filter(|i| *i != 1).collect();
+		assert_eq!(keys, expected)
+	}
+}
+
+#[test]
+fn small_map_equality_ignores_order() {
+	let a: SmallMap<&str, usize> = vec![("a", 1), ("b", 2)].into_iter().collect();
+	let b: SmallMap<&str, usize> = vec![("b", 2), ("a", 1)].into_iter().collect();
+	let c: SmallMap<&str, usize> = vec![("a", 1), ("b", 3)].into_iter().collect();
+	assert_eq!(a, b);
+	assert_ne!(a, c)
+}
+
+fn number(s: &str) -> Number {
+	json::parse(s).unwrap().as_number().unwrap()
+}
+
+#[test]
+fn json_numbers_as_integers() {
+	assert_eq!(json_number_as_i64(&number("42")), Some(42));
+	assert_eq!(json_number_as_i64(&number("-42")), Some(-42));
+	assert_eq!(json_number_as_i64(&number("1.0")), Some(1));
+	assert_eq!(json_number_as_u64(&number("1e3")), Some(1000));
+	assert_eq!(json_number_as_i64(&number("-9223372036854775808")), Some(i64::MIN));
+
+	// Fractional numbers are not truncated.
+	assert_eq!(json_number_as_i64(&number("1.5")), None);
+	assert_eq!(json_number_as_u64(&number("1.5")), None);
+	assert_eq!(json_number_as_i64(&number("-0.1")), None);
+
+	// Out of range numbers do not wrap around.
+	assert_eq!(json_number_as_i64(&number("9223372036854775808")), None);
+	assert_eq!(json_number_as_u64(&number("9223372036854775808")), Some(9223372036854775808));
+	assert_eq!(json_number_as_u64(&number("1e20")), None);
+	assert_eq!(json_number_as_u64(&number("-1")), None);
+}
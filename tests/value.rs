@@ -0,0 +1,17 @@
+extern crate json;
+extern crate json_ld;
+
+use json::number::Number;
+use json_ld::object::Literal;
+
+fn number(s: &str) -> Number {
+	json::parse(s).unwrap().as_number().unwrap()
+}
+
+#[test]
+fn numeric_accessors() {
+	assert_eq!(Literal::Number(number("42")).as_i64(), Some(42));
+	assert_eq!(Literal::Number(number("42")).as_u64(), Some(42));
+	assert_eq!(Literal::Number(number("1.5")).as_i64(), None);
+	assert_eq!(Literal::Number(number("1.5")).as_f64(), Some(1.5));
+}